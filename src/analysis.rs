@@ -0,0 +1,730 @@
+//! Where each name in a source file is declared and used, for the language
+//! server. The AST has no positions, so this walks the lexer's tokens with
+//! the parser's block structure: `fn`, `if`, `while`, `for`, `match` and
+//! `try` open a scope that `end` closes, lambdas and comprehensions scope
+//! their parameters, and each use of a name resolves to the innermost
+//! declaration in scope. Top-level names resolve anywhere in the file, as
+//! functions can call functions defined after them.
+
+use crate::ast::Stmt;
+use crate::lexer::{Lexer, SourcePosition, Token};
+use crate::resolver::{ModuleResolver, ModuleSource};
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SymbolKind {
+    Function,
+    Struct,
+    Variable,
+    Constant,
+    Parameter,
+    Module,    // `import "m" as name`
+    Import,    // `import {name} from "m"`
+    ReExport,  // `export {name} from "m"`, which doesn't bind the name here
+}
+
+#[derive(Debug, Clone)]
+pub struct Symbol {
+    pub name: String,
+    pub kind: SymbolKind,
+    pub position: SourcePosition,  // Of the declared name
+    pub scope: usize,
+    pub parent: Option<usize>,     // The function it's declared in
+    pub module: Option<String>,    // The module path of a Module, Import or ReExport
+    pub body: Option<usize>,       // A function's scope
+}
+
+/// One appearance of a name: its declaration or a use
+#[derive(Debug, Clone)]
+pub struct Occurrence {
+    pub name: String,
+    pub position: SourcePosition,
+    pub symbol: Option<usize>,      // None for builtins, members and undeclared names
+    pub member_of: Option<String>,  // `m.name` is a member of `m`
+}
+
+impl Occurrence {
+    pub fn end(&self) -> usize {
+        self.position.position + self.name.chars().count()
+    }
+}
+
+/// The source a scope covers, as character offsets
+#[derive(Debug, Clone)]
+pub struct Scope {
+    pub start: usize,
+    pub end: usize,
+    pub parent: Option<usize>,
+}
+
+/// An `import` or `export ... from` statement's module path string
+#[derive(Debug, Clone)]
+pub struct ModuleImport {
+    pub path: String,
+    pub position: SourcePosition,
+    pub end: usize,
+    pub whole: bool,  // `import "m"` without `as`: every export, namespaced for nested paths
+}
+
+#[derive(Debug, Default)]
+pub struct Analysis {
+    pub symbols: Vec<Symbol>,
+    pub occurrences: Vec<Occurrence>,
+    pub scopes: Vec<Scope>,
+    pub imports: Vec<ModuleImport>,
+}
+
+impl Analysis {
+    pub fn new(source: &str) -> Self {
+        Walker::new(source).walk()
+    }
+
+    /// The name at a character offset, including one the cursor is just after
+    pub fn occurrence_at(&self, offset: usize) -> Option<&Occurrence> {
+        self.occurrences.iter().find(|o| o.position.position <= offset && offset <= o.end())
+    }
+
+    pub fn import_at(&self, offset: usize) -> Option<&ModuleImport> {
+        self.imports.iter().find(|i| i.position.position <= offset && offset < i.end)
+    }
+
+    /// The declaration and every use of a symbol
+    pub fn references(&self, symbol: usize) -> impl Iterator<Item = &Occurrence> {
+        self.occurrences.iter().filter(move |o| o.symbol == Some(symbol))
+    }
+
+    pub fn top_level(&self, name: &str) -> Option<&Symbol> {
+        self.symbols.iter().find(|s| s.scope == 0 && s.name == name)
+    }
+
+    /// The symbols in scope at a character offset, innermost first, one per name
+    pub fn visible_at(&self, offset: usize) -> Vec<&Symbol> {
+        let innermost = (0..self.scopes.len())
+            .filter(|&i| self.scopes[i].start <= offset && offset < self.scopes[i].end)
+            .max_by_key(|&i| self.scopes[i].start)
+            .unwrap_or(0);
+        let mut chain = Vec::new();
+        let mut scope = Some(innermost);
+        while let Some(index) = scope {
+            chain.push(index);
+            scope = self.scopes[index].parent;
+        }
+
+        let mut seen = HashSet::new();
+        let mut visible = Vec::new();
+        for scope in chain {
+            for symbol in self.symbols.iter().filter(|s| s.scope == scope && s.kind != SymbolKind::ReExport) {
+                // Locals exist from their declaration on; top-level names everywhere
+                let declared = scope == 0 || symbol.position.position < offset;
+                if declared && seen.insert(symbol.name.as_str()) {
+                    visible.push(symbol);
+                }
+            }
+        }
+        visible
+    }
+}
+
+// ========== Token Walk ==========
+
+#[derive(Debug, Clone, PartialEq)]
+enum FrameKind {
+    Global,
+    Block,
+    Try,
+    Match,
+    Function,
+    Lambda,         // Ends with its expression: at a newline, comma or closer at its depth
+    Comprehension,  // `[x for x in xs]`, ending at its bracket
+}
+
+#[derive(Debug, Clone)]
+struct Frame {
+    kind: FrameKind,
+    scope: usize,
+    depth: usize,  // Bracket depth it opened at
+    function: Option<usize>,
+}
+
+struct Walker {
+    tokens: Vec<(Token, SourcePosition, String)>,
+    i: usize,
+    analysis: Analysis,
+    names: Vec<HashMap<String, usize>>,  // Each scope's declared names
+    frames: Vec<Frame>,
+    brackets: Vec<Token>,
+    params_end: Option<usize>,           // Token index closing a parameter list
+    handled: HashSet<usize>,             // Tokens a declaration already consumed
+    deferred: Vec<(usize, usize)>,       // (scope, symbol) visible from the next line
+    unresolved: Vec<usize>,              // Occurrences to try against top-level names at the end
+}
+
+impl Walker {
+    fn new(source: &str) -> Self {
+        let tokens = Lexer::new(source).tokenize_with_text().into_iter()
+            .map(|(located, text)| (located.token, located.position, text))
+            .collect();
+        let mut analysis = Analysis::default();
+        analysis.scopes.push(Scope { start: 0, end: usize::MAX, parent: None });
+        Walker {
+            tokens,
+            i: 0,
+            analysis,
+            names: vec![HashMap::new()],
+            frames: vec![Frame { kind: FrameKind::Global, scope: 0, depth: 0, function: None }],
+            brackets: Vec::new(),
+            params_end: None,
+            handled: HashSet::new(),
+            deferred: Vec::new(),
+            unresolved: Vec::new(),
+        }
+    }
+
+    fn walk(mut self) -> Analysis {
+        while self.i < self.tokens.len() {
+            if !self.handled.contains(&self.i) {
+                self.visit();
+            }
+            self.i += 1;
+        }
+        self.flush_deferred();
+        for index in std::mem::take(&mut self.unresolved) {
+            let name = &self.analysis.occurrences[index].name;
+            self.analysis.occurrences[index].symbol = self.names[0].get(name).copied();
+        }
+        self.analysis
+    }
+
+    fn token(&self, i: usize) -> Option<&Token> {
+        self.tokens.get(i).map(|(token, _, _)| token)
+    }
+
+    fn depth(&self) -> usize {
+        self.brackets.len()
+    }
+
+    fn frame(&self) -> &Frame {
+        self.frames.last().expect("the global frame is never popped")
+    }
+
+    fn visit(&mut self) {
+        let token = self.tokens[self.i].0.clone();
+        let next = self.token(self.i + 1).cloned();
+        match token {
+            Token::Newline | Token::Semicolon | Token::Eof => {
+                self.close_lambdas(|frame, depth| frame.depth == depth);
+                // Inside `let f = lambda(n) -> {`, `f` is already declared
+                self.flush_deferred();
+            }
+            Token::Comma => self.close_lambdas(|frame, depth| frame.depth == depth),
+            Token::LeftParen | Token::LeftBracket | Token::LeftBrace | Token::LeftBracePipe => {
+                self.brackets.push(token.clone());
+                if matches!(token, Token::LeftParen | Token::LeftBracket) {
+                    self.open_comprehension();
+                }
+            }
+            Token::RightParen | Token::RightBracket | Token::RightBrace | Token::RightPipeBrace => {
+                self.brackets.pop();
+                let depth = self.depth();
+                self.close_frames(|frame| {
+                    matches!(frame.kind, FrameKind::Lambda | FrameKind::Comprehension) && frame.depth > depth
+                });
+                if self.params_end == Some(self.i) {
+                    self.params_end = None;
+                }
+            }
+            Token::If | Token::For if self.in_comprehension() => {}
+            Token::If | Token::While => self.open(FrameKind::Block),
+            Token::Match => self.open(FrameKind::Match),
+            Token::Try => self.open(FrameKind::Try),
+            Token::For => {
+                self.open(FrameKind::Block);
+                let end = self.find_at_depth(self.i + 1, |t| matches!(t, Token::In));
+                self.declare_pattern(self.i + 1, end, SymbolKind::Variable, false);
+            }
+            Token::Catch => {
+                if self.frame().kind == FrameKind::Try {
+                    self.pop_frame();
+                }
+                self.open(FrameKind::Block);
+                if let Some(Token::Identifier(_)) = next {
+                    self.declare(self.i + 1, SymbolKind::Variable, false);
+                }
+            }
+            Token::End => {
+                self.close_frames(|frame| matches!(frame.kind, FrameKind::Lambda | FrameKind::Comprehension));
+                if self.frame().kind != FrameKind::Global {
+                    self.pop_frame();
+                }
+            }
+            Token::Fn if matches!(next, Some(Token::Identifier(_))) => {
+                let symbol = self.declare(self.i + 1, SymbolKind::Function, false);
+                self.open(FrameKind::Function);
+                let frame = self.frames.last_mut().unwrap();
+                frame.function = Some(symbol);
+                self.analysis.symbols[symbol].body = Some(frame.scope);
+                self.params_end = self.matching_paren(self.i + 2);
+            }
+            Token::Fn | Token::Lambda => {
+                self.open(FrameKind::Lambda);
+                self.params_end = self.matching_paren(self.i + 1);
+            }
+            Token::Let => {
+                let end = self.find_at_depth(self.i + 1, |t| matches!(t, Token::Equal));
+                self.declare_pattern(self.i + 1, end, SymbolKind::Variable, true);
+            }
+            Token::Const if matches!(next, Some(Token::Identifier(_))) => {
+                self.declare(self.i + 1, SymbolKind::Constant, true);
+            }
+            Token::Export if matches!(next, Some(Token::Identifier(_))) => {
+                self.declare(self.i + 1, SymbolKind::Variable, true);
+            }
+            Token::Export | Token::Import => self.import(next),
+            Token::Struct if matches!(next, Some(Token::Identifier(_))) => {
+                self.declare(self.i + 1, SymbolKind::Struct, false);
+                // Field names aren't variables
+                if let Some(close) = self.matching(self.i + 2, &Token::LeftBrace, &Token::RightBrace) {
+                    self.handled.extend(self.i + 2..=close);
+                }
+            }
+            Token::Identifier(_) => self.identifier(),
+            Token::InterpolatedString(_) => self.interpolations(),
+            _ => {}
+        }
+    }
+
+    fn identifier(&mut self) {
+        let prev = if self.i > 0 { self.token(self.i - 1).cloned() } else { None };
+        let next = self.token(self.i + 1).cloned();
+
+        // Parameters: (a, b = 1, ...rest)
+        if self.params_end.is_some_and(|end| self.i < end)
+            && matches!(prev, Some(Token::LeftParen | Token::Comma | Token::Ellipsis))
+        {
+            self.declare(self.i, SymbolKind::Parameter, false);
+            return;
+        }
+        // A match arm binding the value: `n -> n * 2`
+        if self.frame().kind == FrameKind::Match
+            && matches!(prev, Some(Token::Newline))
+            && matches!(next, Some(Token::Arrow))
+        {
+            self.declare(self.i, SymbolKind::Variable, false);
+            return;
+        }
+        if let Some(Token::Dot) = prev {
+            let object = match self.i.checked_sub(2).and_then(|i| self.token(i)) {
+                Some(Token::Identifier(object)) => Some(object.clone()),
+                _ => None,
+            };
+            self.push_occurrence(self.i, None, object);
+            return;
+        }
+        // Dictionary keys, struct fields and `sep:` are labels; slices and steps aren't
+        let in_brackets = matches!(self.brackets.last(), Some(Token::LeftBracket));
+        if matches!(next, Some(Token::Colon)) && !in_brackets && !matches!(prev, Some(Token::DotDot | Token::DotDotEq)) {
+            return;
+        }
+        // Keyword arguments: f(name="Bob")
+        if matches!(next, Some(Token::Equal)) && matches!(self.brackets.last(), Some(Token::LeftParen)) {
+            return;
+        }
+        self.reference(self.i);
+    }
+
+    fn reference(&mut self, i: usize) {
+        let Token::Identifier(name) = &self.tokens[i].0 else {
+            return;
+        };
+        let symbol = self.frames.iter().rev().find_map(|frame| self.names[frame.scope].get(name).copied());
+        let index = self.push_occurrence(i, symbol, None);
+        if symbol.is_none() {
+            self.unresolved.push(index);
+        }
+    }
+
+    fn push_occurrence(&mut self, i: usize, symbol: Option<usize>, member_of: Option<String>) -> usize {
+        let (token, position, _) = &self.tokens[i];
+        let Token::Identifier(name) = token else {
+            unreachable!("occurrences are identifiers");
+        };
+        self.analysis.occurrences.push(Occurrence { name: name.clone(), position: position.clone(), symbol, member_of });
+        self.analysis.occurrences.len() - 1
+    }
+
+    /// Declare the identifier at token `i` in the current scope. Deferred
+    /// declarations become visible on the next line, so `let x = x + 1`
+    /// reads the outer `x`
+    fn declare(&mut self, i: usize, kind: SymbolKind, deferred: bool) -> usize {
+        let (Token::Identifier(name), position, _) = &self.tokens[i] else {
+            unreachable!("declarations are identifiers");
+        };
+        let scope = self.frame().scope;
+        let symbol = Symbol {
+            name: name.clone(),
+            kind,
+            position: position.clone(),
+            scope,
+            parent: self.frames.iter().rev().find_map(|f| f.function),
+            module: None,
+            body: None,
+        };
+        let name = name.clone();
+        self.analysis.symbols.push(symbol);
+        let index = self.analysis.symbols.len() - 1;
+        if deferred {
+            self.deferred.push((scope, index));
+        } else {
+            self.names[scope].insert(name, index);
+        }
+        self.push_occurrence(i, Some(index), None);
+        self.handled.insert(i);
+        index
+    }
+
+    /// Declare the names a `let` or `for` pattern binds, in tokens start..end
+    fn declare_pattern(&mut self, start: usize, end: usize, kind: SymbolKind, deferred: bool) {
+        for i in start..end {
+            if let Token::Identifier(_) = self.tokens[i].0 {
+                // `{name, age: years}` binds `years`; `age` is a key
+                if matches!(self.token(i + 1), Some(Token::Colon)) {
+                    self.handled.insert(i);
+                } else {
+                    self.declare(i, kind, deferred);
+                }
+            }
+        }
+    }
+
+    fn flush_deferred(&mut self) {
+        for (scope, symbol) in std::mem::take(&mut self.deferred) {
+            let name = self.analysis.symbols[symbol].name.clone();
+            self.names[scope].insert(name, symbol);
+        }
+    }
+
+    /// `import "m"`, `import "m" as name`, `import {a, b} from "m"` and `export {a} from "m"`
+    fn import(&mut self, next: Option<Token>) {
+        let path_index = match next {
+            Some(Token::String(_)) => self.i + 1,
+            Some(Token::LeftBrace | Token::Star) => {
+                let Some(from) = (self.i + 1..self.tokens.len()).find(|&i| matches!(self.tokens[i].0, Token::From | Token::Newline)) else {
+                    return;
+                };
+                from + 1
+            }
+            _ => return,
+        };
+        let Some((Token::String(path), position, text)) = self.tokens.get(path_index).cloned() else {
+            return;
+        };
+        let is_import = matches!(self.tokens[self.i].0, Token::Import);
+        self.analysis.imports.push(ModuleImport {
+            end: position.position + text.chars().count(),
+            position,
+            path: path.clone(),
+            whole: is_import && path_index == self.i + 1 && !matches!(self.token(path_index + 1), Some(Token::As)),
+        });
+
+        let mut names = Vec::new();
+        if path_index == self.i + 1 {
+            if let (Some(Token::As), Some(Token::Identifier(_))) = (self.token(path_index + 1), self.token(path_index + 2)) {
+                names.push((path_index + 2, SymbolKind::Module));
+            }
+        } else {
+            for i in self.i + 1..path_index {
+                if let Token::Identifier(_) = self.tokens[i].0 {
+                    names.push((i, SymbolKind::Import));
+                }
+            }
+        }
+        for (i, kind) in names {
+            let kind = if is_import { kind } else { SymbolKind::ReExport };
+            let index = self.declare(i, kind, false);
+            if kind == SymbolKind::ReExport {
+                let scope = self.frame().scope;
+                self.names[scope].remove(&self.analysis.symbols[index].name);
+            }
+            self.analysis.symbols[index].module = Some(path.clone());
+        }
+        self.handled.extend(self.i + 1..=path_index);
+    }
+
+    /// `$name` and `${expression}` inside a string use names too
+    fn interpolations(&mut self) {
+        let (_, start, text) = self.tokens[self.i].clone();
+        let chars: Vec<char> = text.chars().collect();
+        let mut position = start.clone();
+        let mut k = 0;
+        while k < chars.len() {
+            if chars[k] == '\\' {
+                advance(&mut position, chars[k]);
+                k += 1;
+            } else if chars[k] == '$' {
+                let (open, close) = if chars.get(k + 1) == Some(&'{') { (k + 2, '}') } else { (k + 1, '\0') };
+                let end = (open..chars.len())
+                    .find(|&j| if close == '}' { chars[j] == '}' } else { !(chars[j].is_alphanumeric() || chars[j] == '_') })
+                    .unwrap_or(chars.len());
+                let mut expression_start = position.clone();
+                for &c in &chars[k..open] {
+                    advance(&mut expression_start, c);
+                }
+                let expression: String = chars[open..end].iter().collect();
+                self.expression_references(&expression, &expression_start);
+                for &c in &chars[k..end] {
+                    advance(&mut position, c);
+                }
+                k = end;
+                continue;
+            }
+            if k < chars.len() {
+                advance(&mut position, chars[k]);
+            }
+            k += 1;
+        }
+    }
+
+    fn expression_references(&mut self, expression: &str, start: &SourcePosition) {
+        let tokens = Lexer::new(expression).tokenize_with_positions();
+        for (j, located) in tokens.iter().enumerate() {
+            let Token::Identifier(name) = &located.token else {
+                continue;
+            };
+            if j > 0 && matches!(tokens[j - 1].token, Token::Dot) {
+                continue;
+            }
+            let inner = &located.position;
+            let position = SourcePosition {
+                line: start.line + inner.line - 1,
+                column: if inner.line == 1 { start.column + inner.column - 1 } else { inner.column },
+                position: start.position + inner.position,
+            };
+            let symbol = self.frames.iter().rev().find_map(|frame| self.names[frame.scope].get(name).copied());
+            self.analysis.occurrences.push(Occurrence { name: name.clone(), position, symbol, member_of: None });
+            if symbol.is_none() {
+                self.unresolved.push(self.analysis.occurrences.len() - 1);
+            }
+        }
+    }
+
+    // ========== Scopes ==========
+
+    fn open(&mut self, kind: FrameKind) {
+        let parent = self.frame().scope;
+        self.analysis.scopes.push(Scope { start: self.tokens[self.i].1.position, end: usize::MAX, parent: Some(parent) });
+        self.names.push(HashMap::new());
+        let frame = Frame { kind, scope: self.analysis.scopes.len() - 1, depth: self.depth(), function: None };
+        self.frames.push(frame);
+    }
+
+    fn pop_frame(&mut self) {
+        self.flush_deferred();
+        if let Some(frame) = self.frames.pop() {
+            let (_, position, text) = &self.tokens[self.i];
+            self.analysis.scopes[frame.scope].end = position.position + text.chars().count();
+        }
+    }
+
+    fn close_frames(&mut self, close: impl Fn(&Frame) -> bool) {
+        while self.frames.len() > 1 && close(self.frame()) {
+            self.pop_frame();
+        }
+    }
+
+    fn close_lambdas(&mut self, at: impl Fn(&Frame, usize) -> bool) {
+        let depth = self.depth();
+        self.close_frames(|frame| frame.kind == FrameKind::Lambda && at(frame, depth));
+    }
+
+    fn in_comprehension(&self) -> bool {
+        let frame = self.frame();
+        frame.kind == FrameKind::Comprehension && frame.depth == self.depth()
+    }
+
+    /// `[x for x in xs]` and `(x for x in xs)` bind their names for the whole bracket
+    fn open_comprehension(&mut self) {
+        let Some(close) = self.matching_bracket(self.i) else {
+            return;
+        };
+        let depth = self.depth();
+        let mut inner = 0;
+        let mut for_index = None;
+        for j in self.i + 1..close {
+            match self.tokens[j].0 {
+                Token::LeftParen | Token::LeftBracket | Token::LeftBrace | Token::LeftBracePipe => inner += 1,
+                Token::RightParen | Token::RightBracket | Token::RightBrace | Token::RightPipeBrace => inner -= 1,
+                Token::For if inner == 0 => {
+                    for_index = Some(j);
+                    break;
+                }
+                _ => {}
+            }
+        }
+        let Some(for_index) = for_index else {
+            return;
+        };
+        self.open(FrameKind::Comprehension);
+        self.frames.last_mut().unwrap().depth = depth;
+        let in_index = (for_index..close).find(|&j| matches!(self.tokens[j].0, Token::In)).unwrap_or(close);
+        self.declare_pattern(for_index + 1, in_index, SymbolKind::Variable, false);
+    }
+
+    // ========== Token Search ==========
+
+    /// The first token from `start` matching at the current bracket depth, or
+    /// the end of the line
+    fn find_at_depth(&self, start: usize, found: impl Fn(&Token) -> bool) -> usize {
+        let mut inner = 0usize;
+        for j in start..self.tokens.len() {
+            let token = &self.tokens[j].0;
+            match token {
+                Token::LeftParen | Token::LeftBracket | Token::LeftBrace | Token::LeftBracePipe => inner += 1,
+                Token::RightParen | Token::RightBracket | Token::RightBrace | Token::RightPipeBrace => inner = inner.saturating_sub(1),
+                Token::Newline | Token::Eof => return j,
+                token if inner == 0 && found(token) => return j,
+                _ => {}
+            }
+        }
+        self.tokens.len()
+    }
+
+    fn matching_paren(&self, open: usize) -> Option<usize> {
+        self.matching(open, &Token::LeftParen, &Token::RightParen)
+    }
+
+    fn matching_bracket(&self, open: usize) -> Option<usize> {
+        match self.token(open)? {
+            Token::LeftParen => self.matching(open, &Token::LeftParen, &Token::RightParen),
+            Token::LeftBracket => self.matching(open, &Token::LeftBracket, &Token::RightBracket),
+            _ => None,
+        }
+    }
+
+    fn matching(&self, open: usize, opener: &Token, closer: &Token) -> Option<usize> {
+        if self.token(open) != Some(opener) {
+            return None;
+        }
+        let mut depth = 0;
+        for j in open..self.tokens.len() {
+            let token = &self.tokens[j].0;
+            if token == opener {
+                depth += 1;
+            } else if token == closer {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(j);
+                }
+            }
+        }
+        None
+    }
+}
+
+fn advance(position: &mut SourcePosition, c: char) {
+    position.position += 1;
+    if c == '\n' {
+        position.line += 1;
+        position.column = 1;
+    } else {
+        position.column += 1;
+    }
+}
+
+// ========== Signatures ==========
+
+/// How each function and struct is declared, by name: `fn greet(name, greeting="Hello")`
+pub fn signatures(statements: &[Stmt]) -> HashMap<String, String> {
+    let mut signatures = HashMap::new();
+    collect_signatures(statements, &mut signatures);
+    signatures
+}
+
+fn collect_signatures(statements: &[Stmt], signatures: &mut HashMap<String, String>) {
+    for statement in statements {
+        match statement {
+            Stmt::Function { name, params, body } | Stmt::ExportFunction { name, params, body } => {
+                let params: Vec<String> = params.iter().map(|p| p.to_string()).collect();
+                signatures.entry(name.clone()).or_insert_with(|| format!("fn {}({})", name, params.join(", ")));
+                collect_signatures(body, signatures);
+            }
+            Stmt::Struct { name, fields } => {
+                signatures.entry(name.clone()).or_insert_with(|| format!("struct {} {{ {} }}", name, fields.join(", ")));
+            }
+            Stmt::If { then_branch, elseif_branches, else_branch, .. } => {
+                collect_signatures(then_branch, signatures);
+                for branch in elseif_branches {
+                    collect_signatures(&branch.body, signatures);
+                }
+                collect_signatures(else_branch.as_deref().unwrap_or_default(), signatures);
+            }
+            Stmt::While { body, .. } | Stmt::For { body, .. } => collect_signatures(body, signatures),
+            Stmt::Try { body, catch_body, .. } => {
+                collect_signatures(body, signatures);
+                collect_signatures(catch_body, signatures);
+            }
+            _ => {}
+        }
+    }
+}
+
+// ========== Modules ==========
+
+/// A name a module exports, and where it's defined when that's in a file
+#[derive(Debug, Clone)]
+pub struct ModuleExport {
+    pub name: String,
+    pub source: Option<ModuleSource>,  // None for the modules written in Rust
+    pub position: Option<SourcePosition>,
+    pub signature: Option<String>,
+}
+
+/// Everything `import "module_path"` brings in, following re-exports
+pub fn module_exports(resolver: &ModuleResolver, module_path: &str, importer_dir: Option<&Path>) -> Vec<ModuleExport> {
+    collect_exports(resolver, module_path, importer_dir, 0)
+}
+
+fn collect_exports(resolver: &ModuleResolver, module_path: &str, importer_dir: Option<&Path>, depth: usize) -> Vec<ModuleExport> {
+    // Rust-only modules (base, numpy, csv, dataframe) have no file to point at
+    if let Some(module) = crate::stdlib::get_module(module_path) {
+        let mut names: Vec<String> = module.into_keys().collect();
+        names.sort();
+        return names.into_iter().map(|name| ModuleExport { name, source: None, position: None, signature: None }).collect();
+    }
+    // Re-export chains this deep are almost certainly a cycle
+    if depth > 16 {
+        return Vec::new();
+    }
+    let Ok(source) = resolver.resolve(module_path, importer_dir) else {
+        return Vec::new();
+    };
+    let Ok(text) = source.read() else {
+        return Vec::new();
+    };
+    let tokens = Lexer::new(&text).tokenize_with_positions().into_iter().map(|lt| (lt.token, lt.position)).collect();
+    let Ok(statements) = crate::parser::Parser::new(tokens).parse() else {
+        return Vec::new();
+    };
+    let analysis = Analysis::new(&text);
+    let signatures = signatures(&statements);
+
+    let mut exports = Vec::new();
+    for statement in &statements {
+        match statement {
+            Stmt::Export { name, .. } | Stmt::ExportFunction { name, .. } => exports.push(ModuleExport {
+                name: name.clone(),
+                source: Some(source.clone()),
+                position: analysis.top_level(name).map(|symbol| symbol.position.clone()),
+                signature: signatures.get(name).cloned(),
+            }),
+            Stmt::ExportFrom { module_path, items } => {
+                let nested = collect_exports(resolver, module_path, source.dir().as_deref(), depth + 1);
+                exports.extend(nested.into_iter().filter(|export| {
+                    items.as_ref().is_none_or(|items| items.contains(&export.name))
+                }));
+            }
+            _ => {}
+        }
+    }
+    exports
+}
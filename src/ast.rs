@@ -15,12 +15,29 @@ pub enum AssignTarget {
         object: String,
         field: String,
     },
+    /// Slice or multi-dimensional subscript: a[1, :] = value, a[0:2] = value
+    Subscript {
+        object: String,
+        indices: Vec<IndexComponent>,
+    },
+}
+
+/// One comma-separated part of a subscript like `a[1, 0:2, ::-1]`
+#[derive(Debug, Clone, PartialEq)]
+pub enum IndexComponent {
+    Index(Expr),
+    Slice {
+        from: Option<Box<Expr>>,
+        to: Option<Box<Expr>>,
+        step: Option<Box<Expr>>,
+    },
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum Expr {
     Integer(i64),
     Float(f64),
+    Decimal(rust_decimal::Decimal),
     String(String),
     InterpolatedString(Vec<InterpolationPart>),
     Bool(bool),
@@ -49,6 +66,8 @@ pub enum Expr {
     },
     Array(Vec<Expr>),
     UniqueArray(Vec<Expr>),  // {|1, 2, 3|} - array with unique values only
+    Tuple(Vec<Expr>),  // (a, b) or `return a, b`
+    Spread(Box<Expr>),  // ...items inside an array literal
     ListComprehension {
         expr: Box<Expr>,
         var: String,
@@ -61,7 +80,7 @@ pub enum Expr {
         iterable: Box<Expr>,
         condition: Option<Box<Expr>>,
     },
-    Dictionary(Vec<(String, Expr)>),
+    Dictionary(Vec<DictEntry>),  // Identifier keys are parsed as strings
     Index {
         object: Box<Expr>,
         index: Box<Expr>,
@@ -72,6 +91,11 @@ pub enum Expr {
         to: Option<Box<Expr>>,
         step: Option<Box<Expr>>,
     },
+    /// ndarray subscript with several components: a[1, :]
+    MultiIndex {
+        object: Box<Expr>,
+        indices: Vec<IndexComponent>,
+    },
     Member {
         object: Box<Expr>,
         property: String,
@@ -88,6 +112,7 @@ pub enum Expr {
         start: Box<Expr>,
         end: Box<Expr>,
         step: Option<Box<Expr>>,
+        inclusive: bool,  // a..=b
     },
     Lambda {
         params: Vec<String>,
@@ -151,12 +176,26 @@ pub struct ConditionalElseIfBranch {
 pub struct Parameter {
     pub name: String,
     pub default_value: Option<Expr>,
+    /// `...name` collects the remaining positional arguments into an array (last parameter only)
+    pub variadic: bool,
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum Argument {
     Positional(Expr),
     Keyword { name: String, value: Expr },
+    /// f(...args) - each array element becomes a positional argument
+    Spread(Expr),
+    /// f(**opts) - each dictionary entry becomes a keyword argument
+    KeywordSpread(Expr),
+}
+
+/// One entry of a dictionary literal
+#[derive(Debug, Clone, PartialEq)]
+pub enum DictEntry {
+    Pair(Expr, Expr),
+    /// {...defaults} - copies every entry of another dictionary
+    Spread(Expr),
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -172,10 +211,45 @@ pub enum Pattern {
     Wildcard, // _
 }
 
+/// Left-hand side of `let` and `for`: a plain name or a destructuring pattern
+#[derive(Debug, Clone, PartialEq)]
+pub enum Binding {
+    Name(String),
+    /// (q, r) - exactly as many values as names
+    Tuple(Vec<Binding>),
+    /// [head, ..tail] - the rest name collects the remaining values as an array
+    Array {
+        items: Vec<Binding>,
+        rest: Option<String>,
+    },
+    /// {name, age: years} - dictionary keys or struct fields
+    Dictionary(Vec<(String, Binding)>),
+}
+
+impl Binding {
+    /// Every variable name the binding introduces, left to right
+    pub fn names(&self) -> Vec<&str> {
+        match self {
+            Binding::Name(name) => vec![name.as_str()],
+            Binding::Tuple(items) => items.iter().flat_map(Binding::names).collect(),
+            Binding::Array { items, rest } => {
+                let mut names: Vec<&str> = items.iter().flat_map(Binding::names).collect();
+                names.extend(rest.as_deref());
+                names
+            }
+            Binding::Dictionary(entries) => entries.iter().flat_map(|(_, b)| b.names()).collect(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum Stmt {
     Expression(Expr),
     Let {
+        binding: Binding,
+        value: Expr,
+    },
+    Const {
         name: String,
         value: Expr,
     },
@@ -199,7 +273,7 @@ pub enum Stmt {
         body: Vec<Stmt>,
     },
     For {
-        var: String,
+        binding: Binding,
         iterable: Expr,
         body: Vec<Stmt>,
     },
@@ -217,6 +291,11 @@ pub enum Stmt {
         params: Vec<Parameter>,
         body: Vec<Stmt>,
     },
+    /// export {a, b} from "./sub", or export * from "./sub" (items is None)
+    ExportFrom {
+        module_path: String,
+        items: Option<Vec<String>>,
+    },
     Return(Option<Expr>),
     Print {
         values: Vec<Expr>,
@@ -242,6 +321,7 @@ impl fmt::Display for Expr {
         match self {
             Expr::Integer(n) => write!(f, "{}", n),
             Expr::Float(n) => write!(f, "{}", n),
+            Expr::Decimal(d) => write!(f, "{}d", d),
             Expr::String(s) => write!(f, "\"{}\"", s),
             Expr::InterpolatedString(parts) => {
                 write!(f, "\"")?;
@@ -292,6 +372,15 @@ impl fmt::Display for Expr {
                 }
                 write!(f, "]")
             }
+            Expr::Tuple(elements) => {
+                write!(f, "(")?;
+                for (i, elem) in elements.iter().enumerate() {
+                    if i > 0 { write!(f, ", ")?; }
+                    write!(f, "{}", elem)?;
+                }
+                if elements.len() == 1 { write!(f, ",")?; }
+                write!(f, ")")
+            }
             Expr::UniqueArray(elements) => {
                 write!(f, "{{|")?;
                 for (i, elem) in elements.iter().enumerate() {
@@ -314,14 +403,18 @@ impl fmt::Display for Expr {
                 }
                 write!(f, ">")
             }
-            Expr::Dictionary(pairs) => {
+            Expr::Dictionary(entries) => {
                 write!(f, "{{")?;
-                for (i, (key, value)) in pairs.iter().enumerate() {
+                for (i, entry) in entries.iter().enumerate() {
                     if i > 0 { write!(f, ", ")?; }
-                    write!(f, "{}: {}", key, value)?;
+                    match entry {
+                        DictEntry::Pair(key, value) => write!(f, "{}: {}", key, value)?,
+                        DictEntry::Spread(expr) => write!(f, "...{}", expr)?,
+                    }
                 }
                 write!(f, "}}")
             }
+            Expr::Spread(expr) => write!(f, "...{}", expr),
             Expr::Index { object, index } => {
                 write!(f, "{}[{}]", object, index)
             }
@@ -340,6 +433,10 @@ impl fmt::Display for Expr {
                 write!(f, "{}", parts.join(" "))?;
                 write!(f, "]")
             }
+            Expr::MultiIndex { object, indices } => {
+                let parts: Vec<String> = indices.iter().map(|c| c.to_string()).collect();
+                write!(f, "{}[{}]", object, parts.join(", "))
+            }
             Expr::Member { object, property } => {
                 write!(f, "{}.{}", object, property)
             }
@@ -354,10 +451,11 @@ impl fmt::Display for Expr {
                 }
                 write!(f, "}}")
             }
-            Expr::Range { start, end, step } => {
+            Expr::Range { start, end, step, inclusive } => {
+                write!(f, "{}{}{}", start, if *inclusive { "..=" } else { ".." }, end)?;
                 match step {
-                    Some(step) => write!(f, "{}..{}:{}", start, end, step),
-                    None => write!(f, "{}..{}", start, end),
+                    Some(step) => write!(f, " by {}", step),
+                    None => Ok(()),
                 }
             }
             Expr::Lambda { params, body } => {
@@ -394,6 +492,21 @@ impl fmt::Display for Expr {
     }
 }
 
+impl fmt::Display for IndexComponent {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            IndexComponent::Index(expr) => write!(f, "{}", expr),
+            IndexComponent::Slice { from, to, step } => {
+                if let Some(from_expr) = from { write!(f, "{}", from_expr)?; }
+                write!(f, ":")?;
+                if let Some(to_expr) = to { write!(f, "{}", to_expr)?; }
+                if let Some(step_expr) = step { write!(f, ":{}", step_expr)?; }
+                Ok(())
+            }
+        }
+    }
+}
+
 impl fmt::Display for BinaryOp {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let op = match self {
@@ -428,7 +541,9 @@ impl fmt::Display for UnaryOp {
 
 impl fmt::Display for Parameter {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        if let Some(default) = &self.default_value {
+        if self.variadic {
+            write!(f, "...{}", self.name)
+        } else if let Some(default) = &self.default_value {
             write!(f, "{}={}", self.name, default)
         } else {
             write!(f, "{}", self.name)
@@ -441,6 +556,34 @@ impl fmt::Display for Argument {
         match self {
             Argument::Positional(expr) => write!(f, "{}", expr),
             Argument::Keyword { name, value } => write!(f, "{}={}", name, value),
+            Argument::Spread(expr) => write!(f, "...{}", expr),
+            Argument::KeywordSpread(expr) => write!(f, "**{}", expr),
+        }
+    }
+}
+
+impl fmt::Display for Binding {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Binding::Name(name) => write!(f, "{}", name),
+            Binding::Tuple(items) => {
+                let parts: Vec<String> = items.iter().map(|b| b.to_string()).collect();
+                write!(f, "({})", parts.join(", "))
+            }
+            Binding::Array { items, rest } => {
+                let mut parts: Vec<String> = items.iter().map(|b| b.to_string()).collect();
+                if let Some(rest) = rest {
+                    parts.push(format!("..{}", rest));
+                }
+                write!(f, "[{}]", parts.join(", "))
+            }
+            Binding::Dictionary(entries) => {
+                let parts: Vec<String> = entries.iter().map(|(key, binding)| match binding {
+                    Binding::Name(name) if name == key => key.clone(),
+                    _ => format!("{}: {}", key, binding),
+                }).collect();
+                write!(f, "{{{}}}", parts.join(", "))
+            }
         }
     }
 }
@@ -449,12 +592,17 @@ impl fmt::Display for Stmt {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
             Stmt::Expression(expr) => write!(f, "{}", expr),
-            Stmt::Let { name, value } => write!(f, "let {} = {}", name, value),
+            Stmt::Let { binding, value } => write!(f, "let {} = {}", binding, value),
+            Stmt::Const { name, value } => write!(f, "const {} = {}", name, value),
             Stmt::Assign { target, value } => {
                 match target {
                     AssignTarget::Identifier(name) => write!(f, "{} = {}", name, value),
                     AssignTarget::Index { object, index } => write!(f, "{}[{}] = {}", object, index, value),
                     AssignTarget::Field { object, field } => write!(f, "{}.{} = {}", object, field, value),
+                    AssignTarget::Subscript { object, indices } => {
+                        let parts: Vec<String> = indices.iter().map(|c| c.to_string()).collect();
+                        write!(f, "{}[{}] = {}", object, parts.join(", "), value)
+                    }
                 }
             }
             Stmt::Function { name, params, .. } => {
@@ -467,7 +615,7 @@ impl fmt::Display for Stmt {
             }
             Stmt::If { condition, .. } => write!(f, "if {}", condition),
             Stmt::While { condition, .. } => write!(f, "while {}", condition),
-            Stmt::For { var, iterable, .. } => write!(f, "for {} in {}", var, iterable),
+            Stmt::For { binding, iterable, .. } => write!(f, "for {} in {}", binding, iterable),
             Stmt::Import { module_path, items, alias } => {
                 write!(f, "import")?;
                 if let Some(items) = items {
@@ -493,6 +641,13 @@ impl fmt::Display for Stmt {
                 }
                 write!(f, ")")
             }
+            Stmt::ExportFrom { module_path, items } => {
+                match items {
+                    Some(items) => write!(f, "export {{{}}}", items.join(", "))?,
+                    None => write!(f, "export *")?,
+                }
+                write!(f, " from \"{}\"", module_path)
+            }
             Stmt::Return(Some(expr)) => write!(f, "return {}", expr),
             Stmt::Return(None) => write!(f, "return"),
             Stmt::Print { values, sep, end } => {
@@ -0,0 +1,354 @@
+//! The registry of diagnostic codes. Each parser and linter check reports
+//! its own code; codes are never reused or renumbered, so they are safe to
+//! name in `# ject: allow(...)` comments and `[lints]` configuration.
+//! `ject explain CODE` prints an entry's explanation.
+
+use crate::diagnostic::DiagnosticLevel;
+
+pub struct CodeInfo {
+    pub code: &'static str,
+    pub level: DiagnosticLevel,  // Before any `deny` promotes a warning
+    pub title: &'static str,
+    pub explanation: &'static str,
+}
+
+pub fn lookup(code: &str) -> Option<&'static CodeInfo> {
+    CODES.iter().find(|info| info.code.eq_ignore_ascii_case(code))
+}
+
+pub static CODES: &[CodeInfo] = &[
+    // ========== Errors ==========
+    CodeInfo {
+        code: "E0001",
+        level: DiagnosticLevel::Error,
+        title: "use of undeclared variable",
+        explanation: "\
+A name was used before anything declared it.
+
+    print total      # error: `total` is never declared
+
+Declare variables with `let` (or `const`) before using them, and check the
+spelling; the diagnostic suggests similar names that are in scope:
+
+    let total = 0
+    print total
+",
+    },
+    CodeInfo {
+        code: "E0002",
+        level: DiagnosticLevel::Error,
+        title: "syntax error",
+        explanation: "\
+The parser found a token it did not expect. The message says what it was
+looking for and what it found instead.
+
+    let = 5          # error: expected identifier after 'let'
+
+Parse errors stop the file from being linted or run, so fix the first one
+reported and check again.
+",
+    },
+    CodeInfo {
+        code: "E0003",
+        level: DiagnosticLevel::Error,
+        title: "missing `end`",
+        explanation: "\
+Blocks opened by `fn`, `if`, `while`, `for`, `try` and `match` are
+closed with `end`. This block reached another statement or the end of the
+file without one.
+
+    if x > 0 then
+        print \"positive\"
+    # error: expected 'end'
+
+Add the missing `end`:
+
+    if x > 0 then
+        print \"positive\"
+    end
+",
+    },
+    CodeInfo {
+        code: "E0004",
+        level: DiagnosticLevel::Error,
+        title: "invalid assignment target",
+        explanation: "\
+Only variables, indexes of variables and fields of variables can be assigned
+to.
+
+    get_items()[0] = 1    # error: the left side is a call
+
+Assign to a variable first, then update it:
+
+    let items = get_items()
+    items[0] = 1
+",
+    },
+    CodeInfo {
+        code: "E0005",
+        level: DiagnosticLevel::Error,
+        title: "malformed index or slice",
+        explanation: "\
+Square brackets after a value take an index (`a[0]`), several indexes
+(`m[i, j]`) or a slice (`a[1:3]`, `a[from:1 to:3 step:2]`). They cannot be
+empty, and slice parts need their `:`.
+
+    print a[]              # error: empty brackets
+    print a[from 1 to:3]   # error: expected ':' after 'from'
+",
+    },
+    CodeInfo {
+        code: "E0006",
+        level: DiagnosticLevel::Error,
+        title: "malformed destructuring pattern",
+        explanation: "\
+Destructuring patterns bind names from arrays (`[a, b, ..rest]`) and
+dictionaries (`{name, age: years}`). A `..rest` binding must come last and
+be followed by a name.
+
+    let [..rest, last] = items     # error: '..rest' must be the last item
+
+Reorder the pattern, or index the array directly:
+
+    let last = items[len(items) - 1]
+",
+    },
+    CodeInfo {
+        code: "E0007",
+        level: DiagnosticLevel::Error,
+        title: "malformed parameter list",
+        explanation: "\
+Parameters are names, optionally with a default (`name = value`). A
+`...rest` parameter collects the remaining arguments, so it must be last.
+
+    fn log(...parts, level)      # error: '...' parameter must be last
+
+Move the variadic parameter to the end:
+
+    fn log(level, ...parts)
+",
+    },
+    CodeInfo {
+        code: "E0008",
+        level: DiagnosticLevel::Error,
+        title: "malformed import or export",
+        explanation: "\
+Module paths are strings, and imported or exported names are identifiers:
+
+    import \"math\"
+    import \"math\" as m
+    from \"math\" import sqrt, PI
+    export fn area(r) ... end
+    export {Circle, area} from \"./shapes\"
+
+    import math          # error: expected module path string
+",
+    },
+    CodeInfo {
+        code: "E0009",
+        level: DiagnosticLevel::Error,
+        title: "redeclared constant",
+        explanation: "\
+A `const` cannot be declared again in the same scope.
+
+    const LIMIT = 10
+    let LIMIT = 20       # error
+
+Pick a different name, or declare the first one with `let`.
+",
+    },
+    CodeInfo {
+        code: "E0010",
+        level: DiagnosticLevel::Error,
+        title: "assignment to a constant",
+        explanation: "\
+Constants, and names brought in by `import`, are read-only.
+
+    const LIMIT = 10
+    LIMIT = 20           # error
+    LIMIT += 1           # error
+
+Copy the value into a variable to change it:
+
+    let limit = LIMIT
+    limit += 1
+",
+    },
+    CodeInfo {
+        code: "E0011",
+        level: DiagnosticLevel::Error,
+        title: "assignment to undeclared variable",
+        explanation: "\
+Assignment only changes existing variables; it never creates one.
+
+    count = 0            # error: `count` was never declared
+
+Declare it with `let` first:
+
+    let count = 0
+    count = count + 1
+",
+    },
+    CodeInfo {
+        code: "E0012",
+        level: DiagnosticLevel::Error,
+        title: "index or field assignment on undeclared variable",
+        explanation: "\
+An indexed or field assignment updates a value that must already exist.
+
+    scores[0] = 10       # error: `scores` was never declared
+    point.x = 1          # error: `point` was never declared
+
+Declare the array, dictionary or struct first:
+
+    let scores = [0, 0, 0]
+    scores[0] = 10
+",
+    },
+    CodeInfo {
+        code: "E0013",
+        level: DiagnosticLevel::Error,
+        title: "`return` outside of function",
+        explanation: "\
+`return` ends the function it is in, so it cannot appear at the top level of
+a script or module.
+
+    return 5             # error
+
+To stop a script early, call `exit()` from the system module.
+",
+    },
+    CodeInfo {
+        code: "E0014",
+        level: DiagnosticLevel::Error,
+        title: "import of a private name",
+        explanation: "\
+Only names a module exports can be imported. Top-level names it defines
+without `export` are private to it.
+
+    # shapes.ject
+    fn helper() ... end
+    export fn area(r) ... end
+
+    # main.ject
+    from \"shapes\" import helper    # error: `helper` is private
+
+Export the name from the module if other files need it.
+",
+    },
+    CodeInfo {
+        code: "E0015",
+        level: DiagnosticLevel::Error,
+        title: "use of undeclared struct",
+        explanation: "\
+`new Name {...}` needs a `struct Name` declaration (or an import of one).
+
+    let p = new Point {x: 1, y: 2}   # error: no `struct Point`
+
+Declare the struct first:
+
+    struct Point {
+        x,
+        y
+    }
+",
+    },
+    CodeInfo {
+        code: "E0016",
+        level: DiagnosticLevel::Error,
+        title: "too many arguments",
+        explanation: "\
+A call passed more positional arguments than the function has parameters.
+
+    fn add(a, b) return a + b end
+    add(1, 2, 3)         # error
+
+Remove the extra arguments, or give the function a `...rest` parameter to
+accept any number.
+",
+    },
+    CodeInfo {
+        code: "E0017",
+        level: DiagnosticLevel::Error,
+        title: "argument given more than once",
+        explanation: "\
+Each parameter takes one argument, whether it is passed by position or by
+name.
+
+    fn greet(name, greeting = \"Hello\") ... end
+    greet(\"Ada\", name=\"Grace\")    # error: `name` is given twice
+",
+    },
+    CodeInfo {
+        code: "E0018",
+        level: DiagnosticLevel::Error,
+        title: "unknown keyword argument",
+        explanation: "\
+A keyword argument names a parameter the function does not have.
+
+    fn greet(name, greeting = \"Hello\") ... end
+    greet(\"Ada\", greting=\"Hi\")     # error: no parameter `greting`
+",
+    },
+    CodeInfo {
+        code: "E0019",
+        level: DiagnosticLevel::Error,
+        title: "missing required argument",
+        explanation: "\
+A parameter without a default needs an argument in every call.
+
+    fn greet(name, greeting = \"Hello\") ... end
+    greet(greeting=\"Hi\")          # error: `name` is missing
+
+Pass the argument, or give the parameter a default value.
+",
+    },
+    // ========== Warnings ==========
+    CodeInfo {
+        code: "W0001",
+        level: DiagnosticLevel::Warning,
+        title: "unused variable",
+        explanation: "\
+A variable is declared but never read.
+
+    let result = compute()     # warning: `result` is never used
+
+Remove it, or start its name with `_` to mark it as deliberately unused:
+
+    let _result = compute()
+",
+    },
+    CodeInfo {
+        code: "W0002",
+        level: DiagnosticLevel::Warning,
+        title: "variable redeclared in the same scope",
+        explanation: "\
+A second `let` for a name in the same scope replaces the first variable,
+which is usually a mistake for an assignment.
+
+    let total = 0
+    let total = 10       # warning
+
+Assign to the existing variable instead:
+
+    total = 10
+",
+    },
+    CodeInfo {
+        code: "W0003",
+        level: DiagnosticLevel::Warning,
+        title: "function redefined",
+        explanation: "\
+A function with this name is already defined, and the new definition
+replaces it for the rest of the program.
+
+    fn area(r) return PI * r * r end
+    fn area(w, h) return w * h end     # warning
+
+Give the functions different names, or allow the warning on that line if
+the replacement is deliberate:
+
+    fn area(w, h) return w * h end     # ject: allow(W0003)
+",
+    },
+];
@@ -0,0 +1,549 @@
+//! CSV Module for Ject (import "csv")
+//! RFC 4180 reading and writing with header dictionaries, type inference,
+//! and a streaming row reader for files too large to load at once
+
+use crate::interpreter::RuntimeError;
+use crate::value::Value;
+use std::cell::RefCell;
+use std::collections::{HashMap, VecDeque};
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::rc::Rc;
+
+/// Create csv module
+pub fn create_csv_module() -> HashMap<String, Value> {
+    let mut module = HashMap::new();
+
+    module.insert("parse".to_string(), Value::BuiltinFunction("csv_parse".to_string()));
+    module.insert("read".to_string(), Value::BuiltinFunction("csv_read".to_string()));
+    module.insert("rows".to_string(), Value::BuiltinFunction("csv_rows".to_string()));
+    module.insert("stringify".to_string(), Value::BuiltinFunction("csv_stringify".to_string()));
+    module.insert("write".to_string(), Value::BuiltinFunction("csv_write".to_string()));
+
+    module
+}
+
+pub fn call_csv_function(name: &str, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    match name {
+        "csv_parse" => csv_parse(args),
+        "csv_read" => csv_read(args),
+        "csv_rows" => csv_rows(args),
+        "csv_stringify" => csv_stringify(args),
+        "csv_write" => csv_write(args),
+        _ => Err(RuntimeError {
+            message: format!("Unknown csv function: {}", name),
+        }),
+    }
+}
+
+// ============================================================================
+// Options
+// ============================================================================
+
+/// Settings taken from the optional options dictionary, e.g.
+/// `{"delimiter": ";", "quote": "'", "header": false, "infer_types": false}`
+struct CsvOptions {
+    delimiter: char,
+    quote: char,
+    header: bool,
+    infer_types: bool,
+    columns: Option<Vec<String>>,
+    newline: String,
+}
+
+impl CsvOptions {
+    fn from_value(v: Option<&Value>, fn_name: &str) -> Result<Self, RuntimeError> {
+        let mut options = CsvOptions {
+            delimiter: ',',
+            quote: '"',
+            header: true,
+            infer_types: true,
+            columns: None,
+            newline: "\n".to_string(),
+        };
+        let map = match v {
+            None | Some(Value::Nil) => return Ok(options),
+            Some(Value::Dictionary(map)) => map,
+            Some(other) => return Err(RuntimeError {
+                message: format!("{}() options must be a dictionary, got {}", fn_name, other.type_name()),
+            }),
+        };
+
+        for (key, value) in map {
+            match (key.as_str().unwrap_or_default(), value) {
+                ("delimiter", v) => options.delimiter = single_char(v, fn_name, "delimiter")?,
+                ("quote", v) => options.quote = single_char(v, fn_name, "quote")?,
+                ("header", v) => options.header = v.is_truthy(),
+                ("infer_types", v) => options.infer_types = v.is_truthy(),
+                ("newline", Value::String(s)) => options.newline = s.clone(),
+                ("columns", Value::Array(names)) => {
+                    options.columns = Some(names.iter().map(field_text).collect());
+                }
+                _ => return Err(RuntimeError {
+                    message: format!(
+                        "{}() unknown or invalid option '{}' (expected delimiter, quote, header, infer_types, columns or newline)",
+                        fn_name, key.display()
+                    ),
+                }),
+            }
+        }
+
+        if options.delimiter == options.quote {
+            return Err(RuntimeError {
+                message: format!("{}() delimiter and quote must be different characters", fn_name),
+            });
+        }
+        Ok(options)
+    }
+}
+
+fn single_char(v: &Value, fn_name: &str, option: &str) -> Result<char, RuntimeError> {
+    match v {
+        Value::String(s) if s.chars().count() == 1 => Ok(s.chars().next().unwrap()),
+        _ => Err(RuntimeError {
+            message: format!("{}() {} must be a single character string", fn_name, option),
+        }),
+    }
+}
+
+// ============================================================================
+// Reading
+// ============================================================================
+
+/// parse(text, options) - all rows of a CSV string
+fn csv_parse(args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let text = match args.first() {
+        Some(Value::String(text)) => text,
+        _ => return Err(RuntimeError {
+            message: "parse() requires a CSV string".to_string(),
+        }),
+    };
+    let options = CsvOptions::from_value(args.get(1), "parse")?;
+    rows_to_value(parse_records(text, &options)?, &options)
+}
+
+/// read(path, options) - all rows of a CSV file
+fn csv_read(args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let path = path_arg(args.first(), "read")?;
+    let options = CsvOptions::from_value(args.get(1), "read")?;
+    let text = std::fs::read_to_string(&path).map_err(|_| RuntimeError {
+        message: format!("Failed to read file: {}", path),
+    })?;
+    rows_to_value(parse_records(&text, &options)?, &options)
+}
+
+/// rows(path, options) - a reader that a `for` loop pulls one row at a time
+fn csv_rows(args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let path = path_arg(args.first(), "rows")?;
+    let options = CsvOptions::from_value(args.get(1), "rows")?;
+    let file = File::open(&path).map_err(|_| RuntimeError {
+        message: format!("Failed to read file: {}", path),
+    })?;
+    Ok(Value::CsvReader(CsvReader(Rc::new(RefCell::new(RowStream {
+        path,
+        reader: BufReader::new(file),
+        parser: RecordParser::new(&options),
+        options,
+        header: None,
+        rows: 0,
+        at_end: false,
+    })))))
+}
+
+/// A parsed field; quoted fields are always kept as strings
+struct Field {
+    text: String,
+    quoted: bool,
+}
+
+/// All records of a CSV string
+fn parse_records(text: &str, options: &CsvOptions) -> Result<Vec<Vec<Field>>, RuntimeError> {
+    let mut parser = RecordParser::new(options);
+    parser.feed(text)?;
+    parser.finish()?;
+    Ok(parser.records.into())
+}
+
+/// RFC 4180: fields separated by the delimiter, records by CRLF or LF, quoted fields
+/// may contain delimiters, newlines and doubled quotes. Blank lines are skipped.
+/// Text can arrive in pieces, so files read whole and streamed parse the same way.
+struct RecordParser {
+    delimiter: char,
+    quote: char,
+    records: VecDeque<Vec<Field>>,
+    record: Vec<Field>,
+    field: String,
+    quoted: bool,
+    state: ParseState,
+    line: usize,
+    quote_line: usize,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum ParseState {
+    Unquoted,
+    InQuotes,
+    /// After a quote inside a quoted field: doubled, or the closing quote
+    QuoteInQuotes,
+    /// After a CR ending a record, where a following LF belongs to it
+    AfterCr,
+}
+
+impl RecordParser {
+    fn new(options: &CsvOptions) -> Self {
+        RecordParser {
+            delimiter: options.delimiter,
+            quote: options.quote,
+            records: VecDeque::new(),
+            record: Vec::new(),
+            field: String::new(),
+            quoted: false,
+            state: ParseState::Unquoted,
+            line: 1,
+            quote_line: 1,
+        }
+    }
+
+    /// Parse more text; completed records are queued in `records`
+    fn feed(&mut self, text: &str) -> Result<(), RuntimeError> {
+        for c in text.chars() {
+            match self.state {
+                ParseState::InQuotes => {
+                    if c == self.quote {
+                        self.state = ParseState::QuoteInQuotes;
+                    } else {
+                        if c == '\n' {
+                            self.line += 1;
+                        }
+                        self.field.push(c);
+                    }
+                    continue;
+                }
+                ParseState::QuoteInQuotes if c == self.quote => {
+                    self.field.push(c);
+                    self.state = ParseState::InQuotes;
+                    continue;
+                }
+                ParseState::AfterCr if c == '\n' => {
+                    self.state = ParseState::Unquoted;
+                    continue;
+                }
+                _ => self.state = ParseState::Unquoted,
+            }
+
+            if c == self.quote && self.field.is_empty() && !self.quoted {
+                self.state = ParseState::InQuotes;
+                self.quoted = true;
+                self.quote_line = self.line;
+            } else if c == self.delimiter {
+                self.end_field();
+            } else if c == '\n' || c == '\r' {
+                if c == '\r' {
+                    self.state = ParseState::AfterCr;
+                }
+                self.line += 1;
+                self.end_field();
+                self.end_record();
+            } else if self.quoted {
+                return Err(RuntimeError {
+                    message: format!("CSV line {}: unexpected character '{}' after a closing quote", self.line, c),
+                });
+            } else {
+                self.field.push(c);
+            }
+        }
+        Ok(())
+    }
+
+    /// The end of the text: queue the last record if it had no line break
+    fn finish(&mut self) -> Result<(), RuntimeError> {
+        if self.state == ParseState::InQuotes {
+            return Err(RuntimeError {
+                message: format!("CSV line {}: unterminated quoted field", self.quote_line),
+            });
+        }
+        if !self.field.is_empty() || self.quoted || !self.record.is_empty() {
+            self.end_field();
+            self.end_record();
+        }
+        Ok(())
+    }
+
+    fn end_field(&mut self) {
+        self.record.push(Field { text: std::mem::take(&mut self.field), quoted: self.quoted });
+        self.quoted = false;
+    }
+
+    /// Queue the record unless the line was blank
+    fn end_record(&mut self) {
+        let record = std::mem::take(&mut self.record);
+        let blank = record.len() == 1 && record[0].text.is_empty() && !record[0].quoted;
+        if !blank {
+            self.records.push_back(record);
+        }
+    }
+}
+
+/// Column names from the columns option, or the first record when header is on
+fn take_header(records: &mut Vec<Vec<Field>>, options: &CsvOptions) -> Option<Vec<String>> {
+    match (&options.columns, options.header && !records.is_empty()) {
+        (Some(columns), _) => Some(columns.clone()),
+        (None, true) => Some(records.remove(0).into_iter().map(|f| f.text).collect()),
+        (None, false) => None,
+    }
+}
+
+fn rows_to_value(mut records: Vec<Vec<Field>>, options: &CsvOptions) -> Result<Value, RuntimeError> {
+    let header = take_header(&mut records, options);
+    let rows = records.into_iter()
+        .enumerate()
+        .map(|(i, record)| record_to_value(record, header.as_deref(), options, i + 1))
+        .collect::<Result<_, _>>()?;
+    Ok(Value::Array(rows))
+}
+
+/// A record as a dictionary keyed by the header, or as an array without one
+fn record_to_value(record: Vec<Field>, header: Option<&[String]>, options: &CsvOptions, row: usize) -> Result<Value, RuntimeError> {
+    let values = record.into_iter().map(|f| field_value(f, options.infer_types));
+    match header {
+        None => Ok(Value::Array(values.collect())),
+        Some(header) => {
+            let values: Vec<Value> = values.collect();
+            if values.len() != header.len() {
+                return Err(RuntimeError {
+                    message: format!("CSV row {} has {} fields but the header has {}", row, values.len(), header.len()),
+                });
+            }
+            Ok(Value::Dictionary(header.iter().map(|name| Value::String(name.clone())).zip(values).collect()))
+        }
+    }
+}
+
+/// Column names and typed rows of a CSV string, for modules that build their own
+/// tables. Without a header the columns are named "column_1", "column_2", ...
+pub(crate) fn parse_table(text: &str, options: Option<&Value>, fn_name: &str) -> Result<(Vec<String>, Vec<Vec<Value>>), RuntimeError> {
+    let options = CsvOptions::from_value(options, fn_name)?;
+    let mut records = parse_records(text, &options)?;
+    let header = take_header(&mut records, &options).unwrap_or_else(|| {
+        let width = records.first().map_or(0, Vec::len);
+        (1..=width).map(|i| format!("column_{}", i)).collect()
+    });
+    let rows = records.into_iter()
+        .enumerate()
+        .map(|(i, record)| {
+            if record.len() != header.len() {
+                return Err(RuntimeError {
+                    message: format!("CSV row {} has {} fields but the header has {}", i + 1, record.len(), header.len()),
+                });
+            }
+            Ok(record.into_iter().map(|f| field_value(f, options.infer_types)).collect())
+        })
+        .collect::<Result<_, _>>()?;
+    Ok((header, rows))
+}
+
+/// Unquoted fields become int, float, bool or nil (when empty) if they look like one,
+/// except numbers written with leading zeros
+fn field_value(field: Field, infer_types: bool) -> Value {
+    if field.quoted || !infer_types {
+        return Value::String(field.text);
+    }
+    let text = field.text.trim();
+    if text.is_empty() {
+        return Value::Nil;
+    }
+    // Leading zeros mark codes and IDs like 007 or 02134, which a number would lose
+    let digits = text.strip_prefix(['+', '-']).unwrap_or(text).as_bytes();
+    if digits.len() > 1 && digits[0] == b'0' && digits[1].is_ascii_digit() {
+        return Value::String(field.text);
+    }
+    if let Ok(n) = text.parse::<i64>() {
+        return Value::Integer(n);
+    }
+    // Require a digit so words like "inf" and "nan" stay strings
+    if text.bytes().any(|b| b.is_ascii_digit()) {
+        if let Ok(f) = text.parse::<f64>() {
+            return Value::Float(f);
+        }
+    }
+    match text {
+        "true" => Value::Bool(true),
+        "false" => Value::Bool(false),
+        _ => Value::String(field.text),
+    }
+}
+
+// ============================================================================
+// Streaming Reader
+// ============================================================================
+
+/// Lazily reads a CSV file row by row; shared so the value can be cloned freely.
+/// Each row is handed out once, so a second loop over the same reader sees no rows.
+#[derive(Clone)]
+pub struct CsvReader(Rc<RefCell<RowStream>>);
+
+struct RowStream {
+    path: String,
+    reader: BufReader<File>,
+    options: CsvOptions,
+    parser: RecordParser,
+    header: Option<Vec<String>>,
+    rows: usize,
+    at_end: bool,
+}
+
+impl CsvReader {
+    /// The next row, or None at the end of the file
+    pub fn next_row(&self) -> Result<Option<Value>, RuntimeError> {
+        let mut stream = self.0.borrow_mut();
+        loop {
+            let Some(record) = stream.read_record()? else {
+                return Ok(None);
+            };
+            if stream.header.is_none() {
+                if let Some(columns) = &stream.options.columns {
+                    stream.header = Some(columns.clone());
+                } else if stream.options.header {
+                    stream.header = Some(record.into_iter().map(|f| f.text).collect());
+                    continue;
+                }
+            }
+            stream.rows += 1;
+            let header = stream.header.clone();
+            return record_to_value(record, header.as_deref(), &stream.options, stream.rows).map(Some);
+        }
+    }
+
+    pub fn path(&self) -> String {
+        self.0.borrow().path.clone()
+    }
+}
+
+impl RowStream {
+    /// Feed the parser a line at a time until it completes a record
+    fn read_record(&mut self) -> Result<Option<Vec<Field>>, RuntimeError> {
+        let mut text = String::new();
+        loop {
+            if let Some(record) = self.parser.records.pop_front() {
+                return Ok(Some(record));
+            }
+            if self.at_end {
+                return Ok(None);
+            }
+            text.clear();
+            let read = self.reader.read_line(&mut text).map_err(|e| RuntimeError {
+                message: format!("Failed to read file: {} ({})", self.path, e),
+            })?;
+            if read == 0 {
+                self.at_end = true;
+                self.parser.finish()?;
+            } else {
+                self.parser.feed(&text)?;
+            }
+        }
+    }
+}
+
+impl std::fmt::Debug for CsvReader {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "CsvReader({})", self.0.borrow().path)
+    }
+}
+
+impl PartialEq for CsvReader {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+// ============================================================================
+// Writing
+// ============================================================================
+
+/// stringify(rows, options) - rows of arrays or dictionaries as CSV text
+fn csv_stringify(args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let rows = match args.first() {
+        Some(Value::Array(rows)) => rows,
+        _ => return Err(RuntimeError {
+            message: "stringify() requires an array of rows".to_string(),
+        }),
+    };
+    let options = CsvOptions::from_value(args.get(1), "stringify")?;
+    Ok(Value::String(format_rows(rows, &options)?))
+}
+
+/// write(path, rows, options)
+fn csv_write(args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let path = path_arg(args.first(), "write")?;
+    let rows = match args.get(1) {
+        Some(Value::Array(rows)) => rows,
+        _ => return Err(RuntimeError {
+            message: "write() requires a file path and an array of rows".to_string(),
+        }),
+    };
+    let options = CsvOptions::from_value(args.get(2), "write")?;
+    std::fs::write(&path, format_rows(rows, &options)?).map_err(|_| RuntimeError {
+        message: format!("Failed to write to file: {}", path),
+    })?;
+    Ok(Value::Nil)
+}
+
+/// Dictionary rows are written under a header (the `columns` option, or the keys of
+/// the first row in order); array rows are written as they are
+fn format_rows(rows: &[Value], options: &CsvOptions) -> Result<String, RuntimeError> {
+    let columns: Option<Vec<Value>> = match (&options.columns, rows.first()) {
+        (Some(columns), _) => Some(columns.iter().map(|c| Value::String(c.clone())).collect()),
+        (None, Some(Value::Dictionary(first))) => Some(first.keys().cloned().collect()),
+        _ => None,
+    };
+
+    let mut out = String::new();
+    if let (Some(columns), true) = (&columns, options.header) {
+        let header: Vec<String> = columns.iter().map(|c| quote_field(&field_text(c), options)).collect();
+        out.push_str(&header.join(&options.delimiter.to_string()));
+        out.push_str(&options.newline);
+    }
+
+    for (i, row) in rows.iter().enumerate() {
+        let fields: Vec<String> = match (row, &columns) {
+            (Value::Array(items), _) => items.iter().map(field_text).collect(),
+            (Value::Dictionary(map), Some(columns)) => columns.iter()
+                .map(|c| map.get(c).map(field_text).unwrap_or_default())
+                .collect(),
+            (other, _) => return Err(RuntimeError {
+                message: format!("CSV row {} must be an array or a dictionary, got {}", i + 1, other.type_name()),
+            }),
+        };
+        let quoted: Vec<String> = fields.iter().map(|f| quote_field(f, options)).collect();
+        out.push_str(&quoted.join(&options.delimiter.to_string()));
+        out.push_str(&options.newline);
+    }
+    Ok(out)
+}
+
+fn field_text(v: &Value) -> String {
+    match v {
+        Value::String(s) => s.clone(),
+        Value::Nil => String::new(),
+        other => other.to_string(),
+    }
+}
+
+/// Quote a field only when it contains the delimiter, the quote or a line break
+fn quote_field(text: &str, options: &CsvOptions) -> String {
+    let needs_quotes = text.chars().any(|c| c == options.delimiter || c == options.quote || c == '\n' || c == '\r');
+    if !needs_quotes {
+        return text.to_string();
+    }
+    let q = options.quote.to_string();
+    format!("{}{}{}", q, text.replace(&q, &q.repeat(2)), q)
+}
+
+fn path_arg(v: Option<&Value>, fn_name: &str) -> Result<String, RuntimeError> {
+    match v {
+        Some(Value::String(path)) => Ok(path.clone()),
+        _ => Err(RuntimeError {
+            message: format!("{}() requires a string file path", fn_name),
+        }),
+    }
+}
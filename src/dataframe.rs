@@ -0,0 +1,803 @@
+//! DataFrame Module for Ject (import "dataframe")
+//! Labelled, typed columns built from rows, CSV or JSON, with selection,
+//! filtering, sorting, group-by aggregation and joins
+
+use crate::interpreter::{Callback, RuntimeError};
+use crate::value::Value;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::fmt;
+
+/// Create dataframe module
+pub fn create_dataframe_module() -> HashMap<String, Value> {
+    let mut module = HashMap::new();
+
+    // Construction
+    module.insert("from_rows".to_string(), Value::BuiltinFunction("df_from_rows".to_string()));
+    module.insert("from_columns".to_string(), Value::BuiltinFunction("df_from_columns".to_string()));
+    module.insert("from_csv".to_string(), Value::BuiltinFunction("df_from_csv".to_string()));
+    module.insert("read_csv".to_string(), Value::BuiltinFunction("df_read_csv".to_string()));
+    module.insert("from_json".to_string(), Value::BuiltinFunction("df_from_json".to_string()));
+    module.insert("read_json".to_string(), Value::BuiltinFunction("df_read_json".to_string()));
+
+    // Inspection
+    module.insert("columns".to_string(), Value::BuiltinFunction("df_columns".to_string()));
+    module.insert("dtypes".to_string(), Value::BuiltinFunction("df_dtypes".to_string()));
+    module.insert("shape".to_string(), Value::BuiltinFunction("df_shape".to_string()));
+    module.insert("column".to_string(), Value::BuiltinFunction("df_column".to_string()));
+    module.insert("rows".to_string(), Value::BuiltinFunction("df_rows".to_string()));
+    module.insert("head".to_string(), Value::BuiltinFunction("df_head".to_string()));
+
+    // Transformation
+    module.insert("select".to_string(), Value::BuiltinFunction("df_select".to_string()));
+    module.insert("filter".to_string(), Value::BuiltinFunction("df_filter".to_string()));
+    module.insert("sort_by".to_string(), Value::BuiltinFunction("df_sort_by".to_string()));
+    module.insert("group_by".to_string(), Value::BuiltinFunction("df_group_by".to_string()));
+    module.insert("join".to_string(), Value::BuiltinFunction("df_join".to_string()));
+
+    module
+}
+
+pub fn call_dataframe_function(name: &str, args: Vec<Value>, call: &mut Callback) -> Result<Value, RuntimeError> {
+    match name {
+        "df_from_rows" => df_from_rows(args),
+        "df_from_columns" => df_from_columns(args),
+        "df_from_csv" => df_from_csv(args),
+        "df_read_csv" => df_read_csv(args),
+        "df_from_json" => df_from_json(args),
+        "df_read_json" => df_read_json(args),
+        "df_columns" => df_columns(args),
+        "df_dtypes" => df_dtypes(args),
+        "df_shape" => df_shape(args),
+        "df_column" => df_column(args),
+        "df_rows" => df_rows(args),
+        "df_head" => df_head(args),
+        "df_select" => df_select(args),
+        "df_filter" => df_filter(args, call),
+        "df_sort_by" => df_sort_by(args, call),
+        "df_group_by" => df_group_by(args),
+        "df_join" => df_join(args),
+        _ => Err(RuntimeError {
+            message: format!("Unknown dataframe function: {}", name),
+        }),
+    }
+}
+
+// ============================================================================
+// DataFrame Structure
+// ============================================================================
+
+/// A table of equally long, named columns
+#[derive(Debug, Clone, PartialEq)]
+pub struct DataFrame {
+    columns: Vec<Column>,
+    rows: usize,
+}
+
+/// A column's values share one dtype; nil marks a missing value in any column
+#[derive(Debug, Clone, PartialEq)]
+struct Column {
+    name: String,
+    dtype: DType,
+    values: Vec<Value>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum DType {
+    Int,
+    Float,
+    Bool,
+    String,
+    Object,
+}
+
+impl DType {
+    /// Ints mixed with floats make a float column; any other mix is an object column
+    fn infer(values: &[Value]) -> DType {
+        let mut dtype = None;
+        for value in values {
+            let this = match value {
+                Value::Nil => continue,
+                Value::Integer(_) => DType::Int,
+                Value::Float(_) => DType::Float,
+                Value::Bool(_) => DType::Bool,
+                Value::String(_) => DType::String,
+                _ => return DType::Object,
+            };
+            dtype = Some(match (dtype, this) {
+                (None, this) => this,
+                (Some(current), this) if current == this => current,
+                (Some(DType::Int), DType::Float) | (Some(DType::Float), DType::Int) => DType::Float,
+                _ => return DType::Object,
+            });
+        }
+        dtype.unwrap_or(DType::Object)
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            DType::Int => "int",
+            DType::Float => "float",
+            DType::Bool => "boolean",
+            DType::String => "string",
+            DType::Object => "object",
+        }
+    }
+}
+
+impl Column {
+    fn new(name: String, mut values: Vec<Value>) -> Self {
+        let dtype = DType::infer(&values);
+        if dtype == DType::Float {
+            for value in values.iter_mut() {
+                if let Value::Integer(n) = value {
+                    *value = Value::Float(*n as f64);
+                }
+            }
+        }
+        Column { name, dtype, values }
+    }
+}
+
+impl DataFrame {
+    fn new(columns: Vec<(String, Vec<Value>)>, fn_name: &str) -> Result<Self, RuntimeError> {
+        let rows = columns.first().map_or(0, |(_, values)| values.len());
+        let mut seen = Vec::new();
+        for (name, values) in &columns {
+            if seen.contains(&name) {
+                return Err(RuntimeError {
+                    message: format!("{}() got duplicate column '{}'", fn_name, name),
+                });
+            }
+            if values.len() != rows {
+                return Err(RuntimeError {
+                    message: format!("{}() column '{}' has {} values but the first column has {}", fn_name, name, values.len(), rows),
+                });
+            }
+            seen.push(name);
+        }
+        Ok(DataFrame {
+            columns: columns.into_iter().map(|(name, values)| Column::new(name, values)).collect(),
+            rows,
+        })
+    }
+
+    /// Build from row-major data with the given column names
+    fn from_records(names: Vec<String>, records: Vec<Vec<Value>>, fn_name: &str) -> Result<Self, RuntimeError> {
+        let mut columns: Vec<Vec<Value>> = vec![Vec::with_capacity(records.len()); names.len()];
+        for record in records {
+            for (column, value) in columns.iter_mut().zip(record) {
+                column.push(value);
+            }
+        }
+        DataFrame::new(names.into_iter().zip(columns).collect(), fn_name)
+    }
+
+    /// Number of rows
+    pub fn len(&self) -> usize {
+        self.rows
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rows == 0
+    }
+
+    /// Row `i` as a dictionary keyed by column name
+    pub fn row(&self, i: usize) -> Value {
+        Value::Dictionary(self.columns.iter().map(|c| (Value::String(c.name.clone()), c.values[i].clone())).collect())
+    }
+
+    /// Every row as a dictionary
+    pub fn to_rows(&self) -> Value {
+        Value::Array((0..self.rows).map(|i| self.row(i)).collect())
+    }
+
+    fn column_index(&self, name: &str) -> Result<usize, RuntimeError> {
+        self.columns.iter().position(|c| c.name == name).ok_or_else(|| RuntimeError {
+            message: format!("Column '{}' not found in dataframe", name),
+        })
+    }
+
+    /// The rows at `indices`, in that order; dtypes are kept
+    fn take(&self, indices: &[usize]) -> DataFrame {
+        DataFrame {
+            columns: self.columns.iter()
+                .map(|c| Column {
+                    name: c.name.clone(),
+                    dtype: c.dtype,
+                    values: indices.iter().map(|&i| c.values[i].clone()).collect(),
+                })
+                .collect(),
+            rows: indices.len(),
+        }
+    }
+
+    /// Values of the given columns in row `i`
+    fn key(&self, columns: &[usize], i: usize) -> Vec<Value> {
+        columns.iter().map(|&c| self.columns[c].values[i].clone()).collect()
+    }
+}
+
+// ============================================================================
+// Display
+// ============================================================================
+
+/// Frames longer than this print their first and last rows around an ellipsis
+const MAX_DISPLAY_ROWS: usize = 20;
+
+impl fmt::Display for DataFrame {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let footer = format!("[{} rows x {} columns]", self.rows, self.columns.len());
+        if self.columns.is_empty() {
+            return write!(f, "{}", footer);
+        }
+
+        let shown: Vec<Option<usize>> = if self.rows > MAX_DISPLAY_ROWS {
+            (0..10).map(Some).chain(std::iter::once(None)).chain((self.rows - 5..self.rows).map(Some)).collect()
+        } else {
+            (0..self.rows).map(Some).collect()
+        };
+        let cells: Vec<Vec<String>> = self.columns.iter()
+            .map(|c| shown.iter().map(|row| row.map_or("...".to_string(), |i| cell_text(&c.values[i]))).collect())
+            .collect();
+        let widths: Vec<usize> = self.columns.iter()
+            .zip(&cells)
+            .map(|(c, column)| {
+                column.iter().map(|s| s.chars().count())
+                    .chain([c.name.chars().count(), c.dtype.name().len()])
+                    .max()
+                    .unwrap_or(0)
+            })
+            .collect();
+
+        let write_line = |f: &mut fmt::Formatter, texts: &[&str], right: &[bool]| -> fmt::Result {
+            write!(f, "|")?;
+            for ((text, width), right) in texts.iter().zip(&widths).zip(right) {
+                if *right {
+                    write!(f, " {:>width$} |", text, width = width)?;
+                } else {
+                    write!(f, " {:<width$} |", text, width = width)?;
+                }
+            }
+            writeln!(f)
+        };
+
+        let left = vec![false; self.columns.len()];
+        let numeric: Vec<bool> = self.columns.iter().map(|c| matches!(c.dtype, DType::Int | DType::Float)).collect();
+        let names: Vec<&str> = self.columns.iter().map(|c| c.name.as_str()).collect();
+        let dtypes: Vec<&str> = self.columns.iter().map(|c| c.dtype.name()).collect();
+        write_line(f, &names, &left)?;
+        write_line(f, &dtypes, &left)?;
+        write!(f, "|")?;
+        for width in &widths {
+            write!(f, "{}|", "-".repeat(width + 2))?;
+        }
+        writeln!(f)?;
+        for row in 0..shown.len() {
+            let texts: Vec<&str> = cells.iter().map(|column| column[row].as_str()).collect();
+            write_line(f, &texts, &numeric)?;
+        }
+        write!(f, "{}", footer)
+    }
+}
+
+/// Strings print bare, with newlines escaped so a cell stays on one line
+fn cell_text(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.replace('\n', "\\n"),
+        other => other.to_string(),
+    }
+}
+
+// ============================================================================
+// Construction
+// ============================================================================
+
+/// from_rows(rows, columns) - from an array of dictionaries (or of arrays with column names)
+fn df_from_rows(args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let rows = match args.first() {
+        Some(Value::Array(rows)) => rows,
+        _ => return Err(RuntimeError {
+            message: "from_rows() requires an array of rows".to_string(),
+        }),
+    };
+    let names = names_arg(args.get(1), "from_rows")?;
+    Ok(Value::DataFrame(frame_from_rows(rows, names, "from_rows")?))
+}
+
+/// Dictionary rows fill missing keys with nil; without explicit names the columns are
+/// the first row's keys in order, then any new keys from later rows
+fn frame_from_rows(rows: &[Value], names: Option<Vec<String>>, fn_name: &str) -> Result<DataFrame, RuntimeError> {
+    let names = match names {
+        Some(names) => names,
+        None if rows.iter().all(|row| matches!(row, Value::Dictionary(_))) => {
+            let mut names: Vec<String> = Vec::new();
+            for row in rows {
+                if let Value::Dictionary(map) = row {
+                    for key in map.keys() {
+                        let name = column_name(key, fn_name)?;
+                        if !names.contains(&name) {
+                            names.push(name);
+                        }
+                    }
+                }
+            }
+            names
+        }
+        None => {
+            let width = match rows.first() {
+                Some(Value::Array(values)) => values.len(),
+                _ => 0,
+            };
+            (1..=width).map(|i| format!("column_{}", i)).collect()
+        }
+    };
+
+    let mut records = Vec::with_capacity(rows.len());
+    for (i, row) in rows.iter().enumerate() {
+        match row {
+            Value::Dictionary(map) => {
+                records.push(names.iter().map(|name| map.get(name.as_str()).cloned().unwrap_or(Value::Nil)).collect());
+            }
+            Value::Array(values) if values.len() == names.len() => records.push(values.clone()),
+            Value::Array(values) => return Err(RuntimeError {
+                message: format!("{}() row {} has {} values but there are {} columns", fn_name, i, values.len(), names.len()),
+            }),
+            other => return Err(RuntimeError {
+                message: format!("{}() rows must be dictionaries or arrays, got {}", fn_name, other.type_name()),
+            }),
+        }
+    }
+    DataFrame::from_records(names, records, fn_name)
+}
+
+/// from_columns(columns, order) - from a dictionary of equally long arrays
+fn df_from_columns(args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let map = match args.first() {
+        Some(Value::Dictionary(map)) => map,
+        _ => return Err(RuntimeError {
+            message: "from_columns() requires a dictionary of column arrays".to_string(),
+        }),
+    };
+    let names = match names_arg(args.get(1), "from_columns")? {
+        Some(names) => names,
+        None => map.keys().map(|key| column_name(key, "from_columns")).collect::<Result<_, _>>()?,
+    };
+    let columns = names.into_iter()
+        .map(|name| match map.get(name.as_str()) {
+            Some(Value::Array(values)) => Ok((name, values.clone())),
+            Some(other) => Err(RuntimeError {
+                message: format!("from_columns() column '{}' must be an array, got {}", name, other.type_name()),
+            }),
+            None => Err(RuntimeError {
+                message: format!("Column '{}' not found in from_columns() input", name),
+            }),
+        })
+        .collect::<Result<_, _>>()?;
+    Ok(Value::DataFrame(DataFrame::new(columns, "from_columns")?))
+}
+
+/// from_csv(text, options) - options are the csv module's (delimiter, header, ...)
+fn df_from_csv(args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let text = match args.first() {
+        Some(Value::String(text)) => text,
+        _ => return Err(RuntimeError {
+            message: "from_csv() requires a CSV string".to_string(),
+        }),
+    };
+    let (names, records) = crate::csv::parse_table(text, args.get(1), "from_csv")?;
+    Ok(Value::DataFrame(DataFrame::from_records(names, records, "from_csv")?))
+}
+
+/// read_csv(path, options)
+fn df_read_csv(args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let text = read_file(args.first(), "read_csv")?;
+    let (names, records) = crate::csv::parse_table(&text, args.get(1), "read_csv")?;
+    Ok(Value::DataFrame(DataFrame::from_records(names, records, "read_csv")?))
+}
+
+/// from_json(text) - an array of objects (rows) or an object of arrays (columns)
+fn df_from_json(args: Vec<Value>) -> Result<Value, RuntimeError> {
+    match args.first() {
+        Some(Value::String(text)) => Ok(Value::DataFrame(frame_from_json(text, "from_json")?)),
+        _ => Err(RuntimeError {
+            message: "from_json() requires a JSON string".to_string(),
+        }),
+    }
+}
+
+/// read_json(path)
+fn df_read_json(args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let text = read_file(args.first(), "read_json")?;
+    Ok(Value::DataFrame(frame_from_json(&text, "read_json")?))
+}
+
+fn frame_from_json(text: &str, fn_name: &str) -> Result<DataFrame, RuntimeError> {
+    use crate::stdlib::json_to_ject_value;
+
+    let json = serde_json::from_str::<serde_json::Value>(text).map_err(|e| RuntimeError {
+        message: format!("Failed to parse JSON: {}", e),
+    })?;
+    let expected = || RuntimeError {
+        message: format!("{}() expects an array of objects or an object of arrays", fn_name),
+    };
+    match json {
+        serde_json::Value::Array(items) => {
+            let rows = items.into_iter()
+                .map(|item| match item {
+                    serde_json::Value::Object(obj) => Ok(Value::Dictionary(
+                        obj.into_iter().map(|(k, v)| (Value::String(k), json_to_ject_value(v))).collect(),
+                    )),
+                    _ => Err(expected()),
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            frame_from_rows(&rows, None, fn_name)
+        }
+        serde_json::Value::Object(obj) => {
+            let columns = obj.into_iter()
+                .map(|(name, values)| match values {
+                    serde_json::Value::Array(values) => Ok((name, values.into_iter().map(json_to_ject_value).collect())),
+                    _ => Err(expected()),
+                })
+                .collect::<Result<_, _>>()?;
+            DataFrame::new(columns, fn_name)
+        }
+        _ => Err(expected()),
+    }
+}
+
+// ============================================================================
+// Inspection
+// ============================================================================
+
+/// columns(frame) - column names in order
+fn df_columns(args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let frame = frame_arg(args.first(), "columns")?;
+    Ok(Value::Array(frame.columns.iter().map(|c| Value::String(c.name.clone())).collect()))
+}
+
+/// dtypes(frame) - {"name": "string", "age": "int", ...}
+fn df_dtypes(args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let frame = frame_arg(args.first(), "dtypes")?;
+    Ok(Value::Dictionary(
+        frame.columns.iter().map(|c| (Value::String(c.name.clone()), Value::String(c.dtype.name().to_string()))).collect(),
+    ))
+}
+
+/// shape(frame) - [rows, columns]
+fn df_shape(args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let frame = frame_arg(args.first(), "shape")?;
+    Ok(Value::Array(vec![
+        Value::Integer(frame.rows as i64),
+        Value::Integer(frame.columns.len() as i64),
+    ]))
+}
+
+/// column(frame, name) - one column's values as an array
+fn df_column(args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let frame = frame_arg(args.first(), "column")?;
+    let name = match args.get(1) {
+        Some(Value::String(name)) => name,
+        _ => return Err(RuntimeError {
+            message: "column() requires a column name".to_string(),
+        }),
+    };
+    Ok(Value::Array(frame.columns[frame.column_index(name)?].values.clone()))
+}
+
+/// rows(frame) - every row as a dictionary
+fn df_rows(args: Vec<Value>) -> Result<Value, RuntimeError> {
+    Ok(frame_arg(args.first(), "rows")?.to_rows())
+}
+
+/// head(frame, n) - the first n rows (default 5)
+fn df_head(args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let frame = frame_arg(args.first(), "head")?;
+    let n = match args.get(1) {
+        None | Some(Value::Nil) => 5,
+        Some(Value::Integer(n)) if *n >= 0 => *n as usize,
+        _ => return Err(RuntimeError {
+            message: "head() row count must be a non-negative integer".to_string(),
+        }),
+    };
+    let indices: Vec<usize> = (0..n.min(frame.rows)).collect();
+    Ok(Value::DataFrame(frame.take(&indices)))
+}
+
+// ============================================================================
+// Transformation
+// ============================================================================
+
+/// select(frame, names) - a frame with only the named columns, in that order
+fn df_select(args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let frame = frame_arg(args.first(), "select")?;
+    let names = column_names_arg(args.get(1), "select")?;
+    let columns = names.iter()
+        .map(|name| frame.column_index(name).map(|i| frame.columns[i].clone()))
+        .collect::<Result<_, _>>()?;
+    Ok(Value::DataFrame(DataFrame { columns, rows: frame.rows }))
+}
+
+/// filter(frame, fn(row)) - the rows for which the function returns a truthy value
+fn df_filter(args: Vec<Value>, call: &mut Callback) -> Result<Value, RuntimeError> {
+    let frame = frame_arg(args.first(), "filter")?;
+    let predicate = function_arg(args.get(1), "filter")?;
+    let mut keep = Vec::new();
+    for i in 0..frame.rows {
+        if call(predicate, vec![frame.row(i)])?.is_truthy() {
+            keep.push(i);
+        }
+    }
+    Ok(Value::DataFrame(frame.take(&keep)))
+}
+
+/// sort_by(frame, key, descending) - key is a column name, an array of names, or
+/// fn(row) returning the sort key. The sort is stable and nil always sorts last.
+fn df_sort_by(args: Vec<Value>, call: &mut Callback) -> Result<Value, RuntimeError> {
+    let frame = frame_arg(args.first(), "sort_by")?;
+    let keys: Vec<Vec<Value>> = match args.get(1) {
+        Some(key @ (Value::Lambda { .. } | Value::Function { .. } | Value::ModuleFunction { .. })) => {
+            (0..frame.rows).map(|i| Ok(vec![call(key, vec![frame.row(i)])?])).collect::<Result<_, RuntimeError>>()?
+        }
+        key => {
+            let columns = column_names_arg(key, "sort_by")?
+                .iter()
+                .map(|name| frame.column_index(name))
+                .collect::<Result<Vec<_>, _>>()?;
+            (0..frame.rows).map(|i| frame.key(&columns, i)).collect()
+        }
+    };
+    let descending = args.get(2).is_some_and(Value::is_truthy);
+
+    let mut order: Vec<usize> = (0..frame.rows).collect();
+    order.sort_by(|&a, &b| {
+        keys[a].iter().zip(&keys[b])
+            .map(|(x, y)| match (x, y) {
+                (Value::Nil, Value::Nil) => Ordering::Equal,
+                (Value::Nil, _) => Ordering::Greater,
+                (_, Value::Nil) => Ordering::Less,
+                _ if descending => y.partial_cmp(x).unwrap_or(Ordering::Equal),
+                _ => x.partial_cmp(y).unwrap_or(Ordering::Equal),
+            })
+            .find(|o| *o != Ordering::Equal)
+            .unwrap_or(Ordering::Equal)
+    });
+    Ok(Value::DataFrame(frame.take(&order)))
+}
+
+/// group_by(frame, keys, aggregations) - one row per distinct key, in order of first
+/// appearance. Aggregations map a column to "sum", "mean", "count", "min" or "max";
+/// an array of them names the results "<column>_<aggregation>".
+fn df_group_by(args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let frame = frame_arg(args.first(), "group_by")?;
+    let key_names = column_names_arg(args.get(1), "group_by")?;
+    let key_columns = key_names.iter().map(|name| frame.column_index(name)).collect::<Result<Vec<_>, _>>()?;
+    let specs = match args.get(2) {
+        Some(Value::Dictionary(map)) => {
+            let mut specs = Vec::new();
+            for (name, spec) in map {
+                let name = column_name(name, "group_by")?;
+                let column = frame.column_index(&name)?;
+                match spec {
+                    Value::String(agg) => specs.push((name, column, agg.clone())),
+                    Value::Array(aggs) => {
+                        for agg in aggs {
+                            let agg = match agg {
+                                Value::String(agg) => agg,
+                                other => return Err(RuntimeError {
+                                    message: format!("group_by() aggregation must be a string, got {}", other.type_name()),
+                                }),
+                            };
+                            specs.push((format!("{}_{}", name, agg), column, agg.clone()));
+                        }
+                    }
+                    other => return Err(RuntimeError {
+                        message: format!("group_by() aggregation must be a string or array, got {}", other.type_name()),
+                    }),
+                }
+            }
+            specs
+        }
+        _ => return Err(RuntimeError {
+            message: "group_by() requires a dictionary of aggregations, e.g. {\"amount\": \"sum\"}".to_string(),
+        }),
+    };
+
+    // Keys compare by value, like dictionary keys; the map keeps first-appearance order
+    let mut groups: indexmap::IndexMap<Vec<Value>, Vec<usize>> = indexmap::IndexMap::new();
+    for i in 0..frame.rows {
+        groups.entry(frame.key(&key_columns, i)).or_default().push(i);
+    }
+
+    let mut columns: Vec<(String, Vec<Value>)> = key_names.iter()
+        .enumerate()
+        .map(|(k, name)| (name.clone(), groups.iter().map(|(key, _)| key[k].clone()).collect()))
+        .collect();
+    for (name, column, agg) in specs {
+        let source = &frame.columns[column];
+        let values = groups.iter()
+            .map(|(_, rows)| aggregate(source, rows, &agg))
+            .collect::<Result<_, _>>()?;
+        columns.push((name, values));
+    }
+    Ok(Value::DataFrame(DataFrame::new(columns, "group_by")?))
+}
+
+/// Aggregate one group of a column; nil values are skipped
+fn aggregate(column: &Column, rows: &[usize], agg: &str) -> Result<Value, RuntimeError> {
+    let values: Vec<&Value> = rows.iter().map(|&i| &column.values[i]).filter(|v| !matches!(v, Value::Nil)).collect();
+    let numeric = || -> Result<Vec<f64>, RuntimeError> {
+        values.iter()
+            .map(|v| match v {
+                Value::Integer(n) => Ok(*n as f64),
+                Value::Float(x) => Ok(*x),
+                other => Err(RuntimeError {
+                    message: format!("group_by() cannot {} column '{}' containing {}", agg, column.name, other.type_name()),
+                }),
+            })
+            .collect()
+    };
+    match agg {
+        "count" => Ok(Value::Integer(values.len() as i64)),
+        "sum" if column.dtype == DType::Int => values.iter()
+            .try_fold(0i64, |total, v| match v {
+                Value::Integer(n) => total.checked_add(*n),
+                _ => Some(total),
+            })
+            .map(Value::Integer)
+            .ok_or_else(|| RuntimeError {
+                message: format!("group_by() integer overflow in the sum of column '{}'", column.name),
+            }),
+        "sum" => Ok(Value::Float(numeric()?.iter().sum())),
+        "mean" => {
+            let numbers = numeric()?;
+            if numbers.is_empty() {
+                Ok(Value::Nil)
+            } else {
+                Ok(Value::Float(numbers.iter().sum::<f64>() / numbers.len() as f64))
+            }
+        }
+        "min" | "max" => {
+            let pick = if agg == "min" { Ordering::Less } else { Ordering::Greater };
+            Ok(values.into_iter()
+                .reduce(|best, v| if v.partial_cmp(best) == Some(pick) { v } else { best })
+                .cloned()
+                .unwrap_or(Value::Nil))
+        }
+        _ => Err(RuntimeError {
+            message: format!("group_by() unknown aggregation '{}' (expected sum, mean, count, min or max)", agg),
+        }),
+    }
+}
+
+/// join(left, right, on, how) - rows whose `on` columns are equal, combined. `how` is
+/// "inner" (default) or "left", which keeps unmatched left rows with nil right values.
+/// Right columns whose names clash with the left get a "_right" suffix; nil keys never match.
+fn df_join(args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let left = frame_arg(args.first(), "join")?;
+    let right = frame_arg(args.get(1), "join")?;
+    let on = column_names_arg(args.get(2), "join")?;
+    let keep_unmatched = match args.get(3) {
+        None | Some(Value::Nil) => false,
+        Some(Value::String(how)) if how == "inner" => false,
+        Some(Value::String(how)) if how == "left" => true,
+        _ => return Err(RuntimeError {
+            message: "join() how must be \"inner\" or \"left\"".to_string(),
+        }),
+    };
+    let left_keys = on.iter().map(|name| left.column_index(name)).collect::<Result<Vec<_>, _>>()?;
+    let right_keys = on.iter().map(|name| right.column_index(name)).collect::<Result<Vec<_>, _>>()?;
+
+    let mut index: indexmap::IndexMap<Vec<Value>, Vec<usize>> = indexmap::IndexMap::new();
+    for i in 0..right.rows {
+        let key = right.key(&right_keys, i);
+        if !key.contains(&Value::Nil) {
+            index.entry(key).or_default().push(i);
+        }
+    }
+
+    let mut pairs: Vec<(usize, Option<usize>)> = Vec::new();
+    for i in 0..left.rows {
+        let key = left.key(&left_keys, i);
+        let matches = if key.contains(&Value::Nil) { None } else { index.get(&key) };
+        match matches {
+            Some(rows) => pairs.extend(rows.iter().map(|&j| (i, Some(j)))),
+            None if keep_unmatched => pairs.push((i, None)),
+            None => {}
+        }
+    }
+
+    let mut columns: Vec<(String, Vec<Value>)> = left.columns.iter()
+        .map(|c| (c.name.clone(), pairs.iter().map(|&(i, _)| c.values[i].clone()).collect()))
+        .collect();
+    for (c, column) in right.columns.iter().enumerate() {
+        if right_keys.contains(&c) {
+            continue;
+        }
+        let name = if left.column_index(&column.name).is_ok() {
+            format!("{}_right", column.name)
+        } else {
+            column.name.clone()
+        };
+        let values = pairs.iter().map(|&(_, j)| j.map_or(Value::Nil, |j| column.values[j].clone())).collect();
+        columns.push((name, values));
+    }
+    Ok(Value::DataFrame(DataFrame::new(columns, "join")?))
+}
+
+// ============================================================================
+// Argument Helpers
+// ============================================================================
+
+fn frame_arg<'a>(v: Option<&'a Value>, fn_name: &str) -> Result<&'a DataFrame, RuntimeError> {
+    match v {
+        Some(Value::DataFrame(frame)) => Ok(frame),
+        Some(other) => Err(RuntimeError {
+            message: format!("{}() requires a dataframe, got {}", fn_name, other.type_name()),
+        }),
+        None => Err(RuntimeError {
+            message: format!("{}() requires a dataframe", fn_name),
+        }),
+    }
+}
+
+fn function_arg<'a>(v: Option<&'a Value>, fn_name: &str) -> Result<&'a Value, RuntimeError> {
+    match v {
+        Some(f @ (Value::Lambda { .. } | Value::Function { .. } | Value::ModuleFunction { .. })) => Ok(f),
+        _ => Err(RuntimeError {
+            message: format!("{}() requires a function taking a row", fn_name),
+        }),
+    }
+}
+
+/// A single column name or an array of them
+fn column_names_arg(v: Option<&Value>, fn_name: &str) -> Result<Vec<String>, RuntimeError> {
+    match v {
+        Some(Value::String(name)) => Ok(vec![name.clone()]),
+        Some(Value::Array(_)) => Ok(names_arg(v, fn_name)?.unwrap_or_default()),
+        _ => Err(RuntimeError {
+            message: format!("{}() requires a column name or an array of column names", fn_name),
+        }),
+    }
+}
+
+/// A dictionary key used as a column name
+fn column_name(key: &Value, fn_name: &str) -> Result<String, RuntimeError> {
+    match key {
+        Value::String(name) => Ok(name.clone()),
+        other => Err(RuntimeError {
+            message: format!("{}() column names must be strings, got {}", fn_name, other.type_name()),
+        }),
+    }
+}
+
+/// Optional array of column names
+fn names_arg(v: Option<&Value>, fn_name: &str) -> Result<Option<Vec<String>>, RuntimeError> {
+    match v {
+        None | Some(Value::Nil) => Ok(None),
+        Some(Value::Array(names)) => names.iter()
+            .map(|name| match name {
+                Value::String(name) => Ok(name.clone()),
+                other => Err(RuntimeError {
+                    message: format!("{}() column names must be strings, got {}", fn_name, other.type_name()),
+                }),
+            })
+            .collect::<Result<_, _>>()
+            .map(Some),
+        Some(other) => Err(RuntimeError {
+            message: format!("{}() column names must be an array, got {}", fn_name, other.type_name()),
+        }),
+    }
+}
+
+fn read_file(v: Option<&Value>, fn_name: &str) -> Result<String, RuntimeError> {
+    let path = match v {
+        Some(Value::String(path)) => path,
+        _ => return Err(RuntimeError {
+            message: format!("{}() requires a file path", fn_name),
+        }),
+    };
+    std::fs::read_to_string(path).map_err(|_| RuntimeError {
+        message: format!("Failed to read file: {}", path),
+    })
+}
@@ -1,4 +1,5 @@
 use colored::*;
+use std::io::{self, Write};
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum DiagnosticLevel {
@@ -74,11 +75,19 @@ impl Diagnostic {
     }
 }
 
-pub struct DiagnosticRenderer;
+pub struct DiagnosticRenderer {
+    stderr: bool,
+}
 
 impl DiagnosticRenderer {
     pub fn new() -> Self {
-        DiagnosticRenderer
+        DiagnosticRenderer { stderr: false }
+    }
+
+    /// Print to stderr instead, for commands whose stdout is their output
+    pub fn with_stderr(mut self) -> Self {
+        self.stderr = true;
+        self
     }
     
     pub fn render(&self, diagnostic: &Diagnostic, filename: Option<&str>, source_code: Option<&str>) {
@@ -107,10 +116,18 @@ impl DiagnosticRenderer {
             }
         }
         
-        Self::render_diagnostic(&diag);
+        if self.stderr {
+            let _ = Self::write_diagnostic(&mut io::stderr().lock(), &diag);
+        } else {
+            Self::render_diagnostic(&diag);
+        }
     }
-    
+
     pub fn render_diagnostic(diagnostic: &Diagnostic) {
+        let _ = Self::write_diagnostic(&mut io::stdout().lock(), diagnostic);
+    }
+
+    fn write_diagnostic(out: &mut impl Write, diagnostic: &Diagnostic) -> io::Result<()> {
         let level_str = match diagnostic.level {
             DiagnosticLevel::Error => "error".red().bold(),
             DiagnosticLevel::Warning => "warning".yellow().bold(),
@@ -120,26 +137,26 @@ impl DiagnosticRenderer {
 
         // Print the main diagnostic line
         if let Some(code) = &diagnostic.code {
-            print!("{}: {}: ", level_str, code.bold());
+            write!(out, "{}: {}: ", level_str, code.bold())?;
         } else {
-            print!("{}: ", level_str);
+            write!(out, "{}: ", level_str)?;
         }
-        println!("{}", diagnostic.message.bold());
+        writeln!(out, "{}", diagnostic.message.bold())?;
 
         // Print location if available
         if let (Some(filename), Some(line), Some(column)) = (&diagnostic.filename, diagnostic.line, diagnostic.column) {
-            println!("{} {}:{}:{}", 
+            writeln!(out, "{} {}:{}:{}", 
                 "-->".blue().bold(), 
                 filename.bold(), 
                 line.to_string().blue().bold(), 
                 column.to_string().blue().bold()
-            );
+            )?;
         } else if let (Some(line), Some(column)) = (diagnostic.line, diagnostic.column) {
-            println!("{} line {}:{}", 
+            writeln!(out, "{} line {}:{}", 
                 "-->".blue().bold(), 
                 line.to_string().blue().bold(), 
                 column.to_string().blue().bold()
-            );
+            )?;
         }
 
         // Print source line with context if available
@@ -147,29 +164,30 @@ impl DiagnosticRenderer {
             let line_num_width = line.to_string().len();
             let padding = " ".repeat(line_num_width + 1);
             
-            println!("{} {}", padding, "|".blue().bold());
-            println!("{} {} {}", 
+            writeln!(out, "{} {}", padding, "|".blue().bold())?;
+            writeln!(out, "{} {} {}", 
                 line.to_string().blue().bold(), 
                 "|".blue().bold(), 
                 source_line
-            );
+            )?;
             
             // Print the caret indicator
             let spaces_before_caret = " ".repeat(column.saturating_sub(1));
-            println!("{} {} {}{}", 
+            writeln!(out, "{} {} {}{}", 
                 padding, 
                 "|".blue().bold(), 
                 spaces_before_caret,
                 "^".red().bold()
-            );
+            )?;
         }
 
         // Print help if available
         if let Some(help) = &diagnostic.help {
-            println!("{} {}", "help:".green().bold(), help);
+            writeln!(out, "{} {}", "help:".green().bold(), help)?;
         }
 
-        println!(); // Empty line for separation
+        writeln!(out)?; // Empty line for separation
+        Ok(())
     }
 
     pub fn render_summary(errors: usize, warnings: usize) {
@@ -195,19 +213,148 @@ impl DiagnosticRenderer {
     }
 }
 
-pub fn parse_lint_message(message: &str) -> Diagnostic {
-    if message.starts_with("error:") {
-        Diagnostic::error(message[6..].trim().to_string())
-            .with_code("E0001".to_string())
-    } else if message.starts_with("warning:") {
-        Diagnostic::warning(message[8..].trim().to_string())
-            .with_code("W0001".to_string())
-    } else {
-        // Fallback
-        if message.contains("error") {
-            Diagnostic::error(message.to_string())
-        } else {
-            Diagnostic::warning(message.to_string())
+// ========== Machine-readable Output ==========
+
+/// Where a diagnostic points: 1-based, with `end_column` just past the
+/// word under the caret
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Span {
+    pub line: usize,
+    pub column: usize,
+    pub end_column: usize,
+}
+
+impl DiagnosticLevel {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DiagnosticLevel::Error => "error",
+            DiagnosticLevel::Warning => "warning",
+            DiagnosticLevel::Note => "note",
+            DiagnosticLevel::Help => "help",
         }
     }
 }
+
+impl Diagnostic {
+    pub fn span(&self) -> Option<Span> {
+        let (line, column) = (self.line?, self.column?);
+        let word_len = self.source_line.as_ref()
+            .map(|source| {
+                source.chars()
+                    .skip(column.saturating_sub(1))
+                    .take_while(|c| c.is_alphanumeric() || *c == '_')
+                    .count()
+            })
+            .unwrap_or(0);
+        Some(Span { line, column, end_column: column + word_len.max(1) })
+    }
+
+    /// The message without any `help:` lines the linter appended to it, and
+    /// the help text from either place
+    pub fn message_and_help(&self) -> (String, Option<String>) {
+        match self.message.split_once("\n  help: ") {
+            Some((message, help)) if self.help.is_none() => {
+                // A list of suggestions follows as `- item` lines
+                let mut lines = help.lines();
+                let first = lines.next().unwrap_or_default().to_string();
+                let items: Vec<&str> = lines.map(|line| line.trim().trim_start_matches("- ")).collect();
+                let help = if items.is_empty() { first } else { format!("{} {}", first, items.join(", ")) };
+                (message.to_string(), Some(help))
+            }
+            _ => (self.message.clone(), self.help.clone()),
+        }
+    }
+
+    pub fn to_json(&self) -> serde_json::Value {
+        let (message, help) = self.message_and_help();
+        serde_json::json!({
+            "level": self.level.as_str(),
+            "code": self.code,
+            "message": message,
+            "help": help,
+            "file": self.filename,
+            "span": self.span().map(|span| serde_json::json!({
+                "line": span.line,
+                "column": span.column,
+                "end_line": span.line,
+                "end_column": span.end_column,
+            })),
+        })
+    }
+}
+
+pub fn count_levels(diagnostics: &[Diagnostic]) -> (usize, usize) {
+    let errors = diagnostics.iter().filter(|d| d.level == DiagnosticLevel::Error).count();
+    let warnings = diagnostics.iter().filter(|d| d.level == DiagnosticLevel::Warning).count();
+    (errors, warnings)
+}
+
+/// `ject check --format json`: every diagnostic, then a summary
+pub fn json_report(diagnostics: &[Diagnostic], files: usize) -> serde_json::Value {
+    let (errors, warnings) = count_levels(diagnostics);
+    serde_json::json!({
+        "diagnostics": diagnostics.iter().map(Diagnostic::to_json).collect::<Vec<_>>(),
+        "summary": {
+            "files": files,
+            "errors": errors,
+            "warnings": warnings,
+        },
+    })
+}
+
+/// `ject check --format sarif`: a SARIF 2.1.0 log with one run, for code
+/// scanning tools that read static analysis results
+pub fn sarif_report(diagnostics: &[Diagnostic]) -> serde_json::Value {
+    let mut rules: Vec<&str> = diagnostics.iter().filter_map(|d| d.code.as_deref()).collect();
+    rules.sort();
+    rules.dedup();
+
+    let results: Vec<serde_json::Value> = diagnostics.iter().map(|diagnostic| {
+        let (message, help) = diagnostic.message_and_help();
+        let mut result = serde_json::json!({
+            "ruleId": diagnostic.code,
+            "level": match diagnostic.level {
+                DiagnosticLevel::Error => "error",
+                DiagnosticLevel::Warning => "warning",
+                DiagnosticLevel::Note | DiagnosticLevel::Help => "note",
+            },
+            "message": { "text": message },
+        });
+        if let Some(filename) = &diagnostic.filename {
+            let mut location = serde_json::json!({ "artifactLocation": { "uri": filename } });
+            if let Some(span) = diagnostic.span() {
+                location["region"] = serde_json::json!({
+                    "startLine": span.line,
+                    "startColumn": span.column,
+                    "endColumn": span.end_column,
+                });
+            }
+            result["locations"] = serde_json::json!([{ "physicalLocation": location }]);
+        }
+        if let Some(help) = help {
+            result["properties"] = serde_json::json!({ "help": help });
+        }
+        result
+    }).collect();
+
+    serde_json::json!({
+        "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "ject",
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "rules": rules.iter().map(|code| {
+                        let mut rule = serde_json::json!({ "id": code });
+                        if let Some(info) = crate::codes::lookup(code) {
+                            rule["shortDescription"] = serde_json::json!({ "text": info.title });
+                        }
+                        rule
+                    }).collect::<Vec<_>>(),
+                },
+            },
+            "results": results,
+        }],
+    })
+}
@@ -0,0 +1,103 @@
+//! Signatures and one-line descriptions of the CorLib builtins, for editor
+//! hover and completion. Keep in step with `stdlib::create_corlib`.
+
+pub struct BuiltinDoc {
+    pub name: &'static str,
+    pub signature: &'static str,
+    pub summary: &'static str,
+}
+
+pub fn lookup(name: &str) -> Option<&'static BuiltinDoc> {
+    BUILTINS.iter().find(|doc| doc.name == name)
+}
+
+macro_rules! builtin {
+    ($name:literal, $signature:literal, $summary:literal) => {
+        BuiltinDoc { name: $name, signature: $signature, summary: $summary }
+    };
+}
+
+pub static BUILTINS: &[BuiltinDoc] = &[
+    // ========== Type Inspection & Conversion ==========
+    builtin!("type_of", "type_of(value)", "The name of a value's type: \"int\", \"float\", \"string\", \"array\", ..."),
+    builtin!("to_int", "to_int(value)", "Converts a number, string or bool to an integer, truncating floats."),
+    builtin!("to_float", "to_float(value)", "Converts a number or string to a float."),
+    builtin!("to_string", "to_string(value)", "The value as it would print."),
+    builtin!("to_bool", "to_bool(value)", "Converts a value to a bool; the strings \"false\", \"no\", \"off\", \"0\" and \"\" are false."),
+    builtin!("decimal", "decimal(value)", "Converts an integer, float or string to an exact decimal, like the `2.50d` literal."),
+
+    // ========== Collection Primitives ==========
+    builtin!("len", "len(collection)", "The number of items in an array, dictionary, string or range."),
+    builtin!("range", "range(start, end, step)", "A lazy range from start up to, not including, end. `range(n)` starts at 0."),
+    builtin!("collect", "collect(iterable)", "The items of a range or generator as an array."),
+    builtin!("push", "push(array, value)", "Appends a value to an array in place."),
+    builtin!("pop", "pop(array)", "Removes and returns the last item of an array."),
+
+    // ========== Dictionary Primitives ==========
+    builtin!("keys", "keys(dict)", "A dictionary's keys, in insertion order."),
+    builtin!("values", "values(dict)", "A dictionary's values, in insertion order."),
+    builtin!("items", "items(dict)", "A dictionary's entries as (key, value) tuples."),
+    builtin!("get", "get(dict, key, default)", "The value for a key, or the default (nil if omitted) when it's missing."),
+    builtin!("remove", "remove(dict, key)", "A copy of the dictionary without the key."),
+    builtin!("merge", "merge(...dicts)", "A new dictionary with every entry; later dictionaries win."),
+    builtin!("update", "update(dict, changes)", "A copy of the dictionary with entries from a dictionary or (key, value) pairs."),
+    builtin!("map_values", "map_values(dict, fn)", "A new dictionary with each value passed through the function."),
+    builtin!("filter_keys", "filter_keys(dict, keys_or_fn)", "A new dictionary with the keys listed, or the keys the function accepts."),
+
+    // ========== Array Primitives ==========
+    builtin!("sum", "sum(array)", "The sum of an array of numbers."),
+    builtin!("contains", "contains(collection, value)", "Whether an array, range or unique array has the value, or a dictionary has the key."),
+    builtin!("index_of", "index_of(array, value)", "The position of the first match in an array, or -1."),
+    builtin!("first", "first(array)", "The first item of an array, or nil if it's empty."),
+    builtin!("last", "last(array)", "The last item of an array, or nil if it's empty."),
+    builtin!("sort", "sort(array)", "A sorted copy of the array."),
+    builtin!("reverse", "reverse(array)", "A reversed copy of the array."),
+    builtin!("unique", "unique(array)", "The array's items without duplicates, keeping their first positions."),
+
+    // ========== Higher-Order Functions ==========
+    builtin!("map", "map(collection, fn)", "A new array with each item passed through the function."),
+    builtin!("filter", "filter(collection, fn)", "A new array with the items the function accepts."),
+    builtin!("reduce", "reduce(collection, fn, initial)", "Folds the items into one value: `fn(accumulator, item)`, starting from initial."),
+
+    // ========== Math Primitives ==========
+    builtin!("abs", "abs(number)", "The absolute value."),
+    builtin!("sqrt", "sqrt(number)", "The square root, as a float."),
+    builtin!("pow", "pow(base, exponent)", "base raised to exponent; integers stay integers for non-negative exponents."),
+    builtin!("divmod", "divmod(a, b)", "The quotient and remainder of integer division, as a (q, r) tuple."),
+    builtin!("sin", "sin(radians)", "The sine of an angle."),
+    builtin!("cos", "cos(radians)", "The cosine of an angle."),
+    builtin!("tan", "tan(radians)", "The tangent of an angle."),
+    builtin!("floor", "floor(number)", "The largest integer not greater than the number."),
+    builtin!("ceil", "ceil(number)", "The smallest integer not less than the number."),
+    builtin!("round", "round(number)", "The nearest integer, rounding halves away from zero."),
+    builtin!("round_decimal", "round_decimal(value, places, mode)", "Rounds a decimal to places (0 if omitted); mode is \"half_even\" (the default), \"half_up\", \"floor\", ..."),
+    builtin!("min", "min(...values)", "The smallest of the arguments."),
+    builtin!("max", "max(...values)", "The largest of the arguments."),
+    builtin!("random", "random()", "A random float in [0, 1)."),
+    builtin!("random_int", "random_int(min, max)", "A random integer between min and max, inclusive."),
+
+    // ========== String Primitives ==========
+    builtin!("upper", "upper(string)", "The string in upper case."),
+    builtin!("lower", "lower(string)", "The string in lower case."),
+    builtin!("trim", "trim(string)", "The string without leading and trailing whitespace."),
+    builtin!("split", "split(string, separator)", "The parts of the string between separators, as an array."),
+    builtin!("join", "join(array, separator)", "The array's items as one string, with the separator between them."),
+    builtin!("replace", "replace(string, pattern, replacement)", "The string with every occurrence of pattern replaced."),
+    builtin!("char_at", "char_at(string, index)", "The character at an index, as a string."),
+    builtin!("substring", "substring(string, start, end)", "The characters from start up to, not including, end (or the end of the string)."),
+    builtin!("repeat", "repeat(string, count)", "The string repeated count times."),
+
+    // ========== I/O Primitives ==========
+    builtin!("input", "input(prompt)", "Prints the prompt and reads a line from stdin, without its newline."),
+    builtin!("print", "print(...values)", "Prints the values separated by spaces; the `print a, b` statement does the same."),
+    builtin!("read_file", "read_file(path)", "The contents of a file, as a string."),
+    builtin!("write_file", "write_file(path, contents)", "Writes a string to a file, replacing it."),
+    builtin!("args", "args()", "The script's command-line arguments, as an array of strings."),
+
+    // ========== Testing ==========
+    builtin!("assert", "assert(condition, message)", "Throws an error with the message if the condition is false."),
+
+    // ========== Constants ==========
+    builtin!("PI", "PI", "The ratio of a circle's circumference to its diameter, 3.14159..."),
+    builtin!("E", "E", "Euler's number, 2.71828..."),
+];
@@ -0,0 +1,301 @@
+//! `ject fmt`: rewrites source with four-space indentation per block and
+//! bracket, one space between tokens where spacing is free, and at most one
+//! blank line in a row. Line breaks between statements and inside brackets
+//! stay where they are, comments are kept as written, and formatting an
+//! already formatted file changes nothing.
+//!
+//! The formatter works on the lexer's tokens rather than the AST, which
+//! drops comments and spelling. The parser checks the input first and the
+//! output afterwards, so a formatted file always means the same program.
+
+use crate::lexer::{Lexer, Token};
+use crate::parser::{ParseError, Parser};
+use std::fmt;
+
+const INDENT: &str = "    ";
+
+#[derive(Debug)]
+pub enum FormatError {
+    /// The input doesn't parse, so there's no structure to format by
+    Parse(ParseError),
+    /// The formatted source would parse to a different program
+    Changed,
+}
+
+impl fmt::Display for FormatError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FormatError::Parse(error) => write!(f, "{}", error),
+            FormatError::Changed => write!(f, "formatting would change what the program does; leaving it as written"),
+        }
+    }
+}
+
+impl std::error::Error for FormatError {}
+
+pub fn format_source(source: &str) -> Result<String, FormatError> {
+    let before = parse(source).map_err(FormatError::Parse)?;
+    let formatted = Formatter::default().format(&lines(source));
+    match parse(&formatted) {
+        Ok(after) if after == before => Ok(formatted),
+        _ => Err(FormatError::Changed),
+    }
+}
+
+/// The program a source parses to, for comparing before and after
+fn parse(source: &str) -> Result<String, ParseError> {
+    let tokens = Lexer::new(source).tokenize_with_positions().into_iter()
+        .map(|lt| (lt.token, lt.position))
+        .collect();
+    Parser::new(tokens).parse().map(|statements| format!("{:?}", statements))
+}
+
+// ========== Source Lines ==========
+
+#[derive(Debug, Clone)]
+enum Item {
+    Token(Token, String),  // With its source text
+    Comment(String, usize),  // With the column it started at
+}
+
+/// The source's tokens and comments, split into lines at each newline token
+/// (newlines inside strings and block comments don't split)
+fn lines(source: &str) -> Vec<Vec<Item>> {
+    let mut lexer = Lexer::new(source);
+    let tokens = lexer.tokenize_with_text();
+    let mut comments = lexer.comments().iter().peekable();
+
+    let mut lines = vec![Vec::new()];
+    for (located, text) in tokens {
+        while let Some(comment) = comments.next_if(|c| c.position.position < located.position.position) {
+            lines.last_mut().unwrap().push(Item::Comment(comment.text.trim_end().to_string(), comment.position.column));
+        }
+        match located.token {
+            Token::Newline => lines.push(Vec::new()),
+            Token::Eof => break,
+            token => lines.last_mut().unwrap().push(Item::Token(token, text)),
+        }
+    }
+    lines
+}
+
+// ========== Formatting ==========
+
+#[derive(Debug, Clone, PartialEq)]
+enum FrameKind {
+    Block,                       // Closed by `end`
+    Try,                         // `try`, which `catch` continues
+    Bracket { comprehension: bool },  // `[x for x in xs if x > 0]` has no blocks
+    Brace,                       // Dictionaries, struct bodies, lambda blocks, `{| |}`
+    Paren,
+}
+
+/// An open block or bracket, and the indentation of the line that opened it
+#[derive(Debug, Clone)]
+struct Frame {
+    kind: FrameKind,
+    indent: usize,
+}
+
+#[derive(Default)]
+struct Formatter {
+    frames: Vec<Frame>,
+}
+
+impl Formatter {
+    fn format(mut self, lines: &[Vec<Item>]) -> String {
+        let mut out = String::new();
+        let mut blank = false;
+        for line in lines {
+            if line.is_empty() {
+                blank = !out.is_empty();
+                continue;
+            }
+            if blank {
+                out.push('\n');
+                blank = false;
+            }
+            let indent = self.line_indent(line);
+            out.push_str(&INDENT.repeat(indent));
+            out.push_str(&self.render(line, indent));
+            out.push('\n');
+        }
+        out
+    }
+
+    /// A line that closes something lines up with the line that opened it;
+    /// anything else is one level inside the innermost open frame
+    fn line_indent(&self, line: &[Item]) -> usize {
+        let inner = self.frames.last().map_or(0, |frame| frame.indent + 1);
+        match line.first() {
+            Some(Item::Token(token, _)) if closes(token) || continues_block(token) => {
+                self.frames.last().map_or(0, |frame| frame.indent)
+            }
+            _ => inner,
+        }
+    }
+
+    /// The line's items with normalized spacing, updating the open frames as it goes
+    fn render(&mut self, line: &[Item], indent: usize) -> String {
+        let mut out = String::new();
+        let mut prev: Option<&Token> = None;
+        let mut prev_prefix = false;  // The previous token was a prefix operator
+        let mut after_range = false;  // A `..` since the last comma or bracket, so `:` is a step
+
+        for (i, item) in line.iter().enumerate() {
+            let (token, text) = match item {
+                Item::Comment(text, column) => {
+                    if !out.is_empty() {
+                        out.push_str(if text.starts_with("#*") { " " } else { "  " });
+                    }
+                    // A block comment's later lines move with its first one
+                    let moved = (INDENT.len() * indent + out.chars().count()) as isize - (*column as isize - 1);
+                    out.push_str(&shift_continuation_lines(text, moved));
+                    prev = None;
+                    continue;
+                }
+                Item::Token(token, text) => (token, text),
+            };
+
+            let prefix = is_prefix(token, prev);
+            if !out.is_empty() && self.space_before(token, prev, prev_prefix, after_range) {
+                out.push(' ');
+            }
+            out.push_str(text);
+
+            let next = line[i + 1..].iter().find_map(|item| match item {
+                Item::Token(token, _) => Some(token),
+                Item::Comment(..) => None,
+            });
+            self.update_frames(token, next, indent);
+            match token {
+                Token::DotDot | Token::DotDotEq => after_range = true,
+                Token::Comma => after_range = false,
+                token if opens(token) || closes(token) => after_range = false,
+                _ => {}
+            }
+            prev = Some(token);
+            prev_prefix = prefix;
+        }
+        out
+    }
+
+    fn space_before(&self, token: &Token, prev: Option<&Token>, prev_prefix: bool, after_range: bool) -> bool {
+        let Some(prev) = prev else {
+            return true;  // After a comment
+        };
+        if prev_prefix || matches!(prev, Token::Dot | Token::DotDot | Token::DotDotEq) {
+            return false;
+        }
+        if matches!(prev, Token::LeftParen | Token::LeftBracket | Token::LeftBrace | Token::LeftBracePipe) {
+            return false;
+        }
+        match token {
+            Token::Comma | Token::Semicolon | Token::Dot | Token::DotDot | Token::DotDotEq => false,
+            Token::RightParen | Token::RightBracket | Token::RightBrace | Token::RightPipeBrace => false,
+            Token::PlusPlus | Token::MinusMinus => !is_operand(prev),
+            Token::LeftParen => !matches!(prev, Token::Identifier(_) | Token::RightParen | Token::RightBracket | Token::Fn | Token::Lambda),
+            Token::LeftBracket => !is_operand(prev) || matches!(prev, Token::End),
+            // Slices and range steps are tight: a[1:3], 1..10:2; keys are not: {a: 1}
+            Token::Colon => false,
+            _ if matches!(prev, Token::Colon) => !(after_range || self.in_brackets()),
+            _ => true,
+        }
+    }
+
+    fn in_brackets(&self) -> bool {
+        matches!(self.frames.last(), Some(Frame { kind: FrameKind::Bracket { .. }, .. }))
+    }
+
+    fn update_frames(&mut self, token: &Token, next: Option<&Token>, indent: usize) {
+        // `[x for x in xs if cond]`: neither keyword opens a block
+        if let Some(Frame { kind: FrameKind::Bracket { comprehension }, .. }) = self.frames.last_mut() {
+            match token {
+                Token::For => {
+                    *comprehension = true;
+                    return;
+                }
+                Token::If if *comprehension => return,
+                _ => {}
+            }
+        }
+        let kind = match token {
+            // `fn name(...)` has a body; `fn(x) -> x` is a lambda
+            Token::Fn if !matches!(next, Some(Token::Identifier(_))) => None,
+            Token::If | Token::While | Token::For | Token::Fn | Token::Match => Some(FrameKind::Block),
+            Token::Try => Some(FrameKind::Try),
+            Token::Catch => {
+                // `try ... catch e ... end` continues the block; after `try ... end` it opens one
+                match self.frames.last_mut() {
+                    Some(frame) if frame.kind == FrameKind::Try => frame.kind = FrameKind::Block,
+                    _ => self.frames.push(Frame { kind: FrameKind::Block, indent }),
+                }
+                None
+            }
+            Token::LeftParen => Some(FrameKind::Paren),
+            Token::LeftBracket => Some(FrameKind::Bracket { comprehension: false }),
+            Token::LeftBrace | Token::LeftBracePipe => Some(FrameKind::Brace),
+            token if closes(token) => {
+                self.frames.pop();
+                None
+            }
+            _ => None,
+        };
+        if let Some(kind) = kind {
+            self.frames.push(Frame { kind, indent });
+        }
+    }
+}
+
+fn opens(token: &Token) -> bool {
+    matches!(token,
+        Token::LeftParen | Token::LeftBracket | Token::LeftBrace | Token::LeftBracePipe |
+        Token::If | Token::While | Token::For | Token::Fn | Token::Try | Token::Match)
+}
+
+fn closes(token: &Token) -> bool {
+    matches!(token, Token::End | Token::RightParen | Token::RightBracket | Token::RightBrace | Token::RightPipeBrace)
+}
+
+/// Lines starting with these line up with their block's opening line
+fn continues_block(token: &Token) -> bool {
+    matches!(token, Token::Else | Token::ElseIf | Token::Catch)
+}
+
+/// Whether a token ends a value, so that a following `-` is binary and `++` postfix
+fn is_operand(token: &Token) -> bool {
+    matches!(token,
+        Token::Integer(_) | Token::Float(_) | Token::Decimal(_) | Token::String(_) |
+        Token::InterpolatedString(_) | Token::Bool(_) | Token::True | Token::False | Token::Nil |
+        Token::Identifier(_) | Token::RightParen | Token::RightBracket | Token::RightBrace |
+        Token::RightPipeBrace | Token::End | Token::PlusPlus | Token::MinusMinus)
+}
+
+/// A comment with every line after the first moved `by` columns right, or left as
+/// far as its leading spaces and tabs allow, so they stay aligned with the first
+fn shift_continuation_lines(text: &str, by: isize) -> String {
+    let mut lines = text.split('\n');
+    let mut out = lines.next().unwrap_or_default().to_string();
+    for line in lines {
+        out.push('\n');
+        if by >= 0 {
+            if !line.trim().is_empty() {
+                out.push_str(&" ".repeat(by as usize));
+            }
+            out.push_str(line);
+        } else {
+            let strip = line.chars().take(by.unsigned_abs()).take_while(|c| matches!(c, ' ' | '\t')).count();
+            out.push_str(&line[strip..]);
+        }
+    }
+    out
+}
+
+/// Operators written against their operand: -x, !done, ++i, ...rest, **opts
+fn is_prefix(token: &Token, prev: Option<&Token>) -> bool {
+    match token {
+        Token::Bang | Token::Ellipsis | Token::StarStar => true,
+        Token::Minus | Token::PlusPlus | Token::MinusMinus => !prev.is_some_and(is_operand),
+        _ => false,
+    }
+}
@@ -1,10 +1,13 @@
-use crate::ast::{Expr, Stmt, BinaryOp, UnaryOp, Argument};
+use crate::ast::{Binding, DictEntry, Expr, Stmt, BinaryOp, UnaryOp, Argument, IndexComponent};
+use crate::numpy::AxisIndex;
+use crate::range::Range;
+use crate::resolver::{ModuleResolver, ModuleSource};
 use crate::lexer::InterpolationPart;
 use crate::value::{Value, Environment};
 use std::fmt;
-use std::fs;
 use std::path::Path;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use rust_decimal::Decimal;
 
 /// Get a helpful suggestion for runtime errors
 pub fn get_runtime_suggestion(message: &str) -> String {
@@ -22,6 +25,9 @@ pub fn get_runtime_suggestion(message: &str) -> String {
     if msg.contains("continue") {
         return "'continue' can only be used inside for or while loops.".to_string();
     }
+    if msg.contains("must be hashable") {
+        return "Floats, nil and functions can't be keys; convert the key with to_string() or round it to an int.".to_string();
+    }
     if msg.contains("sqrt") || msg.contains("pow") {
         return "sqrt() requires non-negative numbers. pow(0,0) is undefined.".to_string();
     }
@@ -43,8 +49,86 @@ impl std::error::Error for RuntimeError {}
 
 type RuntimeResult<T> = Result<T, RuntimeError>;
 
+/// Calls a Ject function value from a builtin that takes a callback (map_values, dataframe filter, ...)
+pub type Callback<'a> = dyn FnMut(&Value, Vec<Value>) -> Result<Value, RuntimeError> + 'a;
+
+/// Positional argument values and (name, value) keyword arguments of a call
+type EvaluatedArguments = (Vec<Value>, Vec<(String, Value)>);
+
+/// Check that a value can be used as a dictionary key
+pub fn dictionary_key(key: Value) -> RuntimeResult<Value> {
+    hashable(key, "Dictionary keys")
+}
+
+/// Check that a value is hashable, naming what it is for ("Dictionary keys", "Collection items")
+pub fn hashable(value: Value, what: &str) -> RuntimeResult<Value> {
+    if value.is_hashable() {
+        Ok(value)
+    } else {
+        Err(RuntimeError {
+            message: format!(
+                "{} must be hashable (int, bool, string, decimal, or an array, tuple or struct of those), got {}",
+                what,
+                value.type_name()
+            ),
+        })
+    }
+}
+
+/// Elements that `...value` splices into an array literal or a call
+fn spread_values(value: Value) -> RuntimeResult<Vec<Value>> {
+    match value {
+        Value::Array(items) | Value::Tuple(items) | Value::UniqueArray(items) => Ok(items),
+        Value::Range(range) => Ok(range.to_vec()),
+        other => Err(RuntimeError {
+            message: format!("Cannot spread {}; '...' requires an array, tuple or range", other.type_name()),
+        }),
+    }
+}
+
 pub struct Interpreter {
     environment: Environment,
+    modules: ModuleRegistry,
+    resolver: ModuleResolver,
+    current_dir: Option<std::path::PathBuf>,  // Directory of the file being run, for ./ imports
+    script_args: Vec<String>,                 // Command-line arguments, returned by args()
+}
+
+/// Modules loaded from files, keyed by canonical path, so each one is
+/// evaluated once and its exports shared by every importer
+#[derive(Default)]
+struct ModuleRegistry {
+    loaded: HashMap<std::path::PathBuf, LoadedModule>,
+    loading: Vec<std::path::PathBuf>,  // Modules being evaluated, outermost first
+}
+
+/// What a module offers importers, plus the top-level names it keeps to
+/// itself so that importing one of those can say it is private
+#[derive(Debug, Clone, Default)]
+struct LoadedModule {
+    exports: HashMap<String, Value>,
+    private: HashSet<String>,
+}
+
+impl LoadedModule {
+    fn get(&self, module_path: &str, name: &str) -> RuntimeResult<Value> {
+        if let Some(value) = self.exports.get(name) {
+            return Ok(value.clone());
+        }
+        let message = if self.private.contains(name) {
+            format!("'{}' is private to module '{}'; only exported names can be imported", name, module_path)
+        } else {
+            format!("Module '{}' does not export '{}'", module_path, name)
+        };
+        Err(RuntimeError { message })
+    }
+}
+
+/// A module path as shown in errors, relative to the working directory when possible
+fn display_module_path(path: &Path) -> String {
+    let relative = std::env::current_dir().ok()
+        .and_then(|cwd| path.strip_prefix(cwd).ok().map(Path::to_path_buf));
+    relative.as_deref().unwrap_or(path).display().to_string()
 }
 
 #[derive(Debug)]
@@ -60,11 +144,13 @@ impl Interpreter {
     pub fn new() -> Self {
         let mut environment = Environment::new();
 
-        // Load CorLib (core library - always available)
+        // Load CorLib (core library - always available) as read-only bindings in
+        // an outer scope: scripts may shadow `len` with `let`, but not reassign it
         let corlib = crate::stdlib::create_corlib();
         for (name, value) in corlib {
-            environment.define(name, value);
+            environment.define_constant(name, value);
         }
+        environment.push_scope();
 
         // Note: Standard library modules are now loaded via import statements
         // CorLib (core library) is loaded above
@@ -72,8 +158,30 @@ impl Interpreter {
 
         Interpreter {
             environment,
+            modules: ModuleRegistry::default(),
+            resolver: ModuleResolver::default(),
+            current_dir: None,
+            script_args: Vec::new(),
         }
     }
+
+    /// Arguments given to the script on the command line, for args()
+    pub fn with_args(mut self, args: Vec<String>) -> Self {
+        self.script_args = args;
+        self
+    }
+
+    /// Resolve imports with the given search paths instead of the default ones
+    pub fn with_resolver(mut self, resolver: ModuleResolver) -> Self {
+        self.resolver = resolver;
+        self
+    }
+
+    /// The script being run, so its `./` and `../` imports resolve next to it
+    pub fn with_script_path(mut self, path: &Path) -> Self {
+        self.current_dir = path.parent().map(Path::to_path_buf);
+        self
+    }
     
 //     fn load_stdlib_from_ject(environment: &mut Environment) -> RuntimeResult<()> {
 //         // Load stdlib/index.ject
@@ -119,6 +227,11 @@ impl Interpreter {
 //         Ok(())
 //     }
 //     
+    /// Evaluate a single expression in the global environment, for `ject eval`
+    pub fn evaluate(&mut self, expr: &Expr) -> RuntimeResult<Value> {
+        self.evaluate_expression(expr)
+    }
+
     pub fn interpret(&mut self, statements: &[Stmt]) -> RuntimeResult<()> {
         for statement in statements {
             match self.execute_statement(statement)? {
@@ -145,9 +258,15 @@ impl Interpreter {
                 self.evaluate_expression(expr)?;
                 Ok(ControlFlow::None)
             }
-            Stmt::Let { name, value } => {
+            Stmt::Let { binding, value } => {
                 let val = self.evaluate_expression(value)?;
-                self.environment.define(name.clone(), val);
+                self.bind(binding, val)?;
+                Ok(ControlFlow::None)
+            }
+            Stmt::Const { name, value } => {
+                let val = self.evaluate_expression(value)?;
+                self.check_redeclaration(name)?;
+                self.environment.define_constant(name.clone(), val);
                 Ok(ControlFlow::None)
             }
             Stmt::Assign { target, value } => {
@@ -155,6 +274,11 @@ impl Interpreter {
                 
                 match target {
                     crate::ast::AssignTarget::Identifier(name) => {
+                        if self.environment.is_constant(name) {
+                            return Err(RuntimeError {
+                                message: format!("Cannot assign to constant '{}'", name),
+                            });
+                        }
                         if self.environment.set(&name, val) {
                             Ok(ControlFlow::None)
                         } else {
@@ -164,6 +288,7 @@ impl Interpreter {
                         }
                     }
                     crate::ast::AssignTarget::Index { object, index } => {
+                        self.check_mutable(object)?;
                         let obj = self.environment.get(&object)
                             .ok_or_else(|| RuntimeError {
                                 message: format!("Undefined variable '{}'", object),
@@ -186,25 +311,26 @@ impl Interpreter {
                                 Err(RuntimeError { message: "Array index must be integer".to_string() })
                             }
                         } else if let Value::Dictionary(mut dict) = obj {
-                            if let Value::String(key) = idx {
-                                dict.insert(key, val);
-                                self.environment.set(&object, Value::Dictionary(dict));
-                                Ok(ControlFlow::None)
-                            } else {
-                                Err(RuntimeError { message: "Dictionary key must be string".to_string() })
-                            }
+                            dict.insert(dictionary_key(idx)?, val);
+                            self.environment.set(&object, Value::Dictionary(dict));
+                            Ok(ControlFlow::None)
+                        } else if let Value::NdArray(mut arr) = obj {
+                            crate::numpy::assign_with_value(&mut arr, &idx, &val)?;
+                            self.environment.set(object, Value::NdArray(arr));
+                            Ok(ControlFlow::None)
                         } else {
                             Err(RuntimeError { message: format!("Cannot index into {}", obj.type_name()) })
                         }
                     }
                     crate::ast::AssignTarget::Field { object, field } => {
+                        self.check_mutable(object)?;
                         let obj = self.environment.get(&object)
                             .ok_or_else(|| RuntimeError {
                                 message: format!("Undefined variable '{}'", object),
                             })?;
                         
                         if let Value::Dictionary(mut dict) = obj {
-                            dict.insert(field.clone(), val);
+                            dict.insert(Value::String(field.clone()), val);
                             self.environment.set(&object, Value::Dictionary(dict));
                             Ok(ControlFlow::None)
                         } else if let Value::StructInstance { struct_name, mut fields } = obj {
@@ -215,6 +341,22 @@ impl Interpreter {
                             Err(RuntimeError { message: format!("Cannot assign field on {}", obj.type_name()) })
                         }
                     }
+                    crate::ast::AssignTarget::Subscript { object, indices } => {
+                        self.check_mutable(object)?;
+                        let obj = self.environment.get(object)
+                            .ok_or_else(|| RuntimeError {
+                                message: format!("Undefined variable '{}'", object),
+                            })?;
+                        let axes = self.evaluate_index_components(indices)?;
+
+                        if let Value::NdArray(mut arr) = obj {
+                            crate::numpy::assign(&mut arr, &axes, &val)?;
+                            self.environment.set(object, Value::NdArray(arr));
+                            Ok(ControlFlow::None)
+                        } else {
+                            Err(RuntimeError { message: format!("Slice assignment is only supported on ndarrays, not {}", obj.type_name()) })
+                        }
+                    }
                 }
             }
             Stmt::Function { name, params, body } => {
@@ -222,6 +364,7 @@ impl Interpreter {
                     params: params.clone(),
                     body: body.clone(),
                 };
+                self.check_redeclaration(name)?;
                 self.environment.define(name.clone(), func);
                 Ok(ControlFlow::None)
             }
@@ -249,7 +392,11 @@ impl Interpreter {
             }
             Stmt::While { condition, body } => {
                 while self.evaluate_expression(condition)?.is_truthy() {
-                    match self.execute_block(body)? {
+                    // Each iteration gets its own scope, as in `for`
+                    self.environment.push_scope();
+                    let flow = self.execute_block(body);
+                    self.environment.pop_scope();
+                    match flow? {
                         ControlFlow::Return(value) => return Ok(ControlFlow::Return(value)),
                         ControlFlow::Throw(error) => return Ok(ControlFlow::Throw(error)),
                         ControlFlow::Break => break,
@@ -259,65 +406,67 @@ impl Interpreter {
                 }
                 Ok(ControlFlow::None)
             }
-            Stmt::For { var, iterable, body } => {
+            Stmt::For { binding: var, iterable, body } => {
                 let iter_value = self.evaluate_expression(iterable)?;
 
                 match iter_value {
-                    Value::Array(elements) => {
+                    Value::Array(elements) | Value::Tuple(elements) => {
                         for element in elements {
-                            self.environment.push_scope();
-                            self.environment.define(var.clone(), element);
-
-                            match self.execute_block(body)? {
-                                ControlFlow::Return(value) => {
-                                    self.environment.pop_scope();
-                                    return Ok(ControlFlow::Return(value));
-                                }
-                                ControlFlow::Throw(error) => {
-                                    self.environment.pop_scope();
-                                    return Ok(ControlFlow::Throw(error));
-                                }
-                                ControlFlow::Break => {
-                                    self.environment.pop_scope();
-                                    break;
-                                }
-                                ControlFlow::Continue => {
-                                    self.environment.pop_scope();
-                                    continue;
-                                }
-                                ControlFlow::None => {}
+                            match self.execute_loop_iteration(var, element, body)? {
+                                ControlFlow::Break => break,
+                                ControlFlow::None | ControlFlow::Continue => {}
+                                flow => return Ok(flow),
+                            }
+                        }
+                    }
+                    Value::Range(range) => {
+                        // Each element is computed as the loop reaches it
+                        for element in range.iter() {
+                            match self.execute_loop_iteration(var, element, body)? {
+                                ControlFlow::Break => break,
+                                ControlFlow::None | ControlFlow::Continue => {}
+                                flow => return Ok(flow),
                             }
-
-                            self.environment.pop_scope();
                         }
                     }
                     Value::String(s) => {
                         // Iterate over characters in string
                         for ch in s.chars() {
-                            self.environment.push_scope();
-                            self.environment.define(var.clone(), Value::String(ch.to_string()));
-
-                            match self.execute_block(body)? {
-                                ControlFlow::Return(value) => {
-                                    self.environment.pop_scope();
-                                    return Ok(ControlFlow::Return(value));
-                                }
-                                ControlFlow::Throw(error) => {
-                                    self.environment.pop_scope();
-                                    return Ok(ControlFlow::Throw(error));
-                                }
-                                ControlFlow::Break => {
-                                    self.environment.pop_scope();
-                                    break;
-                                }
-                                ControlFlow::Continue => {
-                                    self.environment.pop_scope();
-                                    continue;
-                                }
-                                ControlFlow::None => {}
+                            match self.execute_loop_iteration(var, Value::String(ch.to_string()), body)? {
+                                ControlFlow::Break => break,
+                                ControlFlow::None | ControlFlow::Continue => {}
+                                flow => return Ok(flow),
+                            }
+                        }
+                    }
+                    Value::Dictionary(dict) => {
+                        // Keys in insertion order
+                        for key in dict.into_keys() {
+                            match self.execute_loop_iteration(var, key, body)? {
+                                ControlFlow::Break => break,
+                                ControlFlow::None | ControlFlow::Continue => {}
+                                flow => return Ok(flow),
+                            }
+                        }
+                    }
+                    Value::DataFrame(frame) => {
+                        // Each row as a dictionary keyed by column name
+                        for i in 0..frame.len() {
+                            match self.execute_loop_iteration(var, frame.row(i), body)? {
+                                ControlFlow::Break => break,
+                                ControlFlow::None | ControlFlow::Continue => {}
+                                flow => return Ok(flow),
+                            }
+                        }
+                    }
+                    Value::CsvReader(reader) => {
+                        // Rows are read from the file one at a time
+                        while let Some(row) = reader.next_row()? {
+                            match self.execute_loop_iteration(var, row, body)? {
+                                ControlFlow::Break => break,
+                                ControlFlow::None | ControlFlow::Continue => {}
+                                flow => return Ok(flow),
                             }
-
-                            self.environment.pop_scope();
                         }
                     }
                     _ => {
@@ -354,6 +503,14 @@ impl Interpreter {
                 self.environment.define(name.clone(), func);
                 Ok(ControlFlow::None)
             }
+            Stmt::ExportFrom { module_path, items } => {
+                // Outside a module there is nothing to export to; still load and check it
+                let module = self.import_module(module_path)?;
+                for item in items.iter().flatten() {
+                    module.get(module_path, item)?;
+                }
+                Ok(ControlFlow::None)
+            }
             Stmt::Print { values, sep, end } => {
                 // Evaluate all values
                 let mut output = Vec::new();
@@ -438,11 +595,116 @@ impl Interpreter {
         }
         Ok(ControlFlow::None)
     }
-    
+
+    /// Run one pass of a `for` body with the loop variable bound in its own scope
+    fn execute_loop_iteration(&mut self, var: &Binding, element: Value, body: &[Stmt]) -> RuntimeResult<ControlFlow> {
+        self.environment.push_scope();
+        let flow = match self.bind(var, element) {
+            Ok(()) => self.execute_block(body),
+            Err(e) => Err(e),
+        };
+        self.environment.pop_scope();
+        flow
+    }
+
+    /// `let`, `const` and `fn` can't replace a constant declared in the same scope
+    fn check_redeclaration(&self, name: &str) -> RuntimeResult<()> {
+        if self.environment.is_constant_in_scope(name) {
+            return Err(RuntimeError {
+                message: format!("Cannot redeclare constant '{}'", name),
+            });
+        }
+        Ok(())
+    }
+
+    /// Index, field and slice assignment change the value in place, so a
+    /// constant can't be their target either
+    fn check_mutable(&self, name: &str) -> RuntimeResult<()> {
+        if self.environment.is_constant(name) {
+            return Err(RuntimeError {
+                message: format!("Cannot modify constant '{}'", name),
+            });
+        }
+        Ok(())
+    }
+
+    /// Define the names of a `let`/`for` binding, destructuring the value if needed
+    fn bind(&mut self, binding: &Binding, value: Value) -> RuntimeResult<()> {
+        match binding {
+            Binding::Name(name) => {
+                self.check_redeclaration(name)?;
+                self.environment.define(name.clone(), value);
+                Ok(())
+            }
+            Binding::Tuple(items) => {
+                let values = match value {
+                    Value::Tuple(values) | Value::Array(values) => values,
+                    Value::Range(range) => range.to_vec(),
+                    other => return Err(RuntimeError {
+                        message: format!("Cannot destructure {} into {}", other.type_name(), binding),
+                    }),
+                };
+                if values.len() != items.len() {
+                    return Err(RuntimeError {
+                        message: format!("Cannot destructure {} values into {} names in {}", values.len(), items.len(), binding),
+                    });
+                }
+                for (item, value) in items.iter().zip(values) {
+                    self.bind(item, value)?;
+                }
+                Ok(())
+            }
+            Binding::Array { items, rest } => {
+                let mut values = match value {
+                    Value::Array(values) | Value::Tuple(values) | Value::UniqueArray(values) => values,
+                    Value::Range(range) => range.to_vec(),
+                    other => return Err(RuntimeError {
+                        message: format!("Cannot destructure {} into {}", other.type_name(), binding),
+                    }),
+                };
+                let fits = match rest {
+                    Some(_) => values.len() >= items.len(),
+                    None => values.len() == items.len(),
+                };
+                if !fits {
+                    return Err(RuntimeError {
+                        message: format!("Cannot destructure {} values into {} names in {}", values.len(), items.len(), binding),
+                    });
+                }
+                let remaining = values.split_off(items.len());
+                for (item, value) in items.iter().zip(values) {
+                    self.bind(item, value)?;
+                }
+                if let Some(rest) = rest {
+                    self.environment.define(rest.clone(), Value::Array(remaining));
+                }
+                Ok(())
+            }
+            Binding::Dictionary(entries) => {
+                for (key, item) in entries {
+                    let field = match &value {
+                        Value::Dictionary(dict) => dict.get(key.as_str()).cloned().ok_or_else(|| RuntimeError {
+                            message: format!("Key '{}' not found in dictionary", key),
+                        })?,
+                        Value::StructInstance { struct_name, fields } => fields.get(key).cloned().ok_or_else(|| RuntimeError {
+                            message: format!("Struct '{}' has no field '{}'", struct_name, key),
+                        })?,
+                        other => return Err(RuntimeError {
+                            message: format!("Cannot destructure {} into {}", other.type_name(), binding),
+                        }),
+                    };
+                    self.bind(item, field)?;
+                }
+                Ok(())
+            }
+        }
+    }
+
     fn evaluate_expression(&mut self, expr: &Expr) -> RuntimeResult<Value> {
         match expr {
             Expr::Integer(n) => Ok(Value::Integer(*n)),
             Expr::Float(f) => Ok(Value::Float(*f)),
+            Expr::Decimal(d) => Ok(Value::Decimal(*d)),
             Expr::String(s) => Ok(Value::String(s.clone())),
             Expr::InterpolatedString(parts) => {
                 let mut result = String::new();
@@ -519,10 +781,26 @@ let mut parser = crate::parser::Parser::new_simple(tokens);
             Expr::Array(elements) => {
                 let mut values = Vec::new();
                 for element in elements {
-                    values.push(self.evaluate_expression(element)?);
+                    match element {
+                        Expr::Spread(inner) => {
+                            let spread = self.evaluate_expression(inner)?;
+                            values.extend(spread_values(spread)?);
+                        }
+                        _ => values.push(self.evaluate_expression(element)?),
+                    }
                 }
                 Ok(Value::Array(values))
             }
+            Expr::Spread(_) => Err(RuntimeError {
+                message: "'...' can only be used inside an array literal or a call".to_string(),
+            }),
+            Expr::Tuple(elements) => {
+                let mut values = Vec::new();
+                for element in elements {
+                    values.push(self.evaluate_expression(element)?);
+                }
+                Ok(Value::Tuple(values))
+            }
             Expr::UniqueArray(elements) => {
                 // Evaluate all elements and deduplicate
                 let mut seen = std::collections::HashSet::new();
@@ -543,18 +821,19 @@ let mut parser = crate::parser::Parser::new_simple(tokens);
                 let iter_value = self.evaluate_expression(iterable)?;
                 
                 // Get elements from iterable (array, unique array, or range)
-                let elements = match iter_value {
-                    Value::Array(arr) => arr,
-                    Value::UniqueArray(arr) => arr,
+                let elements: Box<dyn Iterator<Item = Value>> = match iter_value {
+                    Value::Array(arr) => Box::new(arr.into_iter()),
+                    Value::UniqueArray(arr) => Box::new(arr.into_iter()),
+                    Value::Range(range) => Box::new(range.iter()),
                     _ => return Err(RuntimeError { message: "Can only iterate over arrays or ranges".to_string() }),
                 };
                 
                 let mut result = Vec::new();
                 
-                for item in &elements {
+                for item in elements {
                     // Create new scope for loop variable
                     self.environment.push_scope();
-                    self.environment.define(var.clone(), item.clone());
+                    self.environment.define(var.clone(), item);
                     
                     // Check condition if present
                     let include = if let Some(cond) = condition {
@@ -580,17 +859,18 @@ let mut parser = crate::parser::Parser::new_simple(tokens);
                 // In the future, this could return a lazy iterator
                 let iter_value = self.evaluate_expression(iterable)?;
                 
-                let elements = match iter_value {
-                    Value::Array(arr) => arr,
-                    Value::UniqueArray(arr) => arr,
+                let elements: Box<dyn Iterator<Item = Value>> = match iter_value {
+                    Value::Array(arr) => Box::new(arr.into_iter()),
+                    Value::UniqueArray(arr) => Box::new(arr.into_iter()),
+                    Value::Range(range) => Box::new(range.iter()),
                     _ => return Err(RuntimeError { message: "Can only iterate over arrays or ranges".to_string() }),
                 };
                 
                 let mut result = Vec::new();
                 
-                for item in &elements {
+                for item in elements {
                     self.environment.push_scope();
-                    self.environment.define(var.clone(), item.clone());
+                    self.environment.define(var.clone(), item);
                     
                     let include = if let Some(cond) = condition {
                         let cond_value = self.evaluate_expression(cond)?;
@@ -610,11 +890,23 @@ let mut parser = crate::parser::Parser::new_simple(tokens);
                 // Return as array for now (could be made lazy in future)
                 Ok(Value::Array(result))
             }
-            Expr::Dictionary(pairs) => {
-                let mut map = std::collections::HashMap::new();
-                for (key, value_expr) in pairs {
-                    let value = self.evaluate_expression(value_expr)?;
-                    map.insert(key.clone(), value);
+            Expr::Dictionary(entries) => {
+                let mut map = indexmap::IndexMap::new();
+                for entry in entries {
+                    match entry {
+                        DictEntry::Pair(key_expr, value_expr) => {
+                            let key = dictionary_key(self.evaluate_expression(key_expr)?)?;
+                            let value = self.evaluate_expression(value_expr)?;
+                            map.insert(key, value);
+                        }
+                        // Later entries win, as with merge()
+                        DictEntry::Spread(expr) => match self.evaluate_expression(expr)? {
+                            Value::Dictionary(other) => map.extend(other),
+                            other => return Err(RuntimeError {
+                                message: format!("Cannot spread {} into a dictionary", other.type_name()),
+                            }),
+                        },
+                    }
                 }
                 Ok(Value::Dictionary(map))
             }
@@ -623,7 +915,7 @@ let mut parser = crate::parser::Parser::new_simple(tokens);
                 let idx = self.evaluate_expression(index)?;
 
                 match (obj, idx) {
-                    (Value::Array(arr), Value::Integer(i)) => {
+                    (Value::Array(arr) | Value::Tuple(arr), Value::Integer(i)) => {
                         // Handle negative indices
                         let actual_index = if i < 0 {
                             (arr.len() as i64 + i) as usize
@@ -639,9 +931,25 @@ let mut parser = crate::parser::Parser::new_simple(tokens);
                             })
                         }
                     }
-                    (Value::Dictionary(dict), Value::String(key)) => {
+                    (Value::Range(range), Value::Integer(i)) => range.get(i).ok_or_else(|| RuntimeError {
+                        message: format!("Range index out of bounds: {}", i),
+                    }),
+                    // a[r] selects the elements at each position in r, e.g. a[1..=3]
+                    (Value::Array(arr) | Value::Tuple(arr), Value::Range(positions)) => {
+                        Ok(Value::Array(positions.positions(arr.len())?.map(|i| arr[i].clone()).collect()))
+                    }
+                    (Value::Range(range), Value::Range(positions)) => {
+                        Ok(Value::Array(positions.positions(range.len())?.filter_map(|i| range.get(i as i64)).collect()))
+                    }
+                    (Value::String(s), Value::Range(positions)) => {
+                        let chars: Vec<char> = s.chars().collect();
+                        Ok(Value::String(positions.positions(chars.len())?.map(|i| chars[i]).collect()))
+                    }
+                    (Value::Dictionary(dict), key) => {
+                        let key = dictionary_key(key)?;
                         Ok(dict.get(&key).cloned().unwrap_or(Value::Nil))
                     }
+                    (Value::NdArray(arr), idx) => crate::numpy::index_with_value(&arr, &idx),
                     (Value::String(s), Value::Integer(i)) => {
                         // Handle negative indices for strings
                         let chars: Vec<char> = s.chars().collect();
@@ -801,11 +1109,39 @@ let mut parser = crate::parser::Parser::new_simple(tokens);
 
                         Ok(Value::String(result))
                     }
+                    Value::NdArray(arr) => {
+                        let bound = |v: Option<Value>| match v {
+                            None => Ok(None),
+                            Some(Value::Integer(n)) => Ok(Some(n)),
+                            Some(_) => Err(RuntimeError { message: "Slice bounds must be integers".to_string() }),
+                        };
+                        let axis = AxisIndex::Slice { start: bound(from_val)?, stop: bound(to_val)?, step: step_i };
+                        crate::numpy::index(&arr, &[axis])
+                    }
+                    Value::Range(range) => {
+                        // Slicing a range gives another lazy range
+                        let bound = |v: Option<Value>| match v {
+                            None => Ok(None),
+                            Some(Value::Integer(n)) => Ok(Some(n)),
+                            Some(_) => Err(RuntimeError { message: "Slice bounds must be integers".to_string() }),
+                        };
+                        Ok(Value::Range(range.slice(bound(from_val)?, bound(to_val)?, step_i)?))
+                    }
                     _ => Err(RuntimeError {
                         message: format!("Cannot slice {}", obj.type_name()),
                     }),
                 }
             }
+            Expr::MultiIndex { object, indices } => {
+                let obj = self.evaluate_expression(object)?;
+                let axes = self.evaluate_index_components(indices)?;
+                match obj {
+                    Value::NdArray(arr) => crate::numpy::index(&arr, &axes),
+                    _ => Err(RuntimeError {
+                        message: format!("Cannot use a multi-dimensional index on {} (only ndarrays support a[i, j])", obj.type_name()),
+                    }),
+                }
+            }
             Expr::Lambda { params, body } => {
                 // Capture current environment for closure support
                 Ok(Value::Lambda {
@@ -887,55 +1223,16 @@ let mut parser = crate::parser::Parser::new_simple(tokens);
                     })
                 }
             }
-            Expr::Range { start, end, step } => {
+            Expr::Range { start, end, step, inclusive } => {
                 let start_val = self.evaluate_expression(start)?;
                 let end_val = self.evaluate_expression(end)?;
-
-                let step_val = if let Some(step_expr) = step {
-                    self.evaluate_expression(step_expr)?
-                } else {
-                    Value::Integer(1)  // Default step is 1
-                };
-
-                // Convert to integers (floor floats)
-                let start_i = match start_val {
-                    Value::Integer(n) => n,
-                    Value::Float(f) => f.floor() as i64,
-                    _ => return Err(RuntimeError { message: "Range start must be a number".to_string() }),
-                };
-                let end_i = match end_val {
-                    Value::Integer(n) => n,
-                    Value::Float(f) => f.floor() as i64,
-                    _ => return Err(RuntimeError { message: "Range end must be a number".to_string() }),
-                };
-                let step_i = match step_val {
-                    Value::Integer(n) => n,
-                    Value::Float(f) => f.floor() as i64,
-                    _ => return Err(RuntimeError { message: "Range step must be a number".to_string() }),
+                let step_val = match step {
+                    Some(step_expr) => Some(self.evaluate_expression(step_expr)?),
+                    None => None,
                 };
 
-                if step_i == 0 {
-                    return Err(RuntimeError {
-                        message: "Range step cannot be zero".to_string(),
-                    });
-                }
-
-                let mut result = Vec::new();
-                let mut current = start_i;
-
-                if step_i > 0 {
-                    while current < end_i {
-                        result.push(Value::Integer(current));
-                        current += step_i;
-                    }
-                } else {
-                    while current > end_i {
-                        result.push(Value::Integer(current));
-                        current += step_i;
-                    }
-                }
-
-                Ok(Value::Array(result))
+                // Lazy: elements are produced as the range is used
+                Ok(Value::Range(Range::new(&start_val, &end_val, step_val.as_ref(), *inclusive)?))
             }
             Expr::ConditionalExpr { condition, then_expr, elseif_branches, else_expr } => {
                 let cond_value = self.evaluate_expression(condition)?;
@@ -983,6 +1280,15 @@ let mut parser = crate::parser::Parser::new_simple(tokens);
     }
     
     fn evaluate_binary_op(&self, left: &Value, op: &BinaryOp, right: &Value) -> RuntimeResult<Value> {
+        if let Some(result) = self.evaluate_decimal_op(left, op, right) {
+            return result;
+        }
+        if matches!(left, Value::NdArray(_)) || matches!(right, Value::NdArray(_)) {
+            if let Some(result) = crate::numpy::binary_op(left, op, right) {
+                return result;
+            }
+        }
+
         match (left, op, right) {
             // Arithmetic
             (Value::Integer(a), BinaryOp::Add, Value::Integer(b)) => Ok(Value::Integer(a + b)),
@@ -1053,7 +1359,14 @@ let mut parser = crate::parser::Parser::new_simple(tokens);
             (Value::String(a), BinaryOp::Equal, Value::String(b)) => Ok(Value::Bool(a == b)),
             (Value::Bool(a), BinaryOp::Equal, Value::Bool(b)) => Ok(Value::Bool(a == b)),
             (Value::Array(a), BinaryOp::Equal, Value::Array(b)) => Ok(Value::Bool(a == b)),
+            (Value::Tuple(a), BinaryOp::Equal, Value::Tuple(b)) => Ok(Value::Bool(a == b)),
             (Value::Collection(a), BinaryOp::Equal, Value::Collection(b)) => Ok(Value::Bool(a == b)),
+            (Value::Dictionary(a), BinaryOp::Equal, Value::Dictionary(b)) => Ok(Value::Bool(a == b)),
+            // Ranges equal anything with the same elements, so 0..3 == [0, 1, 2]
+            (Value::Range(a), BinaryOp::Equal, Value::Range(b)) => Ok(Value::Bool(a.iter().eq(b.iter()))),
+            (Value::Range(range), BinaryOp::Equal, Value::Array(arr)) | (Value::Array(arr), BinaryOp::Equal, Value::Range(range)) => {
+                Ok(Value::Bool(range.len() == arr.len() && range.iter().eq(arr.iter().cloned())))
+            }
             (Value::Nil, BinaryOp::Equal, Value::Nil) => Ok(Value::Bool(true)),
             (_, BinaryOp::Equal, _) => Ok(Value::Bool(false)),
             
@@ -1087,7 +1400,7 @@ let mut parser = crate::parser::Parser::new_simple(tokens);
             (Value::Float(a), BinaryOp::GreaterEqual, Value::Integer(b)) => Ok(Value::Bool(*a >= (*b as f64))),
             
             // In operator - check if left value is contained in right value
-            (left_val, BinaryOp::In, Value::Array(arr)) => {
+            (left_val, BinaryOp::In, Value::Array(arr) | Value::Tuple(arr)) => {
                 for item in arr {
                     let equal = self.evaluate_binary_op(left_val, &BinaryOp::Equal, item)?;
                     if let Value::Bool(true) = equal {
@@ -1104,11 +1417,14 @@ let mut parser = crate::parser::Parser::new_simple(tokens);
                 let left_str = left_val.to_string();
                 Ok(Value::Bool(s.contains(&left_str)))
             }
-            (Value::String(key), BinaryOp::In, Value::Dictionary(dict)) => {
+            (key, BinaryOp::In, Value::Dictionary(dict)) => {
                 Ok(Value::Bool(dict.contains_key(key)))
             }
-            (Value::String(key), BinaryOp::In, Value::Collection(set)) => {
-                Ok(Value::Bool(set.contains(key)))
+            (item, BinaryOp::In, Value::Range(range)) => {
+                Ok(Value::Bool(range.contains(item)))
+            }
+            (item, BinaryOp::In, Value::Collection(set)) => {
+                Ok(Value::Bool(set.contains(item)))
             }
             
             // Logical
@@ -1133,10 +1449,117 @@ let mut parser = crate::parser::Parser::new_simple(tokens);
         }
     }
     
+    /// Evaluate the components of `a[i, j:k]` for ndarray indexing
+    fn evaluate_index_components(&mut self, indices: &[IndexComponent]) -> RuntimeResult<Vec<AxisIndex>> {
+        let mut axes = Vec::with_capacity(indices.len());
+        for component in indices {
+            let axis = match component {
+                IndexComponent::Index(expr) => match self.evaluate_expression(expr)? {
+                    Value::Integer(i) => AxisIndex::At(i),
+                    other => return Err(RuntimeError {
+                        message: format!("ndarray index must be an integer, got {}", other.type_name()),
+                    }),
+                },
+                IndexComponent::Slice { from, to, step } => {
+                    let step = match self.evaluate_slice_bound(step)? {
+                        Some(0) => return Err(RuntimeError { message: "Slice step cannot be zero".to_string() }),
+                        Some(n) => n,
+                        None => 1,
+                    };
+                    AxisIndex::Slice {
+                        start: self.evaluate_slice_bound(from)?,
+                        stop: self.evaluate_slice_bound(to)?,
+                        step,
+                    }
+                }
+            };
+            axes.push(axis);
+        }
+        Ok(axes)
+    }
+
+    fn evaluate_slice_bound(&mut self, bound: &Option<Box<Expr>>) -> RuntimeResult<Option<i64>> {
+        match bound {
+            None => Ok(None),
+            Some(expr) => match self.evaluate_expression(expr)? {
+                Value::Integer(n) => Ok(Some(n)),
+                other => Err(RuntimeError {
+                    message: format!("Slice bounds must be integers, got {}", other.type_name()),
+                }),
+            },
+        }
+    }
+
+    /// Arithmetic and comparisons involving a decimal. Integers are promoted exactly;
+    /// floats may only be compared, since mixing them into arithmetic would lose exactness.
+    /// Returns None when the operands are not decimal-related so the caller can continue.
+    fn evaluate_decimal_op(&self, left: &Value, op: &BinaryOp, right: &Value) -> Option<RuntimeResult<Value>> {
+        let (a, b) = match (left, right) {
+            (Value::Decimal(a), Value::Decimal(b)) => (*a, *b),
+            (Value::Decimal(a), Value::Integer(b)) => (*a, Decimal::from(*b)),
+            (Value::Integer(a), Value::Decimal(b)) => (Decimal::from(*a), *b),
+            (Value::Decimal(_), Value::Float(_)) | (Value::Float(_), Value::Decimal(_)) => {
+                let as_float = |v: &Value| match v {
+                    Value::Decimal(d) => Value::Float(crate::value::decimal_to_f64(d)),
+                    other => other.clone(),
+                };
+                return match op {
+                    BinaryOp::Add | BinaryOp::Subtract | BinaryOp::Multiply | BinaryOp::Divide | BinaryOp::Modulo => {
+                        Some(Err(RuntimeError {
+                            message: format!(
+                                "Cannot mix decimal and float in arithmetic: {} {} {} (convert the float with decimal())",
+                                left.type_name(), op, right.type_name()
+                            ),
+                        }))
+                    }
+                    BinaryOp::Equal | BinaryOp::NotEqual | BinaryOp::Less | BinaryOp::Greater
+                    | BinaryOp::LessEqual | BinaryOp::GreaterEqual => {
+                        Some(self.evaluate_binary_op(&as_float(left), op, &as_float(right)))
+                    }
+                    _ => None,
+                };
+            }
+            _ => return None,
+        };
+
+        let overflow = || RuntimeError {
+            message: format!("Decimal overflow: {} {} {}", a, op, b),
+        };
+        let result = match op {
+            BinaryOp::Add => a.checked_add(b).map(Value::Decimal).ok_or_else(overflow),
+            BinaryOp::Subtract => a.checked_sub(b).map(Value::Decimal).ok_or_else(overflow),
+            BinaryOp::Multiply => a.checked_mul(b).map(Value::Decimal).ok_or_else(overflow),
+            BinaryOp::Divide => {
+                if b.is_zero() {
+                    Err(RuntimeError { message: "Division by zero".to_string() })
+                } else {
+                    a.checked_div(b).map(Value::Decimal).ok_or_else(overflow)
+                }
+            }
+            BinaryOp::Modulo => {
+                if b.is_zero() {
+                    Err(RuntimeError { message: "Modulo by zero".to_string() })
+                } else {
+                    a.checked_rem(b).map(Value::Decimal).ok_or_else(overflow)
+                }
+            }
+            BinaryOp::Equal => Ok(Value::Bool(a == b)),
+            BinaryOp::NotEqual => Ok(Value::Bool(a != b)),
+            BinaryOp::Less => Ok(Value::Bool(a < b)),
+            BinaryOp::Greater => Ok(Value::Bool(a > b)),
+            BinaryOp::LessEqual => Ok(Value::Bool(a <= b)),
+            BinaryOp::GreaterEqual => Ok(Value::Bool(a >= b)),
+            _ => return None,
+        };
+        Some(result)
+    }
+
     fn evaluate_unary_op(&self, op: &UnaryOp, operand: &Value) -> RuntimeResult<Value> {
         match (op, operand) {
             (UnaryOp::Negate, Value::Integer(n)) => Ok(Value::Integer(-n)),
             (UnaryOp::Negate, Value::Float(f)) => Ok(Value::Float(-f)),
+            (UnaryOp::Negate, Value::Decimal(d)) => Ok(Value::Decimal(-*d)),
+            (UnaryOp::Negate, Value::NdArray(arr)) => crate::numpy::negate(arr),
             (UnaryOp::Not, val) => Ok(Value::Bool(!val.is_truthy())),
             (op, operand) => Err(RuntimeError {
                 message: format!("Unsupported unary operation: {} {}", op, operand.type_name()),
@@ -1145,168 +1568,85 @@ let mut parser = crate::parser::Parser::new_simple(tokens);
     }
     
     fn load_module(&mut self, module_path: &str, items: &Option<Vec<String>>, alias: &Option<String>) -> RuntimeResult<()> {
-        // First check if this is a builtin module (base conversion, etc.)
-        if let Some(module_functions) = crate::stdlib::get_module(module_path) {
-            // It's a builtin module - load the requested functions
-            let module_env = if let Some(items) = items {
-                // Selective import: import {item1, item2} from "module"
-                let mut selected = HashMap::new();
-                for item in items {
-                    if let Some(value) = module_functions.get(item) {
-                        selected.insert(item.clone(), value.clone());
-                    } else {
-                        return Err(RuntimeError {
-                            message: format!("'{}' not found in module '{}'", item, module_path),
-                        });
-                    }
-                }
-                selected
-            } else if let Some(alias_name) = alias {
-                // Import with alias: import "module" as m
-                let mut aliased = HashMap::new();
-                aliased.insert(alias_name.clone(), Value::ModuleObject(module_functions));
-                aliased
-            } else {
-                // Full import: import "module" - load all functions directly
-                module_functions
-            };
-
-            // Add module functions to current environment
-            for (name, value) in module_env {
-                self.environment.define(name, value);
-            }
+        let module = self.import_module(module_path)?;
 
-            return Ok(());
-        }
-
-        // Not a builtin module - load from file
-        // Get the directory where the executable is located for resolving stdlib paths
-        let exe_dir = std::env::current_exe()
-            .ok()
-            .and_then(|p| p.parent().map(|p| p.to_path_buf()))
-            .unwrap_or_else(|| std::env::current_dir().unwrap_or_default());
-
-        // Try to find project root (go up from target/release or target/debug)
-        let project_root = exe_dir.parent()
-            .and_then(|p| p.parent())
-            .map(|p| p.to_path_buf())
-            .unwrap_or_else(|| exe_dir.clone());
-
-        // Get home directory for ~ expansion
-        let home_dir = std::env::var("HOME")
-            .ok()
-            .map(|h| std::path::PathBuf::from(h));
-
-        // Get current working directory
-        let cwd = std::env::current_dir().unwrap_or_default();
-
-        // Determine the module file path based on import style
-        let module_file_path = if module_path.starts_with("~/") {
-            // Home directory path: import "~/Documents/MyModule"
-            if let Some(ref home) = home_dir {
-                let path_without_tilde = module_path.trim_start_matches("~/");
-                let path_with_ext = if path_without_tilde.ends_with(".ject") {
-                    path_without_tilde.to_string()
-                } else {
-                    format!("{}.ject", path_without_tilde)
-                };
-                let full_path = home.join(&path_with_ext);
-                if full_path.exists() {
-                    full_path.to_string_lossy().to_string()
-                } else {
-                    return Err(RuntimeError {
-                        message: format!("Module '{}' not found at {}", module_path, full_path.display()),
-                    });
+        // Import the exported values based on import type, read-only
+        match (items, alias) {
+            (Some(item_list), None) => {
+                // import {item1, item2} from "module"
+                for item_name in item_list {
+                    let value = module.get(module_path, item_name)?;
+                    self.environment.define_constant(item_name.clone(), value);
                 }
-            } else {
-                return Err(RuntimeError {
-                    message: "Could not determine home directory for '~' path".to_string(),
-                });
-            }
-        } else if module_path.starts_with("/") {
-            // Absolute path: import "/home/user/mymodule"
-            let path_with_ext = if module_path.ends_with(".ject") {
-                module_path.to_string()
-            } else {
-                format!("{}.ject", module_path)
-            };
-            let full_path = std::path::PathBuf::from(&path_with_ext);
-            if full_path.exists() {
-                full_path.to_string_lossy().to_string()
-            } else {
-                return Err(RuntimeError {
-                    message: format!("Module '{}' not found at {}", module_path, full_path.display()),
-                });
             }
-        } else if module_path.starts_with("./") || module_path.starts_with("../") {
-            // Relative path from current directory
-            let path_with_ext = if module_path.ends_with(".ject") {
-                module_path.to_string()
-            } else {
-                format!("{}.ject", module_path)
-            };
-            let full_path = cwd.join(&path_with_ext);
-            if full_path.exists() {
-                full_path.to_string_lossy().to_string()
-            } else {
-                return Err(RuntimeError {
-                    message: format!("Module '{}' not found at {}", module_path, full_path.display()),
-                });
+            (None, Some(alias_name)) => {
+                // import "module" as alias
+                self.environment.define_constant(alias_name.clone(), Value::ModuleObject(module.exports));
             }
-        } else if module_path.contains("/") {
-            // Path relative to project root (e.g., "modules/math" or "lib/utils")
-            let path_with_ext = if module_path.ends_with(".ject") {
-                module_path.to_string()
-            } else {
-                format!("{}.ject", module_path)
-            };
-            let full_path = project_root.join(&path_with_ext);
-            if full_path.exists() {
-                full_path.to_string_lossy().to_string()
-            } else {
-                // Try cwd as fallback
-                let cwd_path = cwd.join(&path_with_ext);
-                if cwd_path.exists() {
-                    cwd_path.to_string_lossy().to_string()
+            (None, None) => {
+                if let Some(namespace) = crate::resolver::namespace(module_path) {
+                    // import "geo/shapes" - exports are reached through shapes.name
+                    self.environment.define_constant(namespace.to_string(), Value::ModuleObject(module.exports));
                 } else {
-                    return Err(RuntimeError {
-                        message: format!("Module '{}' not found at {}", module_path, full_path.display()),
-                    });
+                    // import "module" - import all exports directly
+                    for (name, value) in module.exports {
+                        self.environment.define_constant(name, value);
+                    }
                 }
             }
-        } else {
-            // Simple module name - check stdlib directory (relative to project root first, then cwd)
-            let module_name = if module_path.ends_with(".ject") {
-                module_path.trim_end_matches(".ject").to_string()
-            } else {
-                module_path.to_string()
-            };
-            
-            // Try project root stdlib first
-            let stdlib_path = project_root.join("stdlib").join(format!("{}.ject", module_name));
-            if stdlib_path.exists() {
-                stdlib_path.to_string_lossy().to_string()
-            } else {
-                // Try cwd stdlib
-                let cwd_stdlib = cwd.join("stdlib").join(format!("{}.ject", module_name));
-                if cwd_stdlib.exists() {
-                    cwd_stdlib.to_string_lossy().to_string()
-                } else {
-                    return Err(RuntimeError {
-                        message: format!("Module '{}' not found. Expected at 'stdlib/{}.ject' or provide a path.", module_path, module_name),
-                    });
-                }
+            (Some(_), Some(_)) => {
+                return Err(RuntimeError {
+                    message: "Cannot use both specific imports and alias in the same import statement".to_string(),
+                });
             }
-        };
+        }
+
+        Ok(())
+    }
+
+    /// A builtin module (base conversion, numpy, ...) or a module file, found
+    /// relative to the file currently running
+    fn import_module(&mut self, module_path: &str) -> RuntimeResult<LoadedModule> {
+        if let Some(exports) = crate::stdlib::get_module(module_path) {
+            return Ok(LoadedModule { exports, private: HashSet::new() });
+        }
 
-        if !Path::new(&module_file_path).exists() {
+        let source = self.resolver.resolve(module_path, self.current_dir.as_deref())
+            .map_err(|message| RuntimeError { message })?;
+        let key = source.key().map_err(|e| RuntimeError {
+            message: format!("Failed to read module '{}': {}", module_path, e),
+        })?;
+        self.module_exports(module_path, &source, &key)
+    }
+
+    /// Exports of the module identified by `path`, evaluating it on first import only.
+    /// Re-entering a module that is still being evaluated is an import cycle
+    fn module_exports(&mut self, module_path: &str, source: &ModuleSource, path: &Path) -> RuntimeResult<LoadedModule> {
+        if let Some(module) = self.modules.loaded.get(path) {
+            return Ok(module.clone());
+        }
+        if let Some(start) = self.modules.loading.iter().position(|loading| loading == path) {
+            let chain: Vec<String> = self.modules.loading[start..].iter()
+                .chain(std::iter::once(&path.to_path_buf()))
+                .map(|p| display_module_path(p))
+                .collect();
             return Err(RuntimeError {
-                message: format!("Module '{}' not found at {}", module_path, module_file_path),
+                message: format!("Import cycle: {}", chain.join(" -> ")),
             });
         }
-        
+
+        self.modules.loading.push(path.to_path_buf());
+        let exports = self.evaluate_module(module_path, source);
+        self.modules.loading.pop();
+
+        let module = exports?;
+        self.modules.loaded.insert(path.to_path_buf(), module.clone());
+        Ok(module)
+    }
+
+    fn evaluate_module(&mut self, module_path: &str, source: &ModuleSource) -> RuntimeResult<LoadedModule> {
         // Read and parse the module file
-        let module_content = fs::read_to_string(&module_file_path)
+        let module_content = source.read()
             .map_err(|e| RuntimeError {
                 message: format!("Failed to read module '{}': {}", module_path, e),
             })?;
@@ -1314,7 +1654,7 @@ let mut parser = crate::parser::Parser::new_simple(tokens);
         let mut lexer = crate::lexer::Lexer::new(&module_content);
         let located_tokens = lexer.tokenize_with_positions();
         let tokens: Vec<crate::lexer::Token> = located_tokens.into_iter().map(|lt| lt.token).collect();
-let mut parser = crate::parser::Parser::new_simple(tokens);
+        let mut parser = crate::parser::Parser::new_simple(tokens);
         let statements = parser.parse().map_err(|e| RuntimeError {
             message: format!("Parse error in module '{}': {}", module_path, e),
         })?;
@@ -1322,19 +1662,31 @@ let mut parser = crate::parser::Parser::new_simple(tokens);
         // Create a new environment for the module
         let mut module_env = Environment::new();
         
-        // Load standard library into module environment 
+        // Load standard library into module environment, read-only as for scripts
         let stdlib = crate::stdlib::create_stdlib();
         for (name, value) in stdlib {
-            module_env.define(name, value);
+            module_env.define_constant(name, value);
         }
+        module_env.push_scope();
         
-        // Save current environment and switch to module environment
+        // Save current environment and switch to module environment; it is
+        // restored even when the module fails part way through
+        // The module's own ./ imports are relative to its file
         let saved_env = std::mem::replace(&mut self.environment, module_env);
-        
+        let saved_dir = std::mem::replace(&mut self.current_dir, source.dir());
+        let exports = self.execute_module(&statements);
+        self.environment = saved_env;
+        self.current_dir = saved_dir;
+        exports
+    }
+
+    /// Run a module's statements in the current (module) environment and
+    /// collect its exports
+    fn execute_module(&mut self, statements: &[Stmt]) -> RuntimeResult<LoadedModule> {
         // First, execute all non-export statements to build up the module environment
-        for statement in &statements {
+        for statement in statements {
             match statement {
-                Stmt::Export { .. } | Stmt::ExportFunction { .. } => {
+                Stmt::Export { .. } | Stmt::ExportFunction { .. } | Stmt::ExportFrom { .. } => {
                     // Skip export statements for now
                 }
                 _ => {
@@ -1345,7 +1697,7 @@ let mut parser = crate::parser::Parser::new_simple(tokens);
         
         // Process export functions and define them in the module environment first
         // This ensures they're available in the module scope for potential self-references
-        for statement in &statements {
+        for statement in statements {
             if let Stmt::ExportFunction { name, params, body } = statement {
                 let func = Value::Function {
                     params: params.clone(),
@@ -1357,7 +1709,7 @@ let mut parser = crate::parser::Parser::new_simple(tokens);
         
         // Now process export statements and create module functions with proper closure
         let mut exports = HashMap::new();
-        for statement in &statements {
+        for statement in statements {
             match statement {
                 Stmt::Export { name, value } => {
                     let val = self.evaluate_expression(value)?;
@@ -1373,49 +1725,29 @@ let mut parser = crate::parser::Parser::new_simple(tokens);
                     };
                     exports.insert(name.clone(), func);
                 }
-                _ => {
-                    // Already processed
-                }
-            }
-        }
-        
-        // Restore original environment
-        self.environment = saved_env;
-        
-        // Import the exported values based on import type
-        match (items, alias) {
-            (Some(item_list), None) => {
-                // import {item1, item2} from "module"
-                for item_name in item_list {
-                    if let Some(value) = exports.get(item_name) {
-                        self.environment.define(item_name.clone(), value.clone());
-                    } else {
-                        return Err(RuntimeError {
-                            message: format!("Module '{}' does not export '{}'", module_path, item_name),
-                        });
+                Stmt::ExportFrom { module_path, items } => {
+                    let module = self.import_module(module_path)?;
+                    match items {
+                        Some(items) => {
+                            for item in items {
+                                exports.insert(item.clone(), module.get(module_path, item)?);
+                            }
+                        }
+                        None => exports.extend(module.exports),
                     }
                 }
-            }
-            (None, Some(alias_name)) => {
-                // import "module" as alias
-                // Create a module object with all exports
-                let module_obj = Value::ModuleObject(exports);
-                self.environment.define(alias_name.clone(), module_obj);
-            }
-            (None, None) => {
-                // import "module" - import all exports directly
-                for (name, value) in exports {
-                    self.environment.define(name, value);
+                _ => {
+                    // Already processed
                 }
             }
-            (Some(_), Some(_)) => {
-                return Err(RuntimeError {
-                    message: "Cannot use both specific imports and alias in the same import statement".to_string(),
-                });
-            }
         }
-        
-        Ok(())
+
+        // Everything else the module defined stays behind in its closures
+        let private = self.environment.local_names()
+            .filter(|name| !exports.contains_key(*name))
+            .cloned()
+            .collect();
+        Ok(LoadedModule { exports, private })
     }
     
     fn call_function(&mut self, func: Value, args: &[Argument]) -> RuntimeResult<Value> {
@@ -1476,133 +1808,237 @@ let mut parser = crate::parser::Parser::new_simple(tokens);
                 self.environment = saved_env;
                 Ok(result)
             }
-            Value::Lambda { params, body, closure_env } => {
-                // Save current environment and use closure environment
-                let saved_env = std::mem::replace(&mut self.environment, closure_env.clone());
-                
-                // Convert arguments to old format for lambdas (they don't support defaults yet)
-                let mut arg_values = Vec::new();
-                for arg in args {
-                    match arg {
-                        Argument::Positional(expr) => {
-                            arg_values.push(self.evaluate_expression(expr)?);
-                        }
-                        Argument::Keyword { .. } => {
-                            return Err(RuntimeError {
-                                message: "Lambdas do not support keyword arguments".to_string(),
-                            });
+            Value::Lambda { .. } => {
+                // Lambdas take positional arguments only (no defaults yet)
+                let (arg_values, keyword_values) = self.evaluate_arguments(args)?;
+                if !keyword_values.is_empty() {
+                    return Err(RuntimeError {
+                        message: "Lambdas do not support keyword arguments".to_string(),
+                    });
+                }
+                self.call_value(&func, arg_values)
+            }
+            Value::BuiltinFunction(name) => {
+                // Convert arguments to old format for builtin functions
+                let (mut arg_values, keyword_values) = self.evaluate_arguments(args)?;
+                // Builtins that don't know about ranges see the array a range stands for
+                if !crate::stdlib::accepts_range(&name) {
+                    for arg in arg_values.iter_mut() {
+                        if let Value::Range(range) = arg {
+                            *arg = Value::Array(range.to_vec());
                         }
                     }
                 }
-                
-                if arg_values.len() != params.len() {
+                // Only numpy functions take keywords (axis=, keepdims=, ...)
+                if !keyword_values.is_empty() && !name.starts_with("np_") {
                     return Err(RuntimeError {
-                        message: format!("Expected {} arguments but got {}", params.len(), arg_values.len()),
+                        message: "Builtin functions do not support keyword arguments".to_string(),
                     });
                 }
-                
+
+                // Try numpy functions first (they have np_ prefix)
+                if name.starts_with("np_") {
+                    crate::numpy::call_numpy_function_with_keywords(&name, arg_values, keyword_values)
+                } else if name.starts_with("df_") {
+                    // dataframe functions may call back into Ject (filter, sort_by)
+                    crate::dataframe::call_dataframe_function(&name, arg_values, &mut |f, args| self.call_value(f, args))
+                } else if name == "args" {
+                    if !arg_values.is_empty() {
+                        return Err(RuntimeError { message: "args() takes no arguments".to_string() });
+                    }
+                    Ok(Value::Array(self.script_args.iter().cloned().map(Value::String).collect()))
+                } else if name == "map_values" || name == "filter_keys" {
+                    crate::stdlib::call_dictionary_function(&name, arg_values, &mut |f, args| self.call_value(f, args))
+                } else {
+                    crate::stdlib::call_builtin_function(&name, arg_values)
+                }
+            }
+            _ => Err(RuntimeError {
+                message: format!("Cannot call {}", func.type_name()),
+            }),
+        }
+    }
+    
+    /// Call a function value with already evaluated arguments, for builtins that
+    /// take a callback (dataframe filter and sort_by, map_values, filter_keys)
+    fn call_value(&mut self, func: &Value, args: Vec<Value>) -> RuntimeResult<Value> {
+        match func {
+            Value::Lambda { params, body, closure_env } => {
+                if args.len() != params.len() {
+                    return Err(RuntimeError {
+                        message: format!("Expected {} arguments but got {}", params.len(), args.len()),
+                    });
+                }
+
+                // Save current environment and use closure environment
+                let saved_env = std::mem::replace(&mut self.environment, closure_env.clone());
                 self.environment.push_scope();
-                
-                for (param, arg) in params.iter().zip(arg_values.iter()) {
-                    self.environment.define(param.clone(), arg.clone());
+
+                for (param, arg) in params.iter().zip(args) {
+                    self.environment.define(param.clone(), arg);
                 }
-                
-                let result = match &body {
-                    crate::ast::LambdaBody::Expression(ref expr) => {
-                        self.evaluate_expression(&expr)?
-                    }
-                    crate::ast::LambdaBody::Block(statements) => {
-                        match self.execute_block(&statements)? {
-                            ControlFlow::Return(value) => value,
-                            ControlFlow::Throw(error) => return Err(RuntimeError {
-                                message: format!("Error in lambda: {}", error),
-                            }),
-                            ControlFlow::Break | ControlFlow::Continue => {
-                                return Err(RuntimeError {
-                                    message: "break/continue in lambda".to_string(),
-                                });
-                            }
-                            ControlFlow::None => Value::Nil,
-                        }
-                    }
+
+                let result = match body {
+                    crate::ast::LambdaBody::Expression(expr) => self.evaluate_expression(expr),
+                    crate::ast::LambdaBody::Block(statements) => match self.execute_block(statements) {
+                        Ok(ControlFlow::Return(value)) => Ok(value),
+                        Ok(ControlFlow::Throw(error)) => Err(RuntimeError {
+                            message: format!("Error in lambda: {}", error),
+                        }),
+                        Ok(ControlFlow::Break | ControlFlow::Continue) => Err(RuntimeError {
+                            message: "break/continue in lambda".to_string(),
+                        }),
+                        Ok(ControlFlow::None) => Ok(Value::Nil),
+                        Err(e) => Err(e),
+                    },
                 };
 
                 self.environment.pop_scope();
                 // Restore original environment
                 self.environment = saved_env;
-                Ok(result)
+                result
             }
-            Value::BuiltinFunction(name) => {
-                // Convert arguments to old format for builtin functions
-                let mut arg_values = Vec::new();
-                for arg in args {
-                    match arg {
-                        Argument::Positional(expr) => {
-                            arg_values.push(self.evaluate_expression(expr)?);
-                        }
-                        Argument::Keyword { .. } => {
-                            return Err(RuntimeError {
-                                message: "Builtin functions do not support keyword arguments".to_string(),
+            Value::Function { params, body } | Value::ModuleFunction { params, body, .. } => {
+                let variadic = params.last().is_some_and(|p| p.variadic);
+                if args.len() > params.len() && !variadic {
+                    return Err(RuntimeError {
+                        message: format!("Expected at most {} arguments but got {}", params.len(), args.len()),
+                    });
+                }
+
+                let saved_env = match func {
+                    Value::ModuleFunction { closure_env, .. } => Some(std::mem::replace(&mut self.environment, closure_env.clone())),
+                    _ => None,
+                };
+                self.environment.push_scope();
+
+                // Missing trailing arguments fall back to their defaults
+                let mut args = args.into_iter();
+                let mut result = Ok(Value::Nil);
+                for param in params {
+                    if param.variadic {
+                        self.environment.define(param.name.clone(), Value::Array(args.by_ref().collect()));
+                        break;
+                    }
+                    let value = match (args.next(), &param.default_value) {
+                        (Some(value), _) => value,
+                        (None, Some(default)) => match self.evaluate_expression(default) {
+                            Ok(value) => value,
+                            Err(e) => {
+                                result = Err(e);
+                                break;
+                            }
+                        },
+                        (None, None) => {
+                            result = Err(RuntimeError {
+                                message: format!("Missing required argument '{}'", param.name),
                             });
+                            break;
                         }
-                    }
+                    };
+                    self.environment.define(param.name.clone(), value);
                 }
 
-                // Try numpy functions first (they have np_ prefix)
-                if name.starts_with("np_") {
-                    crate::numpy::call_numpy_function(&name, arg_values)
-                } else {
-                    crate::stdlib::call_builtin_function(&name, arg_values)
+                if result.is_ok() {
+                    result = match self.execute_block(body) {
+                        Ok(ControlFlow::Return(value)) => Ok(value),
+                        Ok(ControlFlow::Throw(error)) => Err(RuntimeError {
+                            message: format!("Error in function: {}", error),
+                        }),
+                        Ok(ControlFlow::Break | ControlFlow::Continue) => Err(RuntimeError {
+                            message: "break/continue in function".to_string(),
+                        }),
+                        Ok(ControlFlow::None) => Ok(Value::Nil),
+                        Err(e) => Err(e),
+                    };
                 }
+
+                self.environment.pop_scope();
+                if let Some(saved_env) = saved_env {
+                    self.environment = saved_env;
+                }
+                result
             }
             _ => Err(RuntimeError {
                 message: format!("Cannot call {}", func.type_name()),
             }),
         }
     }
-    
+
+    /// Evaluate call arguments in order, expanding `...array` into positional
+    /// arguments and `**dict` into keyword arguments
+    fn evaluate_arguments(&mut self, args: &[Argument]) -> RuntimeResult<EvaluatedArguments> {
+        let mut positional = Vec::new();
+        let mut keywords = Vec::new();
+        for arg in args {
+            match arg {
+                Argument::Positional(expr) => positional.push(self.evaluate_expression(expr)?),
+                Argument::Keyword { name, value } => keywords.push((name.clone(), self.evaluate_expression(value)?)),
+                Argument::Spread(expr) => {
+                    let value = self.evaluate_expression(expr)?;
+                    positional.extend(spread_values(value)?);
+                }
+                Argument::KeywordSpread(expr) => match self.evaluate_expression(expr)? {
+                    Value::Dictionary(dict) => {
+                        for (key, value) in dict {
+                            match key {
+                                Value::String(name) => keywords.push((name, value)),
+                                other => return Err(RuntimeError {
+                                    message: format!("Keyword argument names must be strings, got {}", other),
+                                }),
+                            }
+                        }
+                    }
+                    other => return Err(RuntimeError {
+                        message: format!("'**' requires a dictionary, got {}", other.type_name()),
+                    }),
+                },
+            }
+        }
+        Ok((positional, keywords))
+    }
+
     fn resolve_arguments(&mut self, params: &[crate::ast::Parameter], args: &[Argument]) -> RuntimeResult<Vec<Value>> {
+        let (positional, keywords) = self.evaluate_arguments(args)?;
         let mut resolved_args = vec![None; params.len()];
-        let mut positional_count = 0;
+        // A trailing ...rest parameter takes whatever positional arguments are left over
+        let fixed = params.iter().take_while(|p| !p.variadic).count();
+        let mut extra = Vec::new();
         
         // First pass: handle positional arguments
-        for arg in args {
-            match arg {
-                Argument::Positional(expr) => {
-                    if positional_count >= params.len() {
-                        return Err(RuntimeError {
-                            message: "Too many positional arguments".to_string(),
-                        });
-                    }
-                    resolved_args[positional_count] = Some(self.evaluate_expression(expr)?);
-                    positional_count += 1;
-                }
-                Argument::Keyword { .. } => {
-                    // We'll handle keyword arguments in the second pass
-                }
+        for (i, value) in positional.into_iter().enumerate() {
+            if i < fixed {
+                resolved_args[i] = Some(value);
+            } else if fixed < params.len() {
+                extra.push(value);
+            } else {
+                return Err(RuntimeError {
+                    message: "Too many positional arguments".to_string(),
+                });
             }
         }
+        if fixed < params.len() {
+            resolved_args[fixed] = Some(Value::Array(extra));
+        }
         
         // Second pass: handle keyword arguments
-        for arg in args {
-            if let Argument::Keyword { name, value } = arg {
-                // Find the parameter with this name
-                let param_index = params.iter().position(|p| p.name == *name);
-                
-                match param_index {
-                    Some(index) => {
-                        if resolved_args[index].is_some() {
-                            return Err(RuntimeError {
-                                message: format!("Argument '{}' specified multiple times", name),
-                            });
-                        }
-                        resolved_args[index] = Some(self.evaluate_expression(value)?);
-                    }
-                    None => {
+        for (name, value) in keywords {
+            // Find the parameter with this name
+            let param_index = params[..fixed].iter().position(|p| p.name == name);
+            
+            match param_index {
+                Some(index) => {
+                    if resolved_args[index].is_some() {
                         return Err(RuntimeError {
-                            message: format!("Unknown parameter '{}'", name),
+                            message: format!("Argument '{}' specified multiple times", name),
                         });
                     }
+                    resolved_args[index] = Some(value);
+                }
+                None => {
+                    return Err(RuntimeError {
+                        message: format!("Unknown parameter '{}'", name),
+                    });
                 }
             }
         }
@@ -1645,9 +2081,13 @@ let mut parser = crate::parser::Parser::new_simple(tokens);
             Expr::Identifier(name) => {
                 let current = self.environment.get(name)
                     .ok_or_else(|| RuntimeError { message: format!("Undefined variable '{}'", name) })?;
+                if self.environment.is_constant(name) {
+                    return Err(RuntimeError { message: format!("Cannot assign to constant '{}'", name) });
+                }
                 let new_value = match current {
                     Value::Integer(n) => Value::Integer(if is_increment { n + 1 } else { n - 1 }),
                     Value::Float(f) => Value::Float(if is_increment { f + 1.0 } else { f - 1.0 }),
+                    Value::Decimal(d) => Value::Decimal(if is_increment { d + Decimal::ONE } else { d - Decimal::ONE }),
                     _ => return Err(RuntimeError { message: "Can only increment/decrement numbers".to_string() }),
                 };
                 self.environment.set(name, new_value.clone());
@@ -1676,6 +2116,7 @@ let mut parser = crate::parser::Parser::new_simple(tokens);
                 let (arr, is_unique) = match array_val {
                     Value::Array(arr) => (arr, false),
                     Value::UniqueArray(arr) => (arr, true),
+                    Value::Range(range) => (range.to_vec(), false),
                     _ => return Err(RuntimeError { message: "map() requires an array or unique array".to_string() }),
                 };
 
@@ -1739,6 +2180,7 @@ let mut parser = crate::parser::Parser::new_simple(tokens);
                 let (arr, is_unique) = match array_val {
                     Value::Array(arr) => (arr, false),
                     Value::UniqueArray(arr) => (arr, true),
+                    Value::Range(range) => (range.to_vec(), false),
                     _ => return Err(RuntimeError { message: "filter() requires an array or unique array".to_string() }),
                 };
 
@@ -1807,6 +2249,10 @@ let mut parser = crate::parser::Parser::new_simple(tokens);
                 } else {
                     Value::Nil
                 };
+                let array_val = match array_val {
+                    Value::Range(range) => Value::Array(range.to_vec()),
+                    other => other,
+                };
                 
                 if let Value::Array(arr) = array_val {
                     if let Value::Lambda { params, body, closure_env } = func_val {
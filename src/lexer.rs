@@ -25,6 +25,14 @@ impl LocatedToken {
     }
 }
 
+/// A `#` or `#* ... *#` comment. The parser never sees these; the lexer
+/// keeps them for the formatter
+#[derive(Debug, Clone, PartialEq)]
+pub struct Comment {
+    pub text: String,
+    pub position: SourcePosition,
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum InterpolationPart {
     Text(String),
@@ -36,6 +44,7 @@ pub enum Token {
     // Literals
     Integer(i64),
     Float(f64),
+    Decimal(rust_decimal::Decimal),
     String(String),
     InterpolatedString(Vec<InterpolationPart>),
     Bool(bool),
@@ -45,6 +54,7 @@ pub enum Token {
     
     // Keywords
     Let,
+    Const,
     Fn,
     Lambda,
     If,
@@ -81,6 +91,7 @@ pub enum Token {
     Plus,
     Minus,
     Star,
+    StarStar,        // ** (keyword spread in calls)
     Slash,
     Percent,
     Equal,
@@ -117,6 +128,8 @@ pub enum Token {
     Comma,
     Dot,
     DotDot,
+    DotDotEq,        // ..= (inclusive range)
+    Ellipsis,        // ... (spread and rest)
     Colon,
     Semicolon,
     Arrow,
@@ -125,6 +138,9 @@ pub enum Token {
     // Special
     Newline,
     Eof,
+
+    // A literal the lexer couldn't read, with why; the parser reports it
+    Invalid(String),
 }
 
 impl fmt::Display for Token {
@@ -132,6 +148,7 @@ impl fmt::Display for Token {
         match self {
             Token::Integer(n) => write!(f, "{}", n),
             Token::Float(n) => write!(f, "{}", n),
+            Token::Decimal(d) => write!(f, "{}d", d),
             Token::String(s) => write!(f, "\"{}\"", s),
             Token::Bool(b) => write!(f, "{}", b),
             Token::Identifier(s) => write!(f, "{}", s),
@@ -146,6 +163,7 @@ pub struct Lexer {
     line: usize,
     column: usize,
     current_char: Option<char>,
+    comments: Vec<Comment>,
 }
 
 impl Lexer {
@@ -159,6 +177,7 @@ impl Lexer {
             line: 1,
             column: 1,
             current_char,
+            comments: Vec::new(),
         }
     }
     
@@ -193,15 +212,28 @@ impl Lexer {
     }
     
     fn skip_comment(&mut self) {
+        let start = self.current_position();
         while let Some(ch) = self.current_char {
             if ch == '\n' {
                 break;
             }
             self.advance();
         }
+        self.record_comment(start);
+    }
+
+    fn record_comment(&mut self, start: SourcePosition) {
+        let text = self.input[start.position..self.position].iter().collect();
+        self.comments.push(Comment { text, position: start });
+    }
+
+    /// The comments skipped so far, in source order
+    pub fn comments(&self) -> &[Comment] {
+        &self.comments
     }
     
     fn skip_multiline_comment(&mut self) {
+        let start = self.current_position();
         // Skip #*
         self.advance();
         self.advance();
@@ -214,6 +246,7 @@ impl Lexer {
                 // Found *#, skip both and exit
                 self.advance();
                 self.advance();
+                self.record_comment(start);
                 return;
             }
             self.advance();
@@ -269,6 +302,18 @@ impl Lexer {
             }
         }
 
+        // Decimal literal: 12.50d (exact fixed-point, not allowed with an exponent)
+        if self.current_char == Some('d')
+            && !number.contains(['e', 'E'])
+            && !self.peek().is_some_and(|c| c.is_alphanumeric() || c == '_')
+        {
+            self.advance();
+            return match number.parse() {
+                Ok(decimal) => Token::Decimal(decimal),
+                Err(_) => Token::Invalid(format!("Decimal literal {}d is out of range", number)),
+            };
+        }
+
         if is_float {
             Token::Float(number.parse().unwrap_or(0.0))
         } else {
@@ -439,6 +484,7 @@ impl Lexer {
         
         match identifier.as_str() {
             "let" => Token::Let,
+            "const" => Token::Const,
             "fn" => Token::Fn,
             "lambda" => Token::Lambda,
             "if" => Token::If,
@@ -545,6 +591,11 @@ impl Lexer {
                         self.advance();
                         return LocatedToken::new(Token::StarEqual, start_pos);
                     }
+                    if self.peek() == Some('*') {
+                        self.advance();
+                        self.advance();
+                        return LocatedToken::new(Token::StarStar, start_pos);
+                    }
                     self.advance();
                     return LocatedToken::new(Token::Star, start_pos);
                 }
@@ -643,6 +694,14 @@ impl Lexer {
                     if self.peek() == Some('.') {
                         self.advance();
                         self.advance();
+                        if self.current_char == Some('.') {
+                            self.advance();
+                            return LocatedToken::new(Token::Ellipsis, start_pos);
+                        }
+                        if self.current_char == Some('=') {
+                            self.advance();
+                            return LocatedToken::new(Token::DotDotEq, start_pos);
+                        }
                         return LocatedToken::new(Token::DotDot, start_pos);
                     }
                     self.advance();
@@ -667,7 +726,7 @@ impl Lexer {
                     continue;
                 }
                 Some(ch) => {
-                    println!("Unexpected character: {}", ch);
+                    eprintln!("Unexpected character: {}", ch);
                     self.advance();
                     continue;
                 }
@@ -704,4 +763,22 @@ impl Lexer {
         
         tokens
     }
+
+    /// Every token with the source text it was read from, for tools that
+    /// rewrite source rather than run it
+    pub fn tokenize_with_text(&mut self) -> Vec<(LocatedToken, String)> {
+        let mut tokens = Vec::new();
+
+        loop {
+            let located_token = self.next_token();
+            let text = self.input[located_token.position.position..self.position].iter().collect();
+            let is_eof = matches!(located_token.token, Token::Eof);
+            tokens.push((located_token, text));
+            if is_eof {
+                break;
+            }
+        }
+
+        tokens
+    }
 }
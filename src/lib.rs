@@ -5,8 +5,18 @@ pub mod interpreter;
 pub mod value;
 pub mod stdlib;
 pub mod numpy;
+pub mod csv;
+pub mod dataframe;
+pub mod range;
+pub mod resolver;
+pub mod package;
 pub mod error;
 pub mod diagnostic;
+pub mod codes;
+pub mod formatter;
+pub mod docs;
+pub mod analysis;
+pub mod lsp;
 pub mod linter;
 
 #[cfg(test)]
@@ -1,12 +1,15 @@
 use std::collections::{HashMap, HashSet};
 use crate::ast::{Stmt, Expr, Parameter, Argument};
 use crate::diagnostic::Diagnostic;
+use crate::resolver::ModuleResolver;
+use std::path::Path;
 
 #[derive(Debug, Clone)]
 struct Variable {
     name: String,
     used: bool,
     declared_in_scope: usize,
+    constant: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -17,12 +20,14 @@ struct FunctionSignature {
 
 #[derive(Debug, Clone)]
 struct LintError {
+    code: &'static str,
     message: String,
     position: Option<crate::lexer::SourcePosition>,
 }
 
 #[derive(Debug, Clone)]
 struct LintWarning {
+    code: &'static str,
     message: String,
     position: Option<crate::lexer::SourcePosition>,
 }
@@ -39,6 +44,17 @@ pub struct Linter {
     // Store positioned tokens to find locations of identifiers
     positioned_tokens: Vec<(crate::lexer::Token, crate::lexer::SourcePosition)>,
     source: String,
+    resolver: ModuleResolver,
+    file_dir: Option<std::path::PathBuf>, // Directory of the linted file, for ./ imports
+    denied: HashSet<String>,              // Warning codes reported as errors; "warnings" means all
+    declarations_seen: HashMap<String, usize>, // `fn NAME` and `let NAME` declarations analyzed so far
+}
+
+/// A module file's exported names, and the top-level names it keeps private
+#[derive(Debug, Default)]
+struct ModuleNames {
+    exports: Vec<String>,
+    private: HashSet<String>,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -61,6 +77,10 @@ impl Linter {
             in_function: false,
             positioned_tokens: Vec::new(),
             source: String::new(),
+            resolver: ModuleResolver::default(),
+            file_dir: None,
+            denied: HashSet::new(),
+            declarations_seen: HashMap::new(),
         };
         
         // Add built-in functions to the functions set
@@ -77,13 +97,26 @@ impl Linter {
         self.functions.insert("to_float".to_string());
         self.functions.insert("to_string".to_string());
         self.functions.insert("to_bool".to_string());
+        self.functions.insert("decimal".to_string());
 
         // Collection primitives
         self.functions.insert("len".to_string());
         self.functions.insert("range".to_string());
+        self.functions.insert("collect".to_string());
         self.functions.insert("push".to_string());
         self.functions.insert("pop".to_string());
 
+        // Dictionary primitives
+        self.functions.insert("keys".to_string());
+        self.functions.insert("values".to_string());
+        self.functions.insert("items".to_string());
+        self.functions.insert("get".to_string());
+        self.functions.insert("remove".to_string());
+        self.functions.insert("merge".to_string());
+        self.functions.insert("update".to_string());
+        self.functions.insert("map_values".to_string());
+        self.functions.insert("filter_keys".to_string());
+
         // Array primitives
         self.functions.insert("sum".to_string());
         self.functions.insert("contains".to_string());
@@ -103,12 +136,14 @@ impl Linter {
         self.functions.insert("abs".to_string());
         self.functions.insert("sqrt".to_string());
         self.functions.insert("pow".to_string());
+        self.functions.insert("divmod".to_string());
         self.functions.insert("sin".to_string());
         self.functions.insert("cos".to_string());
         self.functions.insert("tan".to_string());
         self.functions.insert("floor".to_string());
         self.functions.insert("ceil".to_string());
         self.functions.insert("round".to_string());
+        self.functions.insert("round_decimal".to_string());
         self.functions.insert("min".to_string());
         self.functions.insert("max".to_string());
         self.functions.insert("random".to_string());
@@ -129,13 +164,14 @@ impl Linter {
         self.functions.insert("print".to_string());
         self.functions.insert("read_file".to_string());
         self.functions.insert("write_file".to_string());
+        self.functions.insert("args".to_string());
 
         // Testing
         self.functions.insert("assert".to_string());
 
         // Constants (variables, not functions)
-        self.declare_variable("PI".to_string());
-        self.declare_variable("E".to_string());
+        self.declare_constant("PI".to_string());
+        self.declare_constant("E".to_string());
 
         // ========== Module Functions (available via import) ==========
         
@@ -290,6 +326,23 @@ impl Linter {
         self
     }
 
+    pub fn with_resolver(mut self, resolver: ModuleResolver) -> Self {
+        self.resolver = resolver;
+        self
+    }
+
+    /// The file being linted, so its `./` and `../` imports resolve next to it
+    pub fn with_file(mut self, path: &std::path::Path) -> Self {
+        self.file_dir = path.parent().map(|dir| dir.to_path_buf());
+        self
+    }
+
+    /// Report these warning codes as errors (`--deny`, or `deny` under `[lints]` in ject.toml)
+    pub fn with_denied(mut self, codes: &[String]) -> Self {
+        self.denied = codes.iter().map(|code| code.to_uppercase()).collect();
+        self
+    }
+
     pub fn lint(&mut self, statements: &[Stmt]) -> (Vec<Diagnostic>, bool) {
         self.scopes.clear();
         self.scopes.push(HashMap::new()); // Global scope
@@ -302,6 +355,7 @@ impl Linter {
         self.add_builtin_functions();
         
         self.function_signatures.clear();
+        self.declarations_seen.clear();
         self.in_function = false;
 
         // Single pass: analyze all statements
@@ -317,16 +371,18 @@ impl Linter {
                     // CorLib constants
                     "PI" | "E" |
                     // CorLib functions
-                    "type_of" | "to_int" | "to_float" | "to_string" | "to_bool" |
-                    "len" | "range" | "push" | "pop" |
+                    "type_of" | "to_int" | "to_float" | "to_string" | "to_bool" | "decimal" |
+                    "len" | "range" | "collect" | "push" | "pop" |
+                    "keys" | "values" | "items" | "get" | "remove" | "merge" | "update" |
+                    "map_values" | "filter_keys" |
                     "sum" | "contains" | "index_of" | "first" | "last" |
                     "sort" | "reverse" | "unique" |
                     "map" | "filter" | "reduce" |
-                    "abs" | "sqrt" | "pow" | "sin" | "cos" | "tan" |
-                    "floor" | "ceil" | "round" | "min" | "max" | "random" |
+                    "abs" | "sqrt" | "pow" | "divmod" | "sin" | "cos" | "tan" |
+                    "floor" | "ceil" | "round" | "round_decimal" | "min" | "max" | "random" |
                     "upper" | "lower" | "trim" | "split" | "join" | "replace" |
                     "char_at" | "substring" |
-                    "input" | "print" | "read_file" | "write_file" |
+                    "input" | "print" | "read_file" | "write_file" | "args" |
                     "assert" |
                     // Math module
                     "log" | "log10" | "exp" | "log2" | "ln" |
@@ -340,7 +396,7 @@ impl Linter {
                     "pad_left" | "pad_right" | "pad_center" |
                     "starts_with" | "ends_with" | "contains_str" |
                     "count" | "find" |
-                    "replace_all" | "replace_first" | "remove" |
+                    "replace_all" | "replace_first" |
                     "repeat" | "reverse_str" |
                     "left" | "right" | "truncate" |
                     "is_empty" | "is_numeric" | "is_alpha" | "is_alphanumeric" |
@@ -376,43 +432,15 @@ impl Linter {
 
                 if !var.used && !var.name.starts_with('_') && !is_stdlib_constant {
                     self.warnings.push(LintWarning {
+                        code: "W0001",
                         message: format!("unused variable `{}`", var.name),
-                        position: None,
+                        position: identifier_position(&self.positioned_tokens, &var.name),
                     });
                 }
             }
         }
 
-        // Convert to diagnostics
-        let mut diagnostics = Vec::new();
-        let mut has_errors = false;
-        
-        for error in &self.errors {
-            has_errors = true;
-            let mut diagnostic = Diagnostic::error(error.message.clone()).with_code("E0001".to_string());
-            if let Some(pos) = &error.position {
-                diagnostic = diagnostic.with_location(pos.line, pos.column);
-                // Add source line context
-                if let Some(source_line) = self.get_source_line(pos.line) {
-                    diagnostic = diagnostic.with_source_line(source_line);
-                }
-            }
-            diagnostics.push(diagnostic);
-        }
-        
-        for warning in &self.warnings {
-            let mut diagnostic = Diagnostic::warning(warning.message.clone()).with_code("W0001".to_string());
-            if let Some(pos) = &warning.position {
-                diagnostic = diagnostic.with_location(pos.line, pos.column);
-                // Add source line context
-                if let Some(source_line) = self.get_source_line(pos.line) {
-                    diagnostic = diagnostic.with_source_line(source_line);
-                }
-            }
-            diagnostics.push(diagnostic);
-        }
-        
-        (diagnostics, has_errors)
+        self.diagnostics()
     }
     
     // REPL-specific linting that maintains state between statements
@@ -429,6 +457,7 @@ impl Linter {
         // Make sure built-in functions are always available
         self.add_builtin_functions();
         
+        self.declarations_seen.clear();  // Tokens are only this input's
         self.in_function = false;
 
         // Single pass: analyze all statements
@@ -438,26 +467,30 @@ impl Linter {
 
         // Don't check for unused variables in REPL mode - they might be used later
         
-        // Convert to diagnostics
+        self.diagnostics()
+    }
+
+    /// The collected errors and warnings as diagnostics, after `# ject: allow(...)`
+    /// comments drop warnings and `denied` promotes them to errors
+    fn diagnostics(&self) -> (Vec<Diagnostic>, bool) {
+        let allowed = Allowed::parse(&self.source);
+        let errors = self.errors.iter()
+            .map(|error| (Diagnostic::error(error.message.clone()), error.code, &error.position));
+        let warnings = self.warnings.iter()
+            .filter(|warning| !allowed.allows(warning.code, warning.position.as_ref().map(|pos| pos.line)))
+            .map(|warning| {
+                let diagnostic = if self.denied.contains(warning.code) || self.denied.contains("WARNINGS") {
+                    Diagnostic::error(warning.message.clone())
+                } else {
+                    Diagnostic::warning(warning.message.clone())
+                };
+                (diagnostic, warning.code, &warning.position)
+            });
+
         let mut diagnostics = Vec::new();
-        let mut has_errors = false;
-        
-        for error in &self.errors {
-            has_errors = true;
-            let mut diagnostic = Diagnostic::error(error.message.clone()).with_code("E0001".to_string());
-            if let Some(pos) = &error.position {
-                diagnostic = diagnostic.with_location(pos.line, pos.column);
-                // Add source line context
-                if let Some(source_line) = self.get_source_line(pos.line) {
-                    diagnostic = diagnostic.with_source_line(source_line);
-                }
-            }
-            diagnostics.push(diagnostic);
-        }
-        
-        for warning in &self.warnings {
-            let mut diagnostic = Diagnostic::warning(warning.message.clone()).with_code("W0001".to_string());
-            if let Some(pos) = &warning.position {
+        for (mut diagnostic, code, position) in errors.chain(warnings) {
+            diagnostic = diagnostic.with_code(code.to_string());
+            if let Some(pos) = position {
                 diagnostic = diagnostic.with_location(pos.line, pos.column);
                 // Add source line context
                 if let Some(source_line) = self.get_source_line(pos.line) {
@@ -466,7 +499,7 @@ impl Linter {
             }
             diagnostics.push(diagnostic);
         }
-        
+        let has_errors = diagnostics.iter().any(|d| d.level == crate::diagnostic::DiagnosticLevel::Error);
         (diagnostics, has_errors)
     }
 
@@ -482,8 +515,9 @@ impl Linter {
                 for var in scope.values() {
                     if !var.used && !var.name.starts_with('_') {
                         self.warnings.push(LintWarning {
+                            code: "W0001",
                             message: format!("unused variable `{}`", var.name),
-                            position: None, // TODO: We could track declaration positions
+                            position: identifier_position(&self.positioned_tokens, &var.name),
                         });
                     }
                 }
@@ -493,6 +527,11 @@ impl Linter {
     }
 
     fn declare_variable(&mut self, name: String) {
+        self.declare_variable_at(name, None);
+    }
+
+    /// Declare a variable, reporting any redeclaration at `position` (by default, the name's first use)
+    fn declare_variable_at(&mut self, name: String, position: Option<crate::lexer::SourcePosition>) {
         if let Some(current_scope) = self.scopes.last_mut() {
             if current_scope.contains_key(&name) {
                 // Don't warn about redeclaration of builtin constants in REPL mode
@@ -501,9 +540,19 @@ impl Linter {
                     return;  // Silently ignore PI/E redeclaration
                 }
                 
-                let position = self.find_identifier_position(&name);
+                let constant = current_scope[&name].constant;
+                let position = position.or_else(|| self.find_identifier_position(&name));
+                if constant {
+                    self.errors.push(LintError {
+                        code: "E0009",
+                        message: format!("cannot redeclare constant `{}`", name),
+                        position,
+                    });
+                    return;
+                }
                 self.warnings.push(LintWarning {
-                    message: format!("warning: variable `{}` is already declared in this scope", name),
+                    code: "W0002",
+                    message: format!("variable `{}` is already declared in this scope", name),
                     position,
                 });
             } else {
@@ -511,11 +560,48 @@ impl Linter {
                     name,
                     used: false,
                     declared_in_scope: self.current_scope_id,
+                    constant: false,
                 });
             }
         }
     }
 
+    /// Declare a read-only binding (`const`, builtin constants, imports)
+    fn declare_constant(&mut self, name: String) {
+        self.declare_variable(name.clone());
+        if let Some(var) = self.scopes.last_mut().and_then(|scope| scope.get_mut(&name)) {
+            var.constant = true;
+        }
+    }
+
+    /// Imported names are read-only, but a later import may replace an earlier
+    /// one's name (a warning, as for any redeclaration)
+    fn declare_import(&mut self, name: String) {
+        if let Some(var) = self.scopes.last_mut().and_then(|scope| scope.get_mut(&name)) {
+            var.constant = false;
+        }
+        self.declare_constant(name);
+    }
+
+    /// Whether the innermost declaration of `name` is a constant
+    fn is_constant(&self, name: &str) -> bool {
+        self.scopes.iter().rev()
+            .find_map(|scope| scope.get(name))
+            .is_some_and(|var| var.constant)
+    }
+
+    /// Report an assignment (`what` is "assign to" or "modify") whose target is a constant
+    fn check_not_constant(&mut self, name: &str, what: &str) {
+        if self.is_constant(name) {
+            let position = self.find_identifier_position(name);
+            self.errors.push(LintError {
+                code: "E0010",
+                message: format!("cannot {} constant `{}`", what, name),
+                position,
+            });
+        }
+    }
+
     fn use_variable(&mut self, name: &str) -> bool {
         // Look for variable in scopes from innermost to outermost
         for scope in self.scopes.iter_mut().rev() {
@@ -539,115 +625,20 @@ impl Linter {
         }
     }
     
-    fn get_module_exports(&self, module_path: &str) -> Result<Vec<String>, ()> {
-        use std::fs;
-        use std::path::Path;
-
-        // Get the directory where the executable is located for resolving stdlib paths
-        let exe_dir = std::env::current_exe()
-            .ok()
-            .and_then(|p| p.parent().map(|p| p.to_path_buf()))
-            .unwrap_or_else(|| std::env::current_dir().unwrap_or_default());
-
-        // Try to find project root (go up from target/release or target/debug)
-        let project_root = exe_dir.parent()
-            .and_then(|p| p.parent())
-            .map(|p| p.to_path_buf())
-            .unwrap_or_else(|| exe_dir.clone());
-
-        // Get current working directory
-        let cwd = std::env::current_dir().unwrap_or_default();
-
-        // Determine the module file path (same logic as interpreter)
-        let module_file_path = if module_path.starts_with("~/") {
-            // Home directory path
-            if let Ok(home) = std::env::var("HOME") {
-                let path_without_tilde = module_path.trim_start_matches("~/");
-                let path_with_ext = if path_without_tilde.ends_with(".ject") {
-                    path_without_tilde.to_string()
-                } else {
-                    format!("{}.ject", path_without_tilde)
-                };
-                let full_path = std::path::PathBuf::from(home).join(&path_with_ext);
-                if full_path.exists() {
-                    full_path.to_string_lossy().to_string()
-                } else {
-                    return Err(());
-                }
-            } else {
-                return Err(());
-            }
-        } else if module_path.starts_with("/") {
-            // Absolute path
-            let path_with_ext = if module_path.ends_with(".ject") {
-                module_path.to_string()
-            } else {
-                format!("{}.ject", module_path)
-            };
-            let full_path = std::path::PathBuf::from(&path_with_ext);
-            if full_path.exists() {
-                full_path.to_string_lossy().to_string()
-            } else {
-                return Err(());
-            }
-        } else if module_path.starts_with("./") || module_path.starts_with("../") {
-            // Relative path from cwd
-            let path_with_ext = if module_path.ends_with(".ject") {
-                module_path.to_string()
-            } else {
-                format!("{}.ject", module_path)
-            };
-            let full_path = cwd.join(&path_with_ext);
-            if full_path.exists() {
-                full_path.to_string_lossy().to_string()
-            } else {
-                return Err(());
-            }
-        } else if module_path.contains("/") {
-            // Path relative to project root
-            let path_with_ext = if module_path.ends_with(".ject") {
-                module_path.to_string()
-            } else {
-                format!("{}.ject", module_path)
-            };
-            let full_path = project_root.join(&path_with_ext);
-            if full_path.exists() {
-                full_path.to_string_lossy().to_string()
-            } else {
-                let cwd_path = cwd.join(&path_with_ext);
-                if cwd_path.exists() {
-                    cwd_path.to_string_lossy().to_string()
-                } else {
-                    return Err(());
-                }
-            }
-        } else {
-            // Simple module name - check stdlib directory
-            let module_name = if module_path.ends_with(".ject") {
-                module_path.trim_end_matches(".ject").to_string()
-            } else {
-                module_path.to_string()
-            };
-            
-            let stdlib_path = project_root.join("stdlib").join(format!("{}.ject", module_name));
-            if stdlib_path.exists() {
-                stdlib_path.to_string_lossy().to_string()
-            } else {
-                let cwd_stdlib = cwd.join("stdlib").join(format!("{}.ject", module_name));
-                if cwd_stdlib.exists() {
-                    cwd_stdlib.to_string_lossy().to_string()
-                } else {
-                    return Err(());
-                }
-            }
-        };
-
-        if !Path::new(&module_file_path).exists() {
+    fn get_module_exports(&self, module_path: &str, importer_dir: Option<&Path>, depth: usize) -> Result<ModuleNames, ()> {
+        // Rust-only modules (base, numpy, csv, dataframe) have no file to read
+        if let Some(module) = crate::stdlib::get_module(module_path) {
+            return Ok(ModuleNames { exports: module.into_keys().collect(), private: HashSet::new() });
+        }
+        // Re-export chains this deep are almost certainly a cycle
+        if depth > 16 {
             return Err(());
         }
 
+        let source = self.resolver.resolve(module_path, importer_dir).map_err(|_| ())?;
+
         // Read and parse the module file
-        let module_content = match fs::read_to_string(&module_file_path) {
+        let module_content = match source.read() {
             Ok(content) => content,
             Err(_) => return Err(()),
         };
@@ -662,21 +653,34 @@ impl Linter {
             Err(_) => return Err(()),
         };
 
-        // Extract export names
-        let mut exports = Vec::new();
+        // Extract export names, and the top-level names that stay private
+        let mut names = ModuleNames::default();
         for statement in &statements {
             match statement {
-                crate::ast::Stmt::Export { name, .. } => {
-                    exports.push(name.clone());
+                Stmt::Export { name, .. } | Stmt::ExportFunction { name, .. } => {
+                    names.exports.push(name.clone());
                 }
-                crate::ast::Stmt::ExportFunction { name, .. } => {
-                    exports.push(name.clone());
+                Stmt::ExportFrom { items: Some(items), .. } => {
+                    names.exports.extend(items.iter().cloned());
+                }
+                Stmt::ExportFrom { module_path, items: None } => {
+                    let nested = self.get_module_exports(module_path, source.dir().as_deref(), depth + 1)?;
+                    names.exports.extend(nested.exports);
+                }
+                Stmt::Let { binding, .. } => {
+                    names.private.extend(binding.names().into_iter().map(String::from));
+                }
+                Stmt::Const { name, .. } | Stmt::Function { name, .. } | Stmt::Struct { name, .. } => {
+                    names.private.insert(name.clone());
                 }
                 _ => {}
             }
         }
+        for name in &names.exports {
+            names.private.remove(name);
+        }
 
-        Ok(exports)
+        Ok(names)
     }
     
     fn find_variable(&self, name: &str) -> bool {
@@ -730,11 +734,18 @@ impl Linter {
 
     fn analyze_statement(&mut self, stmt: &Stmt) {
         match stmt {
-            Stmt::Let { name, value } => {
+            Stmt::Let { binding, value } => {
                 // Analyze the value expression first
                 self.analyze_expr(value);
-                // Then declare the variable (Rust-like: can't use variable before declaration)
-                self.declare_variable(name.clone());
+                // Then declare the variables (Rust-like: can't use variable before declaration)
+                for name in binding.names() {
+                    let position = self.next_declaration_position(crate::lexer::Token::Let, name);
+                    self.declare_variable_at(name.to_string(), position);
+                }
+            }
+            Stmt::Const { name, value } => {
+                self.analyze_expr(value);
+                self.declare_constant(name.clone());
             }
             Stmt::Assign { target, value } => {
                 // Analyze the value expression first
@@ -745,41 +756,60 @@ impl Linter {
                         if !self.use_variable(&name) {
                             let position = self.find_identifier_position(&name);
                             self.errors.push(LintError {
+                                code: "E0011",
                                 message: format!("cannot assign to undeclared variable `{}`", name),
                                 position,
                             });
                         }
+                        self.check_not_constant(name, "assign to");
                     }
                     crate::ast::AssignTarget::Index { object, index } => {
                         // Check if the array variable exists
                         if !self.use_variable(&object) {
                             let position = self.find_identifier_position(&object);
                             self.errors.push(LintError {
+                                code: "E0012",
                                 message: format!("cannot index into undeclared variable `{}`", object),
                                 position,
                             });
                         }
+                        self.check_not_constant(object, "modify");
                         // Analyze the index expression
                         self.analyze_expr(&index);
                     }
+                    crate::ast::AssignTarget::Subscript { object, indices } => {
+                        if !self.use_variable(object) {
+                            let position = self.find_identifier_position(object);
+                            self.errors.push(LintError {
+                                code: "E0012",
+                                message: format!("cannot index into undeclared variable `{}`", object),
+                                position,
+                            });
+                        }
+                        self.check_not_constant(object, "modify");
+                        self.analyze_index_components(indices);
+                    }
                     crate::ast::AssignTarget::Field { object, field: _ } => {
                         // Check if the object variable exists
                         if !self.use_variable(&object) {
                             let position = self.find_identifier_position(&object);
                             self.errors.push(LintError {
+                                code: "E0012",
                                 message: format!("cannot assign field on undeclared variable `{}`", object),
                                 position,
                             });
                         }
+                        self.check_not_constant(object, "modify");
                     }
                 }
             }
             Stmt::Function { name, params, body } => {
                 // Check for function redeclaration
+                let position = self.next_declaration_position(crate::lexer::Token::Fn, name);
                 if self.functions.contains(name) {
-                    let position = self.find_identifier_position(name);
                     self.warnings.push(LintWarning {
-                        message: format!("warning: function `{}` is already defined", name),
+                        code: "W0003",
+                        message: format!("function `{}` is already defined", name),
                         position,
                     });
                 }
@@ -850,11 +880,13 @@ impl Linter {
                 }
                 self.pop_scope();
             }
-            Stmt::For { var, iterable, body } => {
+            Stmt::For { binding, iterable, body } => {
                 self.analyze_expr(iterable);
-                // For loop creates its own scope with the loop variable
+                // For loop creates its own scope with the loop variables
                 self.push_scope();
-                self.declare_variable(var.clone());
+                for name in binding.names() {
+                    self.declare_variable(name.to_string());
+                }
                 for stmt in body {
                     self.analyze_statement(stmt);
                 }
@@ -864,6 +896,7 @@ impl Linter {
                 self.analyze_expr(expr);
                 if !self.in_function {
                     self.errors.push(LintError {
+                        code: "E0013",
                         message: "`return` outside of function".to_string(),
                         position: None, // TODO: Could find 'return' keyword position
                     });
@@ -872,6 +905,7 @@ impl Linter {
             Stmt::Return(None) => {
                 if !self.in_function {
                     self.errors.push(LintError {
+                        code: "E0013",
                         message: "`return` outside of function".to_string(),
                         position: None, // TODO: Could find 'return' keyword position
                     });
@@ -890,23 +924,34 @@ impl Linter {
             Stmt::Import { module_path, items, alias } => {
                 // Handle selective imports
                 if let Some(item_list) = items {
+                    let module = self.get_module_exports(module_path, self.file_dir.as_deref(), 0).ok();
                     for item in item_list {
-                        self.declare_variable(item.clone());
+                        if module.as_ref().is_some_and(|module| module.private.contains(item)) {
+                            let position = self.find_identifier_position(item);
+                            self.errors.push(LintError {
+                                code: "E0014",
+                                message: format!("`{}` is private to module `{}`", item, module_path),
+                                position,
+                            });
+                        }
+                        self.declare_import(item.clone());
                     }
-                } else if alias.is_none() {
+                } else if let Some(namespace) = alias.as_deref().or(crate::resolver::namespace(module_path)) {
+                    // import "m" as m, or import "geo/shapes" binding `shapes`
+                    self.declare_import(namespace.to_string());
+                } else {
                     // Full import - need to load module to know what's exported
                     // Try to load the module and get its exports
-                    if let Ok(exports) = self.get_module_exports(module_path) {
-                        for export_name in exports {
-                            self.declare_variable(export_name);
+                    if let Ok(module) = self.get_module_exports(module_path, self.file_dir.as_deref(), 0) {
+                        for export_name in module.exports {
+                            self.declare_import(export_name);
                         }
                     }
                     // If we can't load the module, we'll let the runtime handle the error
                 }
-
-                if let Some(alias_name) = alias {
-                    self.declare_variable(alias_name.clone());
-                }
+            }
+            Stmt::ExportFrom { .. } => {
+                // Re-exports bind nothing in this file
             }
             Stmt::Export { name, value } => {
                 self.analyze_expr(value);
@@ -914,10 +959,11 @@ impl Linter {
             }
             Stmt::ExportFunction { name, params, body } => {
                 // Check for function redeclaration
+                let position = self.next_declaration_position(crate::lexer::Token::Fn, name);
                 if self.functions.contains(name) {
-                    let position = self.find_identifier_position(name);
                     self.warnings.push(LintWarning {
-                        message: format!("warning: function `{}` is already defined", name),
+                        code: "W0003",
+                        message: format!("function `{}` is already defined", name),
                         position,
                     });
                 }
@@ -1015,6 +1061,7 @@ impl Linter {
                     }
                     
                     self.errors.push(LintError {
+                        code: "E0001",
                         message,
                         position,
                     });
@@ -1031,7 +1078,9 @@ impl Linter {
                 self.analyze_expr(callee);
                 for arg in args {
                     match arg {
-                        crate::ast::Argument::Positional(expr) => self.analyze_expr(expr),
+                        crate::ast::Argument::Positional(expr)
+                        | crate::ast::Argument::Spread(expr)
+                        | crate::ast::Argument::KeywordSpread(expr) => self.analyze_expr(expr),
                         crate::ast::Argument::Keyword { value, .. } => self.analyze_expr(value),
                     }
                 }
@@ -1045,6 +1094,10 @@ impl Linter {
                 self.analyze_expr(object);
                 self.analyze_expr(index);
             }
+            Expr::MultiIndex { object, indices } => {
+                self.analyze_expr(object);
+                self.analyze_index_components(indices);
+            }
             Expr::Member { object, .. } => {
                 self.analyze_expr(object);
             }
@@ -1056,6 +1109,7 @@ impl Linter {
                 if !self.find_variable(struct_name) {
                     let position = self.find_identifier_position(struct_name);
                     self.errors.push(LintError {
+                        code: "E0015",
                         message: format!("use of undeclared struct `{}`", struct_name),
                         position,
                     });
@@ -1065,17 +1119,24 @@ impl Linter {
                     self.analyze_expr(field_value);
                 }
             }
-            Expr::Array(elements) => {
+            Expr::Array(elements) | Expr::Tuple(elements) => {
                 for elem in elements {
                     self.analyze_expr(elem);
                 }
             }
-            Expr::Dictionary(pairs) => {
-                for (_, value) in pairs {
-                    self.analyze_expr(value);
+            Expr::Spread(inner) => self.analyze_expr(inner),
+            Expr::Dictionary(entries) => {
+                for entry in entries {
+                    match entry {
+                        crate::ast::DictEntry::Pair(key, value) => {
+                            self.analyze_expr(key);
+                            self.analyze_expr(value);
+                        }
+                        crate::ast::DictEntry::Spread(expr) => self.analyze_expr(expr),
+                    }
                 }
             }
-            Expr::Range { start, end, step } => {
+            Expr::Range { start, end, step, .. } => {
                 self.analyze_expr(start);
                 self.analyze_expr(end);
                 if let Some(step_expr) = step {
@@ -1112,14 +1173,33 @@ impl Linter {
                     self.pop_scope();
                 }
             }
+            Expr::Increment { target, .. } | Expr::Decrement { target, .. } => {
+                self.analyze_expr(target);
+                if let Expr::Identifier(name) = &**target {
+                    self.check_not_constant(name, "assign to");
+                }
+            }
             // Literals don't need analysis
-            Expr::Integer(_) | Expr::Float(_) | Expr::String(_) | 
+            Expr::Integer(_) | Expr::Float(_) | Expr::Decimal(_) | Expr::String(_) | 
             Expr::InterpolatedString(_) | Expr::Bool(_) | Expr::Nil => {}
             
             _ => {}
         }
     }
 
+    fn analyze_index_components(&mut self, indices: &[crate::ast::IndexComponent]) {
+        for component in indices {
+            match component {
+                crate::ast::IndexComponent::Index(expr) => self.analyze_expr(expr),
+                crate::ast::IndexComponent::Slice { from, to, step } => {
+                    for bound in [from, to, step].into_iter().flatten() {
+                        self.analyze_expr(bound);
+                    }
+                }
+            }
+        }
+    }
+
     fn analyze_pattern(&mut self, pattern: &crate::ast::Pattern) {
         match pattern {
             crate::ast::Pattern::Literal(expr) => {
@@ -1135,16 +1215,23 @@ impl Linter {
         }
     }
     
+    /// Where the next `fn NAME` or `let NAME` is, counting the ones already analyzed,
+    /// so that a redeclaration is reported where it happens rather than at the first one
+    fn next_declaration_position(&mut self, keyword: crate::lexer::Token, name: &str) -> Option<crate::lexer::SourcePosition> {
+        let seen = self.declarations_seen.entry(format!("{:?} {}", keyword, name)).or_insert(0);
+        let nth = *seen;
+        *seen += 1;
+        let identifier = crate::lexer::Token::Identifier(name.to_string());
+        self.positioned_tokens.windows(2)
+            .filter(|pair| pair[0].0 == keyword && pair[1].0 == identifier)
+            .nth(nth)
+            .map(|pair| pair[1].1.clone())
+            .or_else(|| self.find_identifier_position(name))
+    }
+
     // Helper method to find the position of an identifier in the positioned tokens
     fn find_identifier_position(&self, identifier: &str) -> Option<crate::lexer::SourcePosition> {
-        for (token, position) in &self.positioned_tokens {
-            if let crate::lexer::Token::Identifier(name) = token {
-                if name == identifier {
-                    return Some(position.clone());
-                }
-            }
-        }
-        None
+        identifier_position(&self.positioned_tokens, identifier)
     }
     
     fn validate_function_call(&mut self, func_name: &str, args: &[Argument]) {
@@ -1152,23 +1239,36 @@ impl Linter {
             // Simulate the argument resolution logic from the interpreter
             let mut resolved_args = vec![false; signature.parameters.len()]; // track which args are provided
             let mut positional_count = 0;
+            // A trailing ...rest parameter accepts any number of extra positional arguments
+            let fixed = signature.parameters.iter().take_while(|p| !p.variadic).count();
+            if fixed < signature.parameters.len() {
+                resolved_args[fixed] = true;
+            }
+            // Spread arguments are only known at runtime, so counts can't be fully checked
+            let spreads_positional = args.iter().any(|a| matches!(a, Argument::Spread(_)));
+            let spreads_keywords = args.iter().any(|a| matches!(a, Argument::KeywordSpread(_)));
             
             // First pass: handle positional arguments
             for arg in args {
                 match arg {
-                    Argument::Positional(_) => {
-                        if positional_count >= signature.parameters.len() {
+                    // Which parameters a spread fills isn't known until runtime
+                    Argument::Positional(_) if spreads_positional => {}
+                    Argument::Positional(_) if positional_count >= fixed => {
+                        if fixed == signature.parameters.len() {
                             let position = self.find_identifier_position(func_name);
                             self.errors.push(LintError {
+                                code: "E0016",
                                 message: format!("too many arguments for function `{}`", func_name),
                                 position,
                             });
                             return;
                         }
+                    }
+                    Argument::Positional(_) => {
                         resolved_args[positional_count] = true;
                         positional_count += 1;
                     }
-                    Argument::Keyword { .. } => {
+                    Argument::Keyword { .. } | Argument::Spread(_) | Argument::KeywordSpread(_) => {
                         // We'll handle keyword arguments in the second pass
                     }
                 }
@@ -1178,13 +1278,14 @@ impl Linter {
             for arg in args {
                 if let Argument::Keyword { name, .. } = arg {
                     // Find the parameter with this name
-                    let param_index = signature.parameters.iter().position(|p| p.name == *name);
+                    let param_index = signature.parameters[..fixed].iter().position(|p| p.name == *name);
                     
                     match param_index {
                         Some(index) => {
                             if resolved_args[index] {
                                 let position = self.find_identifier_position(func_name);
                                 self.errors.push(LintError {
+                                    code: "E0017",
                                     message: format!("argument `{}` specified multiple times in call to `{}`", name, func_name),
                                     position,
                                 });
@@ -1195,6 +1296,7 @@ impl Linter {
                         None => {
                             let position = self.find_identifier_position(func_name);
                             self.errors.push(LintError {
+                                code: "E0018",
                                 message: format!("unknown parameter `{}` for function `{}`", name, func_name),
                                 position,
                             });
@@ -1205,10 +1307,14 @@ impl Linter {
             }
             
             // Third pass: check for missing required arguments
+            if spreads_positional || spreads_keywords {
+                return;
+            }
             for (i, param) in signature.parameters.iter().enumerate() {
                 if !resolved_args[i] && param.default_value.is_none() {
                     let position = self.find_identifier_position(func_name);
                     self.errors.push(LintError {
+                        code: "E0019",
                         message: format!("missing required argument `{}` for function `{}`", param.name, func_name),
                         position,
                     });
@@ -1219,3 +1325,110 @@ impl Linter {
     }
 
 }
+
+fn identifier_position(tokens: &[(crate::lexer::Token, crate::lexer::SourcePosition)], identifier: &str) -> Option<crate::lexer::SourcePosition> {
+    tokens.iter().find_map(|(token, position)| match token {
+        crate::lexer::Token::Identifier(name) if name == identifier => Some(position.clone()),
+        _ => None,
+    })
+}
+
+/// Warning codes a file silences with comments:
+///
+/// ```text
+/// # ject: allow-file(W0001)        anywhere: the whole file
+/// let x = 1  # ject: allow(W0002)  after code: that line
+/// # ject: allow(W0003, W0002)      on its own: the next line
+/// ```
+#[derive(Debug, Default)]
+struct Allowed {
+    file: HashSet<String>,
+    lines: HashMap<usize, HashSet<String>>,
+}
+
+impl Allowed {
+    fn parse(source: &str) -> Self {
+        let mut allowed = Allowed::default();
+        // Most files have no directives, so only lex again for the comments when one might be there
+        if !source.contains("ject: allow") {
+            return allowed;
+        }
+        let mut lexer = crate::lexer::Lexer::new(source);
+        lexer.tokenize_with_positions();
+        let lines: Vec<&str> = source.lines().collect();
+        for comment in lexer.comments() {
+            // Directives are line comments; `#* ... *#` blocks are just text
+            if comment.text.starts_with("#*") {
+                continue;
+            }
+            let Some(start) = comment.text.find("# ject: allow") else {
+                continue;
+            };
+            let directive = &comment.text[start + "# ject: ".len()..];
+            let Some((kind, rest)) = directive.split_once('(') else {
+                continue;
+            };
+            let Some((list, _)) = rest.split_once(')') else {
+                continue;
+            };
+            let codes = list.split(',').map(|code| code.trim().to_uppercase()).filter(|code| !code.is_empty());
+            let line = comment.position.line;  // 1-based
+            match kind.trim() {
+                "allow-file" => allowed.file.extend(codes),
+                "allow" => {
+                    let before = lines.get(line - 1).map_or("", |text| text);
+                    let own_line = before.chars().take(comment.position.column - 1).all(char::is_whitespace);
+                    let target = if own_line { line + 1 } else { line };
+                    allowed.lines.entry(target).or_default().extend(codes);
+                }
+                _ => {}
+            }
+        }
+        allowed
+    }
+
+    fn allows(&self, code: &str, line: Option<usize>) -> bool {
+        self.file.contains(code)
+            || line.and_then(|line| self.lines.get(&line)).is_some_and(|codes| codes.contains(code))
+    }
+}
+
+/// Parse and lint a script; the statements come back only when there were no errors
+pub fn diagnose(source: &str, linter: Linter, filename: &str) -> (Option<Vec<Stmt>>, Vec<Diagnostic>) {
+    let mut lexer = crate::lexer::Lexer::new(source);
+    let located_tokens = lexer.tokenize_with_positions();
+    let positioned_tokens: Vec<(crate::lexer::Token, crate::lexer::SourcePosition)> = located_tokens.into_iter().map(|lt| (lt.token, lt.position)).collect();
+    
+    // Clone positioned tokens for linter before parser consumes them
+    let positioned_tokens_for_linter = positioned_tokens.clone();
+    let mut parser = crate::parser::Parser::new(positioned_tokens);
+
+    match parser.parse() {
+        Ok(statements) => {
+            // Run linter to detect errors and warnings
+            let mut linter = linter
+                .with_tokens_and_source(positioned_tokens_for_linter, source.to_string());
+            let (diagnostics, has_errors) = linter.lint(&statements);
+            let diagnostics = diagnostics.into_iter()
+                .map(|diagnostic| diagnostic.with_filename(filename.to_string()))
+                .collect();
+            (if has_errors { None } else { Some(statements) }, diagnostics)
+        }
+        Err(error) => (None, vec![parse_diagnostic(&error, source, filename)]),
+    }
+}
+
+pub fn parse_diagnostic(error: &crate::parser::ParseError, source: &str, filename: &str) -> Diagnostic {
+    let mut diagnostic = Diagnostic::error(error.message.clone())
+        .with_code(error.code.to_string())
+        .with_filename(filename.to_string());
+
+    // Use position information if available
+    if let (Some(line), Some(column)) = (error.line, error.column) {
+        diagnostic = diagnostic.with_location(line, column);
+        if let Some(source_line) = source.lines().nth(line.saturating_sub(1)) {
+            diagnostic = diagnostic.with_source_line(source_line.to_string());
+        }
+    }
+    diagnostic
+}
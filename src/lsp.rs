@@ -0,0 +1,594 @@
+//! `ject lsp`: a language server speaking the Language Server Protocol over
+//! stdio. Open documents are linted on every change, and `analysis` answers
+//! go-to-definition, hover, completion, document symbols and rename from
+//! the document's tokens, so they keep working while the file is half
+//! written and doesn't parse.
+
+use crate::analysis::{self, Analysis, ModuleExport, Symbol, SymbolKind};
+use crate::diagnostic::{Diagnostic, DiagnosticLevel};
+use crate::lexer::{Lexer, SourcePosition, Token};
+use crate::linter::{self, Linter};
+use crate::resolver::{self, ModuleResolver, ModuleSource};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::io::{self, BufRead, Write};
+use std::path::{Path, PathBuf};
+
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+const REQUEST_FAILED: i64 = -32803;
+
+type RequestResult = Result<Value, (i64, String)>;
+
+// ========== Transport ==========
+
+/// The next message from the client, or None at the end of input. A body
+/// that isn't JSON comes back as null
+pub fn read_message(input: &mut impl BufRead) -> io::Result<Option<Value>> {
+    let mut length = None;
+    loop {
+        let mut header = String::new();
+        if input.read_line(&mut header)? == 0 {
+            return Ok(None);
+        }
+        let header = header.trim_end();
+        if header.is_empty() {
+            if length.is_some() {
+                break;
+            }
+            continue;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.eq_ignore_ascii_case("Content-Length") {
+                length = value.trim().parse::<usize>().ok();
+            }
+        }
+    }
+    let mut body = vec![0; length.unwrap_or(0)];
+    input.read_exact(&mut body)?;
+    Ok(Some(serde_json::from_slice(&body).unwrap_or(Value::Null)))
+}
+
+pub fn write_message(output: &mut impl Write, message: &Value) -> io::Result<()> {
+    let body = message.to_string();
+    write!(output, "Content-Length: {}\r\n\r\n{}", body.len(), body)?;
+    output.flush()
+}
+
+// ========== Documents ==========
+
+struct Document {
+    uri: String,
+    text: Vec<char>,
+    line_starts: Vec<usize>,  // Character offset of each line
+    path: Option<PathBuf>,
+    analysis: Analysis,
+    signatures: HashMap<String, String>,
+}
+
+impl Document {
+    fn new(uri: &str, text: &str, previous: Option<Document>) -> Self {
+        let chars: Vec<char> = text.chars().collect();
+        let mut line_starts = vec![0];
+        line_starts.extend(chars.iter().enumerate().filter(|(_, &c)| c == '\n').map(|(i, _)| i + 1));
+
+        // Keep the last signatures while the file doesn't parse
+        let tokens = Lexer::new(text).tokenize_with_positions().into_iter().map(|lt| (lt.token, lt.position)).collect();
+        let signatures = match crate::parser::Parser::new(tokens).parse() {
+            Ok(statements) => analysis::signatures(&statements),
+            Err(_) => previous.map(|document| document.signatures).unwrap_or_default(),
+        };
+        Document {
+            uri: uri.to_string(),
+            path: uri_to_path(uri),
+            analysis: Analysis::new(text),
+            text: chars,
+            line_starts,
+            signatures,
+        }
+    }
+
+    fn source(&self) -> String {
+        self.text.iter().collect()
+    }
+
+    fn dir(&self) -> Option<&Path> {
+        self.path.as_deref().and_then(Path::parent)
+    }
+
+    /// The character offset of an LSP position (a line and a UTF-16 column)
+    fn offset(&self, position: &Value) -> Option<usize> {
+        let line = position["line"].as_u64()? as usize;
+        let character = position["character"].as_u64()? as usize;
+        let start = *self.line_starts.get(line)?;
+        let mut units = 0;
+        let mut offset = start;
+        while offset < self.text.len() && self.text[offset] != '\n' && units < character {
+            units += self.text[offset].len_utf16();
+            offset += 1;
+        }
+        Some(offset)
+    }
+
+    fn position(&self, offset: usize) -> Value {
+        let offset = offset.min(self.text.len());
+        let line = self.line_starts.partition_point(|&start| start <= offset) - 1;
+        let character: usize = self.text[self.line_starts[line]..offset].iter().map(|c| c.len_utf16()).sum();
+        json!({ "line": line, "character": character })
+    }
+
+    fn range(&self, start: usize, end: usize) -> Value {
+        json!({ "start": self.position(start), "end": self.position(end) })
+    }
+
+    fn name_range(&self, position: &SourcePosition, name: &str) -> Value {
+        self.range(position.position, position.position + name.chars().count())
+    }
+
+    /// The line's text before an offset
+    fn line_before(&self, offset: usize) -> String {
+        let line = self.line_starts.partition_point(|&start| start <= offset) - 1;
+        self.text[self.line_starts[line]..offset].iter().collect()
+    }
+}
+
+// ========== Server ==========
+
+pub struct Server {
+    resolver: ModuleResolver,
+    denied: Vec<String>,
+    documents: HashMap<String, Document>,
+    shut_down: bool,
+}
+
+impl Server {
+    pub fn new(resolver: ModuleResolver, denied: Vec<String>) -> Self {
+        Server { resolver, denied, documents: HashMap::new(), shut_down: false }
+    }
+
+    /// Serve until the client sends `exit` or closes the stream; true if it
+    /// asked to shut down first, as the protocol expects
+    pub fn run(&mut self, input: &mut impl BufRead, output: &mut impl Write) -> io::Result<bool> {
+        while let Some(message) = read_message(input)? {
+            if message["method"] == "exit" {
+                break;
+            }
+            for reply in self.handle(&message) {
+                write_message(output, &reply)?;
+            }
+        }
+        Ok(self.shut_down)
+    }
+
+    /// The replies and notifications for one client message
+    pub fn handle(&mut self, message: &Value) -> Vec<Value> {
+        let Some(method) = message["method"].as_str() else {
+            return Vec::new();  // A response to a request of ours; we send none
+        };
+        let params = &message["params"];
+        let Some(id) = message.get("id") else {
+            return self.notification(method, params);
+        };
+        let result = if self.shut_down {
+            Err((INVALID_REQUEST, "the server is shutting down".to_string()))
+        } else {
+            self.request(method, params)
+        };
+        let reply = match result {
+            Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+            Err((code, message)) => json!({ "jsonrpc": "2.0", "id": id, "error": { "code": code, "message": message } }),
+        };
+        vec![reply]
+    }
+
+    fn request(&mut self, method: &str, params: &Value) -> RequestResult {
+        match method {
+            "initialize" => Ok(json!({
+                "capabilities": {
+                    "textDocumentSync": { "openClose": true, "change": 1 },
+                    "definitionProvider": true,
+                    "hoverProvider": true,
+                    "completionProvider": { "triggerCharacters": ["."] },
+                    "documentSymbolProvider": true,
+                    "renameProvider": true,
+                },
+                "serverInfo": { "name": "ject", "version": env!("CARGO_PKG_VERSION") },
+            })),
+            "shutdown" => {
+                self.shut_down = true;
+                Ok(Value::Null)
+            }
+            "textDocument/definition" => Ok(self.with_document(params, Server::definition)),
+            "textDocument/hover" => Ok(self.with_document(params, Server::hover)),
+            "textDocument/completion" => Ok(self.with_document(params, Server::completion)),
+            "textDocument/documentSymbol" => Ok(self.with_document(params, |_, document, _| Some(document_symbols(document)))),
+            "textDocument/rename" => self.rename(params),
+            _ => Err((METHOD_NOT_FOUND, format!("unsupported method '{}'", method))),
+        }
+    }
+
+    fn notification(&mut self, method: &str, params: &Value) -> Vec<Value> {
+        let uri = params["textDocument"]["uri"].as_str().unwrap_or_default().to_string();
+        let text = match method {
+            "textDocument/didOpen" => params["textDocument"]["text"].as_str(),
+            // Full sync: the last change is the whole document
+            "textDocument/didChange" => params["contentChanges"].as_array()
+                .and_then(|changes| changes.last())
+                .and_then(|change| change["text"].as_str()),
+            "textDocument/didClose" => {
+                self.documents.remove(&uri);
+                return vec![publish(&uri, Vec::new())];
+            }
+            _ => None,
+        };
+        let Some(text) = text else {
+            return Vec::new();
+        };
+        let previous = self.documents.remove(&uri);
+        let document = Document::new(&uri, text, previous);
+        let diagnostics = self.diagnostics(&document);
+        self.documents.insert(uri.clone(), document);
+        vec![publish(&uri, diagnostics)]
+    }
+
+    fn with_document(&self, params: &Value, answer: impl Fn(&Server, &Document, usize) -> Option<Value>) -> Value {
+        let document = params["textDocument"]["uri"].as_str().and_then(|uri| self.documents.get(uri));
+        let Some(document) = document else {
+            return Value::Null;
+        };
+        let offset = document.offset(&params["position"]).unwrap_or(0);
+        answer(self, document, offset).unwrap_or(Value::Null)
+    }
+
+    fn diagnostics(&self, document: &Document) -> Vec<Value> {
+        let mut linter = Linter::new().with_resolver(self.resolver.clone()).with_denied(&self.denied);
+        if let Some(path) = &document.path {
+            linter = linter.with_file(path);
+        }
+        let filename = document.path.as_ref().map(|p| p.display().to_string()).unwrap_or_default();
+        let (_, diagnostics) = linter::diagnose(&document.source(), linter, &filename);
+        diagnostics.iter().map(|diagnostic| lsp_diagnostic(document, diagnostic)).collect()
+    }
+
+    // ========== Definitions ==========
+
+    fn definition(&self, document: &Document, offset: usize) -> Option<Value> {
+        if let Some(import) = document.analysis.import_at(offset) {
+            let Ok(ModuleSource::File(path)) = self.resolver.resolve(&import.path, document.dir()) else {
+                return None;
+            };
+            let start = json!({ "line": 0, "character": 0 });
+            return Some(json!({ "uri": path_to_uri(&path), "range": { "start": start, "end": start } }));
+        }
+        let occurrence = document.analysis.occurrence_at(offset)?;
+        let export = match occurrence.symbol.map(|s| &document.analysis.symbols[s]) {
+            Some(symbol) if symbol.module.is_some() && symbol.kind != SymbolKind::Module => {
+                self.export(document, symbol.module.as_deref()?, &symbol.name)
+            }
+            Some(symbol) => return document_location(document, &symbol.position, &symbol.name),
+            None => match &occurrence.member_of {
+                Some(object) => self.export(document, &self.namespace_module(document, object, offset)?, &occurrence.name),
+                None => self.whole_import_export(document, &occurrence.name),
+            },
+        };
+        let export = export?;
+        let (Some(ModuleSource::File(path)), Some(position)) = (&export.source, &export.position) else {
+            // Defined in Rust, or somewhere without a file: point at the import
+            let symbol = occurrence.symbol.map(|s| &document.analysis.symbols[s])?;
+            return document_location(document, &symbol.position, &symbol.name);
+        };
+        let source = std::fs::read_to_string(path).ok()?;
+        let module = Document::new(&path_to_uri(path), &source, None);
+        document_location(&module, position, &export.name)
+    }
+
+    fn export(&self, document: &Document, module_path: &str, name: &str) -> Option<ModuleExport> {
+        analysis::module_exports(&self.resolver, module_path, document.dir()).into_iter().find(|e| e.name == name)
+    }
+
+    /// The module a name refers to before a `.`: `import "m" as name`, or the
+    /// namespace of `import "geo/shapes"`
+    fn namespace_module(&self, document: &Document, name: &str, offset: usize) -> Option<String> {
+        let alias = document.analysis.visible_at(offset).into_iter()
+            .find(|symbol| symbol.name == name && symbol.kind == SymbolKind::Module)
+            .and_then(|symbol| symbol.module.clone());
+        alias.or_else(|| {
+            document.analysis.imports.iter()
+                .find(|import| import.whole && resolver::namespace(&import.path) == Some(name))
+                .map(|import| import.path.clone())
+        })
+    }
+
+    /// Exports `import "m"` brings in without a namespace
+    fn whole_imports(&self, document: &Document) -> Vec<ModuleExport> {
+        document.analysis.imports.iter()
+            .filter(|import| import.whole && resolver::namespace(&import.path).is_none())
+            .flat_map(|import| analysis::module_exports(&self.resolver, &import.path, document.dir()))
+            .collect()
+    }
+
+    fn whole_import_export(&self, document: &Document, name: &str) -> Option<ModuleExport> {
+        self.whole_imports(document).into_iter().find(|export| export.name == name)
+    }
+
+    // ========== Hover ==========
+
+    fn hover(&self, document: &Document, offset: usize) -> Option<Value> {
+        if let Some(import) = document.analysis.import_at(offset) {
+            let path = match self.resolver.resolve(&import.path, document.dir()) {
+                Ok(ModuleSource::File(path)) => path.display().to_string(),
+                Ok(ModuleSource::Bundled { .. }) => "bundled standard library".to_string(),
+                Err(_) if crate::stdlib::get_module(&import.path).is_some() => "built-in module".to_string(),
+                Err(message) => message,
+            };
+            let range = document.range(import.position.position, import.end);
+            return Some(hover(&format!("module \"{}\"", import.path), Some(&path), range));
+        }
+
+        let occurrence = document.analysis.occurrence_at(offset)?;
+        let range = document.name_range(&occurrence.position, &occurrence.name);
+        let (signature, summary) = match occurrence.symbol.map(|s| &document.analysis.symbols[s]) {
+            Some(symbol) => self.describe(document, symbol),
+            None => {
+                let export = match &occurrence.member_of {
+                    Some(object) => self.namespace_module(document, object, offset)
+                        .and_then(|module| self.export(document, &module, &occurrence.name)),
+                    None => self.whole_import_export(document, &occurrence.name),
+                };
+                match (export, crate::docs::lookup(&occurrence.name)) {
+                    (Some(export), _) => (export.signature.unwrap_or(export.name), None),
+                    (None, Some(doc)) if occurrence.member_of.is_none() => {
+                        (doc.signature.to_string(), Some(doc.summary.to_string()))
+                    }
+                    _ => return None,
+                }
+            }
+        };
+        Some(hover(&signature, summary.as_deref(), range))
+    }
+
+    fn describe(&self, document: &Document, symbol: &Symbol) -> (String, Option<String>) {
+        let name = &symbol.name;
+        let signature = || document.signatures.get(name).cloned();
+        let from = |module: &Option<String>| format!("from \"{}\"", module.as_deref().unwrap_or_default());
+        match symbol.kind {
+            SymbolKind::Function => (signature().unwrap_or_else(|| format!("fn {}", name)), None),
+            SymbolKind::Struct => (signature().unwrap_or_else(|| format!("struct {}", name)), None),
+            SymbolKind::Variable => (format!("let {}", name), None),
+            SymbolKind::Constant => (format!("const {}", name), None),
+            SymbolKind::Parameter => {
+                let function = symbol.parent.map(|f| &document.analysis.symbols[f]);
+                let of = function.and_then(|f| document.signatures.get(&f.name).cloned());
+                (format!("(parameter) {}", name), of.map(|signature| format!("parameter of `{}`", signature)))
+            }
+            SymbolKind::Module => (format!("import \"{}\" as {}", symbol.module.as_deref().unwrap_or_default(), name), None),
+            SymbolKind::Import | SymbolKind::ReExport => {
+                let export = symbol.module.as_deref().and_then(|module| self.export(document, module, name));
+                let signature = export.and_then(|export| export.signature).unwrap_or_else(|| name.clone());
+                (signature, Some(from(&symbol.module)))
+            }
+        }
+    }
+
+    // ========== Completion ==========
+
+    fn completion(&self, document: &Document, offset: usize) -> Option<Value> {
+        let before = document.line_before(offset);
+        let word_start = before.trim_end_matches(|c: char| c.is_alphanumeric() || c == '_');
+        let mut items = Vec::new();
+
+        // `m.` lists the module's exports
+        if let Some(object) = word_start.strip_suffix('.') {
+            let object: String = object.chars().rev().take_while(|c| c.is_alphanumeric() || *c == '_').collect();
+            let object: String = object.chars().rev().collect();
+            if let Some(module) = self.namespace_module(document, &object, offset) {
+                let exports = analysis::module_exports(&self.resolver, &module, document.dir());
+                items.extend(exports.iter().map(export_item));
+            }
+            return Some(json!(items));
+        }
+
+        for symbol in document.analysis.visible_at(offset) {
+            let detail = match symbol.kind {
+                SymbolKind::Function | SymbolKind::Struct => document.signatures.get(&symbol.name).cloned(),
+                SymbolKind::Module | SymbolKind::Import => symbol.module.as_ref().map(|m| format!("from \"{}\"", m)),
+                _ => None,
+            };
+            items.push(json!({ "label": symbol.name, "kind": completion_kind(symbol.kind), "detail": detail }));
+        }
+        items.extend(self.whole_imports(document).iter().map(export_item));
+        for doc in crate::docs::BUILTINS {
+            let kind = if doc.name.chars().all(|c| c.is_uppercase()) { 21 } else { 3 };
+            items.push(json!({
+                "label": doc.name,
+                "kind": kind,
+                "detail": doc.signature,
+                "documentation": doc.summary,
+            }));
+        }
+        Some(json!(items))
+    }
+
+    // ========== Rename ==========
+
+    fn rename(&self, params: &Value) -> RequestResult {
+        let uri = params["textDocument"]["uri"].as_str().unwrap_or_default();
+        let Some(document) = self.documents.get(uri) else {
+            return Ok(Value::Null);
+        };
+        let new_name = params["newName"].as_str().unwrap_or_default();
+        if !is_identifier(new_name) {
+            return Err((INVALID_PARAMS, format!("'{}' is not a valid name", new_name)));
+        }
+        let offset = document.offset(&params["position"]).unwrap_or(0);
+        let Some(occurrence) = document.analysis.occurrence_at(offset) else {
+            return Err((REQUEST_FAILED, "there is no name here to rename".to_string()));
+        };
+        let Some(index) = occurrence.symbol else {
+            let what = if crate::docs::lookup(&occurrence.name).is_some() { "a builtin" } else { "not declared in this file" };
+            return Err((REQUEST_FAILED, format!("`{}` is {}", occurrence.name, what)));
+        };
+        let symbol = &document.analysis.symbols[index];
+        if matches!(symbol.kind, SymbolKind::Import | SymbolKind::ReExport) {
+            let module = symbol.module.as_deref().unwrap_or_default();
+            return Err((REQUEST_FAILED, format!("`{}` is imported from \"{}\"; rename it there", symbol.name, module)));
+        }
+
+        let edits: Vec<Value> = document.analysis.references(index)
+            .map(|o| json!({ "range": document.name_range(&o.position, &o.name), "newText": new_name }))
+            .collect();
+        Ok(json!({ "changes": { uri: edits } }))
+    }
+}
+
+// ========== Conversions ==========
+
+fn publish(uri: &str, diagnostics: Vec<Value>) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "method": "textDocument/publishDiagnostics",
+        "params": { "uri": uri, "diagnostics": diagnostics },
+    })
+}
+
+fn lsp_diagnostic(document: &Document, diagnostic: &Diagnostic) -> Value {
+    let range = match (diagnostic.line, diagnostic.span()) {
+        (Some(line), Some(span)) if line >= 1 && line <= document.line_starts.len() => {
+            let start = document.line_starts[line - 1];
+            document.range(start + span.column.saturating_sub(1), start + span.end_column.saturating_sub(1))
+        }
+        _ => document.range(0, 0),
+    };
+    let (message, help) = diagnostic.message_and_help();
+    let message = match help {
+        Some(help) => format!("{}\nhelp: {}", message, help),
+        None => message,
+    };
+    let severity = match diagnostic.level {
+        DiagnosticLevel::Error => 1,
+        DiagnosticLevel::Warning => 2,
+        DiagnosticLevel::Note => 3,
+        DiagnosticLevel::Help => 4,
+    };
+    json!({ "range": range, "severity": severity, "code": diagnostic.code, "source": "ject", "message": message })
+}
+
+fn hover(signature: &str, summary: Option<&str>, range: Value) -> Value {
+    let mut value = format!("```ject\n{}\n```", signature);
+    if let Some(summary) = summary {
+        value.push_str("\n\n");
+        value.push_str(summary);
+    }
+    json!({ "contents": { "kind": "markdown", "value": value }, "range": range })
+}
+
+/// The file's top-level declarations, with what's declared inside each function
+fn document_symbols(document: &Document) -> Value {
+    let symbols = &document.analysis.symbols;
+    let listed = |parent: Option<usize>| -> Vec<usize> {
+        (0..symbols.len())
+            .filter(|&i| symbols[i].parent == parent && (parent.is_some() || symbols[i].scope == 0))
+            .filter(|&i| matches!(symbols[i].kind,
+                SymbolKind::Function | SymbolKind::Struct | SymbolKind::Variable | SymbolKind::Constant | SymbolKind::Module))
+            .collect()
+    };
+    fn build(document: &Document, index: usize, listed: &dyn Fn(Option<usize>) -> Vec<usize>) -> Value {
+        let symbol = &document.analysis.symbols[index];
+        let selection = document.name_range(&symbol.position, &symbol.name);
+        let range = match symbol.body.map(|scope| &document.analysis.scopes[scope]) {
+            Some(scope) => document.range(scope.start, scope.end),
+            None => selection.clone(),
+        };
+        let children: Vec<Value> = if symbol.kind == SymbolKind::Function {
+            listed(Some(index)).into_iter().map(|child| build(document, child, listed)).collect()
+        } else {
+            Vec::new()
+        };
+        json!({
+            "name": symbol.name,
+            "detail": document.signatures.get(&symbol.name),
+            "kind": symbol_kind(symbol.kind),
+            "range": range,
+            "selectionRange": selection,
+            "children": children,
+        })
+    }
+    json!(listed(None).into_iter().map(|index| build(document, index, &listed)).collect::<Vec<_>>())
+}
+
+fn export_item(export: &ModuleExport) -> Value {
+    let kind = match &export.signature {
+        Some(signature) if signature.starts_with("struct") => 22,
+        Some(_) => 3,
+        None if export.name.chars().all(|c| c.is_uppercase() || c == '_') => 21,
+        None => 6,
+    };
+    json!({ "label": export.name, "kind": kind, "detail": export.signature })
+}
+
+/// LSP `CompletionItemKind`
+fn completion_kind(kind: SymbolKind) -> u8 {
+    match kind {
+        SymbolKind::Function | SymbolKind::Import => 3,
+        SymbolKind::Struct => 22,
+        SymbolKind::Constant => 21,
+        SymbolKind::Module => 9,
+        SymbolKind::Variable | SymbolKind::Parameter | SymbolKind::ReExport => 6,
+    }
+}
+
+/// LSP `SymbolKind`
+fn symbol_kind(kind: SymbolKind) -> u8 {
+    match kind {
+        SymbolKind::Module => 2,
+        SymbolKind::Function | SymbolKind::Import | SymbolKind::ReExport => 12,
+        SymbolKind::Variable | SymbolKind::Parameter => 13,
+        SymbolKind::Constant => 14,
+        SymbolKind::Struct => 23,
+    }
+}
+
+fn document_location(document: &Document, position: &SourcePosition, name: &str) -> Option<Value> {
+    Some(json!({ "uri": document.uri, "range": document.name_range(position, name) }))
+}
+
+/// A name the lexer reads as one identifier, so not a keyword
+fn is_identifier(name: &str) -> bool {
+    let tokens = Lexer::new(name).tokenize();
+    matches!(tokens.as_slice(), [Token::Identifier(read), Token::Eof] if read == name)
+}
+
+pub fn uri_to_path(uri: &str) -> Option<PathBuf> {
+    let path = uri.strip_prefix("file://")?;
+    let bytes = path.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = std::str::from_utf8(bytes.get(i + 1..i + 3).unwrap_or_default()).unwrap_or_default();
+        match u8::from_str_radix(hex, 16) {
+            Ok(byte) if bytes[i] == b'%' => {
+                decoded.push(byte);
+                i += 3;
+            }
+            _ => {
+                decoded.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+    Some(PathBuf::from(String::from_utf8(decoded).ok()?))
+}
+
+pub fn path_to_uri(path: &Path) -> String {
+    let path = std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+    let mut uri = String::from("file://");
+    for byte in path.to_string_lossy().bytes() {
+        if byte.is_ascii_alphanumeric() || b"/-_.~".contains(&byte) {
+            uri.push(byte as char);
+        } else {
+            uri.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    uri
+}
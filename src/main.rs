@@ -5,51 +5,570 @@ mod value;
 mod interpreter;
 mod stdlib;
 mod numpy;
+mod csv;
+mod dataframe;
+mod range;
+mod resolver;
+mod package;
 mod error;
 mod linter;
 mod diagnostic;
+mod codes;
+mod formatter;
+mod docs;
+mod analysis;
+mod lsp;
+
+// The CLI lives in the binary, so its tests are compiled with it
+#[cfg(test)]
+#[path = "tests/cli_tests.rs"]
+mod cli_tests;
 
 use lexer::Lexer;
 use parser::Parser;
+use formatter::FormatError;
 use interpreter::{Interpreter, get_runtime_suggestion};
-use diagnostic::DiagnosticRenderer;
-use std::{env, fs};
+use diagnostic::{Diagnostic, DiagnosticRenderer};
+use package::Package;
+use resolver::ModuleResolver;
+use clap::{Parser as ClapParser, Subcommand, ValueEnum};
+use std::io::{IsTerminal, Read};
+use std::path::{Path, PathBuf};
+use std::fs;
 use rustyline::error::ReadlineError;
 use rustyline::DefaultEditor;
 
+/// `ject FILE [ARGS]...` runs a script; with no file it starts the REPL,
+/// or runs the program piped to stdin
+#[derive(ClapParser)]
+#[command(
+    name = "ject",
+    version,
+    about = "The Ject programming language",
+    override_usage = "ject [OPTIONS] [FILE] [ARGS]...\n       ject [OPTIONS] <COMMAND>"
+)]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    /// Disable colored output
+    #[arg(long, global = true)]
+    no_color: bool,
+
+    /// Also search DIR for imports (repeatable)
+    #[arg(long = "module-path", value_name = "DIR", global = true)]
+    module_paths: Vec<PathBuf>,
+
+    /// Report warning CODE as an error; `warnings` means every warning (repeatable)
+    #[arg(long, value_name = "CODE", global = true)]
+    deny: Vec<String>,
+}
+
+/// `ject check --format`
+#[derive(Clone, Copy, ValueEnum)]
+enum OutputFormat {
+    /// Colored, human-readable diagnostics
+    Text,
+    /// One JSON document with every diagnostic and a summary
+    Json,
+    /// A SARIF 2.1.0 log, for code scanning tools
+    Sarif,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Run a script, or the current package's entry when no file is given
+    Run {
+        /// Script to run; `-` reads it from stdin
+        file: Option<String>,
+        /// Arguments for the script (after `--`), returned by args()
+        #[arg(last = true)]
+        args: Vec<String>,
+    },
+    /// Lint scripts without running them; exits with 1 if there are errors, 3 if only warnings
+    Check {
+        /// Scripts to check; `-` reads one from stdin
+        #[arg(required = true)]
+        files: Vec<String>,
+        /// How to print diagnostics
+        #[arg(long, value_enum, default_value = "text")]
+        format: OutputFormat,
+    },
+    /// Evaluate code from the command line, printing the value of a final expression
+    Eval {
+        #[arg(short = 'e', long = "expr", value_name = "CODE")]
+        code: String,
+        /// Arguments (after `--`) returned by args()
+        #[arg(last = true)]
+        args: Vec<String>,
+    },
+    /// Run test scripts (test_*.ject and *_test.ject) and report which fail
+    Test {
+        /// Files or directories to search; defaults to tests/ or the current directory
+        paths: Vec<PathBuf>,
+    },
+    /// Format scripts in place; with no files, format stdin to stdout
+    Fmt {
+        /// Scripts or directories of .ject files; `-` reads stdin
+        files: Vec<String>,
+        /// Write nothing; list the files that aren't formatted and exit with 1 if any
+        #[arg(long)]
+        check: bool,
+    },
+    /// Start the interactive REPL
+    Repl,
+    /// Run a language server over stdio, for editors
+    Lsp,
+    /// Explain a diagnostic code, such as E0001 or W0003
+    Explain {
+        code: String,
+    },
+    /// Create a new package
+    New {
+        name: String,
+    },
+    /// Add a dependency to the current package
+    Add {
+        /// A package or vendored directory of .ject files
+        #[arg(long, value_name = "DIR")]
+        path: PathBuf,
+    },
+    /// `ject FILE [ARGS]...`: any other first word is a script to run. Matched after
+    /// the commands, so global options may come before either
+    #[command(external_subcommand)]
+    Script(Vec<String>),
+}
+
+/// Options every command shares
+#[derive(Clone)]
+struct Settings {
+    module_paths: Vec<PathBuf>,
+    denied: Vec<String>,  // Warning codes the linter reports as errors
+}
+
+impl Settings {
+    fn resolver(&self) -> ModuleResolver {
+        ModuleResolver::new(self.module_paths.clone())
+    }
+
+    fn linter(&self) -> linter::Linter {
+        linter::Linter::new().with_resolver(self.resolver()).with_denied(&self.denied)
+    }
+}
+
 fn main() {
-    let args: Vec<String> = env::args().collect();
-    
-    if args.len() > 1 {
+    let cli = Cli::parse();
+    if cli.no_color {
+        colored::control::set_override(false);
+    }
+    let mut settings = Settings { module_paths: cli.module_paths, denied: cli.deny };
+    // A package's [lints] and dependencies apply to every command run inside it
+    let package = match Package::discover(Path::new(".")) {
+        Ok(package) => package,
+        Err(message) => {
+            report_error(&message);
+            std::process::exit(1);
+        }
+    };
+    if let Some(package) = &package {
+        settings.denied.extend(package.manifest.deny.iter().cloned());
+        // `ject add` locks after editing the manifest; the others never import
+        let imports = !matches!(
+            cli.command,
+            Some(Command::Add { .. } | Command::New { .. } | Command::Explain { .. } | Command::Fmt { .. })
+        );
+        if imports {
+            match package.lock() {
+                Ok(locked) => settings.module_paths.extend(package.search_paths(&locked)),
+                Err(message) => {
+                    report_error(&message);
+                    std::process::exit(1);
+                }
+            }
+        }
+    }
+
+    let ok = match cli.command {
+        Some(Command::Run { file: Some(file), args }) => run_file(&file, args, &settings),
+        Some(Command::Run { file: None, args }) => run_package(&package, args, &settings),
+        Some(Command::Check { files, format }) => std::process::exit(check_files(&files, format, &settings)),
+        Some(Command::Eval { code, args }) => eval_code(&code, args, &settings),
+        Some(Command::Test { paths }) => run_tests(&paths, &settings),
+        Some(Command::Fmt { files, check }) => format_files(&files, check),
+        Some(Command::Repl) => {
+            run_repl(&settings);
+            true
+        }
+        Some(Command::Lsp) => run_lsp(&settings),
+        Some(Command::Explain { code }) => explain(&code),
+        Some(Command::New { name }) => new_package(&name),
+        Some(Command::Add { path }) => add_dependency(&path),
         // File execution mode
-        let filename = &args[1];
-        run_file(filename);
+        Some(Command::Script(mut argv)) => {
+            let file = argv.remove(0);
+            run_file(&file, argv, &settings)
+        }
+        // A program piped in: echo 'print 1' | ject
+        None if !std::io::stdin().is_terminal() => run_file("-", Vec::new(), &settings),
+        None => {
+            // Interactive REPL mode
+            run_repl(&settings);
+            true
+        }
+    };
+    if !ok {
+        std::process::exit(1);
+    }
+}
+
+fn report_error(message: &str) {
+    use colored::*;
+    eprintln!("{}: {}", "Error".red().bold(), message);
+}
+
+/// A script's source, from stdin when the file is `-`
+fn read_source(filename: &str) -> Option<String> {
+    let result = if filename == "-" {
+        let mut source = String::new();
+        std::io::stdin().read_to_string(&mut source).map(|_| source)
+    } else {
+        fs::read_to_string(filename)
+    };
+    result.map_err(|error| report_error(&format!("reading file '{}': {}", filename, error))).ok()
+}
+
+/// The name diagnostics use for a script, and its path for `./` imports
+fn script_name(filename: &str) -> (String, Option<&Path>) {
+    if filename == "-" {
+        ("<stdin>".to_string(), None)
+    } else {
+        (filename.to_string(), Some(Path::new(filename)))
+    }
+}
+
+fn run_file(filename: &str, args: Vec<String>, settings: &Settings) -> bool {
+    let Some(source) = read_source(filename) else {
+        return false;
+    };
+    let (name, path) = script_name(filename);
+    let mut interpreter = Interpreter::new().with_resolver(settings.resolver()).with_args(args);
+    let mut linter = settings.linter();
+    if let Some(path) = path {
+        interpreter = interpreter.with_script_path(path);
+        linter = linter.with_file(path);
+    }
+    execute_source(&source, &mut interpreter, linter, &name)
+}
+
+/// `ject run` with no file: run the current package's entry
+fn run_package(package: &Option<Package>, args: Vec<String>, settings: &Settings) -> bool {
+    match package {
+        Some(package) => run_file(&package.entry_path().to_string_lossy(), args, settings),
+        None => {
+            report_error(&format!("Could not find {} in the current directory or any parent directory", package::MANIFEST_FILE));
+            false
+        }
+    }
+}
+
+/// `ject check`: lint every file and report all of them together. Exits with
+/// 0 when clean, 1 when there are errors and 3 when there are only warnings
+fn check_files(files: &[String], format: OutputFormat, settings: &Settings) -> i32 {
+    let mut diagnostics = Vec::new();
+    for filename in files {
+        let (name, path) = script_name(filename);
+        let source = if filename == "-" {
+            let mut source = String::new();
+            std::io::stdin().read_to_string(&mut source).map(|_| source)
+        } else {
+            fs::read_to_string(filename)
+        };
+        let source = match source {
+            Ok(source) => source,
+            Err(error) => {
+                let message = format!("reading file '{}': {}", filename, error);
+                if let OutputFormat::Text = format {
+                    report_error(&message);
+                }
+                diagnostics.push(Diagnostic::error(message).with_filename(name));
+                continue;
+            }
+        };
+        let mut linter = settings.linter();
+        if let Some(path) = path {
+            linter = linter.with_file(path);
+        }
+        let (_, file_diagnostics) = linter::diagnose(&source, linter, &name);
+        if let OutputFormat::Text = format {
+            for diagnostic in &file_diagnostics {
+                DiagnosticRenderer::new().render(diagnostic, Some(&name), Some(&source));
+            }
+        }
+        diagnostics.extend(file_diagnostics);
+    }
+
+    match format {
+        OutputFormat::Text => {
+            let (errors, warnings) = diagnostic::count_levels(&diagnostics);
+            DiagnosticRenderer::render_summary(errors, warnings);
+        }
+        OutputFormat::Json => println!("{:#}", diagnostic::json_report(&diagnostics, files.len())),
+        OutputFormat::Sarif => println!("{:#}", diagnostic::sarif_report(&diagnostics)),
+    }
+
+    match diagnostic::count_levels(&diagnostics) {
+        (0, 0) => 0,
+        (0, _) => 3,
+        _ => 1,
+    }
+}
+
+/// `ject eval -e CODE`
+fn eval_code(code: &str, args: Vec<String>, settings: &Settings) -> bool {
+    let mut interpreter = Interpreter::new().with_resolver(settings.resolver()).with_args(args);
+    let Some(mut statements) = compile(code, settings.linter(), "<eval>") else {
+        return false;
+    };
+
+    // A trailing expression is the result: `ject eval -e '1 + 2'` prints 3
+    let result = match statements.pop() {
+        Some(ast::Stmt::Expression(expr)) => interpreter.interpret(&statements)
+            .and_then(|_| interpreter.evaluate(&expr))
+            .map(|value| if value != value::Value::Nil { println!("{}", value) }),
+        Some(last) => {
+            statements.push(last);
+            interpreter.interpret(&statements)
+        }
+        None => Ok(()),
+    };
+    result.map_err(|error| report_runtime_error(&error.message)).is_ok()
+}
+
+/// `ject test`: run each test script in a fresh interpreter
+fn run_tests(paths: &[PathBuf], settings: &Settings) -> bool {
+    let default = if Path::new("tests").is_dir() { "tests" } else { "." };
+    let roots = if paths.is_empty() { vec![PathBuf::from(default)] } else { paths.to_vec() };
+    let mut files = Vec::new();
+    for root in &roots {
+        collect_test_files(root, &mut files);
+    }
+
+    let mut failed = Vec::new();
+    for file in &files {
+        let filename = file.to_string_lossy();
+        println!("test {} ...", filename);
+        if !run_file(&filename, Vec::new(), settings) {
+            failed.push(filename.into_owned());
+        }
+    }
+
+    use colored::*;
+    let passed = files.len() - failed.len();
+    if failed.is_empty() {
+        println!("\ntest result: {}. {} passed; 0 failed", "ok".green(), passed);
     } else {
-        // Interactive REPL mode
-        run_repl();
+        println!("\nfailures:");
+        for filename in &failed {
+            println!("    {}", filename);
+        }
+        println!("\ntest result: {}. {} passed; {} failed", "FAILED".red(), passed, failed.len());
     }
+    failed.is_empty()
 }
 
-fn run_file(filename: &str) {
-    match fs::read_to_string(filename) {
-        Ok(source) => {
-            let mut interpreter = Interpreter::new();
-            execute_source(&source, &mut interpreter, Some(filename.to_string()));
+/// Test scripts under `path`, in name order, skipping hidden directories and target/
+fn collect_test_files(path: &Path, files: &mut Vec<PathBuf>) {
+    if path.is_file() {
+        files.push(path.to_path_buf());
+        return;
+    }
+    let Ok(entries) = fs::read_dir(path) else {
+        return;
+    };
+    let mut entries: Vec<PathBuf> = entries.filter_map(|entry| entry.ok().map(|e| e.path())).collect();
+    entries.sort();
+    for entry in entries {
+        let name = entry.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default();
+        if entry.is_dir() {
+            if !name.starts_with('.') && name != "target" {
+                collect_test_files(&entry, files);
+            }
+        } else if name.ends_with(".ject") && (name.starts_with("test_") || name.ends_with("_test.ject")) {
+            files.push(entry);
+        }
+    }
+}
+
+/// `ject fmt`: rewrite each file that isn't formatted, or with `check`, only
+/// list them. Files that don't parse are reported and left alone
+fn format_files(files: &[String], check: bool) -> bool {
+    if files.is_empty() || files == ["-"] {
+        return format_stdin(check);
+    }
+    let mut ok = true;
+    let mut paths = Vec::new();
+    for file in files {
+        if !Path::new(file).exists() {
+            report_error(&format!("no such file or directory '{}'", file));
+            ok = false;
+        }
+        collect_source_files(Path::new(file), &mut paths);
+    }
+
+    for path in &paths {
+        let filename = path.to_string_lossy();
+        let source = match fs::read_to_string(path) {
+            Ok(source) => source,
+            Err(error) => {
+                report_error(&format!("reading file '{}': {}", filename, error));
+                ok = false;
+                continue;
+            }
+        };
+        let formatted = match format_or_report(&source, &filename, DiagnosticRenderer::new()) {
+            Some(formatted) if formatted != source => formatted,
+            Some(_) => continue,
+            None => {
+                ok = false;
+                continue;
+            }
+        };
+        if check {
+            println!("{}", filename);
+            ok = false;
+        } else if let Err(error) = fs::write(path, formatted) {
+            report_error(&format!("writing file '{}': {}", filename, error));
+            ok = false;
+        }
+    }
+    ok
+}
+
+/// `ject fmt -`: format stdin to stdout, or with `check`, just report whether it's formatted
+fn format_stdin(check: bool) -> bool {
+    let mut source = String::new();
+    if let Err(error) = std::io::stdin().read_to_string(&mut source) {
+        report_error(&format!("reading stdin: {}", error));
+        return false;
+    }
+    // Stdout is the formatted program, so diagnostics go to stderr
+    let Some(formatted) = format_or_report(&source, "<stdin>", DiagnosticRenderer::new().with_stderr()) else {
+        return false;
+    };
+    if check {
+        return formatted == source;
+    }
+    print!("{}", formatted);
+    true
+}
+
+fn format_or_report(source: &str, filename: &str, renderer: DiagnosticRenderer) -> Option<String> {
+    match formatter::format_source(source) {
+        Ok(formatted) => Some(formatted),
+        Err(FormatError::Parse(error)) => {
+            let diagnostic = linter::parse_diagnostic(&error, source, filename);
+            renderer.render(&diagnostic, Some(filename), Some(source));
+            None
         }
         Err(error) => {
-            eprintln!("Error reading file '{}': {}", filename, error);
-            std::process::exit(1);
+            report_error(&format!("{}: {}", filename, error));
+            None
         }
     }
 }
 
-fn run_repl() {
+/// .ject files under `path`, in name order, skipping hidden directories and target/
+fn collect_source_files(path: &Path, files: &mut Vec<PathBuf>) {
+    if path.is_file() {
+        files.push(path.to_path_buf());
+        return;
+    }
+    let Ok(entries) = fs::read_dir(path) else {
+        return;
+    };
+    let mut entries: Vec<PathBuf> = entries.filter_map(|entry| entry.ok().map(|e| e.path())).collect();
+    entries.sort();
+    for entry in entries {
+        let name = entry.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default();
+        if entry.is_dir() {
+            if !name.starts_with('.') && name != "target" {
+                collect_source_files(&entry, files);
+            }
+        } else if name.ends_with(".ject") {
+            files.push(entry);
+        }
+    }
+}
+
+/// `ject lsp`: exits with 0 when the client shut the server down before exiting
+fn run_lsp(settings: &Settings) -> bool {
+    let mut server = lsp::Server::new(settings.resolver(), settings.denied.clone());
+    let stdin = std::io::stdin();
+    match server.run(&mut stdin.lock(), &mut std::io::stdout().lock()) {
+        Ok(shut_down) => shut_down,
+        Err(error) => {
+            report_error(&format!("language server: {}", error));
+            false
+        }
+    }
+}
+
+/// `ject explain CODE`
+fn explain(code: &str) -> bool {
+    use colored::*;
+    match codes::lookup(code) {
+        Some(info) => {
+            println!("{} ({}): {}\n", info.code.bold(), info.level.as_str(), info.title.bold());
+            print!("{}", info.explanation);
+            true
+        }
+        None => {
+            report_error(&format!("no diagnostic code '{}'; codes look like E0001 or W0001", code));
+            false
+        }
+    }
+}
+
+/// `ject new NAME`
+fn new_package(name: &str) -> bool {
+    match package::create_package(Path::new(name), name) {
+        Ok(package) => {
+            println!("Created package '{}' in {}/", package.manifest.name, name);
+            true
+        }
+        Err(message) => {
+            report_error(&message);
+            false
+        }
+    }
+}
+
+/// `ject add --path DIR`, run inside a package
+fn add_dependency(dir: &Path) -> bool {
+    let result = Package::find(Path::new(".")).and_then(|mut package| {
+        let dependency = package.add_path_dependency(dir)?;
+        package.lock()?;
+        Ok(dependency)
+    });
+    match result {
+        Ok(dependency) => {
+            println!("Added {} ({})", dependency.name, dependency.path.display());
+            true
+        }
+        Err(message) => {
+            report_error(&message);
+            false
+        }
+    }
+}
+
+fn run_repl(settings: &Settings) {
     println!("Ject REPL - v0.1.0");
     println!("Use arrow keys to access history");
     println!("'exit' to, well, exit\n");
     
-    let mut interpreter = Interpreter::new();
-    let mut linter = linter::Linter::new();  // Persistent linter for REPL
+    let mut interpreter = Interpreter::new().with_resolver(settings.resolver());
+    let mut linter = settings.linter();  // Persistent linter for REPL
     let mut rl = DefaultEditor::new().expect("Failed to create readline editor");
 
     // Try to load history from file
@@ -87,70 +606,43 @@ fn run_repl() {
     let _ = rl.save_history(".ject_history");
 }
 
-fn execute_source(source: &str, interpreter: &mut Interpreter, filename: Option<String>) {
-    let mut lexer = Lexer::new(source);
-    let located_tokens = lexer.tokenize_with_positions();
-    let positioned_tokens: Vec<(lexer::Token, lexer::SourcePosition)> = located_tokens.into_iter().map(|lt| (lt.token, lt.position)).collect();
-    
-    // Clone positioned tokens for linter before parser consumes them
-    let positioned_tokens_for_linter = positioned_tokens.clone();
-    let mut parser = Parser::new(positioned_tokens);
+/// Parse and lint a script, rendering its diagnostics; the statements come
+/// back only when there were no errors
+fn compile(source: &str, linter: linter::Linter, filename: &str) -> Option<Vec<ast::Stmt>> {
+    let (statements, diagnostics) = linter::diagnose(source, linter, filename);
 
-    match parser.parse() {
-        Ok(statements) => {
-            // Run linter to detect errors and warnings
-            let mut linter = linter::Linter::new()
-                .with_tokens_and_source(positioned_tokens_for_linter, source.to_string());
-            let (diagnostics, has_errors) = linter.lint(&statements);
-            
-            // Create diagnostic renderer for beautiful output
-            let renderer = DiagnosticRenderer::new();
-            
-            // Display all diagnostics with colorful formatting
-            for diagnostic in &diagnostics {
-                renderer.render(diagnostic, filename.as_deref(), Some(source));
-            }
-            
-            // Only run interpreter if no errors were found
-            if !has_errors {
-                match interpreter.interpret(&statements) {
-                    Ok(_) => {}
-                    Err(error) => {
-                        // Display runtime error with colors
-                        use colored::*;
-                        eprintln!("{}: {}", "Runtime Error".red().bold(), error.message.bold());
-                        
-                        // Display suggestion if available
-                        let suggestion = get_runtime_suggestion(&error.message);
-                        if !suggestion.is_empty() {
-                            eprintln!("{} {}", "Tip:".blue().bold(), suggestion.trim().bold());
-                        }
-                    },
-                }
-            } else {
-                // Exit with error code when running files with linter errors
-                if filename.is_some() {
-                    std::process::exit(1);
-                }
-            }
-        }
+    // Display all diagnostics with colorful formatting
+    let renderer = DiagnosticRenderer::new();
+    for diagnostic in &diagnostics {
+        renderer.render(diagnostic, Some(filename), Some(source));
+    }
+    statements
+}
+
+/// Check and run a script; false if it had errors or failed at runtime
+fn execute_source(source: &str, interpreter: &mut Interpreter, linter: linter::Linter, filename: &str) -> bool {
+    // Only run interpreter if no errors were found
+    let Some(statements) = compile(source, linter, filename) else {
+        return false;
+    };
+    match interpreter.interpret(&statements) {
+        Ok(_) => true,
         Err(error) => {
-            // Create diagnostic renderer for parse errors
-            let renderer = DiagnosticRenderer::new();
-            let mut parse_diagnostic = crate::diagnostic::Diagnostic::error(error.message.clone())
-                .with_code("E0002".to_string());
-            
-            // Use position information if available
-            if let (Some(line), Some(column)) = (error.line, error.column) {
-                parse_diagnostic = parse_diagnostic.with_location(line, column);
-            }
-            
-            renderer.render(&parse_diagnostic, filename.as_deref(), Some(source));
-            
-            if filename.is_some() {
-                std::process::exit(1);
-            }
-        },
+            report_runtime_error(&error.message);
+            false
+        }
+    }
+}
+
+fn report_runtime_error(message: &str) {
+    // Display runtime error with colors
+    use colored::*;
+    eprintln!("{}: {}", "Runtime Error".red().bold(), message.bold());
+    
+    // Display suggestion if available
+    let suggestion = get_runtime_suggestion(message);
+    if !suggestion.is_empty() {
+        eprintln!("{} {}", "Tip:".blue().bold(), suggestion.trim().bold());
     }
 }
 
@@ -182,27 +674,16 @@ fn execute_source_repl(source: &str, interpreter: &mut Interpreter, linter: &mut
 
             // Only run interpreter if no errors were found
             if !has_errors {
-                match interpreter.interpret(&statements) {
-                    Ok(_) => {}
-                    Err(error) => {
-                        // Display runtime error with colors
-                        use colored::*;
-                        eprintln!("{}: {}", "Runtime Error".red().bold(), error.message.bold());
-                        
-                        // Display suggestion if available
-                        let suggestion = get_runtime_suggestion(&error.message);
-                        if !suggestion.is_empty() {
-                            eprintln!("{} {}", "Tip:".blue().bold(), suggestion.trim().bold());
-                        }
-                    },
+                if let Err(error) = interpreter.interpret(&statements) {
+                    report_runtime_error(&error.message);
                 }
             }
         }
         Err(error) => {
             // Create diagnostic renderer for parse errors
             let renderer = DiagnosticRenderer::new();
-            let mut parse_diagnostic = crate::diagnostic::Diagnostic::error(error.message.clone())
-                .with_code("E0002".to_string());
+            let mut parse_diagnostic = Diagnostic::error(error.message.clone())
+                .with_code(error.code.to_string());
 
             // Use position information if available
             if let (Some(line), Some(column)) = (error.line, error.column) {
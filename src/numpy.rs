@@ -2,10 +2,20 @@
 //! Provides high-performance numerical computing capabilities
 //! Built on top of the ndarray crate
 
+use crate::ast::BinaryOp;
 use crate::value::Value;
 use crate::interpreter::RuntimeError;
+use std::cell::RefCell;
 use std::collections::HashMap;
-use ndarray::{Array1, Array2, ArrayD, Dimension};
+use rand::{Rng, SeedableRng};
+use rand::distributions::WeightedIndex;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand_distr::{Binomial, Normal, Poisson, StandardNormal};
+use ndarray::{Array1, Array2, ArrayD, Dimension, IxDyn, Zip};
+
+mod linalg;
+mod npyio;
 
 /// N-dimensional array wrapper
 #[derive(Clone)]
@@ -168,6 +178,10 @@ pub fn create_numpy_module() -> HashMap<String, Value> {
     module.insert("cumprod".to_string(), Value::BuiltinFunction("np_cumprod".to_string()));
     module.insert("any".to_string(), Value::BuiltinFunction("np_any".to_string()));
     module.insert("all".to_string(), Value::BuiltinFunction("np_all".to_string()));
+    module.insert("median".to_string(), Value::BuiltinFunction("np_median".to_string()));
+    module.insert("percentile".to_string(), Value::BuiltinFunction("np_percentile".to_string()));
+    module.insert("nanmean".to_string(), Value::BuiltinFunction("np_nanmean".to_string()));
+    module.insert("nansum".to_string(), Value::BuiltinFunction("np_nansum".to_string()));
 
     // Linear algebra
     module.insert("dot".to_string(), Value::BuiltinFunction("np_dot".to_string()));
@@ -181,6 +195,15 @@ pub fn create_numpy_module() -> HashMap<String, Value> {
     module.insert("tri".to_string(), Value::BuiltinFunction("np_tri".to_string()));
     module.insert("tril".to_string(), Value::BuiltinFunction("np_tril".to_string()));
     module.insert("triu".to_string(), Value::BuiltinFunction("np_triu".to_string()));
+    module.insert("linalg".to_string(), Value::ModuleObject(linalg::create_linalg_module()));
+
+    // File I/O
+    module.insert("loadtxt".to_string(), Value::BuiltinFunction("np_loadtxt".to_string()));
+    module.insert("savetxt".to_string(), Value::BuiltinFunction("np_savetxt".to_string()));
+    module.insert("save".to_string(), Value::BuiltinFunction("np_save".to_string()));
+    module.insert("load".to_string(), Value::BuiltinFunction("np_load".to_string()));
+    module.insert("savez".to_string(), Value::BuiltinFunction("np_savez".to_string()));
+    module.insert("savez_compressed".to_string(), Value::BuiltinFunction("np_savez_compressed".to_string()));
 
     // Sorting and searching
     module.insert("sort".to_string(), Value::BuiltinFunction("np_sort".to_string()));
@@ -210,7 +233,7 @@ pub fn create_numpy_module() -> HashMap<String, Value> {
     module.insert("equal".to_string(), Value::BuiltinFunction("np_equal".to_string()));
     module.insert("not_equal".to_string(), Value::BuiltinFunction("np_not_equal".to_string()));
 
-    // Random module (simplified)
+    // Random module (seeded, module-level generator)
     module.insert("random".to_string(), Value::BuiltinFunction("np_random".to_string()));
     module.insert("rand".to_string(), Value::BuiltinFunction("np_rand".to_string()));
     module.insert("randn".to_string(), Value::BuiltinFunction("np_randn".to_string()));
@@ -219,6 +242,10 @@ pub fn create_numpy_module() -> HashMap<String, Value> {
     module.insert("shuffle".to_string(), Value::BuiltinFunction("np_shuffle".to_string()));
     module.insert("permutation".to_string(), Value::BuiltinFunction("np_permutation".to_string()));
     module.insert("seed".to_string(), Value::BuiltinFunction("np_seed".to_string()));
+    module.insert("normal".to_string(), Value::BuiltinFunction("np_normal".to_string()));
+    module.insert("uniform".to_string(), Value::BuiltinFunction("np_uniform".to_string()));
+    module.insert("binomial".to_string(), Value::BuiltinFunction("np_binomial".to_string()));
+    module.insert("poisson".to_string(), Value::BuiltinFunction("np_poisson".to_string()));
 
     // Constants
     module.insert("PI".to_string(), Value::Float(std::f64::consts::PI));
@@ -229,6 +256,63 @@ pub fn create_numpy_module() -> HashMap<String, Value> {
     module
 }
 
+/// Positional parameter names of the numpy functions that accept keyword arguments,
+/// e.g. `np.sum(m, axis=0, keepdims=true)`
+fn keyword_params(name: &str) -> Option<&'static [&'static str]> {
+    match name {
+        "np_sum" | "np_prod" | "np_mean" | "np_min" | "np_max" | "np_argmin" | "np_argmax" |
+        "np_any" | "np_all" | "np_median" | "np_nanmean" | "np_nansum" => Some(&["a", "axis", "keepdims"]),
+        "np_std" | "np_var" => Some(&["a", "axis", "keepdims", "ddof"]),
+        "np_cumsum" | "np_cumprod" | "np_sort" | "np_argsort" => Some(&["a", "axis"]),
+        "np_percentile" => Some(&["a", "q", "axis", "keepdims"]),
+        "np_linalg_norm" => Some(&["x", "ord"]),
+        "np_loadtxt" => Some(&["fname", "delimiter", "skiprows", "dtype", "comments"]),
+        "np_savetxt" => Some(&["fname", "X", "delimiter", "header", "fmt", "comments"]),
+        _ => None,
+    }
+}
+
+/// Bind keyword arguments to their positions, then dispatch like `call_numpy_function`
+pub fn call_numpy_function_with_keywords(
+    name: &str,
+    args: Vec<Value>,
+    keywords: Vec<(String, Value)>,
+) -> Result<Value, RuntimeError> {
+    if keywords.is_empty() {
+        return call_numpy_function(name, args);
+    }
+
+    let fn_name = name.trim_start_matches("np_");
+    let params = keyword_params(name).ok_or_else(|| RuntimeError {
+        message: format!("{}() does not accept keyword arguments", fn_name),
+    })?;
+    if args.len() > params.len() {
+        return Err(RuntimeError {
+            message: format!("{}() takes at most {} arguments", fn_name, params.len()),
+        });
+    }
+
+    let mut bound: Vec<Option<Value>> = args.into_iter().map(Some).collect();
+    bound.resize(params.len(), None);
+    for (keyword, value) in keywords {
+        let index = params.iter().position(|p| *p == keyword).ok_or_else(|| RuntimeError {
+            message: format!("{}() got an unexpected keyword argument '{}'", fn_name, keyword),
+        })?;
+        if bound[index].is_some() {
+            return Err(RuntimeError {
+                message: format!("{}() got multiple values for argument '{}'", fn_name, keyword),
+            });
+        }
+        bound[index] = Some(value);
+    }
+
+    // Unset trailing parameters are left off so each function applies its own defaults
+    while matches!(bound.last(), Some(None)) {
+        bound.pop();
+    }
+    call_numpy_function(name, bound.into_iter().map(|v| v.unwrap_or(Value::Nil)).collect())
+}
+
 /// Call numpy builtin function
 pub fn call_numpy_function(name: &str, args: Vec<Value>) -> Result<Value, RuntimeError> {
     match name {
@@ -298,6 +382,7 @@ pub fn call_numpy_function(name: &str, args: Vec<Value>) -> Result<Value, Runtim
 
         // Reduction
         "np_sum" => np_sum(args),
+        "np_prod" => np_prod(args),
         "np_mean" => np_mean(args),
         "np_std" => np_std(args),
         "np_var" => np_var(args),
@@ -306,8 +391,13 @@ pub fn call_numpy_function(name: &str, args: Vec<Value>) -> Result<Value, Runtim
         "np_argmin" => np_argmin(args),
         "np_argmax" => np_argmax(args),
         "np_cumsum" => np_cumsum(args),
+        "np_cumprod" => np_cumprod(args),
         "np_any" => np_any(args),
         "np_all" => np_all(args),
+        "np_median" => np_median(args),
+        "np_percentile" => np_percentile(args),
+        "np_nanmean" => np_nanmean(args),
+        "np_nansum" => np_nansum(args),
 
         // Linear algebra
         "np_dot" => np_dot(args),
@@ -317,6 +407,16 @@ pub fn call_numpy_function(name: &str, args: Vec<Value>) -> Result<Value, Runtim
         "np_trace" => np_trace(args),
         "np_diag" => np_diag(args),
 
+        "np_linalg_inv" | "np_linalg_det" | "np_linalg_solve" | "np_linalg_lstsq" | "np_linalg_norm" |
+        "np_linalg_qr" | "np_linalg_cholesky" | "np_linalg_eigh" | "np_linalg_svd" => {
+            linalg::call_linalg_function(name, args)
+        }
+
+        // File I/O
+        "np_loadtxt" | "np_savetxt" | "np_save" | "np_load" | "np_savez" | "np_savez_compressed" => {
+            npyio::call_io_function(name, args)
+        }
+
         // Sorting
         "np_sort" => np_sort(args),
         "np_argsort" => np_argsort(args),
@@ -335,9 +435,18 @@ pub fn call_numpy_function(name: &str, args: Vec<Value>) -> Result<Value, Runtim
         "np_not_equal" => np_not_equal(args),
 
         // Random
+        "np_seed" => np_seed(args),
         "np_random" => np_random(args),
         "np_rand" => np_rand(args),
+        "np_randn" => np_randn(args),
         "np_randint" => np_randint(args),
+        "np_choice" => np_choice(args),
+        "np_shuffle" => np_shuffle(args),
+        "np_permutation" => np_permutation(args),
+        "np_normal" => np_normal(args),
+        "np_uniform" => np_uniform(args),
+        "np_binomial" => np_binomial(args),
+        "np_poisson" => np_poisson(args),
 
         _ => Err(RuntimeError {
             message: format!("Unknown numpy function: {}", name),
@@ -1137,265 +1246,302 @@ fn np_tanh(args: Vec<Value>) -> Result<Value, RuntimeError> {
 // ============================================================================
 
 fn np_sum(args: Vec<Value>) -> Result<Value, RuntimeError> {
-    if args.is_empty() {
-        return Err(RuntimeError {
-            message: "sum() requires an array argument".to_string(),
-        });
-    }
+    let r = reduce_args(&args, "sum", 1)?;
+    Ok(match &r.arr {
+        NdArray::F64(a) => f64_result(reduce(a, r.axis, r.keepdims, |v| v.iter().sum())),
+        other => i64_result(reduce(&to_i64_array(other), r.axis, r.keepdims, |v| v.iter().sum())),
+    })
+}
 
-    match &args[0] {
-        Value::NdArray(arr) => {
-            let data = arr.to_f64_vec()?;
-            let sum: f64 = data.iter().sum();
-            Ok(Value::Float(sum))
-        }
-        Value::Array(arr) => {
-            let sum: f64 = arr.iter().map(|v| value_to_f64(v).unwrap_or(0.0)).sum();
-            Ok(Value::Float(sum))
-        }
-        _ => Err(RuntimeError {
-            message: "sum() requires an array argument".to_string(),
-        }),
-    }
+fn np_prod(args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let r = reduce_args(&args, "prod", 1)?;
+    Ok(match &r.arr {
+        NdArray::F64(a) => f64_result(reduce(a, r.axis, r.keepdims, |v| v.iter().product())),
+        other => i64_result(reduce(&to_i64_array(other), r.axis, r.keepdims, |v| v.iter().product())),
+    })
 }
 
 fn np_mean(args: Vec<Value>) -> Result<Value, RuntimeError> {
-    if args.is_empty() {
-        return Err(RuntimeError {
-            message: "mean() requires an array argument".to_string(),
-        });
-    }
-
-    match &args[0] {
-        Value::NdArray(arr) => {
-            let data = arr.to_f64_vec()?;
-            let sum: f64 = data.iter().sum();
-            Ok(Value::Float(sum / data.len() as f64))
-        }
-        Value::Array(arr) => {
-            let sum: f64 = arr.iter().map(|v| value_to_f64(v).unwrap_or(0.0)).sum();
-            Ok(Value::Float(sum / arr.len() as f64))
-        }
-        _ => Err(RuntimeError {
-            message: "mean() requires an array argument".to_string(),
-        }),
-    }
+    let r = reduce_args(&args, "mean", 1)?;
+    Ok(f64_result(reduce(&to_f64_array(&r.arr), r.axis, r.keepdims, |v| mean_of(&v))))
 }
 
 fn np_std(args: Vec<Value>) -> Result<Value, RuntimeError> {
-    if args.is_empty() {
-        return Err(RuntimeError {
-            message: "std() requires an array argument".to_string(),
-        });
-    }
-
-    let mean_val = np_mean(vec![args[0].clone()])?;
-    let mean = match mean_val {
-        Value::Float(f) => f,
-        _ => return Err(RuntimeError { message: "Internal error".to_string() }),
-    };
-
-    match &args[0] {
-        Value::NdArray(arr) => {
-            let data = arr.to_f64_vec()?;
-            let variance: f64 = data.iter().map(|&x| (x - mean).powi(2)).sum::<f64>() / data.len() as f64;
-            Ok(Value::Float(variance.sqrt()))
-        }
-        Value::Array(arr) => {
-            let data: Vec<f64> = arr.iter().map(|v| value_to_f64(v).unwrap_or(0.0)).collect();
-            let variance: f64 = data.iter().map(|&x| (x - mean).powi(2)).sum::<f64>() / data.len() as f64;
-            Ok(Value::Float(variance.sqrt()))
-        }
-        _ => Err(RuntimeError {
-            message: "std() requires an array argument".to_string(),
-        }),
-    }
+    let r = reduce_args(&args, "std", 1)?;
+    let ddof = ddof_arg(args.get(3))?;
+    Ok(f64_result(reduce(&to_f64_array(&r.arr), r.axis, r.keepdims, |v| variance_of(&v, ddof).sqrt())))
 }
 
 fn np_var(args: Vec<Value>) -> Result<Value, RuntimeError> {
-    let std_val = np_std(args)?;
-    match std_val {
-        Value::Float(f) => Ok(Value::Float(f * f)),
-        _ => Err(RuntimeError { message: "Internal error".to_string() }),
-    }
+    let r = reduce_args(&args, "var", 1)?;
+    let ddof = ddof_arg(args.get(3))?;
+    Ok(f64_result(reduce(&to_f64_array(&r.arr), r.axis, r.keepdims, |v| variance_of(&v, ddof))))
 }
 
 fn np_min(args: Vec<Value>) -> Result<Value, RuntimeError> {
-    if args.is_empty() {
-        return Err(RuntimeError {
-            message: "min() requires an array argument".to_string(),
-        });
-    }
-
-    match &args[0] {
-        Value::NdArray(arr) => {
-            let data = arr.to_f64_vec()?;
-            if data.is_empty() {
-                return Ok(Value::Float(f64::NAN));
-            }
-            Ok(Value::Float(data.iter().cloned().fold(f64::INFINITY, f64::min)))
-        }
-        Value::Array(arr) => {
-            if arr.is_empty() {
-                return Ok(Value::Float(f64::NAN));
-            }
-            let min_val = arr.iter()
-                .filter_map(|v| match v {
-                    Value::Integer(i) => Some(*i as f64),
-                    Value::Float(f) => Some(*f),
-                    _ => None,
-                })
-                .fold(f64::INFINITY, f64::min);
-            Ok(Value::Float(min_val))
-        }
-        _ => Err(RuntimeError {
-            message: "min() requires an array argument".to_string(),
-        }),
-    }
+    let r = reduce_args(&args, "min", 1)?;
+    require_non_empty(&r.arr, "min")?;
+    Ok(match &r.arr {
+        NdArray::F64(a) => f64_result(reduce(a, r.axis, r.keepdims, |v| v.iter().copied().fold(f64::INFINITY, f64::min))),
+        other => i64_result(reduce(&to_i64_array(other), r.axis, r.keepdims, |v| v.iter().copied().min().unwrap_or(0))),
+    })
 }
 
 fn np_max(args: Vec<Value>) -> Result<Value, RuntimeError> {
-    if args.is_empty() {
+    let r = reduce_args(&args, "max", 1)?;
+    require_non_empty(&r.arr, "max")?;
+    Ok(match &r.arr {
+        NdArray::F64(a) => f64_result(reduce(a, r.axis, r.keepdims, |v| v.iter().copied().fold(f64::NEG_INFINITY, f64::max))),
+        other => i64_result(reduce(&to_i64_array(other), r.axis, r.keepdims, |v| v.iter().copied().max().unwrap_or(0))),
+    })
+}
+
+fn np_argmin(args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let r = reduce_args(&args, "argmin", 1)?;
+    require_non_empty(&r.arr, "argmin")?;
+    Ok(i64_result(reduce(&to_f64_array(&r.arr), r.axis, r.keepdims, |v| {
+        v.iter().enumerate()
+            .fold((0, f64::INFINITY), |(min_i, min_v), (i, &x)| if x < min_v { (i, x) } else { (min_i, min_v) })
+            .0 as i64
+    })))
+}
+
+fn np_argmax(args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let r = reduce_args(&args, "argmax", 1)?;
+    require_non_empty(&r.arr, "argmax")?;
+    Ok(i64_result(reduce(&to_f64_array(&r.arr), r.axis, r.keepdims, |v| {
+        v.iter().enumerate()
+            .fold((0, f64::NEG_INFINITY), |(max_i, max_v), (i, &x)| if x > max_v { (i, x) } else { (max_i, max_v) })
+            .0 as i64
+    })))
+}
+
+fn np_cumsum(args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let (arr, axis) = lane_args(&args, "cumsum", None)?;
+    Ok(Value::NdArray(match &arr {
+        NdArray::F64(a) => NdArray::F64(accumulate(a, axis, |acc, x| acc + x)),
+        other => NdArray::I64(accumulate(&to_i64_array(other), axis, |acc, x| acc + x)),
+    }))
+}
+
+fn np_cumprod(args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let (arr, axis) = lane_args(&args, "cumprod", None)?;
+    Ok(Value::NdArray(match &arr {
+        NdArray::F64(a) => NdArray::F64(accumulate(a, axis, |acc, x| acc * x)),
+        other => NdArray::I64(accumulate(&to_i64_array(other), axis, |acc, x| acc * x)),
+    }))
+}
+
+fn np_any(args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let r = reduce_args(&args, "any", 1)?;
+    Ok(bool_result(reduce(&to_f64_array(&r.arr), r.axis, r.keepdims, |v| v.iter().any(|&x| x != 0.0))))
+}
+
+fn np_all(args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let r = reduce_args(&args, "all", 1)?;
+    Ok(bool_result(reduce(&to_f64_array(&r.arr), r.axis, r.keepdims, |v| v.iter().all(|&x| x != 0.0))))
+}
+
+fn np_median(args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let r = reduce_args(&args, "median", 1)?;
+    require_non_empty(&r.arr, "median")?;
+    Ok(f64_result(reduce(&to_f64_array(&r.arr), r.axis, r.keepdims, |v| percentile_of(v, 50.0))))
+}
+
+/// percentile(a, q, axis, keepdims) - `q` in [0, 100], or an array of them to get one result per q
+fn np_percentile(args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let r = reduce_args(&args, "percentile", 2)?;
+    require_non_empty(&r.arr, "percentile")?;
+    let (qs, many) = match args.get(1) {
+        Some(Value::Array(items)) => (items.iter().map(value_to_f64).collect::<Result<Vec<_>, _>>()?, true),
+        Some(Value::NdArray(q)) => (q.to_f64_vec()?, true),
+        Some(q) => (vec![value_to_f64(q)?], false),
+        None => return Err(RuntimeError {
+            message: "percentile() requires an array and a q argument".to_string(),
+        }),
+    };
+    if let Some(q) = qs.iter().find(|q| !(0.0..=100.0).contains(*q)) {
         return Err(RuntimeError {
-            message: "max() requires an array argument".to_string(),
+            message: format!("percentile() q must be between 0 and 100, got {}", q),
         });
     }
 
-    match &args[0] {
-        Value::NdArray(arr) => {
-            let data = arr.to_f64_vec()?;
-            if data.is_empty() {
-                return Ok(Value::Float(f64::NAN));
-            }
-            Ok(Value::Float(data.iter().cloned().fold(f64::NEG_INFINITY, f64::max)))
-        }
-        Value::Array(arr) => {
-            if arr.is_empty() {
-                return Ok(Value::Float(f64::NAN));
-            }
-            let max_val = arr.iter()
-                .filter_map(|v| match v {
-                    Value::Integer(i) => Some(*i as f64),
-                    Value::Float(f) => Some(*f),
-                    _ => None,
-                })
-                .fold(f64::NEG_INFINITY, f64::max);
-            Ok(Value::Float(max_val))
-        }
-        _ => Err(RuntimeError {
-            message: "max() requires an array argument".to_string(),
-        }),
+    let data = to_f64_array(&r.arr);
+    let results: Vec<ArrayD<f64>> = qs.iter()
+        .map(|&q| reduce(&data, r.axis, r.keepdims, |v| percentile_of(v, q)))
+        .collect();
+    if !many {
+        return Ok(f64_result(results.into_iter().next().unwrap()));
     }
+    let views: Vec<_> = results.iter().map(|a| a.view()).collect();
+    Ok(Value::NdArray(NdArray::F64(ndarray::stack(ndarray::Axis(0), &views).unwrap())))
 }
 
-fn np_argmin(args: Vec<Value>) -> Result<Value, RuntimeError> {
-    if args.is_empty() {
-        return Err(RuntimeError {
-            message: "argmin() requires an array argument".to_string(),
-        });
-    }
+fn np_nanmean(args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let r = reduce_args(&args, "nanmean", 1)?;
+    Ok(f64_result(reduce(&to_f64_array(&r.arr), r.axis, r.keepdims, |v| {
+        let present: Vec<f64> = v.into_iter().filter(|x| !x.is_nan()).collect();
+        mean_of(&present)
+    })))
+}
 
-    match &args[0] {
-        Value::NdArray(arr) => {
-            let data = arr.to_f64_vec()?;
-            if data.is_empty() {
-                return Ok(Value::Integer(-1));
-            }
-            let (min_idx, _) = data.iter().enumerate()
-                .fold((0, f64::INFINITY), |(min_i, min_v), (i, &v)| {
-                    if v < min_v { (i, v) } else { (min_i, min_v) }
+fn np_nansum(args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let r = reduce_args(&args, "nansum", 1)?;
+    Ok(match &r.arr {
+        NdArray::F64(a) => f64_result(reduce(a, r.axis, r.keepdims, |v| v.iter().filter(|x| !x.is_nan()).sum())),
+        other => i64_result(reduce(&to_i64_array(other), r.axis, r.keepdims, |v| v.iter().sum())),
+    })
+}
+
+/// Array, axis and keepdims arguments shared by the reductions
+struct ReduceArgs {
+    arr: NdArray,
+    axis: Option<usize>,
+    keepdims: bool,
+}
+
+/// Parse `(a, ..., axis, keepdims)` where the axis argument sits at `axis_pos`
+fn reduce_args(args: &[Value], fn_name: &str, axis_pos: usize) -> Result<ReduceArgs, RuntimeError> {
+    let arr = match args.first() {
+        Some(v) => operand_array(v).map_err(|_| RuntimeError {
+            message: format!("{}() requires an array argument", fn_name),
+        })?,
+        None => return Err(RuntimeError {
+            message: format!("{}() requires an array argument", fn_name),
+        }),
+    };
+    let axis = axis_arg(args.get(axis_pos), arr.ndim(), fn_name)?;
+    let keepdims = args.get(axis_pos + 1).is_some_and(|v| v.is_truthy());
+    Ok(ReduceArgs { arr, axis, keepdims })
+}
+
+/// Parse `(a, axis)` for functions that work lane by lane; nil flattens the array first
+fn lane_args(args: &[Value], fn_name: &str, default_axis: Option<i64>) -> Result<(NdArray, Option<usize>), RuntimeError> {
+    let arr = match args.first() {
+        Some(v) => operand_array(v).map_err(|_| RuntimeError {
+            message: format!("{}() requires an array argument", fn_name),
+        })?,
+        None => return Err(RuntimeError {
+            message: format!("{}() requires an array argument", fn_name),
+        }),
+    };
+    let axis = match (args.get(1), default_axis) {
+        (None, Some(_)) if arr.ndim() == 0 => None,
+        (None, Some(axis)) => axis_arg(Some(&Value::Integer(axis)), arr.ndim(), fn_name)?,
+        (axis, _) => axis_arg(axis, arr.ndim(), fn_name)?,
+    };
+    Ok((arr, axis))
+}
+
+/// Resolve an axis argument, counting negative axes from the end
+fn axis_arg(v: Option<&Value>, ndim: usize, fn_name: &str) -> Result<Option<usize>, RuntimeError> {
+    match v {
+        None | Some(Value::Nil) => Ok(None),
+        Some(Value::Integer(axis)) => {
+            let resolved = if *axis < 0 { *axis + ndim as i64 } else { *axis };
+            if resolved < 0 || resolved >= ndim as i64 {
+                return Err(RuntimeError {
+                    message: format!("{}() axis {} is out of bounds for array of dimension {}", fn_name, axis, ndim),
                 });
-            Ok(Value::Integer(min_idx as i64))
+            }
+            Ok(Some(resolved as usize))
         }
-        _ => Err(RuntimeError {
-            message: "argmin() requires an ndarray argument".to_string(),
+        Some(other) => Err(RuntimeError {
+            message: format!("{}() axis must be an integer, got {}", fn_name, other.type_name()),
         }),
     }
 }
 
-fn np_argmax(args: Vec<Value>) -> Result<Value, RuntimeError> {
-    if args.is_empty() {
+fn ddof_arg(v: Option<&Value>) -> Result<f64, RuntimeError> {
+    match v {
+        None | Some(Value::Nil) => Ok(0.0),
+        Some(v) => value_to_f64(v),
+    }
+}
+
+fn require_non_empty(arr: &NdArray, fn_name: &str) -> Result<(), RuntimeError> {
+    if arr.len() == 0 {
         return Err(RuntimeError {
-            message: "argmax() requires an array argument".to_string(),
+            message: format!("{}() of an empty array has no result", fn_name),
         });
     }
+    Ok(())
+}
 
-    match &args[0] {
-        Value::NdArray(arr) => {
-            let data = arr.to_f64_vec()?;
-            if data.is_empty() {
-                return Ok(Value::Integer(-1));
-            }
-            let (max_idx, _) = data.iter().enumerate()
-                .fold((0, f64::NEG_INFINITY), |(max_i, max_v), (i, &v)| {
-                    if v > max_v { (i, v) } else { (max_i, max_v) }
-                });
-            Ok(Value::Integer(max_idx as i64))
+/// Apply `f` to every lane along `axis`, or to the whole array when `axis` is None
+fn reduce<T: Clone, R, F: Fn(Vec<T>) -> R>(a: &ArrayD<T>, axis: Option<usize>, keepdims: bool, f: F) -> ArrayD<R> {
+    match axis {
+        None => {
+            let result = f(a.iter().cloned().collect());
+            let shape = if keepdims { vec![1; a.ndim()] } else { vec![] };
+            ArrayD::from_shape_vec(shape, vec![result]).unwrap()
+        }
+        Some(axis) => {
+            let reduced = a.map_axis(ndarray::Axis(axis), |lane| f(lane.to_vec()));
+            if keepdims { reduced.insert_axis(ndarray::Axis(axis)) } else { reduced }
         }
-        _ => Err(RuntimeError {
-            message: "argmax() requires an ndarray argument".to_string(),
-        }),
     }
 }
 
-fn np_cumsum(args: Vec<Value>) -> Result<Value, RuntimeError> {
-    if args.is_empty() {
-        return Err(RuntimeError {
-            message: "cumsum() requires an array argument".to_string(),
-        });
+/// The array to walk lane by lane and the axis to walk along; no axis means the flattened array
+fn lanes_source<T: Clone>(a: &ArrayD<T>, axis: Option<usize>) -> (ArrayD<T>, usize) {
+    match axis {
+        Some(axis) => (a.clone(), axis),
+        None => (ArrayD::from_shape_vec(vec![a.len()], a.iter().cloned().collect()).unwrap(), 0),
     }
+}
 
-    match &args[0] {
-        Value::NdArray(arr) => {
-            let data = arr.to_f64_vec()?;
-            let mut cumsum = Vec::with_capacity(data.len());
-            let mut sum = 0.0;
-            for &x in &data {
-                sum += x;
-                cumsum.push(sum);
-            }
-            let ndarray = NdArray::F64(ArrayD::from_shape_vec(vec![cumsum.len()], cumsum).unwrap());
-            Ok(Value::NdArray(ndarray))
+fn accumulate<T: Copy, F: Fn(T, T) -> T>(a: &ArrayD<T>, axis: Option<usize>, f: F) -> ArrayD<T> {
+    let (mut out, axis) = lanes_source(a, axis);
+    for mut lane in out.lanes_mut(ndarray::Axis(axis)) {
+        for i in 1..lane.len() {
+            lane[i] = f(lane[i - 1], lane[i]);
         }
-        _ => Err(RuntimeError {
-            message: "cumsum() requires an ndarray argument".to_string(),
-        }),
     }
+    out
 }
 
-fn np_any(args: Vec<Value>) -> Result<Value, RuntimeError> {
-    if args.is_empty() {
-        return Err(RuntimeError {
-            message: "any() requires an array argument".to_string(),
-        });
+fn mean_of(v: &[f64]) -> f64 {
+    v.iter().sum::<f64>() / v.len() as f64
+}
+
+fn variance_of(v: &[f64], ddof: f64) -> f64 {
+    let mean = mean_of(v);
+    v.iter().map(|&x| (x - mean).powi(2)).sum::<f64>() / (v.len() as f64 - ddof)
+}
+
+/// Linear interpolation between the closest ranks, NumPy's default method
+fn percentile_of(mut v: Vec<f64>, q: f64) -> f64 {
+    if v.is_empty() {
+        return f64::NAN;
     }
+    v.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+    let rank = q / 100.0 * (v.len() - 1) as f64;
+    let (lo, hi) = (rank.floor() as usize, rank.ceil() as usize);
+    v[lo] + (v[hi] - v[lo]) * (rank - lo as f64)
+}
 
-    match &args[0] {
-        Value::NdArray(arr) => {
-            let data = arr.to_f64_vec()?;
-            Ok(Value::Bool(data.iter().any(|&x| x != 0.0)))
-        }
-        _ => Err(RuntimeError {
-            message: "any() requires an ndarray argument".to_string(),
-        }),
+fn f64_result(a: ArrayD<f64>) -> Value {
+    if a.ndim() == 0 {
+        Value::Float(a.iter().next().copied().unwrap_or(f64::NAN))
+    } else {
+        Value::NdArray(NdArray::F64(a))
     }
 }
 
-fn np_all(args: Vec<Value>) -> Result<Value, RuntimeError> {
-    if args.is_empty() {
-        return Err(RuntimeError {
-            message: "all() requires an array argument".to_string(),
-        });
+fn i64_result(a: ArrayD<i64>) -> Value {
+    if a.ndim() == 0 {
+        Value::Integer(a.iter().next().copied().unwrap_or(0))
+    } else {
+        Value::NdArray(NdArray::I64(a))
     }
+}
 
-    match &args[0] {
-        Value::NdArray(arr) => {
-            let data = arr.to_f64_vec()?;
-            Ok(Value::Bool(data.iter().all(|&x| x != 0.0)))
-        }
-        _ => Err(RuntimeError {
-            message: "all() requires an ndarray argument".to_string(),
-        }),
+fn bool_result(a: ArrayD<bool>) -> Value {
+    if a.ndim() == 0 {
+        Value::Bool(a.iter().next().copied().unwrap_or(false))
+    } else {
+        Value::NdArray(NdArray::Bool(a))
     }
 }
 
@@ -1592,46 +1738,59 @@ fn np_diag(args: Vec<Value>) -> Result<Value, RuntimeError> {
 // Sorting Functions
 // ============================================================================
 
+/// sort(a, axis) - sorts along the last axis by default; a nil axis sorts the flattened array
 fn np_sort(args: Vec<Value>) -> Result<Value, RuntimeError> {
-    if args.is_empty() {
-        return Err(RuntimeError {
-            message: "sort() requires an array argument".to_string(),
-        });
-    }
-
-    match &args[0] {
-        Value::NdArray(arr) => {
-            let mut data = arr.to_f64_vec()?;
-            data.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
-            let ndarray = NdArray::F64(ArrayD::from_shape_vec(vec![data.len()], data).unwrap());
-            Ok(Value::NdArray(ndarray))
-        }
-        _ => Err(RuntimeError {
-            message: "sort() requires an ndarray argument".to_string(),
-        }),
-    }
+    let (arr, axis) = lane_args(&args, "sort", Some(-1))?;
+    Ok(Value::NdArray(match &arr {
+        NdArray::F64(a) => NdArray::F64(sort_lanes(a, axis, |x, y| x.partial_cmp(y).unwrap_or(std::cmp::Ordering::Equal))),
+        NdArray::I64(a) => NdArray::I64(sort_lanes(a, axis, |x, y| x.cmp(y))),
+        NdArray::Bool(a) => NdArray::Bool(sort_lanes(a, axis, |x, y| x.cmp(y))),
+    }))
 }
 
 fn np_argsort(args: Vec<Value>) -> Result<Value, RuntimeError> {
-    if args.is_empty() {
-        return Err(RuntimeError {
-            message: "argsort() requires an array argument".to_string(),
-        });
-    }
+    let (arr, axis) = lane_args(&args, "argsort", Some(-1))?;
+    let indices = match &arr {
+        NdArray::F64(a) => argsort_lanes(a, axis, |x, y| x.partial_cmp(y).unwrap_or(std::cmp::Ordering::Equal)),
+        NdArray::I64(a) => argsort_lanes(a, axis, |x, y| x.cmp(y)),
+        NdArray::Bool(a) => argsort_lanes(a, axis, |x, y| x.cmp(y)),
+    };
+    Ok(Value::NdArray(NdArray::I64(indices)))
+}
 
-    match &args[0] {
-        Value::NdArray(arr) => {
-            let data = arr.to_f64_vec()?;
-            let mut indices: Vec<usize> = (0..data.len()).collect();
-            indices.sort_by(|&i, &j| data[i].partial_cmp(&data[j]).unwrap_or(std::cmp::Ordering::Equal));
-            
-            let result: Vec<Value> = indices.iter().map(|&i| Value::Integer(i as i64)).collect();
-            Ok(Value::Array(result))
+fn sort_lanes<T, F>(a: &ArrayD<T>, axis: Option<usize>, cmp: F) -> ArrayD<T>
+where
+    T: Copy,
+    F: Fn(&T, &T) -> std::cmp::Ordering,
+{
+    let (mut out, axis) = lanes_source(a, axis);
+    for mut lane in out.lanes_mut(ndarray::Axis(axis)) {
+        let mut sorted = lane.to_vec();
+        sorted.sort_by(&cmp);
+        for (dst, x) in lane.iter_mut().zip(sorted) {
+            *dst = x;
         }
-        _ => Err(RuntimeError {
-            message: "argsort() requires an ndarray argument".to_string(),
-        }),
     }
+    out
+}
+
+fn argsort_lanes<T, F>(a: &ArrayD<T>, axis: Option<usize>, cmp: F) -> ArrayD<i64>
+where
+    T: Copy,
+    F: Fn(&T, &T) -> std::cmp::Ordering,
+{
+    let (source, axis) = lanes_source(a, axis);
+    let mut out = ArrayD::<i64>::zeros(source.raw_dim());
+    Zip::from(out.lanes_mut(ndarray::Axis(axis)))
+        .and(source.lanes(ndarray::Axis(axis)))
+        .for_each(|mut out_lane, lane| {
+            let mut order: Vec<usize> = (0..lane.len()).collect();
+            order.sort_by(|&i, &j| cmp(&lane[i], &lane[j]));
+            for (dst, i) in out_lane.iter_mut().zip(order) {
+                *dst = i as i64;
+            }
+        });
+    out
 }
 
 fn np_where(args: Vec<Value>) -> Result<Value, RuntimeError> {
@@ -1763,28 +1922,52 @@ fn np_not_equal(args: Vec<Value>) -> Result<Value, RuntimeError> {
 // Random Functions
 // ============================================================================
 
-fn np_random(_args: Vec<Value>) -> Result<Value, RuntimeError> {
-    Ok(Value::Float(rand::random::<f64>()))
+thread_local! {
+    /// Module-level generator shared by every np random function; `seed()` replaces it
+    static RNG: RefCell<StdRng> = RefCell::new(StdRng::from_entropy());
 }
 
-fn np_rand(args: Vec<Value>) -> Result<Value, RuntimeError> {
-    let shape = if args.is_empty() {
-        vec![1]
-    } else {
-        match &args[0] {
-            Value::Array(s) => s.iter().filter_map(|v| match v {
-                Value::Integer(i) => Some(*i as usize),
-                _ => None,
-            }).collect(),
-            Value::Integer(n) => vec![*n as usize],
-            _ => vec![1],
+fn with_rng<R>(f: impl FnOnce(&mut StdRng) -> R) -> R {
+    RNG.with(|rng| f(&mut rng.borrow_mut()))
+}
+
+fn np_seed(args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let seed = match args.first() {
+        Some(Value::Integer(n)) if *n >= 0 => *n as u64,
+        Some(Value::Nil) | None => {
+            RNG.with(|rng| *rng.borrow_mut() = StdRng::from_entropy());
+            return Ok(Value::Nil);
         }
+        _ => return Err(RuntimeError {
+            message: "seed() requires a non-negative integer".to_string(),
+        }),
     };
+    RNG.with(|rng| *rng.borrow_mut() = StdRng::seed_from_u64(seed));
+    Ok(Value::Nil)
+}
 
-    let size = shape.iter().product();
-    let data: Vec<f64> = (0..size).map(|_| rand::random::<f64>()).collect();
-    let ndarray = NdArray::F64(ArrayD::from_shape_vec(shape, data).unwrap());
-    Ok(Value::NdArray(ndarray))
+fn np_random(args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let size = size_arg(args.first(), "random")?;
+    Ok(sample_f64(size, |rng| rng.gen::<f64>()))
+}
+
+fn np_rand(args: Vec<Value>) -> Result<Value, RuntimeError> {
+    // rand(2, 3) and rand([2, 3]) are both accepted
+    let size = match args.as_slice() {
+        [] => None,
+        [single] => size_arg(Some(single), "rand")?,
+        dims => Some(dims.iter().map(|d| dim_arg(d, "rand")).collect::<Result<_, _>>()?),
+    };
+    Ok(sample_f64(size, |rng| rng.gen::<f64>()))
+}
+
+fn np_randn(args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let size = match args.as_slice() {
+        [] => None,
+        [single] => size_arg(Some(single), "randn")?,
+        dims => Some(dims.iter().map(|d| dim_arg(d, "randn")).collect::<Result<_, _>>()?),
+    };
+    Ok(sample_f64(size, |rng| rng.sample(StandardNormal)))
 }
 
 fn np_randint(args: Vec<Value>) -> Result<Value, RuntimeError> {
@@ -1796,25 +1979,612 @@ fn np_randint(args: Vec<Value>) -> Result<Value, RuntimeError> {
 
     let low = value_to_i64(&args[0])?;
     let high = value_to_i64(&args[1])?;
-    let shape = if args.len() >= 3 {
-        match &args[2] {
-            Value::Array(s) => s.iter().filter_map(|v| match v {
-                Value::Integer(i) => Some(*i as usize),
-                _ => None,
-            }).collect(),
-            Value::Integer(n) => vec![*n as usize],
-            _ => vec![1],
+    if low >= high {
+        return Err(RuntimeError {
+            message: format!("randint() requires low < high, got {} and {}", low, high),
+        });
+    }
+    // Keep the old default of a one-element array when no size is given
+    let size = size_arg(args.get(2), "randint")?.or(Some(vec![1]));
+    Ok(sample_i64(size, |rng| rng.gen_range(low..high)))
+}
+
+fn np_normal(args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let loc = args.first().map(value_to_f64).transpose()?.unwrap_or(0.0);
+    let scale = args.get(1).map(value_to_f64).transpose()?.unwrap_or(1.0);
+    let dist = Normal::new(loc, scale).map_err(|_| RuntimeError {
+        message: format!("normal() requires scale >= 0, got {}", scale),
+    })?;
+    let size = size_arg(args.get(2), "normal")?;
+    Ok(sample_f64(size, |rng| rng.sample(dist)))
+}
+
+fn np_uniform(args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let low = args.first().map(value_to_f64).transpose()?.unwrap_or(0.0);
+    let high = args.get(1).map(value_to_f64).transpose()?.unwrap_or(1.0);
+    let size = size_arg(args.get(2), "uniform")?;
+    Ok(sample_f64(size, |rng| low + (high - low) * rng.gen::<f64>()))
+}
+
+fn np_binomial(args: Vec<Value>) -> Result<Value, RuntimeError> {
+    if args.len() < 2 {
+        return Err(RuntimeError {
+            message: "binomial() requires n and p arguments".to_string(),
+        });
+    }
+    let n = value_to_i64(&args[0])?;
+    let p = value_to_f64(&args[1])?;
+    if n < 0 {
+        return Err(RuntimeError {
+            message: format!("binomial() requires n >= 0, got {}", n),
+        });
+    }
+    let dist = Binomial::new(n as u64, p).map_err(|_| RuntimeError {
+        message: format!("binomial() requires 0 <= p <= 1, got {}", p),
+    })?;
+    let size = size_arg(args.get(2), "binomial")?;
+    Ok(sample_i64(size, |rng| rng.sample(dist) as i64))
+}
+
+fn np_poisson(args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let lam = args.first().map(value_to_f64).transpose()?.unwrap_or(1.0);
+    let dist = Poisson::new(lam).map_err(|_| RuntimeError {
+        message: format!("poisson() requires lam > 0, got {}", lam),
+    })?;
+    let size = size_arg(args.get(1), "poisson")?;
+    Ok(sample_i64(size, |rng| rng.sample::<f64, _>(dist) as i64))
+}
+
+/// choice(a, size, replace, p) - `a` is a population array or an int n meaning arange(n)
+fn np_choice(args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let population = match args.first() {
+        Some(Value::Integer(n)) if *n > 0 => (0..*n).map(Value::Integer).collect(),
+        Some(a) => population_items(a, "choice")?,
+        None => return Err(RuntimeError {
+            message: "choice() requires a population argument".to_string(),
+        }),
+    };
+    if population.is_empty() {
+        return Err(RuntimeError {
+            message: "choice() population must not be empty".to_string(),
+        });
+    }
+
+    let size = size_arg(args.get(1), "choice")?;
+    let replace = args.get(2).is_none_or(|v| v.is_truthy());
+    let weights = match args.get(3) {
+        None | Some(Value::Nil) => None,
+        Some(p) => {
+            let weights: Vec<f64> = match p {
+                Value::NdArray(arr) => arr.to_f64_vec()?,
+                Value::Array(items) => items.iter().map(value_to_f64).collect::<Result<_, _>>()?,
+                _ => return Err(RuntimeError {
+                    message: "choice() probabilities must be an array".to_string(),
+                }),
+            };
+            if weights.len() != population.len() {
+                return Err(RuntimeError {
+                    message: "choice() probabilities must be the same length as the population".to_string(),
+                });
+            }
+            Some(weights)
+        }
+    };
+
+    let count = size.as_ref().map_or(1, |shape| shape.iter().product());
+    if !replace && count > population.len() {
+        return Err(RuntimeError {
+            message: "choice() cannot take a larger sample than the population when replace is false".to_string(),
+        });
+    }
+
+    let picked: Vec<usize> = with_rng(|rng| -> Result<Vec<usize>, RuntimeError> {
+        match (&weights, replace) {
+            (None, true) => Ok((0..count).map(|_| rng.gen_range(0..population.len())).collect()),
+            (None, false) => Ok(rand::seq::index::sample(rng, population.len(), count).into_vec()),
+            (Some(weights), true) => {
+                let dist = WeightedIndex::new(weights).map_err(|e| RuntimeError {
+                    message: format!("choice() invalid probabilities: {}", e),
+                })?;
+                Ok((0..count).map(|_| rng.sample(&dist)).collect())
+            }
+            (Some(weights), false) => {
+                // Draw one at a time, removing each pick from the remaining weights
+                let mut remaining = weights.clone();
+                let mut picked = Vec::with_capacity(count);
+                for _ in 0..count {
+                    let dist = WeightedIndex::new(&remaining).map_err(|e| RuntimeError {
+                        message: format!("choice() invalid probabilities: {}", e),
+                    })?;
+                    let i = rng.sample(&dist);
+                    remaining[i] = 0.0;
+                    picked.push(i);
+                }
+                Ok(picked)
+            }
+        }
+    })?;
+
+    let items: Vec<Value> = picked.into_iter().map(|i| population[i].clone()).collect();
+    match size {
+        None => Ok(items.into_iter().next().unwrap_or(Value::Nil)),
+        Some(shape) => values_to_ndarray(items, shape),
+    }
+}
+
+/// Returns a shuffled copy; Ject values are passed by value, so the argument is left untouched
+fn np_shuffle(args: Vec<Value>) -> Result<Value, RuntimeError> {
+    match args.first() {
+        Some(Value::Array(items)) => {
+            let mut items = items.clone();
+            with_rng(|rng| items.shuffle(rng));
+            Ok(Value::Array(items))
+        }
+        Some(Value::NdArray(arr)) => Ok(Value::NdArray(shuffle_rows(arr))),
+        _ => Err(RuntimeError {
+            message: "shuffle() requires an array argument".to_string(),
+        }),
+    }
+}
+
+fn np_permutation(args: Vec<Value>) -> Result<Value, RuntimeError> {
+    match args.first() {
+        Some(Value::Integer(n)) if *n >= 0 => {
+            let mut data: Vec<i64> = (0..*n).collect();
+            with_rng(|rng| data.shuffle(rng));
+            Ok(Value::NdArray(NdArray::I64(ArrayD::from_shape_vec(vec![data.len()], data).unwrap())))
+        }
+        Some(_) => np_shuffle(args),
+        None => Err(RuntimeError {
+            message: "permutation() requires an integer or array argument".to_string(),
+        }),
+    }
+}
+
+/// Shuffle an ndarray along its first axis, like NumPy's shuffle
+fn shuffle_rows(arr: &NdArray) -> NdArray {
+    let mut order: Vec<usize> = (0..arr.shape().first().copied().unwrap_or(0)).collect();
+    with_rng(|rng| order.shuffle(rng));
+    match arr {
+        NdArray::F64(a) if a.ndim() > 0 => NdArray::F64(a.select(ndarray::Axis(0), &order)),
+        NdArray::I64(a) if a.ndim() > 0 => NdArray::I64(a.select(ndarray::Axis(0), &order)),
+        NdArray::Bool(a) if a.ndim() > 0 => NdArray::Bool(a.select(ndarray::Axis(0), &order)),
+        other => other.clone(),
+    }
+}
+
+fn population_items(v: &Value, fn_name: &str) -> Result<Vec<Value>, RuntimeError> {
+    match v {
+        Value::Array(items) => Ok(items.clone()),
+        Value::NdArray(NdArray::F64(a)) => Ok(a.iter().map(|&x| Value::Float(x)).collect()),
+        Value::NdArray(NdArray::I64(a)) => Ok(a.iter().map(|&x| Value::Integer(x)).collect()),
+        Value::NdArray(NdArray::Bool(a)) => Ok(a.iter().map(|&x| Value::Bool(x)).collect()),
+        _ => Err(RuntimeError {
+            message: format!("{}() requires an array or a positive integer", fn_name),
+        }),
+    }
+}
+
+/// Pack sampled population items into an ndarray of the requested shape
+fn values_to_ndarray(items: Vec<Value>, shape: Vec<usize>) -> Result<Value, RuntimeError> {
+    if items.iter().all(|v| matches!(v, Value::Integer(_))) {
+        let data = items.iter().map(value_to_i64).collect::<Result<Vec<_>, _>>()?;
+        Ok(Value::NdArray(NdArray::I64(ArrayD::from_shape_vec(shape, data).unwrap())))
+    } else {
+        let data = items.iter().map(value_to_f64).collect::<Result<Vec<_>, _>>()?;
+        Ok(Value::NdArray(NdArray::F64(ArrayD::from_shape_vec(shape, data).unwrap())))
+    }
+}
+
+/// The optional `size` argument: nil/absent for a scalar, an int or a shape array otherwise
+fn size_arg(v: Option<&Value>, fn_name: &str) -> Result<Option<Vec<usize>>, RuntimeError> {
+    match v {
+        None | Some(Value::Nil) => Ok(None),
+        Some(Value::Array(dims)) => Ok(Some(dims.iter().map(|d| dim_arg(d, fn_name)).collect::<Result<_, _>>()?)),
+        Some(d) => Ok(Some(vec![dim_arg(d, fn_name)?])),
+    }
+}
+
+fn dim_arg(v: &Value, fn_name: &str) -> Result<usize, RuntimeError> {
+    match v {
+        Value::Integer(n) if *n >= 0 => Ok(*n as usize),
+        _ => Err(RuntimeError {
+            message: format!("{}() size must be a non-negative integer or an array of them", fn_name),
+        }),
+    }
+}
+
+fn sample_f64<F: FnMut(&mut StdRng) -> f64>(size: Option<Vec<usize>>, mut draw: F) -> Value {
+    with_rng(|rng| match size {
+        None => Value::Float(draw(rng)),
+        Some(shape) => {
+            let data: Vec<f64> = (0..shape.iter().product()).map(|_| draw(rng)).collect();
+            Value::NdArray(NdArray::F64(ArrayD::from_shape_vec(shape, data).unwrap()))
+        }
+    })
+}
+
+fn sample_i64<F: FnMut(&mut StdRng) -> i64>(size: Option<Vec<usize>>, mut draw: F) -> Value {
+    with_rng(|rng| match size {
+        None => Value::Integer(draw(rng)),
+        Some(shape) => {
+            let data: Vec<i64> = (0..shape.iter().product()).map(|_| draw(rng)).collect();
+            Value::NdArray(NdArray::I64(ArrayD::from_shape_vec(shape, data).unwrap()))
+        }
+    })
+}
+
+// ============================================================================
+// Operators and Indexing (used by the interpreter for `a + b`, `a[1, :]`, ...)
+// ============================================================================
+
+/// One component of an ndarray subscript, with all expressions already evaluated
+#[derive(Debug, Clone)]
+pub enum AxisIndex {
+    At(i64),
+    Slice { start: Option<i64>, stop: Option<i64>, step: i64 },
+}
+
+/// The elements picked out by a subscript: their positions in the source array,
+/// in row-major order, and the shape of the resulting array
+struct Selection {
+    points: Vec<Vec<usize>>,
+    shape: Vec<usize>,
+}
+
+/// Element-wise arithmetic and comparison with NumPy broadcasting rules.
+/// Returns None for operators that don't apply element-wise (`and`, `or`, `in`).
+pub fn binary_op(left: &Value, op: &BinaryOp, right: &Value) -> Option<Result<Value, RuntimeError>> {
+    match op {
+        BinaryOp::Add | BinaryOp::Subtract | BinaryOp::Multiply | BinaryOp::Divide | BinaryOp::Modulo
+        | BinaryOp::Equal | BinaryOp::NotEqual | BinaryOp::Less | BinaryOp::Greater
+        | BinaryOp::LessEqual | BinaryOp::GreaterEqual => Some(broadcast_binary_op(left, op, right)),
+        _ => None,
+    }
+}
+
+fn broadcast_binary_op(left: &Value, op: &BinaryOp, right: &Value) -> Result<Value, RuntimeError> {
+    let lhs = operand_array(left)?;
+    let rhs = operand_array(right)?;
+    let shape = broadcast_shapes(&lhs.shape(), &rhs.shape())?;
+
+    let result = match op {
+        BinaryOp::Equal | BinaryOp::NotEqual | BinaryOp::Less | BinaryOp::Greater
+        | BinaryOp::LessEqual | BinaryOp::GreaterEqual => {
+            let (a, b) = (to_f64_array(&lhs), to_f64_array(&rhs));
+            NdArray::Bool(zip_broadcast(&a, &b, &shape, |x, y| match op {
+                BinaryOp::Equal => x == y,
+                BinaryOp::NotEqual => x != y,
+                BinaryOp::Less => x < y,
+                BinaryOp::Greater => x > y,
+                BinaryOp::LessEqual => x <= y,
+                _ => x >= y,
+            }))
+        }
+        // Integer arrays stay integer, except for true division
+        _ if is_integral(&lhs) && is_integral(&rhs) && !matches!(op, BinaryOp::Divide) => {
+            let (a, b) = (to_i64_array(&lhs), to_i64_array(&rhs));
+            if matches!(op, BinaryOp::Modulo) && b.iter().any(|&y| y == 0) {
+                return Err(RuntimeError { message: "Modulo by zero".to_string() });
+            }
+            NdArray::I64(zip_broadcast(&a, &b, &shape, |x, y| match op {
+                BinaryOp::Add => x.wrapping_add(y),
+                BinaryOp::Subtract => x.wrapping_sub(y),
+                BinaryOp::Multiply => x.wrapping_mul(y),
+                // Floor modulo, so the result takes the sign of the divisor like NumPy.
+                // wrapping_rem gives 0 for i64::MIN % -1, and r + y can't overflow
+                // when their signs differ
+                _ => match x.wrapping_rem(y) {
+                    r if r != 0 && (r < 0) != (y < 0) => r + y,
+                    r => r,
+                },
+            }))
+        }
+        _ => {
+            let (a, b) = (to_f64_array(&lhs), to_f64_array(&rhs));
+            NdArray::F64(zip_broadcast(&a, &b, &shape, |x, y| match op {
+                BinaryOp::Add => x + y,
+                BinaryOp::Subtract => x - y,
+                BinaryOp::Multiply => x * y,
+                BinaryOp::Divide => x / y,
+                _ => x - y * (x / y).floor(),
+            }))
+        }
+    };
+    Ok(Value::NdArray(result))
+}
+
+/// Unary minus on an ndarray
+pub fn negate(arr: &NdArray) -> Result<Value, RuntimeError> {
+    match arr {
+        NdArray::F64(a) => Ok(Value::NdArray(NdArray::F64(a.mapv(|x| -x)))),
+        NdArray::I64(a) => Ok(Value::NdArray(NdArray::I64(a.mapv(|x| x.wrapping_neg())))),
+        NdArray::Bool(_) => Err(RuntimeError {
+            message: "Cannot negate a boolean ndarray (use logical_not instead)".to_string(),
+        }),
+    }
+}
+
+/// `a[i]`, `a[1, :]`, `a[::2, 0]` - missing trailing axes are taken whole.
+/// Indexing every axis with an integer gives back a scalar.
+pub fn index(arr: &NdArray, indices: &[AxisIndex]) -> Result<Value, RuntimeError> {
+    let selection = select_axes(&arr.shape(), indices)?;
+    Ok(take(arr, &selection))
+}
+
+/// `a[mask]` with a boolean ndarray, or `a[[0, 2]]` with a list of row indices
+pub fn index_with_value(arr: &NdArray, index: &Value) -> Result<Value, RuntimeError> {
+    let selection = select_with_value(&arr.shape(), index)?;
+    Ok(take(arr, &selection))
+}
+
+/// `a[1, :] = value` - the value is broadcast to the shape of the selection
+pub fn assign(arr: &mut NdArray, indices: &[AxisIndex], value: &Value) -> Result<(), RuntimeError> {
+    let selection = select_axes(&arr.shape(), indices)?;
+    put(arr, &selection, value)
+}
+
+/// `a[mask] = value` / `a[[0, 2]] = value`
+pub fn assign_with_value(arr: &mut NdArray, index: &Value, value: &Value) -> Result<(), RuntimeError> {
+    let selection = select_with_value(&arr.shape(), index)?;
+    put(arr, &selection, value)
+}
+
+fn select_axes(shape: &[usize], indices: &[AxisIndex]) -> Result<Selection, RuntimeError> {
+    if indices.len() > shape.len() {
+        return Err(RuntimeError {
+            message: format!(
+                "Too many indices for array: array is {}-dimensional, but {} were indexed",
+                shape.len(), indices.len()
+            ),
+        });
+    }
+
+    let mut positions = Vec::with_capacity(shape.len());
+    let mut result_shape = Vec::new();
+    for (axis, &len) in shape.iter().enumerate() {
+        match indices.get(axis) {
+            None => {
+                positions.push((0..len).collect());
+                result_shape.push(len);
+            }
+            Some(AxisIndex::At(i)) => {
+                positions.push(vec![resolve_position(*i, axis, len)?]);
+            }
+            Some(AxisIndex::Slice { start, stop, step }) => {
+                let picked = slice_positions(len, *start, *stop, *step)?;
+                result_shape.push(picked.len());
+                positions.push(picked);
+            }
+        }
+    }
+
+    let mut points = Vec::with_capacity(result_shape.iter().product());
+    for_each_position(&positions, |idx| points.push(idx.to_vec()));
+    Ok(Selection { points, shape: result_shape })
+}
+
+fn select_with_value(shape: &[usize], index: &Value) -> Result<Selection, RuntimeError> {
+    let rows: Vec<usize> = match index {
+        Value::Integer(i) => return select_axes(shape, &[AxisIndex::At(*i)]),
+        Value::NdArray(NdArray::Bool(mask)) if mask.shape() == shape => {
+            // Full-shape mask: pick individual elements into a flat array
+            let points: Vec<Vec<usize>> = mask.indexed_iter()
+                .filter(|(_, &keep)| keep)
+                .map(|(idx, _)| idx.slice().to_vec())
+                .collect();
+            let shape = vec![points.len()];
+            return Ok(Selection { points, shape });
+        }
+        Value::NdArray(NdArray::Bool(mask)) if mask.ndim() == 1 && !shape.is_empty() && mask.len() == shape[0] => {
+            mask.iter().enumerate().filter(|(_, &keep)| keep).map(|(i, _)| i).collect()
+        }
+        Value::NdArray(NdArray::Bool(mask)) => {
+            return Err(RuntimeError {
+                message: format!("Boolean index of shape {:?} does not match array of shape {:?}", mask.shape(), shape),
+            });
+        }
+        Value::NdArray(NdArray::I64(idx)) if idx.ndim() == 1 && !shape.is_empty() => {
+            idx.iter().map(|&i| resolve_position(i, 0, shape[0])).collect::<Result<_, _>>()?
+        }
+        Value::Array(items) if !shape.is_empty() => {
+            items.iter()
+                .map(|v| match v {
+                    Value::Integer(i) => resolve_position(*i, 0, shape[0]),
+                    _ => Err(RuntimeError { message: "Index list must contain integers".to_string() }),
+                })
+                .collect::<Result<_, _>>()?
+        }
+        _ => return Err(RuntimeError {
+            message: format!("Cannot index ndarray with {}", index.type_name()),
+        }),
+    };
+
+    // Row selection along the first axis; remaining axes are taken whole
+    let mut result_shape = vec![rows.len()];
+    result_shape.extend_from_slice(&shape[1..]);
+    let mut positions = vec![rows];
+    positions.extend(shape[1..].iter().map(|&len| (0..len).collect::<Vec<_>>()));
+    let mut points = Vec::with_capacity(result_shape.iter().product());
+    for_each_position(&positions, |idx| points.push(idx.to_vec()));
+    Ok(Selection { points, shape: result_shape })
+}
+
+fn resolve_position(i: i64, axis: usize, len: usize) -> Result<usize, RuntimeError> {
+    let pos = if i < 0 { i + len as i64 } else { i };
+    if pos < 0 || pos >= len as i64 {
+        return Err(RuntimeError {
+            message: format!("Index {} is out of bounds for axis {} with size {}", i, axis, len),
+        });
+    }
+    Ok(pos as usize)
+}
+
+/// Positions picked by `start:stop:step` on an axis of length `len`, following Python rules
+fn slice_positions(len: usize, start: Option<i64>, stop: Option<i64>, step: i64) -> Result<Vec<usize>, RuntimeError> {
+    if step == 0 {
+        return Err(RuntimeError { message: "Slice step cannot be zero".to_string() });
+    }
+    let len = len as i64;
+    let normalize = |v: i64, lo: i64, hi: i64| (if v < 0 { v + len } else { v }).clamp(lo, hi);
+
+    let mut picked = Vec::new();
+    if step > 0 {
+        let mut i = start.map_or(0, |s| normalize(s, 0, len));
+        let stop = stop.map_or(len, |s| normalize(s, 0, len));
+        while i < stop {
+            picked.push(i as usize);
+            i += step;
         }
     } else {
-        vec![1]
+        let mut i = start.map_or(len - 1, |s| normalize(s, -1, len - 1));
+        let stop = stop.map_or(-1, |s| normalize(s, -1, len - 1));
+        while i > stop {
+            picked.push(i as usize);
+            i += step;
+        }
+    }
+    Ok(picked)
+}
+
+/// Visit every combination of per-axis positions in row-major order
+fn for_each_position<F: FnMut(&[usize])>(positions: &[Vec<usize>], mut f: F) {
+    if positions.iter().any(|p| p.is_empty()) {
+        return;
+    }
+    let mut counters = vec![0; positions.len()];
+    let mut idx: Vec<usize> = positions.iter().map(|p| p[0]).collect();
+    loop {
+        f(&idx);
+        let mut axis = positions.len();
+        loop {
+            if axis == 0 {
+                return;
+            }
+            axis -= 1;
+            counters[axis] += 1;
+            if counters[axis] < positions[axis].len() {
+                idx[axis] = positions[axis][counters[axis]];
+                break;
+            }
+            counters[axis] = 0;
+            idx[axis] = positions[axis][0];
+        }
+    }
+}
+
+fn take(arr: &NdArray, selection: &Selection) -> Value {
+    fn gather<T: Clone>(a: &ArrayD<T>, selection: &Selection) -> ArrayD<T> {
+        let data = selection.points.iter().map(|p| a[IxDyn(p)].clone()).collect();
+        ArrayD::from_shape_vec(IxDyn(&selection.shape), data).expect("selection shape matches its points")
+    }
+    let result = match arr {
+        NdArray::F64(a) => NdArray::F64(gather(a, selection)),
+        NdArray::I64(a) => NdArray::I64(gather(a, selection)),
+        NdArray::Bool(a) => NdArray::Bool(gather(a, selection)),
+    };
+    // Every axis indexed with an integer: hand back a plain scalar
+    if selection.shape.is_empty() {
+        return match result {
+            NdArray::F64(a) => Value::Float(a.iter().next().copied().unwrap_or_default()),
+            NdArray::I64(a) => Value::Integer(a.iter().next().copied().unwrap_or_default()),
+            NdArray::Bool(a) => Value::Bool(a.iter().next().copied().unwrap_or_default()),
+        };
+    }
+    Value::NdArray(result)
+}
+
+fn put(arr: &mut NdArray, selection: &Selection, value: &Value) -> Result<(), RuntimeError> {
+    fn scatter<T: Clone>(a: &mut ArrayD<T>, selection: &Selection, source: &ArrayD<T>) -> Result<(), RuntimeError> {
+        let view = source.broadcast(IxDyn(&selection.shape))
+            .ok_or_else(|| RuntimeError {
+                message: format!(
+                    "Could not broadcast input array from shape {:?} into shape {:?}",
+                    source.shape(), selection.shape
+                ),
+            })?;
+        for (point, item) in selection.points.iter().zip(view.iter()) {
+            a[IxDyn(point)] = item.clone();
+        }
+        Ok(())
+    }
+    let source = operand_array(value)?;
+    match arr {
+        NdArray::F64(a) => scatter(a, selection, &to_f64_array(&source)),
+        NdArray::I64(a) => scatter(a, selection, &to_i64_array(&source)),
+        NdArray::Bool(a) => scatter(a, selection, &to_f64_array(&source).mapv(|x| x != 0.0)),
+    }
+}
+
+/// Treat a scalar or plain Ject array as an ndarray operand
+fn operand_array(v: &Value) -> Result<NdArray, RuntimeError> {
+    let scalar_shape = IxDyn(&[]);
+    match v {
+        Value::NdArray(a) => Ok(a.clone()),
+        Value::Integer(i) => Ok(NdArray::I64(ArrayD::from_elem(scalar_shape, *i))),
+        Value::Float(f) => Ok(NdArray::F64(ArrayD::from_elem(scalar_shape, *f))),
+        Value::Bool(b) => Ok(NdArray::Bool(ArrayD::from_elem(scalar_shape, *b))),
+        Value::Array(items) => {
+            if items.iter().all(|x| matches!(x, Value::Integer(_))) {
+                let data: Vec<i64> = items.iter().map(value_to_i64).collect::<Result<_, _>>()?;
+                Ok(NdArray::I64(ArrayD::from_shape_vec(IxDyn(&[data.len()]), data).unwrap()))
+            } else {
+                let data: Vec<f64> = items.iter().map(value_to_f64).collect::<Result<_, _>>()?;
+                Ok(NdArray::F64(ArrayD::from_shape_vec(IxDyn(&[data.len()]), data).unwrap()))
+            }
+        }
+        _ => Err(RuntimeError {
+            message: format!("Unsupported ndarray operand: {}", v.type_name()),
+        }),
+    }
+}
+
+/// NumPy broadcasting: align shapes from the right; each pair of axes must match or be 1
+fn broadcast_shapes(a: &[usize], b: &[usize]) -> Result<Vec<usize>, RuntimeError> {
+    let ndim = a.len().max(b.len());
+    let axis_len = |shape: &[usize], axis: usize| {
+        if axis + shape.len() >= ndim { shape[axis + shape.len() - ndim] } else { 1 }
     };
+    (0..ndim)
+        .map(|axis| match (axis_len(a, axis), axis_len(b, axis)) {
+            (x, y) if x == y || y == 1 => Ok(x),
+            (1, y) => Ok(y),
+            _ => Err(RuntimeError {
+                message: format!("Operands could not be broadcast together with shapes {:?} {:?}", a, b),
+            }),
+        })
+        .collect()
+}
+
+fn zip_broadcast<A, B, R, F>(a: &ArrayD<A>, b: &ArrayD<B>, shape: &[usize], f: F) -> ArrayD<R>
+where
+    A: Copy,
+    B: Copy,
+    F: Fn(A, B) -> R,
+{
+    let va = a.broadcast(IxDyn(shape)).expect("shapes checked by broadcast_shapes");
+    let vb = b.broadcast(IxDyn(shape)).expect("shapes checked by broadcast_shapes");
+    Zip::from(&va).and(&vb).map_collect(|&x, &y| f(x, y))
+}
 
-    let size = shape.iter().product();
-    use rand::Rng;
-    let mut rng = rand::thread_rng();
-    let data: Vec<f64> = (0..size).map(|_| rng.gen_range(low..high) as f64).collect();
-    let ndarray = NdArray::F64(ArrayD::from_shape_vec(shape, data).unwrap());
-    Ok(Value::NdArray(ndarray))
+fn is_integral(arr: &NdArray) -> bool {
+    matches!(arr, NdArray::I64(_) | NdArray::Bool(_))
+}
+
+fn to_f64_array(arr: &NdArray) -> ArrayD<f64> {
+    match arr {
+        NdArray::F64(a) => a.clone(),
+        NdArray::I64(a) => a.mapv(|x| x as f64),
+        NdArray::Bool(a) => a.mapv(|x| if x { 1.0 } else { 0.0 }),
+    }
+}
+
+fn to_i64_array(arr: &NdArray) -> ArrayD<i64> {
+    match arr {
+        NdArray::F64(a) => a.mapv(|x| x as i64),
+        NdArray::I64(a) => a.clone(),
+        NdArray::Bool(a) => a.mapv(|x| x as i64),
+    }
 }
 
 // ============================================================================
@@ -0,0 +1,523 @@
+//! Linear algebra for the numpy module (`np.linalg.*`)
+//! Pure Rust implementations on top of ndarray, so no BLAS/LAPACK is needed
+
+use super::{to_f64_array, value_to_f64, NdArray};
+use crate::interpreter::RuntimeError;
+use crate::value::Value;
+use ndarray::{Array1, Array2, ArrayD, Axis, Ix1, Ix2};
+use std::collections::HashMap;
+
+/// Pivots and rotations below this are treated as zero
+const EPSILON: f64 = 1e-12;
+const MAX_SWEEPS: usize = 100;
+
+/// Create the `np.linalg` namespace
+pub(super) fn create_linalg_module() -> HashMap<String, Value> {
+    let mut module = HashMap::new();
+
+    for name in ["inv", "det", "solve", "lstsq", "norm", "qr", "cholesky", "eigh", "svd"] {
+        module.insert(name.to_string(), Value::BuiltinFunction(format!("np_linalg_{}", name)));
+    }
+
+    module
+}
+
+pub(super) fn call_linalg_function(name: &str, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    match name {
+        "np_linalg_inv" => linalg_inv(args),
+        "np_linalg_det" => linalg_det(args),
+        "np_linalg_solve" => linalg_solve(args),
+        "np_linalg_lstsq" => linalg_lstsq(args),
+        "np_linalg_norm" => linalg_norm(args),
+        "np_linalg_qr" => linalg_qr(args),
+        "np_linalg_cholesky" => linalg_cholesky(args),
+        "np_linalg_eigh" => linalg_eigh(args),
+        "np_linalg_svd" => linalg_svd(args),
+        _ => Err(RuntimeError {
+            message: format!("Unknown numpy function: {}", name),
+        }),
+    }
+}
+
+// ============================================================================
+// Functions
+// ============================================================================
+
+fn linalg_inv(args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let a = square_matrix_arg(args.first(), "inv")?;
+    let n = a.nrows();
+    let inverse = solve_lu(&a, &Array2::eye(n), "inv")?;
+    Ok(matrix_value(inverse))
+}
+
+fn linalg_det(args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let a = square_matrix_arg(args.first(), "det")?;
+    Ok(Value::Float(determinant(a)))
+}
+
+/// solve(a, b) - `b` is a vector or a matrix of right-hand sides
+fn linalg_solve(args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let a = square_matrix_arg(args.first(), "solve")?;
+    let (b, was_vector) = rhs_arg(args.get(1), "solve")?;
+    if b.nrows() != a.nrows() {
+        return Err(RuntimeError {
+            message: format!("solve() b has {} rows but a is {}x{}", b.nrows(), a.nrows(), a.ncols()),
+        });
+    }
+    let x = solve_lu(&a, &b, "solve")?;
+    Ok(rhs_value(x, was_vector))
+}
+
+/// lstsq(a, b) - minimum-norm least-squares solution of `a @ x = b`, via the pseudo-inverse
+fn linalg_lstsq(args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let a = matrix_arg(args.first(), "lstsq")?;
+    let (b, was_vector) = rhs_arg(args.get(1), "lstsq")?;
+    if b.nrows() != a.nrows() {
+        return Err(RuntimeError {
+            message: format!("lstsq() b has {} rows but a has {}", b.nrows(), a.nrows()),
+        });
+    }
+
+    let (u, s, vt) = svd(&a);
+    let cutoff = EPSILON * s.first().copied().unwrap_or(0.0) * a.nrows().max(a.ncols()) as f64;
+    let s_inv = s.mapv(|x| if x > cutoff { 1.0 / x } else { 0.0 });
+    // x = V * diag(1/s) * U^T * b
+    let utb = u.t().dot(&b);
+    let scaled = &utb * &s_inv.insert_axis(Axis(1));
+    Ok(rhs_value(vt.t().dot(&scaled), was_vector))
+}
+
+/// norm(x, ord) - vector p-norms; "fro", "nuc", 1, 2 and inf for matrices
+fn linalg_norm(args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let x = array_arg(args.first(), "norm")?;
+    let ord = args.get(1).cloned().unwrap_or(Value::Nil);
+
+    let result = match x.ndim() {
+        0 | 1 => {
+            let v: Vec<f64> = x.iter().map(|x| x.abs()).collect();
+            match ord {
+                Value::Nil => v.iter().map(|x| x * x).sum::<f64>().sqrt(),
+                Value::Float(p) if p == f64::INFINITY => v.iter().copied().fold(0.0, f64::max),
+                Value::Float(p) if p == f64::NEG_INFINITY => v.iter().copied().fold(f64::INFINITY, f64::min),
+                Value::Integer(0) => v.iter().filter(|x| **x != 0.0).count() as f64,
+                Value::Integer(_) | Value::Float(_) => {
+                    let p = value_to_f64(&ord)?;
+                    v.iter().map(|x| x.powf(p)).sum::<f64>().powf(1.0 / p)
+                }
+                _ => return Err(RuntimeError {
+                    message: format!("norm() invalid order {} for a vector", ord),
+                }),
+            }
+        }
+        2 => {
+            let m = x.into_dimensionality::<Ix2>().unwrap();
+            match ord {
+                Value::Nil => m.iter().map(|x| x * x).sum::<f64>().sqrt(),
+                Value::String(ref s) if s == "fro" => m.iter().map(|x| x * x).sum::<f64>().sqrt(),
+                Value::String(ref s) if s == "nuc" => svd(&m).1.sum(),
+                Value::Integer(1) => max_abs_sum(&m, Axis(0)),
+                Value::Float(p) if p == f64::INFINITY => max_abs_sum(&m, Axis(1)),
+                Value::Integer(2) => svd(&m).1.first().copied().unwrap_or(0.0),
+                _ => return Err(RuntimeError {
+                    message: format!("norm() invalid order {} for a matrix", ord),
+                }),
+            }
+        }
+        n => return Err(RuntimeError {
+            message: format!("norm() requires a vector or a matrix, got {} dimensions", n),
+        }),
+    };
+    Ok(Value::Float(result))
+}
+
+/// qr(a) - reduced QR decomposition, returned as [q, r]
+fn linalg_qr(args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let a = matrix_arg(args.first(), "qr")?;
+    let (q, r) = householder_qr(&a);
+    Ok(Value::Array(vec![matrix_value(q), matrix_value(r)]))
+}
+
+/// cholesky(a) - lower-triangular `l` with `l @ l.T == a`
+fn linalg_cholesky(args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let a = square_matrix_arg(args.first(), "cholesky")?;
+    let n = a.nrows();
+    let mut l = Array2::<f64>::zeros((n, n));
+
+    for j in 0..n {
+        let diag = a[[j, j]] - (0..j).map(|k| l[[j, k]] * l[[j, k]]).sum::<f64>();
+        if diag <= 0.0 {
+            return Err(RuntimeError {
+                message: "cholesky() matrix is not positive definite".to_string(),
+            });
+        }
+        l[[j, j]] = diag.sqrt();
+        for i in (j + 1)..n {
+            let off = a[[i, j]] - (0..j).map(|k| l[[i, k]] * l[[j, k]]).sum::<f64>();
+            l[[i, j]] = off / l[[j, j]];
+        }
+    }
+
+    Ok(matrix_value(l))
+}
+
+/// eigh(a) - eigenvalues (ascending) and eigenvectors (columns) of a symmetric matrix, as [w, v].
+/// Only the lower triangle is read, as with NumPy's default `UPLO='L'`
+fn linalg_eigh(args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let mut a = square_matrix_arg(args.first(), "eigh")?;
+    for i in 0..a.nrows() {
+        for j in (i + 1)..a.ncols() {
+            a[[i, j]] = a[[j, i]];
+        }
+    }
+    let (values, vectors) = jacobi_eigen(a);
+    Ok(Value::Array(vec![vector_value(values), matrix_value(vectors)]))
+}
+
+/// svd(a) - reduced singular value decomposition, returned as [u, s, vt] with `s` descending
+fn linalg_svd(args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let a = matrix_arg(args.first(), "svd")?;
+    let (u, s, vt) = svd(&a);
+    Ok(Value::Array(vec![matrix_value(u), vector_value(s), matrix_value(vt)]))
+}
+
+// ============================================================================
+// Algorithms
+// ============================================================================
+
+/// Gaussian elimination with partial pivoting; solves `a @ x = b` for every column of `b`
+fn solve_lu(a: &Array2<f64>, b: &Array2<f64>, fn_name: &str) -> Result<Array2<f64>, RuntimeError> {
+    let n = a.nrows();
+    let tolerance = singular_tolerance(a);
+    let mut a = a.clone();
+    let mut x = b.clone();
+
+    for col in 0..n {
+        let pivot = pivot_row(&a, col);
+        if a[[pivot, col]].abs() <= tolerance {
+            return Err(RuntimeError {
+                message: format!("{}() matrix is singular", fn_name),
+            });
+        }
+        swap_rows(&mut a, col, pivot);
+        swap_rows(&mut x, col, pivot);
+
+        for row in (col + 1)..n {
+            let factor = a[[row, col]] / a[[col, col]];
+            if factor == 0.0 {
+                continue;
+            }
+            for k in col..n {
+                a[[row, k]] -= factor * a[[col, k]];
+            }
+            for k in 0..x.ncols() {
+                x[[row, k]] -= factor * x[[col, k]];
+            }
+        }
+    }
+
+    // Back substitution
+    for row in (0..n).rev() {
+        for k in 0..x.ncols() {
+            let tail: f64 = ((row + 1)..n).map(|j| a[[row, j]] * x[[j, k]]).sum();
+            x[[row, k]] = (x[[row, k]] - tail) / a[[row, row]];
+        }
+    }
+
+    Ok(x)
+}
+
+/// Gaussian elimination as in `solve_lu`, so a matrix `inv` calls singular has determinant 0
+fn determinant(mut a: Array2<f64>) -> f64 {
+    let n = a.nrows();
+    let tolerance = singular_tolerance(&a);
+    let mut det = 1.0;
+
+    for col in 0..n {
+        let pivot = pivot_row(&a, col);
+        if a[[pivot, col]].abs() <= tolerance {
+            return 0.0;
+        }
+        if pivot != col {
+            swap_rows(&mut a, col, pivot);
+            det = -det;
+        }
+        det *= a[[col, col]];
+        for row in (col + 1)..n {
+            let factor = a[[row, col]] / a[[col, col]];
+            for k in col..n {
+                a[[row, k]] -= factor * a[[col, k]];
+            }
+        }
+    }
+
+    det
+}
+
+/// Householder reflections; returns the reduced `q` (m x k) and `r` (k x n) with k = min(m, n)
+fn householder_qr(a: &Array2<f64>) -> (Array2<f64>, Array2<f64>) {
+    let (m, n) = a.dim();
+    let k = m.min(n);
+    let mut r = a.clone();
+    let mut q = Array2::<f64>::eye(m);
+
+    for j in 0..k {
+        let x = r.slice(ndarray::s![j.., j]).to_owned();
+        let norm_x = x.dot(&x).sqrt();
+        if norm_x < EPSILON {
+            continue;
+        }
+        let alpha = if x[0] >= 0.0 { -norm_x } else { norm_x };
+        let mut v = x;
+        v[0] -= alpha;
+        let norm_v = v.dot(&v).sqrt();
+        if norm_v < EPSILON {
+            continue;
+        }
+        v /= norm_v;
+
+        // r[j.., :] -= 2 v (v^T r[j.., :]);  q[:, j..] -= 2 (q[:, j..] v) v^T
+        let mut r_block = r.slice_mut(ndarray::s![j.., ..]);
+        let vr = v.dot(&r_block);
+        for (i, vi) in v.iter().enumerate() {
+            for (c, vrc) in vr.iter().enumerate() {
+                r_block[[i, c]] -= 2.0 * vi * vrc;
+            }
+        }
+        let mut q_block = q.slice_mut(ndarray::s![.., j..]);
+        let qv = q_block.dot(&v);
+        for (row, qvr) in qv.iter().enumerate() {
+            for (i, vi) in v.iter().enumerate() {
+                q_block[[row, i]] -= 2.0 * qvr * vi;
+            }
+        }
+    }
+
+    let q = q.slice(ndarray::s![.., ..k]).to_owned();
+    let mut r = r.slice(ndarray::s![..k, ..]).to_owned();
+    for i in 0..k {
+        for j in 0..i.min(n) {
+            r[[i, j]] = 0.0;
+        }
+    }
+    (q, r)
+}
+
+/// Cyclic Jacobi rotations on a symmetric matrix; eigenvalues come back in ascending order
+fn jacobi_eigen(mut a: Array2<f64>) -> (Array1<f64>, Array2<f64>) {
+    let n = a.nrows();
+    let mut v = Array2::<f64>::eye(n);
+
+    for _ in 0..MAX_SWEEPS {
+        let off: f64 = (0..n).flat_map(|i| (0..n).map(move |j| (i, j)))
+            .filter(|(i, j)| i != j)
+            .map(|(i, j)| a[[i, j]] * a[[i, j]])
+            .sum();
+        if off < EPSILON * EPSILON {
+            break;
+        }
+
+        for p in 0..n {
+            for q in (p + 1)..n {
+                if a[[p, q]].abs() < EPSILON * EPSILON {
+                    continue;
+                }
+                let (c, s) = rotation(a[[p, p]], a[[q, q]], a[[p, q]]);
+                rotate_columns(&mut a, p, q, c, s);
+                rotate_rows(&mut a, p, q, c, s);
+                rotate_columns(&mut v, p, q, c, s);
+            }
+        }
+    }
+
+    let mut order: Vec<usize> = (0..n).collect();
+    order.sort_by(|&i, &j| a[[i, i]].partial_cmp(&a[[j, j]]).unwrap_or(std::cmp::Ordering::Equal));
+    let values = order.iter().map(|&i| a[[i, i]]).collect::<Array1<f64>>();
+    let vectors = v.select(Axis(1), &order);
+    (values, vectors)
+}
+
+/// One-sided Jacobi (Hestenes) SVD; returns the reduced `u`, descending `s` and `vt`
+fn svd(a: &Array2<f64>) -> (Array2<f64>, Array1<f64>, Array2<f64>) {
+    let (m, n) = a.dim();
+    if m < n {
+        // Work on the tall transpose: a.T = v s u.T
+        let (u, s, vt) = svd(&a.t().to_owned());
+        return (vt.t().to_owned(), s, u.t().to_owned());
+    }
+
+    let mut u = a.clone();
+    let mut v = Array2::<f64>::eye(n);
+
+    for _ in 0..MAX_SWEEPS {
+        let mut rotated = false;
+        for p in 0..n {
+            for q in (p + 1)..n {
+                let col_p = u.column(p);
+                let col_q = u.column(q);
+                let alpha = col_p.dot(&col_p);
+                let beta = col_q.dot(&col_q);
+                let gamma = col_p.dot(&col_q);
+                if gamma.abs() <= EPSILON * (alpha * beta).sqrt() || gamma == 0.0 {
+                    continue;
+                }
+                rotated = true;
+                let (c, s) = rotation(alpha, beta, gamma);
+                rotate_columns(&mut u, p, q, c, s);
+                rotate_columns(&mut v, p, q, c, s);
+            }
+        }
+        if !rotated {
+            break;
+        }
+    }
+
+    let norms: Vec<f64> = (0..n).map(|j| u.column(j).dot(&u.column(j)).sqrt()).collect();
+    let mut order: Vec<usize> = (0..n).collect();
+    order.sort_by(|&i, &j| norms[j].partial_cmp(&norms[i]).unwrap_or(std::cmp::Ordering::Equal));
+
+    let s = order.iter().map(|&j| norms[j]).collect::<Array1<f64>>();
+    let mut u = u.select(Axis(1), &order);
+    for (j, &sigma) in s.iter().enumerate() {
+        if sigma > EPSILON {
+            u.column_mut(j).mapv_inplace(|x| x / sigma);
+        }
+    }
+    let vt = v.select(Axis(1), &order).reversed_axes();
+    (u, s, vt)
+}
+
+/// The Jacobi rotation (c, s) that zeroes the off-diagonal entry of [[app, apq], [apq, aqq]]
+fn rotation(app: f64, aqq: f64, apq: f64) -> (f64, f64) {
+    let theta = (aqq - app) / (2.0 * apq);
+    let sign = if theta >= 0.0 { 1.0 } else { -1.0 };
+    let t = sign / (theta.abs() + (theta * theta + 1.0).sqrt());
+    let c = 1.0 / (t * t + 1.0).sqrt();
+    (c, t * c)
+}
+
+fn rotate_columns(a: &mut Array2<f64>, p: usize, q: usize, c: f64, s: f64) {
+    for k in 0..a.nrows() {
+        let (akp, akq) = (a[[k, p]], a[[k, q]]);
+        a[[k, p]] = c * akp - s * akq;
+        a[[k, q]] = s * akp + c * akq;
+    }
+}
+
+fn rotate_rows(a: &mut Array2<f64>, p: usize, q: usize, c: f64, s: f64) {
+    for k in 0..a.ncols() {
+        let (apk, aqk) = (a[[p, k]], a[[q, k]]);
+        a[[p, k]] = c * apk - s * aqk;
+        a[[q, k]] = s * apk + c * aqk;
+    }
+}
+
+/// Pivots at or below this are zero: relative to the largest entry and the size,
+/// like the cutoff `lstsq` applies to singular values
+fn singular_tolerance(a: &Array2<f64>) -> f64 {
+    EPSILON * a.iter().fold(0.0, |max: f64, x| max.max(x.abs())) * a.nrows() as f64
+}
+
+fn pivot_row(a: &Array2<f64>, col: usize) -> usize {
+    (col..a.nrows())
+        .max_by(|&i, &j| a[[i, col]].abs().partial_cmp(&a[[j, col]].abs()).unwrap_or(std::cmp::Ordering::Equal))
+        .unwrap_or(col)
+}
+
+fn swap_rows(a: &mut Array2<f64>, i: usize, j: usize) {
+    if i != j {
+        for k in 0..a.ncols() {
+            a.swap([i, k], [j, k]);
+        }
+    }
+}
+
+fn max_abs_sum(m: &Array2<f64>, axis: Axis) -> f64 {
+    m.mapv(f64::abs).sum_axis(axis).iter().copied().fold(0.0, f64::max)
+}
+
+// ============================================================================
+// Argument Helpers
+// ============================================================================
+
+/// A 2-D ndarray or a Ject array of equal-length rows
+fn matrix_arg(v: Option<&Value>, fn_name: &str) -> Result<Array2<f64>, RuntimeError> {
+    let not_a_matrix = || RuntimeError {
+        message: format!("{}() requires a 2D array argument", fn_name),
+    };
+    match v {
+        Some(Value::NdArray(arr)) => to_f64_array(arr).into_dimensionality::<Ix2>().map_err(|_| not_a_matrix()),
+        Some(Value::Array(rows)) => {
+            let rows: Vec<Vec<f64>> = rows.iter()
+                .map(|row| match row {
+                    Value::Array(items) => items.iter().map(value_to_f64).collect(),
+                    _ => Err(not_a_matrix()),
+                })
+                .collect::<Result<_, _>>()?;
+            let ncols = rows.first().map_or(0, |r| r.len());
+            if rows.iter().any(|r| r.len() != ncols) {
+                return Err(RuntimeError {
+                    message: format!("{}() rows must all have the same length", fn_name),
+                });
+            }
+            let data: Vec<f64> = rows.into_iter().flatten().collect();
+            Array2::from_shape_vec((data.len() / ncols.max(1), ncols), data).map_err(|_| not_a_matrix())
+        }
+        _ => Err(not_a_matrix()),
+    }
+}
+
+fn square_matrix_arg(v: Option<&Value>, fn_name: &str) -> Result<Array2<f64>, RuntimeError> {
+    let m = matrix_arg(v, fn_name)?;
+    if m.nrows() != m.ncols() {
+        return Err(RuntimeError {
+            message: format!("{}() requires a square matrix, got {}x{}", fn_name, m.nrows(), m.ncols()),
+        });
+    }
+    Ok(m)
+}
+
+/// Right-hand side of a linear system; vectors become a single column
+fn rhs_arg(v: Option<&Value>, fn_name: &str) -> Result<(Array2<f64>, bool), RuntimeError> {
+    if v.is_none() {
+        return Err(RuntimeError {
+            message: format!("{}() requires a and b arguments", fn_name),
+        });
+    }
+    let arr = array_arg(v, fn_name)?;
+    match arr.ndim() {
+        1 => Ok((arr.into_dimensionality::<Ix1>().unwrap().insert_axis(Axis(1)), true)),
+        2 => Ok((arr.into_dimensionality::<Ix2>().unwrap(), false)),
+        _ => Err(RuntimeError {
+            message: format!("{}() b must be a vector or a matrix", fn_name),
+        }),
+    }
+}
+
+/// Any ndarray or Ject array, with nested Ject arrays read as matrix rows
+fn array_arg(v: Option<&Value>, fn_name: &str) -> Result<ArrayD<f64>, RuntimeError> {
+    match v {
+        Some(Value::Array(rows)) if rows.iter().any(|r| matches!(r, Value::Array(_))) => {
+            matrix_arg(v, fn_name).map(|m| m.into_dyn())
+        }
+        Some(v) => Ok(to_f64_array(&super::operand_array(v)?)),
+        None => Err(RuntimeError {
+            message: format!("{}() requires an array argument", fn_name),
+        }),
+    }
+}
+
+fn rhs_value(x: Array2<f64>, was_vector: bool) -> Value {
+    if was_vector {
+        vector_value(x.column(0).to_owned())
+    } else {
+        matrix_value(x)
+    }
+}
+
+fn matrix_value(m: Array2<f64>) -> Value {
+    Value::NdArray(NdArray::F64(m.into_dyn()))
+}
+
+fn vector_value(v: Array1<f64>) -> Value {
+    Value::NdArray(NdArray::F64(v.into_dyn()))
+}
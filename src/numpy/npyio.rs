@@ -0,0 +1,432 @@
+//! File I/O for the numpy module: text files (`loadtxt`/`savetxt`), the binary
+//! `.npy` format and `.npz` archives, compatible with NumPy's own readers and writers
+
+use super::{operand_array, NdArray};
+use crate::interpreter::RuntimeError;
+use crate::value::Value;
+use ndarray::{ArrayD, IxDyn};
+use std::io::{Read, Write};
+
+const NPY_MAGIC: &[u8] = b"\x93NUMPY";
+
+pub(super) fn call_io_function(name: &str, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    match name {
+        "np_loadtxt" => np_loadtxt(args),
+        "np_savetxt" => np_savetxt(args),
+        "np_save" => np_save(args),
+        "np_load" => np_load(args),
+        "np_savez" => np_savez(args, zip::CompressionMethod::Stored),
+        "np_savez_compressed" => np_savez(args, zip::CompressionMethod::Deflated),
+        _ => Err(RuntimeError {
+            message: format!("Unknown numpy function: {}", name),
+        }),
+    }
+}
+
+// ============================================================================
+// Text Files
+// ============================================================================
+
+/// loadtxt(fname, delimiter, skiprows, dtype, comments)
+/// Whitespace-separated by default; one row or one column comes back as a 1-D array
+fn np_loadtxt(args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let path = path_arg(args.first(), "loadtxt")?;
+    let delimiter = optional_string(args.get(1), "loadtxt", "delimiter")?;
+    let skiprows = match args.get(2) {
+        None | Some(Value::Nil) => 0,
+        Some(Value::Integer(n)) if *n >= 0 => *n as usize,
+        Some(_) => return Err(RuntimeError {
+            message: "loadtxt() skiprows must be a non-negative integer".to_string(),
+        }),
+    };
+    let integer = match optional_string(args.get(3), "loadtxt", "dtype")?.as_deref() {
+        None | Some("float") | Some("float64") | Some("f8") => false,
+        Some("int") | Some("int64") | Some("i8") => true,
+        Some(other) => return Err(RuntimeError {
+            message: format!("loadtxt() unsupported dtype '{}', expected \"float\" or \"int\"", other),
+        }),
+    };
+    let comments = optional_string(args.get(4), "loadtxt", "comments")?.unwrap_or_else(|| "#".to_string());
+
+    let contents = std::fs::read_to_string(&path).map_err(|e| RuntimeError {
+        message: format!("Failed to read file: {} ({})", path, e),
+    })?;
+
+    let mut rows: Vec<Vec<&str>> = Vec::new();
+    let mut line_numbers = Vec::new();
+    for (i, line) in contents.lines().enumerate().skip(skiprows) {
+        let line = match comments.is_empty() {
+            true => line,
+            false => line.split(comments.as_str()).next().unwrap_or(""),
+        };
+        if line.trim().is_empty() {
+            continue;
+        }
+        let fields: Vec<&str> = match &delimiter {
+            Some(d) => line.split(d.as_str()).map(str::trim).collect(),
+            None => line.split_whitespace().collect(),
+        };
+        if let Some(first) = rows.first() {
+            if fields.len() != first.len() {
+                return Err(RuntimeError {
+                    message: format!("loadtxt() line {} has {} columns, expected {}", i + 1, fields.len(), first.len()),
+                });
+            }
+        }
+        rows.push(fields);
+        line_numbers.push(i + 1);
+    }
+
+    let ncols = rows.first().map_or(0, |r| r.len());
+    let shape = if rows.len() == 1 || ncols == 1 {
+        vec![rows.len() * ncols]
+    } else {
+        vec![rows.len(), ncols]
+    };
+    let fields = rows.iter().zip(&line_numbers).flat_map(|(row, line)| row.iter().map(move |f| (*f, *line)));
+
+    let array = if integer {
+        let data = fields
+            .map(|(f, line)| f.parse::<i64>().map_err(|_| conversion_error(f, "int", line)))
+            .collect::<Result<Vec<_>, _>>()?;
+        NdArray::I64(ArrayD::from_shape_vec(shape, data).unwrap())
+    } else {
+        let data = fields
+            .map(|(f, line)| f.parse::<f64>().map_err(|_| conversion_error(f, "float", line)))
+            .collect::<Result<Vec<_>, _>>()?;
+        NdArray::F64(ArrayD::from_shape_vec(shape, data).unwrap())
+    };
+    Ok(Value::NdArray(array))
+}
+
+/// savetxt(fname, X, delimiter, header, fmt, comments)
+/// `fmt` accepts "%d", "%.Nf", "%.Ne" and "%g"; the default writes the shortest exact form
+fn np_savetxt(args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let path = path_arg(args.first(), "savetxt")?;
+    let arr = match args.get(1) {
+        Some(v) => operand_array(v)?,
+        None => return Err(RuntimeError {
+            message: "savetxt() requires a file name and an array".to_string(),
+        }),
+    };
+    let delimiter = optional_string(args.get(2), "savetxt", "delimiter")?.unwrap_or_else(|| " ".to_string());
+    let header = optional_string(args.get(3), "savetxt", "header")?;
+    let fmt = optional_string(args.get(4), "savetxt", "fmt")?;
+    let comments = optional_string(args.get(5), "savetxt", "comments")?.unwrap_or_else(|| "# ".to_string());
+
+    let shape = arr.shape();
+    let (nrows, ncols) = match shape.len() {
+        0 => (1, 1),
+        1 => (shape[0], 1),
+        2 => (shape[0], shape[1]),
+        n => return Err(RuntimeError {
+            message: format!("savetxt() requires a 1D or 2D array, got {} dimensions", n),
+        }),
+    };
+
+    let values: Vec<String> = match &arr {
+        NdArray::F64(a) => a.iter().map(|&x| format_number(x, fmt.as_deref())).collect::<Result<_, _>>()?,
+        NdArray::I64(a) => a.iter().map(|&x| match fmt {
+            Some(_) => format_number(x as f64, fmt.as_deref()),
+            None => Ok(x.to_string()),
+        }).collect::<Result<_, _>>()?,
+        NdArray::Bool(a) => a.iter().map(|&x| (x as i64).to_string()).collect(),
+    };
+
+    let mut out = String::new();
+    if let Some(header) = header {
+        for line in header.lines() {
+            out.push_str(&comments);
+            out.push_str(line);
+            out.push('\n');
+        }
+    }
+    for row in 0..nrows {
+        out.push_str(&values[row * ncols..(row + 1) * ncols].join(&delimiter));
+        out.push('\n');
+    }
+
+    std::fs::write(&path, out).map_err(|e| RuntimeError {
+        message: format!("Failed to write to file: {} ({})", path, e),
+    })?;
+    Ok(Value::Nil)
+}
+
+fn format_number(x: f64, fmt: Option<&str>) -> Result<String, RuntimeError> {
+    let Some(fmt) = fmt else {
+        return Ok(x.to_string());
+    };
+    let invalid = || RuntimeError {
+        message: format!("savetxt() unsupported fmt '{}'", fmt),
+    };
+    let spec = fmt.strip_prefix('%').ok_or_else(invalid)?;
+    let (precision, kind) = match spec.strip_prefix('.') {
+        Some(rest) => {
+            let digits: String = rest.chars().take_while(|c| c.is_ascii_digit()).collect();
+            (Some(digits.parse::<usize>().map_err(|_| invalid())?), &rest[digits.len()..])
+        }
+        None => (None, spec),
+    };
+    match kind {
+        // Like NumPy, which converts with int() and so truncates toward zero
+        "d" | "i" => Ok(format!("{}", x.trunc() as i64)),
+        "f" => Ok(format!("{:.*}", precision.unwrap_or(6), x)),
+        "e" => Ok(format!("{:.*e}", precision.unwrap_or(6), x)),
+        "g" | "s" => Ok(x.to_string()),
+        _ => Err(invalid()),
+    }
+}
+
+fn conversion_error(field: &str, dtype: &str, line: usize) -> RuntimeError {
+    RuntimeError {
+        message: format!("loadtxt() could not convert '{}' to {} on line {}", field, dtype, line),
+    }
+}
+
+// ============================================================================
+// .npy and .npz
+// ============================================================================
+
+/// save(fname, arr) - writes a .npy file, adding the extension if it is missing
+fn np_save(args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let path = with_extension(path_arg(args.first(), "save")?, ".npy");
+    let arr = match args.get(1) {
+        Some(v) => operand_array(v)?,
+        None => return Err(RuntimeError {
+            message: "save() requires a file name and an array".to_string(),
+        }),
+    };
+    std::fs::write(&path, npy_bytes(&arr)).map_err(|e| RuntimeError {
+        message: format!("Failed to write to file: {} ({})", path, e),
+    })?;
+    Ok(Value::Nil)
+}
+
+/// load(fname) - a .npy file gives an array, a .npz archive a dictionary of arrays by name
+fn np_load(args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let path = path_arg(args.first(), "load")?;
+    let bytes = std::fs::read(&path).map_err(|e| RuntimeError {
+        message: format!("Failed to read file: {} ({})", path, e),
+    })?;
+
+    if bytes.starts_with(NPY_MAGIC) {
+        return Ok(Value::NdArray(parse_npy(&bytes, &path)?));
+    }
+    if !bytes.starts_with(b"PK") {
+        return Err(RuntimeError {
+            message: format!("load() {} is not a .npy or .npz file", path),
+        });
+    }
+
+    let archive_error = |e: zip::result::ZipError| RuntimeError {
+        message: format!("load() could not read archive {}: {}", path, e),
+    };
+    let mut archive = zip::ZipArchive::new(std::io::Cursor::new(bytes)).map_err(archive_error)?;
+    let mut arrays = indexmap::IndexMap::new();
+    for i in 0..archive.len() {
+        let mut entry = archive.by_index(i).map_err(archive_error)?;
+        let name = entry.name().to_string();
+        let mut data = Vec::new();
+        entry.read_to_end(&mut data).map_err(|e| RuntimeError {
+            message: format!("load() could not read {} in {}: {}", name, path, e),
+        })?;
+        let key = name.strip_suffix(".npy").unwrap_or(&name).to_string();
+        let source = format!("{}/{}", path, name);
+        arrays.insert(Value::String(key), Value::NdArray(parse_npy(&data, &source)?));
+    }
+    Ok(Value::Dictionary(arrays))
+}
+
+/// savez(fname, {name: arr, ...}) or savez(fname, a, b, ...) - the latter are stored as arr_0, arr_1, ...
+fn np_savez(args: Vec<Value>, method: zip::CompressionMethod) -> Result<Value, RuntimeError> {
+    let path = with_extension(path_arg(args.first(), "savez")?, ".npz");
+
+    let mut named: Vec<(String, NdArray)> = match args.get(1) {
+        Some(Value::Dictionary(map)) => map.iter()
+            .map(|(k, v)| match k {
+                Value::String(name) => Ok((name.clone(), operand_array(v)?)),
+                other => Err(RuntimeError {
+                    message: format!("savez() array names must be strings, got {}", other.type_name()),
+                }),
+            })
+            .collect::<Result<_, RuntimeError>>()?,
+        _ => args.iter().skip(1).enumerate()
+            .map(|(i, v)| Ok((format!("arr_{}", i), operand_array(v)?)))
+            .collect::<Result<_, RuntimeError>>()?,
+    };
+    named.sort_by(|a, b| a.0.cmp(&b.0));
+
+    let write_error = |e: String| RuntimeError {
+        message: format!("Failed to write to file: {} ({})", path, e),
+    };
+    let file = std::fs::File::create(&path).map_err(|e| write_error(e.to_string()))?;
+    let mut writer = zip::ZipWriter::new(file);
+    let options = zip::write::SimpleFileOptions::default().compression_method(method);
+    for (name, arr) in &named {
+        writer.start_file(format!("{}.npy", name), options).map_err(|e| write_error(e.to_string()))?;
+        writer.write_all(&npy_bytes(arr)).map_err(|e| write_error(e.to_string()))?;
+    }
+    writer.finish().map_err(|e| write_error(e.to_string()))?;
+    Ok(Value::Nil)
+}
+
+/// Version 1.0 .npy: magic, version, little-endian u16 header length, a Python dict
+/// literal padded so the data starts on a 64-byte boundary, then C-order data
+fn npy_bytes(arr: &NdArray) -> Vec<u8> {
+    let descr = match arr {
+        NdArray::F64(_) => "<f8",
+        NdArray::I64(_) => "<i8",
+        NdArray::Bool(_) => "|b1",
+    };
+    let shape = arr.shape();
+    let shape_tuple = match shape.len() {
+        1 => format!("({},)", shape[0]),
+        _ => format!("({})", shape.iter().map(|d| d.to_string()).collect::<Vec<_>>().join(", ")),
+    };
+    let mut header = format!("{{'descr': '{}', 'fortran_order': False, 'shape': {}, }}", descr, shape_tuple);
+    let unpadded = NPY_MAGIC.len() + 4 + header.len() + 1;
+    header.push_str(&" ".repeat((64 - unpadded % 64) % 64));
+    header.push('\n');
+
+    let mut bytes = Vec::with_capacity(NPY_MAGIC.len() + 4 + header.len() + arr.len() * 8);
+    bytes.extend_from_slice(NPY_MAGIC);
+    bytes.extend_from_slice(&[1, 0]);
+    bytes.extend_from_slice(&(header.len() as u16).to_le_bytes());
+    bytes.extend_from_slice(header.as_bytes());
+    match arr {
+        NdArray::F64(a) => a.iter().for_each(|x| bytes.extend_from_slice(&x.to_le_bytes())),
+        NdArray::I64(a) => a.iter().for_each(|x| bytes.extend_from_slice(&x.to_le_bytes())),
+        NdArray::Bool(a) => a.iter().for_each(|&x| bytes.push(x as u8)),
+    }
+    bytes
+}
+
+fn parse_npy(bytes: &[u8], source: &str) -> Result<NdArray, RuntimeError> {
+    let invalid = |what: &str| RuntimeError {
+        message: format!("load() {} is not a valid .npy file: {}", source, what),
+    };
+    if !bytes.starts_with(NPY_MAGIC) || bytes.len() < 10 {
+        return Err(invalid("missing header"));
+    }
+
+    let (header_len, header_start) = match bytes[6] {
+        1 => (u16::from_le_bytes([bytes[8], bytes[9]]) as usize, 10),
+        2 | 3 if bytes.len() >= 12 => (u32::from_le_bytes([bytes[8], bytes[9], bytes[10], bytes[11]]) as usize, 12),
+        v => return Err(invalid(&format!("unsupported format version {}", v))),
+    };
+    let data_start = header_start + header_len;
+    let header = bytes.get(header_start..data_start)
+        .and_then(|h| std::str::from_utf8(h).ok())
+        .ok_or_else(|| invalid("truncated header"))?;
+
+    let descr = header_value(header, "descr")
+        .and_then(|v| v.trim().strip_prefix('\'')?.split('\'').next())
+        .ok_or_else(|| invalid("missing descr"))?;
+    let fortran_order = header_value(header, "fortran_order").is_some_and(|v| v.trim_start().starts_with("True"));
+    let mut shape: Vec<usize> = header_value(header, "shape")
+        .and_then(|v| v.trim().strip_prefix('(')?.split(')').next())
+        .ok_or_else(|| invalid("missing shape"))?
+        .split(',')
+        .map(str::trim)
+        .filter(|d| !d.is_empty())
+        .map(|d| d.parse::<usize>().map_err(|_| invalid("bad shape")))
+        .collect::<Result<_, _>>()?;
+
+    // A dtype is byte order, kind and width, like '<f8'
+    let unsupported = || invalid(&format!("unsupported dtype '{}'", descr));
+    let mut chars = descr.chars();
+    let (endian, kind) = (chars.next(), chars.next());
+    let width: usize = chars.as_str().parse().map_err(|_| unsupported())?;
+    let kind = match (endian, kind, width) {
+        (Some('<' | '>' | '|' | '='), Some(kind @ 'f'), 4 | 8)
+        | (Some('<' | '>' | '|' | '='), Some(kind @ ('i' | 'u')), 1 | 2 | 4 | 8)
+        | (Some('<' | '>' | '|' | '='), Some(kind @ 'b'), 1) => kind,
+        _ => return Err(unsupported()),
+    };
+    let big_endian = endian == Some('>');
+    let size = shape.iter()
+        .try_fold(width, |size, &dim| size.checked_mul(dim))
+        .ok_or_else(|| invalid("shape too large"))?;
+    let data = data_start.checked_add(size)
+        .and_then(|data_end| bytes.get(data_start..data_end))
+        .ok_or_else(|| invalid("truncated data"))?;
+    let words = data.chunks_exact(width).map(|chunk| {
+        let mut word = [0u8; 8];
+        if big_endian {
+            word[8 - width..].copy_from_slice(chunk);
+            u64::from_be_bytes(word)
+        } else {
+            word[..width].copy_from_slice(chunk);
+            u64::from_le_bytes(word)
+        }
+    });
+
+    // Fortran-order data is the C-order data of the transposed array
+    if fortran_order {
+        shape.reverse();
+    }
+    let dim = IxDyn(&shape);
+    let mismatch = |_| invalid("data does not match shape");
+    let arr = match kind {
+        'f' if width == 8 => NdArray::F64(ArrayD::from_shape_vec(dim, words.map(f64::from_bits).collect()).map_err(mismatch)?),
+        'f' => NdArray::F64(ArrayD::from_shape_vec(dim, words.map(|w| f32::from_bits(w as u32) as f64).collect()).map_err(mismatch)?),
+        'i' => {
+            // Sign-extend from the stored width
+            let shift = 64 - 8 * width as u32;
+            NdArray::I64(ArrayD::from_shape_vec(dim, words.map(|w| ((w << shift) as i64) >> shift).collect()).map_err(mismatch)?)
+        }
+        'u' => {
+            let values = words
+                .map(|w| i64::try_from(w).map_err(|_| RuntimeError {
+                    message: format!("load() {} holds uint64 value {}, which does not fit in int64", source, w),
+                }))
+                .collect::<Result<_, _>>()?;
+            NdArray::I64(ArrayD::from_shape_vec(dim, values).map_err(mismatch)?)
+        }
+        _ => NdArray::Bool(ArrayD::from_shape_vec(dim, words.map(|w| w != 0).collect()).map_err(mismatch)?),
+    };
+
+    Ok(match (fortran_order, arr) {
+        (false, arr) => arr,
+        (true, NdArray::F64(a)) => NdArray::F64(a.reversed_axes().as_standard_layout().into_owned()),
+        (true, NdArray::I64(a)) => NdArray::I64(a.reversed_axes().as_standard_layout().into_owned()),
+        (true, NdArray::Bool(a)) => NdArray::Bool(a.reversed_axes().as_standard_layout().into_owned()),
+    })
+}
+
+/// The text after `'key':` in a .npy header dict
+fn header_value<'a>(header: &'a str, key: &str) -> Option<&'a str> {
+    let pattern = format!("'{}':", key);
+    header.find(&pattern).map(|i| &header[i + pattern.len()..])
+}
+
+// ============================================================================
+// Argument Helpers
+// ============================================================================
+
+fn path_arg(v: Option<&Value>, fn_name: &str) -> Result<String, RuntimeError> {
+    match v {
+        Some(Value::String(path)) => Ok(path.clone()),
+        _ => Err(RuntimeError {
+            message: format!("{}() requires a string file path", fn_name),
+        }),
+    }
+}
+
+fn optional_string(v: Option<&Value>, fn_name: &str, param: &str) -> Result<Option<String>, RuntimeError> {
+    match v {
+        None | Some(Value::Nil) => Ok(None),
+        Some(Value::String(s)) => Ok(Some(s.clone())),
+        Some(other) => Err(RuntimeError {
+            message: format!("{}() {} must be a string, got {}", fn_name, param, other.type_name()),
+        }),
+    }
+}
+
+fn with_extension(path: String, extension: &str) -> String {
+    if path.ends_with(extension) {
+        path
+    } else {
+        path + extension
+    }
+}
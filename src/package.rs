@@ -0,0 +1,418 @@
+//! Packages: a `ject.toml` manifest naming the package, its entry script and
+//! its dependencies, and the `ject.lock` they resolve to
+//!
+//! ```toml
+//! [package]
+//! name = "app"
+//! version = "0.1.0"
+//! entry = "src/main.ject"
+//!
+//! [dependencies]
+//! utils = { path = "../utils" }
+//!
+//! [lints]
+//! deny = ["W0003"]    # Report these warnings as errors; "warnings" means all
+//! ```
+//!
+//! A dependency is a directory: either another package with its own manifest
+//! (whose dependencies are resolved too) or a vendored directory of `.ject`
+//! files. Each resolved directory goes on the module search path. The
+//! resolution is kept in `ject.lock` and reused until the manifests change.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+pub const MANIFEST_FILE: &str = "ject.toml";
+pub const LOCK_FILE: &str = "ject.lock";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Manifest {
+    pub name: String,
+    pub version: String,
+    pub entry: String,                  // Script run by `ject run`, relative to the package root
+    pub dependencies: Vec<Dependency>,  // In manifest order
+    pub deny: Vec<String>,              // Warning codes the linter reports as errors
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Dependency {
+    pub name: String,
+    pub path: PathBuf,  // As written, relative to the manifest's directory
+}
+
+/// A dependency as recorded in `ject.lock`
+#[derive(Debug, Clone, PartialEq)]
+pub struct LockedPackage {
+    pub name: String,
+    pub version: Option<String>,  // None for vendored directories without a manifest
+    pub path: PathBuf,            // Relative to the root package
+}
+
+impl Manifest {
+    /// The manifest `ject new` writes
+    pub fn new(name: &str) -> Self {
+        Manifest {
+            name: name.to_string(),
+            version: "0.1.0".to_string(),
+            entry: "src/main.ject".to_string(),
+            dependencies: Vec::new(),
+            deny: Vec::new(),
+        }
+    }
+
+    pub fn parse(source: &str) -> Result<Manifest, String> {
+        let table: toml::Table = source.parse().map_err(|e| format!("Invalid {}: {}", MANIFEST_FILE, e))?;
+
+        let package = table.get("package").and_then(|p| p.as_table())
+            .ok_or_else(|| format!("{} is missing a [package] section", MANIFEST_FILE))?;
+        let field = |key: &str| -> Result<Option<String>, String> {
+            match package.get(key) {
+                None => Ok(None),
+                Some(toml::Value::String(s)) => Ok(Some(s.clone())),
+                Some(_) => Err(format!("package.{} in {} must be a string", key, MANIFEST_FILE)),
+            }
+        };
+        let name = field("name")?.ok_or_else(|| format!("{} is missing package.name", MANIFEST_FILE))?;
+        check_package_name(&name)?;
+
+        let mut dependencies = Vec::new();
+        if let Some(deps) = table.get("dependencies") {
+            let deps = deps.as_table().ok_or_else(|| format!("[dependencies] in {} must be a table", MANIFEST_FILE))?;
+            for (dep_name, spec) in deps {
+                check_package_name(dep_name)?;
+                let path = spec.get("path").and_then(|p| p.as_str()).ok_or_else(|| {
+                    format!("Dependency '{}' must be written as {} = {{ path = \"...\" }}", dep_name, dep_name)
+                })?;
+                dependencies.push(Dependency { name: dep_name.clone(), path: PathBuf::from(path) });
+            }
+        }
+
+        let mut deny = Vec::new();
+        if let Some(codes) = table.get("lints").and_then(|lints| lints.get("deny")) {
+            let invalid = || format!("lints.deny in {} must be an array of strings", MANIFEST_FILE);
+            for code in codes.as_array().ok_or_else(invalid)? {
+                deny.push(code.as_str().ok_or_else(invalid)?.to_string());
+            }
+        }
+
+        Ok(Manifest {
+            name,
+            version: field("version")?.unwrap_or_else(|| "0.1.0".to_string()),
+            entry: field("entry")?.unwrap_or_else(|| "src/main.ject".to_string()),
+            dependencies,
+            deny,
+        })
+    }
+
+    /// Read `ject.toml` from a package directory
+    pub fn load(dir: &Path) -> Result<Manifest, String> {
+        let path = dir.join(MANIFEST_FILE);
+        let source = fs::read_to_string(&path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        Manifest::parse(&source).map_err(|e| format!("{}: {}", path.display(), e))
+    }
+
+    pub fn to_toml(&self) -> String {
+        let mut out = format!(
+            "[package]\nname = {}\nversion = {}\nentry = {}\n",
+            quote(&self.name), quote(&self.version), quote(&self.entry)
+        );
+        out.push_str("\n[dependencies]\n");
+        for dep in &self.dependencies {
+            out.push_str(&format!("{} = {{ path = {} }}\n", dep.name, quote(&path_string(&dep.path))));
+        }
+        if !self.deny.is_empty() {
+            let codes: Vec<String> = self.deny.iter().map(|code| quote(code)).collect();
+            out.push_str(&format!("\n[lints]\ndeny = [{}]\n", codes.join(", ")));
+        }
+        out
+    }
+}
+
+/// A package on disk: its root directory and manifest
+#[derive(Debug, Clone)]
+pub struct Package {
+    pub root: PathBuf,
+    pub manifest: Manifest,
+}
+
+impl Package {
+    /// The package containing `start`, found by walking up to the nearest `ject.toml`
+    pub fn find(start: &Path) -> Result<Package, String> {
+        let start = fs::canonicalize(start).map_err(|e| format!("{}: {}", start.display(), e))?;
+        Package::discover(&start)?
+            .ok_or_else(|| format!("Could not find {} in {} or any parent directory", MANIFEST_FILE, start.display()))
+    }
+
+    /// Like `find`, but None outside any package. A manifest that is there but
+    /// can't be read or parsed is still an error
+    pub fn discover(start: &Path) -> Result<Option<Package>, String> {
+        let Ok(start) = fs::canonicalize(start) else {
+            return Ok(None);
+        };
+        match start.ancestors().find(|dir| dir.join(MANIFEST_FILE).is_file()) {
+            Some(root) => Ok(Some(Package { root: root.to_path_buf(), manifest: Manifest::load(root)? })),
+            None => Ok(None),
+        }
+    }
+
+    pub fn entry_path(&self) -> PathBuf {
+        self.root.join(&self.manifest.entry)
+    }
+
+    /// Every dependency, direct and transitive, in the order they are first required
+    pub fn resolve(&self) -> Result<Vec<LockedPackage>, String> {
+        let mut locked = Vec::new();
+        let mut seen: HashMap<String, PathBuf> = HashMap::new();  // Name -> canonical directory
+        let mut pending: Vec<(Dependency, PathBuf)> = self.manifest.dependencies.iter()
+            .map(|dep| (dep.clone(), dep.path.clone()))
+            .collect();
+        pending.reverse();
+
+        while let Some((dep, relative)) = pending.pop() {
+            let dir = self.root.join(&relative);
+            let canonical = fs::canonicalize(&dir)
+                .ok()
+                .filter(|dir| dir.is_dir())
+                .ok_or_else(|| format!("Dependency '{}' not found at {}", dep.name, path_string(&relative)))?;
+            if let Some(previous) = seen.get(&dep.name) {
+                if *previous == canonical {
+                    continue;
+                }
+                return Err(format!(
+                    "Dependency '{}' is required from both {} and {}",
+                    dep.name, previous.display(), canonical.display()
+                ));
+            }
+            seen.insert(dep.name.clone(), canonical.clone());
+
+            // A package brings its own dependencies; a vendored directory is just files
+            let mut version = None;
+            if canonical.join(MANIFEST_FILE).is_file() {
+                let manifest = Manifest::load(&canonical)?;
+                if manifest.name != dep.name {
+                    return Err(format!(
+                        "Dependency '{}' at {} is package '{}'",
+                        dep.name, path_string(&relative), manifest.name
+                    ));
+                }
+                for nested in manifest.dependencies.iter().rev() {
+                    pending.push((nested.clone(), normalize(&relative.join(&nested.path))));
+                }
+                version = Some(manifest.version);
+            }
+            locked.push(LockedPackage { name: dep.name, version, path: relative });
+        }
+        Ok(locked)
+    }
+
+    /// The locked dependencies: those in `ject.lock` while it still matches the
+    /// manifests, otherwise freshly resolved and written back
+    pub fn lock(&self) -> Result<Vec<LockedPackage>, String> {
+        let path = self.root.join(LOCK_FILE);
+        // A lockfile that no longer parses is regenerated like a stale one
+        if let Some(locked) = fs::read_to_string(&path).ok().and_then(|source| parse_lock(&source).ok()) {
+            if self.is_current(&locked) {
+                return Ok(locked);
+            }
+        }
+        let locked = self.resolve()?;
+        fs::write(&path, render_lock(&locked)).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+        Ok(locked)
+    }
+
+    /// Whether a lockfile still describes this package: every direct dependency is
+    /// locked at its manifest path, every locked directory exists with the locked
+    /// version, and every locked package is still required by something
+    fn is_current(&self, locked: &[LockedPackage]) -> bool {
+        let mut required: Vec<&str> = Vec::new();
+        let mut nested = Vec::new();
+        for dep in &self.manifest.dependencies {
+            if !locked.iter().any(|package| package.name == dep.name && package.path == dep.path) {
+                return false;
+            }
+            required.push(&dep.name);
+        }
+        for package in locked {
+            let dir = self.root.join(&package.path);
+            if !dir.is_dir() {
+                return false;
+            }
+            let version = if dir.join(MANIFEST_FILE).is_file() {
+                match Manifest::load(&dir) {
+                    Ok(manifest) => {
+                        nested.extend(manifest.dependencies.into_iter().map(|dep| dep.name));
+                        Some(manifest.version)
+                    }
+                    Err(_) => return false,
+                }
+            } else {
+                None
+            };
+            if version != package.version {
+                return false;
+            }
+        }
+        required.extend(nested.iter().map(String::as_str));
+        locked.iter().all(|package| required.contains(&package.name.as_str()))
+            && required.iter().all(|name| locked.iter().any(|package| package.name == *name))
+    }
+
+    /// The directories to search for imports, one per locked dependency
+    pub fn search_paths(&self, locked: &[LockedPackage]) -> Vec<PathBuf> {
+        locked.iter().map(|package| self.root.join(&package.path)).collect()
+    }
+
+    /// `ject add --path DIR`: depend on the package or vendored directory at `dir`
+    /// (relative to the working directory), named after its manifest or directory
+    pub fn add_path_dependency(&mut self, dir: &Path) -> Result<Dependency, String> {
+        let canonical = fs::canonicalize(dir)
+            .ok()
+            .filter(|dir| dir.is_dir())
+            .ok_or_else(|| format!("No directory at {}", dir.display()))?;
+        let name = if canonical.join(MANIFEST_FILE).is_file() {
+            Manifest::load(&canonical)?.name
+        } else {
+            let name = canonical.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default();
+            check_package_name(&name)?;
+            name
+        };
+        if name == self.manifest.name {
+            return Err(format!("Package '{}' cannot depend on itself", name));
+        }
+
+        let root = fs::canonicalize(&self.root).map_err(|e| format!("{}: {}", self.root.display(), e))?;
+        let dependency = Dependency { name: name.clone(), path: relative_path(&canonical, &root) };
+        self.write_dependency(&dependency)?;
+        match self.manifest.dependencies.iter_mut().find(|dep| dep.name == name) {
+            Some(existing) => *existing = dependency.clone(),
+            None => self.manifest.dependencies.push(dependency.clone()),
+        }
+        Ok(dependency)
+    }
+
+    /// Set one `[dependencies]` entry in `ject.toml`, editing the file in place so
+    /// comments and keys the manifest doesn't model survive
+    fn write_dependency(&self, dependency: &Dependency) -> Result<(), String> {
+        let path = self.root.join(MANIFEST_FILE);
+        let source = fs::read_to_string(&path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        let mut document: toml_edit::DocumentMut = source.parse()
+            .map_err(|e| format!("{}: Invalid {}: {}", path.display(), MANIFEST_FILE, e))?;
+        let dependencies = document.entry("dependencies")
+            .or_insert(toml_edit::table())
+            .as_table_like_mut()
+            .ok_or_else(|| format!("[dependencies] in {} must be a table", MANIFEST_FILE))?;
+        let mut spec = toml_edit::InlineTable::new();
+        spec.insert("path", path_string(&dependency.path).into());
+        dependencies.insert(&dependency.name, toml_edit::value(spec));
+        fs::write(&path, document.to_string()).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        let path = self.root.join(MANIFEST_FILE);
+        fs::write(&path, self.manifest.to_toml()).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+    }
+}
+
+/// `ject new NAME`: a package directory with a manifest and a hello-world entry
+pub fn create_package(dir: &Path, name: &str) -> Result<Package, String> {
+    check_package_name(name)?;
+    if dir.exists() {
+        return Err(format!("{} already exists", dir.display()));
+    }
+    let manifest = Manifest::new(name);
+    let entry = dir.join(&manifest.entry);
+    if let Some(src) = entry.parent() {
+        fs::create_dir_all(src).map_err(|e| format!("Failed to create {}: {}", src.display(), e))?;
+    }
+    fs::write(&entry, format!("print \"Hello from {}!\"\n", name))
+        .map_err(|e| format!("Failed to write {}: {}", entry.display(), e))?;
+    let package = Package { root: dir.to_path_buf(), manifest };
+    package.save()?;
+    Ok(package)
+}
+
+pub fn render_lock(locked: &[LockedPackage]) -> String {
+    let mut out = String::from("# Generated by ject from ject.toml; do not edit by hand\n");
+    for package in locked {
+        out.push_str(&format!("\n[[package]]\nname = {}\n", quote(&package.name)));
+        if let Some(version) = &package.version {
+            out.push_str(&format!("version = {}\n", quote(version)));
+        }
+        out.push_str(&format!("path = {}\n", quote(&path_string(&package.path))));
+    }
+    out
+}
+
+pub fn parse_lock(source: &str) -> Result<Vec<LockedPackage>, String> {
+    let table: toml::Table = source.parse().map_err(|e| format!("Invalid {}: {}", LOCK_FILE, e))?;
+    let invalid = || format!("{} has a malformed [[package]] entry", LOCK_FILE);
+    let mut locked = Vec::new();
+    if let Some(packages) = table.get("package") {
+        for package in packages.as_array().ok_or_else(invalid)? {
+            let field = |key: &str| package.get(key).and_then(|v| v.as_str()).map(str::to_string);
+            locked.push(LockedPackage {
+                name: field("name").ok_or_else(invalid)?,
+                version: field("version"),
+                path: PathBuf::from(field("path").ok_or_else(invalid)?),
+            });
+        }
+    }
+    Ok(locked)
+}
+
+/// Package names double as TOML keys, so keep them to bare-key characters
+fn check_package_name(name: &str) -> Result<(), String> {
+    let valid = !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+    if valid {
+        Ok(())
+    } else {
+        Err(format!("Invalid package name '{}': use letters, digits, '_' and '-'", name))
+    }
+}
+
+fn quote(s: &str) -> String {
+    toml::Value::String(s.to_string()).to_string()
+}
+
+/// Paths are written with `/` so manifests and lockfiles are portable
+fn path_string(path: &Path) -> String {
+    path.components()
+        .map(|c| c.as_os_str().to_string_lossy().into_owned())
+        .collect::<Vec<_>>()
+        .join("/")
+        .replace("//", "/")
+}
+
+/// Collapse `dir/..` and `.` without touching the filesystem
+fn normalize(path: &Path) -> PathBuf {
+    let mut out = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir if matches!(out.components().next_back(), Some(Component::Normal(_))) => {
+                out.pop();
+            }
+            other => out.push(other),
+        }
+    }
+    out
+}
+
+/// `path` relative to `base`; both are canonical
+fn relative_path(path: &Path, base: &Path) -> PathBuf {
+    let path: Vec<Component> = path.components().collect();
+    let base: Vec<Component> = base.components().collect();
+    let common = path.iter().zip(&base).take_while(|(a, b)| a == b).count();
+    let mut relative = PathBuf::new();
+    for _ in common..base.len() {
+        relative.push("..");
+    }
+    for component in &path[common..] {
+        relative.push(component);
+    }
+    if relative.as_os_str().is_empty() {
+        relative.push(".");
+    }
+    relative
+}
@@ -1,5 +1,5 @@
 use crate::lexer::Token;
-use crate::ast::{Expr, Stmt, BinaryOp, UnaryOp};
+use crate::ast::{Binding, DictEntry, Expr, Stmt, BinaryOp, UnaryOp};
 
 pub struct Parser {
     tokens: Vec<(Token, crate::lexer::SourcePosition)>,
@@ -11,6 +11,7 @@ pub struct ParseError {
     pub message: String,
     pub line: Option<usize>,
     pub column: Option<usize>,
+    pub code: &'static str,  // From the registry in codes.rs
 }
 
 impl std::fmt::Display for ParseError {
@@ -54,6 +55,7 @@ impl Parser {
     fn statement(&mut self) -> ParseResult<Stmt> {
         match &self.peek() {
             Token::Let => self.let_statement(),
+            Token::Const => self.const_statement(),
             Token::Fn => self.function_statement(),
             Token::If => self.if_statement(),
             Token::While => self.while_statement(),
@@ -97,25 +99,40 @@ impl Parser {
                             if let Expr::Identifier(obj_name) = *object {
                                 crate::ast::AssignTarget::Index { object: obj_name, index }
                             } else {
-                                return Err(self.error("Left side of index assignment must be a variable (e.g., arr[i] = x)".to_string()));
+                                return Err(self.error_with_code("E0004", "Left side of index assignment must be a variable (e.g., arr[i] = x)".to_string()));
                             }
                         }
                         Expr::StructAccess { object, field } => {
                             if let Expr::Identifier(obj_name) = *object {
                                 crate::ast::AssignTarget::Field { object: obj_name, field }
                             } else {
-                                return Err(self.error("Left side of field assignment must be a variable (e.g., obj.field = x)".to_string()));
+                                return Err(self.error_with_code("E0004", "Left side of field assignment must be a variable (e.g., obj.field = x)".to_string()));
                             }
                         }
                         Expr::Member { object, property } => {
                             if let Expr::Identifier(obj_name) = *object {
                                 crate::ast::AssignTarget::Field { object: obj_name, field: property }
                             } else {
-                                return Err(self.error("Left side of field assignment must be a variable (e.g., obj.field = x)".to_string()));
+                                return Err(self.error_with_code("E0004", "Left side of field assignment must be a variable (e.g., obj.field = x)".to_string()));
+                            }
+                        }
+                        Expr::Slice { object, from, to, step } => {
+                            if let Expr::Identifier(obj_name) = *object {
+                                let indices = vec![crate::ast::IndexComponent::Slice { from, to, step }];
+                                crate::ast::AssignTarget::Subscript { object: obj_name, indices }
+                            } else {
+                                return Err(self.error_with_code("E0004", "Left side of slice assignment must be a variable (e.g., a[0:2] = x)".to_string()));
+                            }
+                        }
+                        Expr::MultiIndex { object, indices } => {
+                            if let Expr::Identifier(obj_name) = *object {
+                                crate::ast::AssignTarget::Subscript { object: obj_name, indices }
+                            } else {
+                                return Err(self.error_with_code("E0004", "Left side of index assignment must be a variable (e.g., a[i, j] = x)".to_string()));
                             }
                         }
                         _ => {
-                            return Err(self.error("Invalid assignment target.".to_string()));
+                            return Err(self.error_with_code("E0004", "Invalid assignment target.".to_string()));
                         }
                     };
 
@@ -137,6 +154,12 @@ impl Parser {
                                     field: field.clone(),
                                 }
                             }
+                            crate::ast::AssignTarget::Subscript { object, indices } => {
+                                Expr::MultiIndex {
+                                    object: Box::new(Expr::Identifier(object.clone())),
+                                    indices: indices.clone(),
+                                }
+                            }
                         };
                         Expr::Binary {
                             left: Box::new(target_expr),
@@ -160,16 +183,87 @@ impl Parser {
     fn let_statement(&mut self) -> ParseResult<Stmt> {
         self.consume(Token::Let, "Expected 'let'")?;
         
-        let name = if let Token::Identifier(name) = self.advance() {
-            name
-        } else {
-            return Err(self.error("Expected identifier after 'let'".to_string()));
-        };
+        let binding = self.binding("Expected identifier after 'let'")?;
         
         self.consume(Token::Equal, "Expected '=' after variable name")?;
         let value = self.expression()?;
         
-        Ok(Stmt::Let { name, value })
+        Ok(Stmt::Let { binding, value })
+    }
+
+    fn const_statement(&mut self) -> ParseResult<Stmt> {
+        self.consume(Token::Const, "Expected 'const'")?;
+        
+        let name = match self.advance() {
+            Token::Identifier(name) => name,
+            _ => return Err(self.error("Expected identifier after 'const'".to_string())),
+        };
+        
+        self.consume(Token::Equal, "Expected '=' after constant name")?;
+        let value = self.expression()?;
+        
+        Ok(Stmt::Const { name, value })
+    }
+
+    /// Target of `let` and `for`: a name, (q, r), [head, ..tail] or {name, age: years}
+    fn binding(&mut self, message: &str) -> ParseResult<Binding> {
+        match self.advance() {
+            Token::Identifier(name) => Ok(Binding::Name(name)),
+            Token::LeftParen => {
+                let mut items = Vec::new();
+                while !self.check(&Token::RightParen) {
+                    items.push(self.binding("Expected a name in tuple pattern")?);
+                    if !self.match_token(&Token::Comma) {
+                        break;
+                    }
+                }
+                self.consume(Token::RightParen, "Expected ')' after tuple pattern")?;
+                Ok(Binding::Tuple(items))
+            }
+            Token::LeftBracket => {
+                let mut items = Vec::new();
+                let mut rest = None;
+                while !self.check(&Token::RightBracket) {
+                    if self.match_token(&Token::DotDot) || self.match_token(&Token::Ellipsis) {
+                        rest = match self.advance() {
+                            Token::Identifier(name) => Some(name),
+                            _ => return Err(self.error_with_code("E0006", "Expected a name after '..' in array pattern".to_string())),
+                        };
+                        if !self.check(&Token::RightBracket) {
+                            return Err(self.error_with_code("E0006", "'..rest' must be the last item in an array pattern".to_string()));
+                        }
+                        break;
+                    }
+                    items.push(self.binding("Expected a name in array pattern")?);
+                    if !self.match_token(&Token::Comma) {
+                        break;
+                    }
+                }
+                self.consume(Token::RightBracket, "Expected ']' after array pattern")?;
+                Ok(Binding::Array { items, rest })
+            }
+            Token::LeftBrace => {
+                let mut entries = Vec::new();
+                while !self.check(&Token::RightBrace) {
+                    let key = match self.advance() {
+                        Token::Identifier(key) | Token::String(key) => key,
+                        _ => return Err(self.error_with_code("E0006", "Expected a key name in dictionary pattern".to_string())),
+                    };
+                    let binding = if self.match_token(&Token::Colon) {
+                        self.binding("Expected a name after ':' in dictionary pattern")?
+                    } else {
+                        Binding::Name(key.clone())
+                    };
+                    entries.push((key, binding));
+                    if !self.match_token(&Token::Comma) {
+                        break;
+                    }
+                }
+                self.consume(Token::RightBrace, "Expected '}' after dictionary pattern")?;
+                Ok(Binding::Dictionary(entries))
+            }
+            _ => Err(self.error(message.to_string())),
+        }
     }
     
     fn function_statement(&mut self) -> ParseResult<Stmt> {
@@ -183,12 +277,25 @@ impl Parser {
         
         self.consume(Token::LeftParen, "Expected '(' after function name")?;
         
+        let params = self.parameter_list()?;
+        
+        // Skip optional newlines before body
+        while self.match_token(&Token::Newline) {}
+        
+        let body = self.block()?;
+        
+        Ok(Stmt::Function { name, params, body })
+    }
+    
+    /// Parameters up to and including ')': `a, b=1, ...rest`
+    fn parameter_list(&mut self) -> ParseResult<Vec<crate::ast::Parameter>> {
         let mut params = Vec::new();
         if !self.check(&Token::RightParen) {
             loop {
+                let variadic = self.match_token(&Token::Ellipsis);
                 if let Token::Identifier(param_name) = self.advance() {
                     // Check for default value
-                    let default_value = if self.match_token(&Token::Equal) {
+                    let default_value = if !variadic && self.match_token(&Token::Equal) {
                         Some(self.expression()?)
                     } else {
                         None
@@ -197,27 +304,25 @@ impl Parser {
                     params.push(crate::ast::Parameter {
                         name: param_name,
                         default_value,
+                        variadic,
                     });
                 } else {
-                    return Err(self.error("Expected parameter name".to_string()));
+                    return Err(self.error_with_code("E0007", "Expected parameter name".to_string()));
                 }
                 
                 if !self.match_token(&Token::Comma) {
                     break;
                 }
+                if variadic {
+                    return Err(self.error_with_code("E0007", "A '...' parameter must be the last parameter".to_string()));
+                }
             }
         }
         
         self.consume(Token::RightParen, "Expected ')' after parameters")?;
-        
-        // Skip optional newlines before body
-        while self.match_token(&Token::Newline) {}
-        
-        let body = self.block()?;
-        
-        Ok(Stmt::Function { name, params, body })
+        Ok(params)
     }
-    
+
     fn if_statement(&mut self) -> ParseResult<Stmt> {
         self.consume(Token::If, "Expected 'if'")?;
         let condition = self.expression()?;
@@ -341,7 +446,7 @@ impl Parser {
         if needs_end_token {
             // Check if we're at the end of file or encountered a token that shouldn't be here
             if self.is_at_end() {
-                return Err(self.error("Expected 'end' to close if statement but reached end of file".to_string()));
+                return Err(self.error_with_code("E0003", "Expected 'end' to close if statement but reached end of file".to_string()));
             }
             
             // Provide a better error message based on what we found instead of 'end'
@@ -352,7 +457,7 @@ impl Parser {
                     Token::Identifier(_) => "Expected 'end' to close if statement, but found another statement. Did you forget an 'end'?".to_string(),
                     _ => parse_error.message,
                 };
-                return Err(self.error(better_message));
+                return Err(self.error_with_code("E0003", better_message));
             }
         }
         
@@ -382,11 +487,7 @@ impl Parser {
     fn for_statement(&mut self) -> ParseResult<Stmt> {
         self.consume(Token::For, "Expected 'for'")?;
         
-        let var = if let Token::Identifier(name) = self.advance() {
-            name
-        } else {
-            return Err(self.error("Expected variable name in for loop".to_string()));
-        };
+        let binding = self.binding("Expected variable name in for loop")?;
         
         self.consume(Token::In, "Expected 'in' after for variable")?;
         let iterable = self.expression()?;
@@ -399,7 +500,7 @@ impl Parser {
         
         let body = self.block()?;
         
-        Ok(Stmt::For { var, iterable, body })
+        Ok(Stmt::For { binding, iterable, body })
     }
     
     fn return_statement(&mut self) -> ParseResult<Stmt> {
@@ -408,7 +509,17 @@ impl Parser {
         let value = if self.check(&Token::Newline) || self.check(&Token::End) || self.is_at_end() {
             None
         } else {
-            Some(self.expression()?)
+            let first = self.expression()?;
+            if self.check(&Token::Comma) {
+                // return a, b - several values come back as a tuple
+                let mut values = vec![first];
+                while self.match_token(&Token::Comma) {
+                    values.push(self.expression()?);
+                }
+                Some(Expr::Tuple(values))
+            } else {
+                Some(first)
+            }
         };
         
         Ok(Stmt::Return(value))
@@ -489,25 +600,25 @@ impl Parser {
                 if let Expr::Identifier(obj_name) = *object {
                     crate::ast::AssignTarget::Index { object: obj_name, index }
                 } else {
-                    return Err(self.error("Left side of index assignment must be a variable (e.g., arr[i] = x)".to_string()));
+                    return Err(self.error_with_code("E0004", "Left side of index assignment must be a variable (e.g., arr[i] = x)".to_string()));
                 }
             }
             Expr::StructAccess { object, field } => {
                 if let Expr::Identifier(obj_name) = *object {
                     crate::ast::AssignTarget::Field { object: obj_name, field }
                 } else {
-                    return Err(self.error("Left side of field assignment must be a variable (e.g., obj.field = x)".to_string()));
+                    return Err(self.error_with_code("E0004", "Left side of field assignment must be a variable (e.g., obj.field = x)".to_string()));
                 }
             }
             Expr::Member { object, property } => {
                 if let Expr::Identifier(obj_name) = *object {
                     crate::ast::AssignTarget::Field { object: obj_name, field: property }
                 } else {
-                    return Err(self.error("Left side of field assignment must be a variable (e.g., obj.field = x)".to_string()));
+                    return Err(self.error_with_code("E0004", "Left side of field assignment must be a variable (e.g., obj.field = x)".to_string()));
                 }
             }
             _ => {
-                return Err(self.error(format!(
+                return Err(self.error_with_code("E0004", format!(
                     "Invalid assignment target. Can only assign to variables, array indices, or object fields."
                 )));
             }
@@ -528,7 +639,7 @@ impl Parser {
                     if let Token::Identifier(item) = self.advance() {
                         items.push(item);
                     } else {
-                return Err(self.error("Expected identifier in import list".to_string()));
+                return Err(self.error_with_code("E0008", "Expected identifier in import list".to_string()));
                     }
                     
                     if !self.match_token(&Token::Comma) {
@@ -543,7 +654,7 @@ impl Parser {
             let module_path = if let Token::String(path) = self.advance() {
                 path
             } else {
-                return Err(self.error("Expected string after 'from'".to_string()));
+                return Err(self.error_with_code("E0008", "Expected string after 'from'".to_string()));
             };
             
             (module_path, Some(items), None)
@@ -552,14 +663,14 @@ impl Parser {
             let module_path = if let Token::String(path) = self.advance() {
                 path
             } else {
-                return Err(self.error("Expected module path string after 'import'".to_string()));
+                return Err(self.error_with_code("E0008", "Expected module path string after 'import'".to_string()));
             };
             
             let alias = if self.match_token(&Token::As) {
                 if let Token::Identifier(alias_name) = self.advance() {
                     Some(alias_name)
                 } else {
-                return Err(self.error("Expected identifier after 'as'".to_string()));
+                return Err(self.error_with_code("E0008", "Expected identifier after 'as'".to_string()));
                 }
             } else {
                 None
@@ -573,43 +684,23 @@ impl Parser {
     
     fn export_statement(&mut self) -> ParseResult<Stmt> {
         self.consume(Token::Export, "Expected 'export'")?;
+
+        // Re-exports: export {a, b} from "./sub" and export * from "./sub"
+        if self.check(&Token::LeftBrace) || self.check(&Token::Star) {
+            return self.export_from();
+        }
         
         // Check if this is "export fn"
         if self.match_token(&Token::Fn) {
             let name = if let Token::Identifier(name) = self.advance() {
                 name
             } else {
-                return Err(self.error("Expected function name after 'export fn'".to_string()));
+                return Err(self.error_with_code("E0008", "Expected function name after 'export fn'".to_string()));
             };
             
             self.consume(Token::LeftParen, "Expected '(' after function name")?;
             
-            let mut params = Vec::new();
-            if !self.check(&Token::RightParen) {
-                loop {
-                    if let Token::Identifier(param_name) = self.advance() {
-                        // Check for default value
-                        let default_value = if self.match_token(&Token::Equal) {
-                            Some(self.expression()?)
-                        } else {
-                            None
-                        };
-                        
-                        params.push(crate::ast::Parameter {
-                            name: param_name,
-                            default_value,
-                        });
-                    } else {
-                        return Err(self.error("Expected parameter name".to_string()));
-                    }
-                    
-                    if !self.match_token(&Token::Comma) {
-                        break;
-                    }
-                }
-            }
-            
-            self.consume(Token::RightParen, "Expected ')' after parameters")?;
+            let params = self.parameter_list()?;
             
             // Skip optional newlines before body
             while self.match_token(&Token::Newline) {}
@@ -623,7 +714,7 @@ impl Parser {
         let name = if let Token::Identifier(name) = self.advance() {
             name
         } else {
-            return Err(self.error("Expected identifier after 'export'".to_string()));
+            return Err(self.error_with_code("E0008", "Expected identifier after 'export'".to_string()));
         };
         
         self.consume(Token::Equal, "Expected '=' after export name")?;
@@ -632,6 +723,38 @@ impl Parser {
         Ok(Stmt::Export { name, value })
     }
     
+    fn export_from(&mut self) -> ParseResult<Stmt> {
+        let items = if self.match_token(&Token::Star) {
+            None
+        } else {
+            self.consume(Token::LeftBrace, "Expected '{' or '*' after 'export'")?;
+            let mut items = Vec::new();
+            if !self.check(&Token::RightBrace) {
+                loop {
+                    if let Token::Identifier(item) = self.advance() {
+                        items.push(item);
+                    } else {
+                        return Err(self.error_with_code("E0008", "Expected identifier in export list".to_string()));
+                    }
+                    if !self.match_token(&Token::Comma) {
+                        break;
+                    }
+                }
+            }
+            self.consume(Token::RightBrace, "Expected '}' after export list")?;
+            Some(items)
+        };
+
+        self.consume(Token::From, "Expected 'from' after export list")?;
+        let module_path = if let Token::String(path) = self.advance() {
+            path
+        } else {
+            return Err(self.error_with_code("E0008", "Expected string after 'from'".to_string()));
+        };
+
+        Ok(Stmt::ExportFrom { module_path, items })
+    }
+
     fn struct_statement(&mut self) -> ParseResult<Stmt> {
         self.consume(Token::Struct, "Expected 'struct'")?;
         
@@ -757,7 +880,7 @@ impl Parser {
             
             // Before parsing the next statement, check for patterns that suggest missing 'end'
             if self.is_likely_missing_end(&statements) {
-                return Err(self.error("Missing 'end' keyword for if statement".to_string()));
+                return Err(self.error_with_code("E0003", "Missing 'end' keyword for if statement".to_string()));
             }
             
             let stmt = self.statement()?;
@@ -859,10 +982,10 @@ impl Parser {
     }
     
     fn and(&mut self) -> ParseResult<Expr> {
-        let mut expr = self.range()?;
+        let mut expr = self.equality()?;
         
         while self.match_token(&Token::And) {
-            let right = self.range()?;
+            let right = self.equality()?;
             expr = Expr::Binary {
                 left: Box::new(expr),
                 operator: BinaryOp::And,
@@ -873,34 +996,12 @@ impl Parser {
         Ok(expr)
     }
     
-    fn range(&mut self) -> ParseResult<Expr> {
-        let mut expr = self.equality()?;
-        
-        if self.match_token(&Token::DotDot) {
-            let end = self.equality()?;
-            
-            // Check for optional step with colon syntax
-            let step = if self.match_token(&Token::Colon) {
-                Some(Box::new(self.equality()?))
-            } else {
-                None
-            };
-            
-            expr = Expr::Range {
-                start: Box::new(expr),
-                end: Box::new(end),
-                step,
-            };
-        }
-        
-        Ok(expr)
-    }
-    
     fn equality(&mut self) -> ParseResult<Expr> {
-        let mut expr = self.comparison()?;
+        // Ranges bind tighter than == so `0..3 == [0, 1, 2]` compares the range
+        let mut expr = self.range()?;
         
         while let Some(op) = self.match_equality_op() {
-            let right = self.comparison()?;
+            let right = self.range()?;
             expr = Expr::Binary {
                 left: Box::new(expr),
                 operator: op,
@@ -911,11 +1012,44 @@ impl Parser {
         Ok(expr)
     }
     
+    fn range(&mut self) -> ParseResult<Expr> {
+        let expr = self.comparison()?;
+        self.range_rest(expr, Self::comparison)
+    }
+    
+    /// The `..end`, `..=end` and step parts of a range whose start is parsed,
+    /// with the end and step parsed by `bound`
+    fn range_rest(&mut self, start: Expr, bound: fn(&mut Self) -> ParseResult<Expr>) -> ParseResult<Expr> {
+        let inclusive = self.check(&Token::DotDotEq);
+        if !self.match_token(&Token::DotDot) && !self.match_token(&Token::DotDotEq) {
+            return Ok(start);
+        }
+        let end = bound(self)?;
+        
+        // Optional step: `a..b by 2`, or the older colon syntax `a..b:2`
+        let step = if self.match_token(&Token::Colon) || self.match_word("by") {
+            Some(Box::new(bound(self)?))
+        } else {
+            None
+        };
+        
+        Ok(Expr::Range {
+            start: Box::new(start),
+            end: Box::new(end),
+            step,
+            inclusive,
+        })
+    }
+    
     fn comparison(&mut self) -> ParseResult<Expr> {
         let mut expr = self.term()?;
         
         while let Some(op) = self.match_comparison_op() {
-            let right = self.term()?;
+            let mut right = self.term()?;
+            // `x in 0..10` tests membership in the whole range
+            if matches!(op, BinaryOp::In) {
+                right = self.range_rest(right, Self::term)?;
+            }
             expr = Expr::Binary {
                 left: Box::new(expr),
                 operator: op,
@@ -1031,6 +1165,60 @@ impl Parser {
         Ok(expr)
     }
 
+    /// Look ahead (without consuming) for a comma directly inside the current brackets
+    fn has_top_level_comma_in_brackets(&self) -> bool {
+        let mut depth = 0usize;
+        for (token, _) in &self.tokens[self.current..] {
+            match token {
+                Token::LeftParen | Token::LeftBracket | Token::LeftBrace | Token::LeftBracePipe => depth += 1,
+                Token::RightParen | Token::RightBrace | Token::RightPipeBrace => depth = depth.saturating_sub(1),
+                Token::RightBracket if depth == 0 => return false,
+                Token::RightBracket => depth -= 1,
+                Token::Comma if depth == 0 => return true,
+                Token::Eof => return false,
+                _ => {}
+            }
+        }
+        false
+    }
+
+    fn parse_multi_index(&mut self, object: Expr) -> ParseResult<Expr> {
+        let mut indices = Vec::new();
+        loop {
+            indices.push(self.parse_index_component()?);
+            if !self.match_token(&Token::Comma) {
+                break;
+            }
+        }
+        self.consume(Token::RightBracket, "Expected ']' after index")?;
+        Ok(Expr::MultiIndex {
+            object: Box::new(object),
+            indices,
+        })
+    }
+
+    /// One part of a multi-dimensional subscript: `i`, `:`, `a:b`, `a:b:c`, `::c`
+    fn parse_index_component(&mut self) -> ParseResult<crate::ast::IndexComponent> {
+        let ends_part = |p: &Self| p.check(&Token::Comma) || p.check(&Token::RightBracket);
+
+        let start = if self.check(&Token::Colon) { None } else { Some(Box::new(self.expression()?)) };
+        if !self.match_token(&Token::Colon) {
+            // No colon: a plain index
+            return match start {
+                Some(expr) => Ok(crate::ast::IndexComponent::Index(*expr)),
+                None => Err(self.error_with_code("E0005", "Expected index".to_string())),
+            };
+        }
+
+        let stop = if ends_part(self) || self.check(&Token::Colon) { None } else { Some(Box::new(self.expression()?)) };
+        let step = if self.match_token(&Token::Colon) && !ends_part(self) {
+            Some(Box::new(self.expression()?))
+        } else {
+            None
+        };
+        Ok(crate::ast::IndexComponent::Slice { from: start, to: stop, step })
+    }
+
     fn parse_index_or_slice(&mut self, object: Expr) -> ParseResult<Expr> {
         // Check what's inside the brackets
         // Could be: simple index, range (..), or slice with named params (from:/to:/step:)
@@ -1038,9 +1226,14 @@ impl Parser {
         // Check for empty brackets []
         if self.check(&Token::RightBracket) {
             self.advance(); // consume ]
-            return Err(self.error("Empty brackets are not allowed. Use array methods instead.".to_string()));
+            return Err(self.error_with_code("E0005", "Empty brackets are not allowed. Use array methods instead.".to_string()));
         }
         
+        // Multi-dimensional subscript: a[1, :], a[0:2, ::-1]
+        if self.has_top_level_comma_in_brackets() {
+            return self.parse_multi_index(object);
+        }
+
         // Save current position to look ahead
         let start_pos = self.current;
         
@@ -1059,7 +1252,7 @@ impl Parser {
                 // Skip whitespace/newlines
                 while self.match_token(&Token::Newline) {}
             } else {
-                return Err(self.error("Expected ':' after 'from' in slice".to_string()));
+                return Err(self.error_with_code("E0005", "Expected ':' after 'from' in slice".to_string()));
             }
         }
         
@@ -1072,7 +1265,7 @@ impl Parser {
                 // Skip whitespace/newlines
                 while self.match_token(&Token::Newline) {}
             } else {
-                return Err(self.error("Expected ':' after 'to' in slice".to_string()));
+                return Err(self.error_with_code("E0005", "Expected ':' after 'to' in slice".to_string()));
             }
         }
         
@@ -1086,7 +1279,7 @@ impl Parser {
                     // Skip whitespace/newlines
                     while self.match_token(&Token::Newline) {}
                 } else {
-                    return Err(self.error("Expected ':' after 'step' in slice".to_string()));
+                    return Err(self.error_with_code("E0005", "Expected ':' after 'step' in slice".to_string()));
                 }
             }
         }
@@ -1116,15 +1309,22 @@ impl Parser {
         let mut found_colon_before_dotdot = false;
         let mut found_any_colon = false;
         
-        while pos < self.tokens.len() && !matches!(self.tokens[pos].0, Token::RightBracket) {
-            if matches!(self.tokens[pos].0, Token::DotDot) {
-                found_dotdot = true;
-            }
-            if matches!(self.tokens[pos].0, Token::Colon) {
-                found_any_colon = true;
-                if !found_dotdot {
-                    found_colon_before_dotdot = true;
+        // Only look at this bracket's level, so colons in nested dictionaries or
+        // struct literals (d[new Point { x: 1 }]) don't look like slices
+        let mut depth = 0;
+        while pos < self.tokens.len() {
+            match self.tokens[pos].0 {
+                Token::LeftBracket | Token::LeftParen | Token::LeftBrace | Token::LeftBracePipe => depth += 1,
+                Token::RightBracket if depth == 0 => break,
+                Token::RightBracket | Token::RightParen | Token::RightBrace | Token::RightPipeBrace => depth -= 1,
+                Token::DotDot | Token::DotDotEq if depth == 0 => found_dotdot = true,
+                Token::Colon if depth == 0 => {
+                    found_any_colon = true;
+                    if !found_dotdot {
+                        found_colon_before_dotdot = true;
+                    }
                 }
+                _ => {}
             }
             pos += 1;
         }
@@ -1151,7 +1351,7 @@ impl Parser {
             // Expect first colon
             if !self.match_token(&Token::Colon) {
                 // No colon found, this shouldn't happen if we got here
-                return Err(self.error("Expected ':' in slice".to_string()));
+                return Err(self.error_with_code("E0005", "Expected ':' in slice".to_string()));
             }
             
             // Parse second part (between first and second colon, or after second colon)
@@ -1192,8 +1392,9 @@ impl Parser {
         // Check for range syntax: start..end:step
         let expr = self.expression()?;
 
-        // Check if this is a range expression
-        if let Expr::Range { start, end, step } = expr {
+        // Check if this is a range expression; an inclusive range stays an index by
+        // a range value, which the interpreter resolves to the same elements
+        if let Expr::Range { start, end, step, inclusive: false } = expr {
             self.consume(Token::RightBracket, "Expected ']' after slice")?;
             return Ok(Expr::Slice {
                 object: Box::new(object),
@@ -1216,8 +1417,12 @@ impl Parser {
         
         if !self.check(&Token::RightParen) {
             loop {
-                // Check for keyword argument (identifier followed by =)
-                if let Token::Identifier(name) = &self.peek() {
+                if self.match_token(&Token::Ellipsis) {
+                    args.push(crate::ast::Argument::Spread(self.expression()?));
+                } else if self.match_token(&Token::StarStar) {
+                    args.push(crate::ast::Argument::KeywordSpread(self.expression()?));
+                } else if let Token::Identifier(name) = &self.peek() {
+                    // Check for keyword argument (identifier followed by =)
                     if self.peek_ahead(1).map(|t| matches!(t, Token::Equal)).unwrap_or(false) {
                         // This is a keyword argument
                         let param_name = if let Token::Identifier(name) = self.advance() {
@@ -1257,6 +1462,9 @@ impl Parser {
     }
     
     fn primary(&mut self) -> ParseResult<Expr> {
+        if let Token::Invalid(message) = self.peek() {
+            return Err(self.error(message));
+        }
         match self.advance() {
             Token::Match => self.match_expression(),
             Token::If => self.conditional_expression(),
@@ -1267,14 +1475,11 @@ impl Parser {
             Token::Nil => Ok(Expr::Nil),
             Token::Integer(n) => Ok(Expr::Integer(n)),
             Token::Float(n) => Ok(Expr::Float(n)),
+            Token::Decimal(d) => Ok(Expr::Decimal(d)),
             Token::String(s) => Ok(Expr::String(s)),
             Token::InterpolatedString(parts) => Ok(Expr::InterpolatedString(parts)),
             Token::Identifier(name) => Ok(Expr::Identifier(name)),
-            Token::LeftParen => {
-                let expr = self.expression()?;
-                self.consume(Token::RightParen, "Expected ')' after expression")?;
-                Ok(expr)
-            }
+            Token::LeftParen => self.parenthesized(),
             Token::LeftBracket => {
                 // Check if this is a list comprehension: [expr for var in iterable]
                 // Look ahead to see if we have 'for' keyword after first expression
@@ -1332,7 +1537,11 @@ impl Parser {
 
                 if !self.check(&Token::RightBracket) {
                     loop {
-                        elements.push(self.expression()?);
+                        if self.match_token(&Token::Ellipsis) {
+                            elements.push(Expr::Spread(Box::new(self.expression()?)));
+                        } else {
+                            elements.push(self.expression()?);
+                        }
                         if !self.match_token(&Token::Comma) {
                             break;
                         }
@@ -1365,37 +1574,82 @@ impl Parser {
                 Ok(Expr::UniqueArray(elements))
             }
             Token::LeftBrace => {
-                let mut pairs = Vec::new();
-                
+                let mut entries = Vec::new();
+
+                // Entries may be written one per line, as struct fields can
+                while self.match_token(&Token::Newline) {}
+
                 if !self.check(&Token::RightBrace) {
                     loop {
-                        // Parse key: value pairs
+                        if self.match_token(&Token::Ellipsis) {
+                            entries.push(DictEntry::Spread(self.expression()?));
+                            while self.match_token(&Token::Newline) {}
+                            if !self.match_token(&Token::Comma) {
+                                break;
+                            }
+                            while self.match_token(&Token::Newline) {}
+                            continue;
+                        }
+
+                        // Parse key: value pairs; [expr] computes the key
                         let key = match self.advance() {
-                            Token::Identifier(name) => name,
-                            Token::String(s) => s,
+                            Token::Identifier(name) => Expr::String(name),
+                            Token::String(s) => Expr::String(s),
+                            Token::Integer(n) => Expr::Integer(n),
+                            Token::True => Expr::Bool(true),
+                            Token::False => Expr::Bool(false),
+                            Token::LeftBracket => {
+                                let key = self.expression()?;
+                                self.consume(Token::RightBracket, "Expected ']' after computed dictionary key")?;
+                                key
+                            }
+                            Token::LeftParen => self.parenthesized()?,
                             _ => {
-                                return Err(self.error("Expected string or identifier as dictionary key".to_string()));
+                                return Err(self.error("Expected string, identifier, int, bool, tuple or [expression] as dictionary key".to_string()));
                             }
                         };
                         
                         self.consume(Token::Colon, "Expected ':' after dictionary key")?;
                         let value = self.expression()?;
-                        pairs.push((key, value));
+                        entries.push(DictEntry::Pair(key, value));
                         
+                        while self.match_token(&Token::Newline) {}
                         if !self.match_token(&Token::Comma) {
                             break;
                         }
+                        while self.match_token(&Token::Newline) {}
                     }
                 }
                 
                 self.consume(Token::RightBrace, "Expected '}' after dictionary elements")?;
-                Ok(Expr::Dictionary(pairs))
+                Ok(Expr::Dictionary(entries))
             }
             token => Err(self.error(format!("Unexpected token: {:?}", token))),
         }
     }
     
     // Helper methods
+    /// After '(': a grouped expression, or a tuple when there is a comma - (a, b), (a,), ()
+    fn parenthesized(&mut self) -> ParseResult<Expr> {
+        if self.match_token(&Token::RightParen) {
+            return Ok(Expr::Tuple(Vec::new()));
+        }
+        let expr = self.expression()?;
+        if self.match_token(&Token::Comma) {
+            let mut elements = vec![expr];
+            while !self.check(&Token::RightParen) {
+                elements.push(self.expression()?);
+                if !self.match_token(&Token::Comma) {
+                    break;
+                }
+            }
+            self.consume(Token::RightParen, "Expected ')' after tuple")?;
+            return Ok(Expr::Tuple(elements));
+        }
+        self.consume(Token::RightParen, "Expected ')' after expression")?;
+        Ok(expr)
+    }
+
     fn lambda_expression(&mut self) -> ParseResult<Expr> {
         self.consume(Token::LeftParen, "Expected '(' after 'fn'")?;
         let mut params = Vec::new();
@@ -1404,7 +1658,7 @@ impl Parser {
                 if let Token::Identifier(name) = self.advance() {
                     params.push(name);
                 } else {
-                    return Err(self.error("Expected parameter name".to_string()));
+                    return Err(self.error_with_code("E0007", "Expected parameter name".to_string()));
                 }
                 if !self.match_token(&Token::Comma) {
                     break;
@@ -1576,9 +1830,13 @@ impl Parser {
     }
     
     fn parse_pattern(&mut self) -> ParseResult<crate::ast::Pattern> {
+        if let Token::Invalid(message) = self.peek() {
+            return Err(self.error(message));
+        }
         match self.advance() {
             Token::Integer(n) => Ok(crate::ast::Pattern::Literal(Expr::Integer(n))),
             Token::Float(f) => Ok(crate::ast::Pattern::Literal(Expr::Float(f))),
+            Token::Decimal(d) => Ok(crate::ast::Pattern::Literal(Expr::Decimal(d))),
             Token::String(s) => Ok(crate::ast::Pattern::Literal(Expr::String(s))),
             Token::True => Ok(crate::ast::Pattern::Literal(Expr::Bool(true))),
             Token::False => Ok(crate::ast::Pattern::Literal(Expr::Bool(false))),
@@ -1590,7 +1848,7 @@ impl Parser {
                     Ok(crate::ast::Pattern::Identifier(name))
                 }
             }
-            token => Err(self.error(format!("Unexpected token in pattern: {:?}", token))),
+            token => Err(self.error_with_code("E0006", format!("Unexpected token in pattern: {:?}", token))),
         }
     }
     
@@ -1661,6 +1919,16 @@ impl Parser {
         }
     }
     
+    /// Consume an identifier that acts as a keyword only in one place (`by` in ranges)
+    fn match_word(&mut self, word: &str) -> bool {
+        if matches!(&self.peek(), Token::Identifier(name) if name == word) {
+            self.advance();
+            true
+        } else {
+            false
+        }
+    }
+    
     fn check(&self, token: &Token) -> bool {
         if self.is_at_end() {
             false
@@ -1702,12 +1970,18 @@ impl Parser {
         }
     }
     
+    /// A syntax error (E0002) at the current token
     fn error(&self, message: String) -> ParseError {
+        self.error_with_code("E0002", message)
+    }
+
+    fn error_with_code(&self, code: &'static str, message: String) -> ParseError {
         let pos = self.current_position();
         ParseError {
             message,
             line: Some(pos.line),
             column: Some(pos.column),
+            code,
         }
     }
     
@@ -1715,7 +1989,8 @@ impl Parser {
         if self.check(&token) {
             Ok(self.advance())
         } else {
-            Err(self.error(format!("{} but got {:?}", message, self.peek())))
+            let code = if token == Token::End { "E0003" } else { "E0002" };
+            Err(self.error_with_code(code, format!("{} but got {:?}", message, self.peek())))
         }
     }
 }
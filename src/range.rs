@@ -0,0 +1,216 @@
+//! Lazy ranges for Ject (`a..b`, `a..=b`, `a..b by step`, `range()`)
+//! A range stores only its bounds and step; elements are computed on demand,
+//! so iterating, indexing, slicing and membership tests never build an array
+
+use crate::interpreter::RuntimeError;
+use crate::value::Value;
+use std::fmt;
+
+/// Slack for float ranges, so `0..1 by 0.1` stops at 0.9 despite rounding
+const FLOAT_TOLERANCE: f64 = 1e-9;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Range {
+    Int { start: i64, end: i64, step: i64, inclusive: bool },
+    Float { start: f64, end: f64, step: f64, inclusive: bool },
+}
+
+impl Range {
+    /// Build a range from evaluated bounds; any float bound or step makes a float range
+    pub fn new(start: &Value, end: &Value, step: Option<&Value>, inclusive: bool) -> Result<Range, RuntimeError> {
+        let step = step.unwrap_or(&Value::Integer(1));
+        match (start, end, step) {
+            (Value::Integer(start), Value::Integer(end), Value::Integer(step)) => {
+                if *step == 0 {
+                    return Err(RuntimeError { message: "Range step cannot be zero".to_string() });
+                }
+                Ok(Range::Int { start: *start, end: *end, step: *step, inclusive })
+            }
+            _ => {
+                let number = |value: &Value, what: &str| match value {
+                    Value::Integer(n) => Ok(*n as f64),
+                    Value::Float(f) if f.is_finite() => Ok(*f),
+                    Value::Float(_) => Err(RuntimeError { message: format!("Range {} must be finite", what) }),
+                    other => Err(RuntimeError {
+                        message: format!("Range {} must be a number, got {}", what, other.type_name()),
+                    }),
+                };
+                let (start, end, step) = (number(start, "start")?, number(end, "end")?, number(step, "step")?);
+                if step == 0.0 {
+                    return Err(RuntimeError { message: "Range step cannot be zero".to_string() });
+                }
+                Ok(Range::Float { start, end, step, inclusive })
+            }
+        }
+    }
+
+    /// Number of elements, computed from the bounds
+    pub fn len(&self) -> usize {
+        match *self {
+            Range::Int { start, end, step, inclusive } => {
+                let (start, end, step) = (start as i128, end as i128, step as i128);
+                // Distance to travel in the direction of the step
+                let distance = if step > 0 { end - start } else { start - end };
+                let step = step.abs();
+                let count = if inclusive {
+                    if distance < 0 { 0 } else { distance / step + 1 }
+                } else if distance <= 0 {
+                    0
+                } else {
+                    (distance + step - 1) / step
+                };
+                count.min(usize::MAX as i128) as usize
+            }
+            Range::Float { start, end, step, inclusive } => {
+                let steps = (end - start) / step;
+                let count = if inclusive {
+                    if steps < -FLOAT_TOLERANCE { 0.0 } else { (steps + FLOAT_TOLERANCE).floor() + 1.0 }
+                } else if steps <= FLOAT_TOLERANCE {
+                    0.0
+                } else {
+                    (steps - FLOAT_TOLERANCE).ceil()
+                };
+                count as usize
+            }
+        }
+    }
+
+    /// Number of elements as a Ject integer, or an error when there are more than fit
+    pub fn checked_len(&self) -> Result<i64, RuntimeError> {
+        i64::try_from(self.len()).map_err(|_| RuntimeError {
+            message: format!("Range {} has too many elements to count", self),
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The element at a position known to be in bounds
+    fn at(&self, index: usize) -> Value {
+        match *self {
+            Range::Int { start, step, .. } => Value::Integer((start as i128 + index as i128 * step as i128) as i64),
+            Range::Float { start, step, .. } => Value::Float(start + index as f64 * step),
+        }
+    }
+
+    /// The element at an index, counting from the end when negative
+    pub fn get(&self, index: i64) -> Option<Value> {
+        let len = self.len() as i128;
+        let index = if index < 0 { len + index as i128 } else { index as i128 };
+        if index < 0 || index >= len {
+            return None;
+        }
+        Some(self.at(index as usize))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = Value> {
+        let range = *self;
+        (0..range.len()).map(move |i| range.at(i))
+    }
+
+    /// Collect the elements into an array
+    pub fn to_vec(self) -> Vec<Value> {
+        self.iter().collect()
+    }
+
+    /// Whether the value is one of the elements, answered arithmetically
+    pub fn contains(&self, value: &Value) -> bool {
+        match *self {
+            Range::Int { start, step, .. } => {
+                let n = match value {
+                    Value::Integer(n) => *n as i128,
+                    Value::Float(f) if f.fract() == 0.0 && f.abs() < i64::MAX as f64 => *f as i128,
+                    _ => return false,
+                };
+                let offset = n - start as i128;
+                let step = step as i128;
+                offset % step == 0 && offset / step >= 0 && offset / step < self.len() as i128
+            }
+            Range::Float { start, step, .. } => {
+                let x = match value {
+                    Value::Integer(n) => *n as f64,
+                    Value::Float(f) => *f,
+                    _ => return false,
+                };
+                let position = (x - start) / step;
+                let nearest = position.round();
+                (position - nearest).abs() < FLOAT_TOLERANCE && nearest >= 0.0 && nearest < self.len() as f64
+            }
+        }
+    }
+
+    /// `range[from..to:step]`, with the same bounds handling as array slices
+    pub fn slice(&self, from: Option<i64>, to: Option<i64>, step: i64) -> Result<Range, RuntimeError> {
+        let len = self.checked_len()?;
+        let normalize = |n: i64| if n < 0 { len + n } else { n };
+        let (from, to) = if step > 0 {
+            (from.map_or(0, normalize).clamp(0, len), to.map_or(len, normalize).min(len))
+        } else {
+            // Walking backwards from the end itself yields nothing, as for arrays
+            let from = from.map_or(len - 1, normalize).clamp(0, len);
+            let to = to.map_or(-1, normalize).clamp(-1, len);
+            if from == len { (0, 0) } else { (from, to) }
+        };
+        let positions = Range::Int { start: from, end: to, step, inclusive: false };
+        self.select(from, step, positions.len())
+    }
+
+    /// The sub-range at the in-bounds positions `first, first + stride, ...` (`count` of them)
+    fn select(&self, first: i64, stride: i64, count: usize) -> Result<Range, RuntimeError> {
+        if count == 0 {
+            return Ok(match self {
+                Range::Int { .. } => Range::Int { start: 0, end: 0, step: 1, inclusive: false },
+                Range::Float { .. } => Range::Float { start: 0.0, end: 0.0, step: 1.0, inclusive: false },
+            });
+        }
+        // In bounds, so no further from first than the range is long
+        let last = first + (count as i64 - 1) * stride;
+        match (*self, self.at(first as usize), self.at(last as usize)) {
+            (Range::Int { step, .. }, Value::Integer(start), Value::Integer(end)) => {
+                // With one element the step is never taken
+                let step = if count == 1 { Some(step) } else { step.checked_mul(stride) };
+                let step = step.ok_or_else(|| RuntimeError {
+                    message: format!("Slicing {} with step {} gives a step too large for an integer", self, stride),
+                })?;
+                Ok(Range::Int { start, end, step, inclusive: true })
+            }
+            (Range::Float { step, .. }, Value::Float(start), Value::Float(end)) => {
+                Ok(Range::Float { start, end, step: step * stride as f64, inclusive: true })
+            }
+            _ => unreachable!("elements match the range kind"),
+        }
+    }
+
+    /// Array positions named by this range for a sequence of `len` elements,
+    /// with negative bounds counting from the end as in `a[-3..=-1]`
+    pub fn positions(&self, len: usize) -> Result<impl Iterator<Item = usize>, RuntimeError> {
+        let Range::Int { start, end, step, inclusive } = *self else {
+            return Err(RuntimeError { message: "Index ranges must use integers".to_string() });
+        };
+        let len = i64::try_from(len).map_err(|_| RuntimeError {
+            message: "Sequence has too many elements to index by range".to_string(),
+        })?;
+        let normalize = |n: i64| if n < 0 { len + n } else { n };
+        let positions = Range::Int { start: normalize(start), end: normalize(end), step, inclusive };
+        Ok(positions.iter().filter_map(move |position| match position {
+            Value::Integer(i) if (0..len).contains(&i) => Some(i as usize),
+            _ => None,
+        }))
+    }
+}
+
+impl fmt::Display for Range {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (start, end, step, inclusive) = match *self {
+            Range::Int { start, end, step, inclusive } => (Value::Integer(start), Value::Integer(end), Value::Integer(step), inclusive),
+            Range::Float { start, end, step, inclusive } => (Value::Float(start), Value::Float(end), Value::Float(step), inclusive),
+        };
+        write!(f, "{}{}{}", start, if inclusive { "..=" } else { ".." }, end)?;
+        match step {
+            Value::Integer(1) => Ok(()),
+            Value::Float(1.0) => Ok(()),
+            step => write!(f, " by {}", step),
+        }
+    }
+}
@@ -0,0 +1,157 @@
+//! Module path resolution shared by the interpreter and the linter
+//! `./x` and `../x` are relative to the importing file, `~/x` to the home
+//! directory, and bare names (`math`, `lib/utils`) are looked up in the
+//! configured search paths, then the stdlib bundled into the binary, and
+//! finally the working directory and its `stdlib/`
+
+use std::path::{Path, PathBuf};
+
+/// The Ject-source standard library, compiled in so an installed binary
+/// can import it from anywhere
+const BUNDLED_MODULES: &[(&str, &str)] = &[
+    ("array", include_str!("../stdlib/array.ject")),
+    ("datetime", include_str!("../stdlib/datetime.ject")),
+    ("io", include_str!("../stdlib/io.ject")),
+    ("json", include_str!("../stdlib/json.ject")),
+    ("math", include_str!("../stdlib/math.ject")),
+    ("string", include_str!("../stdlib/string.ject")),
+    ("system", include_str!("../stdlib/system.ject")),
+    ("util", include_str!("../stdlib/util.ject")),
+];
+
+/// Where a resolved module's source lives
+#[derive(Debug, Clone, PartialEq)]
+pub enum ModuleSource {
+    File(PathBuf),
+    Bundled { name: &'static str, source: &'static str },
+}
+
+impl ModuleSource {
+    pub fn read(&self) -> std::io::Result<String> {
+        match self {
+            ModuleSource::File(path) => std::fs::read_to_string(path),
+            ModuleSource::Bundled { source, .. } => Ok(source.to_string()),
+        }
+    }
+
+    /// One key per module however it was imported: the canonical file path,
+    /// or `<stdlib>/name.ject` for bundled modules
+    pub fn key(&self) -> std::io::Result<PathBuf> {
+        match self {
+            ModuleSource::File(path) => std::fs::canonicalize(path),
+            ModuleSource::Bundled { name, .. } => Ok(PathBuf::from(format!("<stdlib>/{}.ject", name))),
+        }
+    }
+
+    /// The directory the module's own `./` imports are relative to
+    pub fn dir(&self) -> Option<PathBuf> {
+        match self {
+            ModuleSource::File(path) => path.parent().map(Path::to_path_buf),
+            ModuleSource::Bundled { .. } => None,
+        }
+    }
+}
+
+/// Environment variable holding extra module directories, separated like PATH
+pub const MODULE_PATH_VAR: &str = "JECT_PATH";
+
+#[derive(Debug, Clone)]
+pub struct ModuleResolver {
+    search_paths: Vec<PathBuf>,  // Searched in order, before the default locations
+}
+
+impl ModuleResolver {
+    /// A resolver searching `search_paths` (e.g. from `--module-path`) and then
+    /// the directories listed in `JECT_PATH`
+    pub fn new(mut search_paths: Vec<PathBuf>) -> Self {
+        if let Some(paths) = std::env::var_os(MODULE_PATH_VAR) {
+            search_paths.extend(std::env::split_paths(&paths).filter(|p| !p.as_os_str().is_empty()));
+        }
+        ModuleResolver { search_paths }
+    }
+
+    /// Find the source for `import "<module_path>"` in a file living in `importer_dir`
+    /// (`None` for the REPL, where relative imports use the working directory)
+    pub fn resolve(&self, module_path: &str, importer_dir: Option<&Path>) -> Result<ModuleSource, String> {
+        let path_with_ext = if module_path.ends_with(".ject") {
+            module_path.to_string()
+        } else {
+            format!("{}.ject", module_path)
+        };
+        let cwd = std::env::current_dir().unwrap_or_default();
+
+        let found_at = |full_path: PathBuf| {
+            if full_path.is_file() {
+                Ok(ModuleSource::File(full_path))
+            } else {
+                Err(format!("Module '{}' not found at {}", module_path, full_path.display()))
+            }
+        };
+
+        if let Some(rest) = path_with_ext.strip_prefix("~/") {
+            // Home directory path: import "~/Documents/MyModule"
+            let home = std::env::var_os("HOME")
+                .ok_or_else(|| "Could not determine home directory for '~' path".to_string())?;
+            found_at(PathBuf::from(home).join(rest))
+        } else if module_path.starts_with('/') {
+            // Absolute path: import "/home/user/mymodule"
+            found_at(PathBuf::from(path_with_ext))
+        } else if module_path.starts_with("./") || module_path.starts_with("../") {
+            // Relative to the file doing the import
+            found_at(importer_dir.unwrap_or(&cwd).join(path_with_ext))
+        } else {
+            // Search paths come first so a local file can deliberately replace a bundled module
+            let (mut candidates, fallback) = self.candidates(&path_with_ext, &cwd);
+            if let Some(path) = candidates.iter().find(|path| path.is_file()) {
+                return Ok(ModuleSource::File(path.clone()));
+            }
+            if let Some(module) = bundled(module_path) {
+                return Ok(module);
+            }
+            if fallback.is_file() {
+                return Ok(ModuleSource::File(fallback));
+            }
+            candidates.push(fallback);
+            let searched: Vec<String> = candidates.iter().map(|p| p.display().to_string()).collect();
+            Err(format!("Module '{}' not found. Searched: {}", module_path, searched.join(", ")))
+        }
+    }
+
+    /// Where a bare module name may live: the search path files, tried before
+    /// the bundled stdlib, and the working directory file tried after it
+    fn candidates(&self, path_with_ext: &str, cwd: &Path) -> (Vec<PathBuf>, PathBuf) {
+        let search_paths = self.search_paths.iter().map(|dir| dir.join(path_with_ext)).collect();
+        // "lib/utils" is relative to the working directory itself; "math" lives in its stdlib/
+        let fallback = if path_with_ext.contains('/') {
+            cwd.join(path_with_ext)
+        } else {
+            cwd.join("stdlib").join(path_with_ext)
+        };
+        (search_paths, fallback)
+    }
+}
+
+/// The namespace a full import binds for a nested module path: `import "geo/shapes"`
+/// gives `shapes.Circle`. Single names and file paths (`./x`, `/x`, `~/x`) import flat
+pub fn namespace(module_path: &str) -> Option<&str> {
+    let is_file_path = ["./", "../", "/", "~/"].iter().any(|prefix| module_path.starts_with(prefix));
+    if is_file_path {
+        return None;
+    }
+    let (_, last) = module_path.rsplit_once('/')?;
+    Some(last.strip_suffix(".ject").unwrap_or(last))
+}
+
+impl Default for ModuleResolver {
+    fn default() -> Self {
+        ModuleResolver::new(Vec::new())
+    }
+}
+
+/// The bundled stdlib module for `import "math"` (or `"math.ject"`), if there is one
+fn bundled(module_path: &str) -> Option<ModuleSource> {
+    let name = module_path.strip_suffix(".ject").unwrap_or(module_path);
+    BUNDLED_MODULES.iter()
+        .find(|(bundled_name, _)| *bundled_name == name)
+        .map(|&(name, source)| ModuleSource::Bundled { name, source })
+}
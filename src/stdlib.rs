@@ -1,6 +1,9 @@
 use crate::value::Value;
-use crate::interpreter::RuntimeError;
+use crate::interpreter::{Callback, RuntimeError};
+use crate::range::Range;
 use std::collections::HashMap;
+use rust_decimal::{Decimal, RoundingStrategy};
+use rust_decimal::prelude::ToPrimitive;
 
 /// Create CorLib - Core Library (always available)
 /// These are Rust primitives that CANNOT be written in Ject itself
@@ -14,14 +17,28 @@ pub fn create_corlib() -> HashMap<String, Value> {
     corlib.insert("to_float".to_string(), Value::BuiltinFunction("to_float".to_string()));
     corlib.insert("to_string".to_string(), Value::BuiltinFunction("to_string".to_string()));
     corlib.insert("to_bool".to_string(), Value::BuiltinFunction("to_bool".to_string()));
+    corlib.insert("decimal".to_string(), Value::BuiltinFunction("decimal".to_string()));
 
     // ========== Collection Primitives ==========
     // These need internal access to data structures
     corlib.insert("len".to_string(), Value::BuiltinFunction("len".to_string()));
     corlib.insert("range".to_string(), Value::BuiltinFunction("range".to_string()));
+    corlib.insert("collect".to_string(), Value::BuiltinFunction("collect".to_string()));
     corlib.insert("push".to_string(), Value::BuiltinFunction("push".to_string()));
     corlib.insert("pop".to_string(), Value::BuiltinFunction("pop".to_string()));
 
+    // ========== Dictionary Primitives ==========
+    // These need internal dictionary access
+    corlib.insert("keys".to_string(), Value::BuiltinFunction("keys".to_string()));
+    corlib.insert("values".to_string(), Value::BuiltinFunction("values".to_string()));
+    corlib.insert("items".to_string(), Value::BuiltinFunction("items".to_string()));
+    corlib.insert("get".to_string(), Value::BuiltinFunction("get".to_string()));
+    corlib.insert("remove".to_string(), Value::BuiltinFunction("remove".to_string()));
+    corlib.insert("merge".to_string(), Value::BuiltinFunction("merge".to_string()));
+    corlib.insert("update".to_string(), Value::BuiltinFunction("update".to_string()));
+    corlib.insert("map_values".to_string(), Value::BuiltinFunction("map_values".to_string()));
+    corlib.insert("filter_keys".to_string(), Value::BuiltinFunction("filter_keys".to_string()));
+
     // ========== Array Primitives ==========
     // These need internal array access
     corlib.insert("sum".to_string(), Value::BuiltinFunction("sum".to_string()));
@@ -44,12 +61,14 @@ pub fn create_corlib() -> HashMap<String, Value> {
     corlib.insert("abs".to_string(), Value::BuiltinFunction("abs".to_string()));
     corlib.insert("sqrt".to_string(), Value::BuiltinFunction("sqrt".to_string()));
     corlib.insert("pow".to_string(), Value::BuiltinFunction("pow".to_string()));
+    corlib.insert("divmod".to_string(), Value::BuiltinFunction("divmod".to_string()));
     corlib.insert("sin".to_string(), Value::BuiltinFunction("sin".to_string()));
     corlib.insert("cos".to_string(), Value::BuiltinFunction("cos".to_string()));
     corlib.insert("tan".to_string(), Value::BuiltinFunction("tan".to_string()));
     corlib.insert("floor".to_string(), Value::BuiltinFunction("floor".to_string()));
     corlib.insert("ceil".to_string(), Value::BuiltinFunction("ceil".to_string()));
     corlib.insert("round".to_string(), Value::BuiltinFunction("round".to_string()));
+    corlib.insert("round_decimal".to_string(), Value::BuiltinFunction("round_decimal".to_string()));
     corlib.insert("min".to_string(), Value::BuiltinFunction("min".to_string()));
     corlib.insert("max".to_string(), Value::BuiltinFunction("max".to_string()));
     corlib.insert("random".to_string(), Value::BuiltinFunction("random".to_string()));
@@ -73,6 +92,7 @@ pub fn create_corlib() -> HashMap<String, Value> {
     corlib.insert("print".to_string(), Value::BuiltinFunction("print".to_string()));
     corlib.insert("read_file".to_string(), Value::BuiltinFunction("read_file".to_string()));
     corlib.insert("write_file".to_string(), Value::BuiltinFunction("write_file".to_string()));
+    corlib.insert("args".to_string(), Value::BuiltinFunction("args".to_string()));
 
     // ========== Testing ==========
     corlib.insert("assert".to_string(), Value::BuiltinFunction("assert".to_string()));
@@ -292,6 +312,23 @@ pub fn get_base_module() -> HashMap<String, Value> {
     module
 }
 
+/// Get collections module functions (import "collections")
+/// Sets of hashable values - the builtins need internal set access
+pub fn get_collections_module() -> HashMap<String, Value> {
+    let mut module = HashMap::new();
+
+    for name in [
+        "collection", "add_to", "remove_from", "has", "union", "intersect", "difference",
+        "size", "is_subset", "is_superset", "clear_collection", "to_array",
+    ] {
+        module.insert(name.to_string(), Value::BuiltinFunction(name.to_string()));
+    }
+    module.insert("from_array".to_string(), Value::BuiltinFunction("collection".to_string()));
+    module.insert("is_empty_collection".to_string(), Value::BuiltinFunction("is_empty".to_string()));
+
+    module
+}
+
 /// Get a module by name (for import system)
 /// Returns None for modules that exist as .ject files (they will be loaded from disk)
 /// Only returns Some() for Rust-only modules that don't have .ject equivalents
@@ -302,7 +339,10 @@ pub fn get_module(name: &str) -> Option<HashMap<String, Value>> {
 
         // Rust-only modules (no .ject equivalent)
         "base" => Some(get_base_module()),
+        "collections" => Some(get_collections_module()),
         "numpy" => Some(crate::numpy::create_numpy_module()),
+        "csv" => Some(crate::csv::create_csv_module()),
+        "dataframe" => Some(crate::dataframe::create_dataframe_module()),
 
         // All other modules will be loaded from .ject files
         _ => None,
@@ -326,9 +366,18 @@ pub fn create_stdlib() -> HashMap<String, Value> {
     stdlib
 }
 
+/// Builtins that take a range as it is; any other builtin is passed the
+/// range's elements as an array
+pub fn accepts_range(name: &str) -> bool {
+    matches!(name, "len" | "contains" | "is_empty" | "type_of" | "to_string" | "to_json" | "collect" | "to_array")
+}
+
 pub fn call_builtin_function(name: &str, args: Vec<Value>) -> Result<Value, RuntimeError> {
     std::env::set_var("RUST_BACKTRACE", "full");
 match name {
+        // csv module functions
+        name if name.starts_with("csv_") => crate::csv::call_csv_function(name, args),
+
         // Enhanced array functions
         "sort" => {
             if args.len() != 1 {
@@ -501,6 +550,7 @@ match name {
             match &args[0] {
                 Value::Integer(n) => Ok(Value::Integer(n.abs())),
                 Value::Float(f) => Ok(Value::Float(f.abs())),
+                Value::Decimal(d) => Ok(Value::Decimal(d.abs())),
                 _ => Err(RuntimeError {
                     message: "abs() requires a number".to_string(),
                 }),
@@ -534,6 +584,47 @@ match name {
                 }),
             }
         },
+        "divmod" => {
+            if args.len() != 2 {
+                return Err(RuntimeError {
+                    message: "divmod() takes exactly 2 arguments".to_string(),
+                });
+            }
+            // Floored division: the remainder takes the sign of the divisor
+            match (&args[0], &args[1]) {
+                (Value::Integer(_), Value::Integer(0)) => Err(RuntimeError {
+                    message: "Division by zero".to_string(),
+                }),
+                (Value::Integer(a), Value::Integer(b)) => {
+                    let (mut q, mut r) = (a / b, a % b);
+                    if r != 0 && (r < 0) != (*b < 0) {
+                        q -= 1;
+                        r += b;
+                    }
+                    Ok(Value::Tuple(vec![Value::Integer(q), Value::Integer(r)]))
+                }
+                (a, b) => {
+                    let number = |v: &Value| match v {
+                        Value::Integer(n) => Some(*n as f64),
+                        Value::Float(f) => Some(*f),
+                        _ => None,
+                    };
+                    let (a, b) = match (number(a), number(b)) {
+                        (Some(a), Some(b)) => (a, b),
+                        _ => return Err(RuntimeError {
+                            message: "divmod() requires two numbers".to_string(),
+                        }),
+                    };
+                    if b == 0.0 {
+                        return Err(RuntimeError {
+                            message: "Division by zero".to_string(),
+                        });
+                    }
+                    let q = (a / b).floor();
+                    Ok(Value::Tuple(vec![Value::Float(q), Value::Float(a - q * b)]))
+                }
+            }
+        },
         "pow" => {
             if args.len() != 2 {
                 return Err(RuntimeError {
@@ -673,11 +764,46 @@ match name {
             match &args[0] {
                 Value::Integer(n) => Ok(Value::Integer(*n)),
                 Value::Float(f) => Ok(Value::Integer(f.round() as i64)),
+                Value::Decimal(d) => d.round_dp_with_strategy(0, RoundingStrategy::MidpointAwayFromZero)
+                    .to_i64()
+                    .map(Value::Integer)
+                    .ok_or_else(|| RuntimeError {
+                        message: format!("round() result {} does not fit in an integer", d),
+                    }),
                 _ => Err(RuntimeError {
                     message: "round() requires a number".to_string(),
                 }),
             }
         },
+        "round_decimal" => {
+            if args.is_empty() || args.len() > 3 {
+                return Err(RuntimeError {
+                    message: "round_decimal() takes 1 to 3 arguments (value, places, mode)".to_string(),
+                });
+            }
+            let d = match &args[0] {
+                Value::Decimal(d) => *d,
+                Value::Integer(n) => Decimal::from(*n),
+                _ => return Err(RuntimeError {
+                    message: "round_decimal() requires a decimal".to_string(),
+                }),
+            };
+            let places = match args.get(1) {
+                None => 0,
+                Some(Value::Integer(n)) if *n >= 0 => *n as u32,
+                Some(_) => return Err(RuntimeError {
+                    message: "round_decimal() places must be a non-negative integer".to_string(),
+                }),
+            };
+            let strategy = match args.get(2) {
+                None => RoundingStrategy::MidpointNearestEven,
+                Some(Value::String(mode)) => rounding_strategy(mode)?,
+                Some(_) => return Err(RuntimeError {
+                    message: "round_decimal() mode must be a string".to_string(),
+                }),
+            };
+            Ok(Value::Decimal(d.round_dp_with_strategy(places, strategy)))
+        },
         "min" => {
             if args.is_empty() {
                 return Err(RuntimeError {
@@ -699,6 +825,10 @@ match name {
                     (Value::Float(a), Value::Integer(b)) => {
                         if (*b as f64) < *a { min_val = arg; }
                     }
+                    (Value::Decimal(_), Value::Integer(_) | Value::Float(_) | Value::Decimal(_)) |
+                    (Value::Integer(_) | Value::Float(_), Value::Decimal(_)) => {
+                        if arg < min_val { min_val = arg; }
+                    }
                     _ => return Err(RuntimeError {
                         message: "min() requires all arguments to be numbers".to_string(),
                     }),
@@ -765,6 +895,10 @@ match name {
                     (Value::Float(a), Value::Integer(b)) => {
                         if (*b as f64) > *a { max_val = arg; }
                     }
+                    (Value::Decimal(_), Value::Integer(_) | Value::Float(_) | Value::Decimal(_)) |
+                    (Value::Integer(_) | Value::Float(_), Value::Decimal(_)) => {
+                        if arg > max_val { max_val = arg; }
+                    }
                     _ => return Err(RuntimeError {
                         message: "max() requires all arguments to be numbers".to_string(),
                     }),
@@ -779,10 +913,14 @@ match name {
                 });
             }
             match &args[0] {
-                Value::Array(arr) => Ok(Value::Integer(arr.len() as i64)),
+                Value::Array(arr) | Value::Tuple(arr) => Ok(Value::Integer(arr.len() as i64)),
+                Value::Range(range) => Ok(Value::Integer(range.checked_len()?)),
                 Value::String(s) => Ok(Value::Integer(s.chars().count() as i64)),
+                Value::DataFrame(frame) => Ok(Value::Integer(frame.len() as i64)),
+                Value::Dictionary(dict) => Ok(Value::Integer(dict.len() as i64)),
+                Value::Collection(set) => Ok(Value::Integer(set.len() as i64)),
                 _ => Err(RuntimeError {
-                    message: "len() requires an array or string".to_string(),
+                    message: "len() requires an array, range, string, dictionary or collection".to_string(),
                 }),
             }
         },
@@ -793,6 +931,23 @@ match name {
                 });
             }
             match &args[0] {
+                Value::Array(arr) if arr.iter().any(|v| matches!(v, Value::Decimal(_))) => {
+                    // Any decimal makes the whole sum exact
+                    let mut sum = Decimal::ZERO;
+                    for val in arr {
+                        let term = match val {
+                            Value::Decimal(d) => *d,
+                            Value::Integer(n) => Decimal::from(*n),
+                            _ => return Err(RuntimeError {
+                                message: "sum() cannot mix decimals with non-decimal values other than integers".to_string(),
+                            }),
+                        };
+                        sum = sum.checked_add(term).ok_or_else(|| RuntimeError {
+                            message: "Decimal overflow in sum()".to_string(),
+                        })?;
+                    }
+                    Ok(Value::Decimal(sum))
+                }
                 Value::Array(arr) => {
                     let mut sum = 0.0;
                     let mut is_int = true;
@@ -1129,71 +1284,30 @@ match name {
             }
         },
         "range" => {
+            // range(n), range(start, end) or range(start, end, step), as a lazy range
             match args.len() {
-                1 => {
-                    // range(n) -> [0, 1, 2, ..., n-1]
-                    match &args[0] {
-                        Value::Integer(n) => {
-                            let mut result = Vec::new();
-                            for i in 0..*n {
-                                result.push(Value::Integer(i));
-                            }
-                            Ok(Value::Array(result))
-                        }
-                        _ => Err(RuntimeError {
-                            message: "range() requires an integer".to_string(),
-                        }),
-                    }
-                }
-                2 => {
-                    // range(start, end) -> [start, start+1, ..., end-1]
-                    match (&args[0], &args[1]) {
-                        (Value::Integer(start), Value::Integer(end)) => {
-                            let mut result = Vec::new();
-                            for i in *start..*end {
-                                result.push(Value::Integer(i));
-                            }
-                            Ok(Value::Array(result))
-                        }
-                        _ => Err(RuntimeError {
-                            message: "range() requires integers".to_string(),
-                        }),
-                    }
-                }
-                3 => {
-                    // range(start, end, step) -> [start, start+step, start+2*step, ...]
-                    match (&args[0], &args[1], &args[2]) {
-                        (Value::Integer(start), Value::Integer(end), Value::Integer(step)) => {
-                            if *step == 0 {
-                                return Err(RuntimeError {
-                                    message: "range() step cannot be zero".to_string(),
-                                });
-                            }
-                            let mut result = Vec::new();
-                            let mut current = *start;
-                            if *step > 0 {
-                                while current < *end {
-                                    result.push(Value::Integer(current));
-                                    current += step;
-                                }
-                            } else {
-                                while current > *end {
-                                    result.push(Value::Integer(current));
-                                    current += step;
-                                }
-                            }
-                            Ok(Value::Array(result))
-                        }
-                        _ => Err(RuntimeError {
-                            message: "range() requires integers".to_string(),
-                        }),
-                    }
-                }
+                1 => Range::new(&Value::Integer(0), &args[0], None, false).map(Value::Range),
+                2 => Range::new(&args[0], &args[1], None, false).map(Value::Range),
+                3 => Range::new(&args[0], &args[1], Some(&args[2]), false).map(Value::Range),
                 _ => Err(RuntimeError {
                     message: "range() takes 1, 2, or 3 arguments".to_string(),
                 }),
             }
         },
+        "collect" => {
+            if args.len() != 1 {
+                return Err(RuntimeError {
+                    message: "collect() takes exactly 1 argument".to_string(),
+                });
+            }
+            match &args[0] {
+                Value::Range(range) => Ok(Value::Array(range.to_vec())),
+                Value::Array(items) | Value::Tuple(items) | Value::UniqueArray(items) => Ok(Value::Array(items.clone())),
+                other => Err(RuntimeError {
+                    message: format!("collect() requires a range, array or tuple, got {}", other.type_name()),
+                }),
+            }
+        },
         "random" => {
             if args.len() != 0 {
                 return Err(RuntimeError {
@@ -1360,8 +1474,11 @@ match name {
             }
             match (&args[0], &args[1]) {
                 (Value::Array(arr), value) => Ok(Value::Bool(arr.contains(value))),
+                (Value::Range(range), value) => Ok(Value::Bool(range.contains(value))),
+                (Value::Dictionary(dict), key) => Ok(Value::Bool(dict.contains_key(key))),
+                (Value::Collection(set), item) => Ok(Value::Bool(set.contains(item))),
                 _ => Err(RuntimeError {
-                    message: "contains() requires an array and a value".to_string(),
+                    message: "contains() requires an array, dictionary or collection and a value".to_string(),
                 }),
             }
         },
@@ -1725,6 +1842,10 @@ match name {
             match &args[0] {
                 Value::String(s) => Ok(Value::Bool(s.is_empty())),
                 Value::Array(arr) => Ok(Value::Bool(arr.is_empty())),
+                Value::Range(range) => Ok(Value::Bool(range.is_empty())),
+                Value::DataFrame(frame) => Ok(Value::Bool(frame.is_empty())),
+                Value::Dictionary(dict) => Ok(Value::Bool(dict.is_empty())),
+                Value::Collection(set) => Ok(Value::Bool(set.is_empty())),
                 _ => Ok(Value::Bool(false)),
             }
         },
@@ -1762,6 +1883,9 @@ match name {
             match &args[0] {
                 Value::Integer(n) => Ok(Value::Integer(*n)),
                 Value::Float(f) => Ok(Value::Integer(*f as i64)),
+                Value::Decimal(d) => d.trunc().to_i64().map(Value::Integer).ok_or_else(|| RuntimeError {
+                    message: format!("Cannot convert {} to integer", d),
+                }),
                 Value::String(s) => {
                     // Trim whitespace and try parsing
                     let trimmed = s.trim();
@@ -1792,6 +1916,7 @@ match name {
             match &args[0] {
                 Value::Integer(n) => Ok(Value::Float(*n as f64)),
                 Value::Float(f) => Ok(Value::Float(*f)),
+                Value::Decimal(d) => Ok(Value::Float(crate::value::decimal_to_f64(d))),
                 Value::String(s) => {
                     // Trim whitespace and try parsing
                     let trimmed = s.trim();
@@ -1825,6 +1950,7 @@ match name {
                 Value::Bool(b) => Ok(Value::Bool(*b)),
                 Value::Integer(n) => Ok(Value::Bool(*n != 0)),
                 Value::Float(f) => Ok(Value::Bool(*f != 0.0)),
+                Value::Decimal(d) => Ok(Value::Bool(!d.is_zero())),
                 Value::String(s) => {
                     match s.to_lowercase().as_str() {
                         "true" | "1" | "yes" | "on" => Ok(Value::Bool(true)),
@@ -1836,6 +1962,36 @@ match name {
                 _ => Ok(Value::Bool(args[0].is_truthy())),
             }
         },
+        "decimal" => {
+            if args.len() != 1 {
+                return Err(RuntimeError {
+                    message: "decimal() takes exactly 1 argument".to_string(),
+                });
+            }
+            match &args[0] {
+                Value::Decimal(d) => Ok(Value::Decimal(*d)),
+                Value::Integer(n) => Ok(Value::Decimal(Decimal::from(*n))),
+                // Go through the shortest round-trip text so decimal(0.1) is 0.1, not 0.1000000000000000055...
+                Value::Float(f) if f.is_finite() => f.to_string().parse::<Decimal>()
+                    .or_else(|_| Decimal::from_scientific(&format!("{:e}", f)))
+                    .map(Value::Decimal)
+                    .map_err(|_| RuntimeError {
+                        message: format!("Cannot convert {} to decimal", f),
+                    }),
+                Value::String(s) => {
+                    let trimmed = s.trim();
+                    trimmed.parse::<Decimal>()
+                        .or_else(|_| Decimal::from_scientific(trimmed))
+                        .map(Value::Decimal)
+                        .map_err(|_| RuntimeError {
+                            message: format!("Cannot convert '{}' to decimal", s),
+                        })
+                }
+                _ => Err(RuntimeError {
+                    message: format!("Cannot convert {} to decimal", args[0].type_name()),
+                }),
+            }
+        },
         
         // Math functions
         "sign" => {
@@ -2094,19 +2250,108 @@ match name {
             Ok(Value::Nil)
         },
         
+        // Dictionary functions
+        "keys" | "values" | "items" => {
+            if args.len() != 1 {
+                return Err(RuntimeError {
+                    message: format!("{}() takes exactly 1 argument (dictionary)", name),
+                });
+            }
+            let dict = dictionary_arg(name, &args[0])?;
+            let result = match name {
+                "keys" => dict.keys().cloned().collect(),
+                "values" => dict.values().cloned().collect(),
+                _ => dict.iter()
+                    .map(|(k, v)| Value::Tuple(vec![k.clone(), v.clone()]))
+                    .collect(),
+            };
+            Ok(Value::Array(result))
+        },
+        "get" => {
+            if args.len() < 2 || args.len() > 3 {
+                return Err(RuntimeError {
+                    message: "get() takes 2 or 3 arguments (dictionary, key, default)".to_string(),
+                });
+            }
+            let dict = dictionary_arg(name, &args[0])?;
+            let key = crate::interpreter::dictionary_key(args[1].clone())?;
+            match dict.get(&key) {
+                Some(value) => Ok(value.clone()),
+                None => Ok(args.get(2).cloned().unwrap_or(Value::Nil)),
+            }
+        },
+        "remove" => {
+            if args.len() != 2 {
+                return Err(RuntimeError {
+                    message: "remove() takes exactly 2 arguments (dictionary, key)".to_string(),
+                });
+            }
+            let mut dict = dictionary_arg(name, &args[0])?.clone();
+            let key = crate::interpreter::dictionary_key(args[1].clone())?;
+            dict.shift_remove(&key);
+            Ok(Value::Dictionary(dict))
+        },
+        "merge" => {
+            if args.is_empty() {
+                return Err(RuntimeError {
+                    message: "merge() requires at least 1 dictionary".to_string(),
+                });
+            }
+            // Later dictionaries win; keys keep the position they were first seen at
+            let mut merged = indexmap::IndexMap::new();
+            for arg in &args {
+                for (key, value) in dictionary_arg(name, arg)? {
+                    merged.insert(key.clone(), value.clone());
+                }
+            }
+            Ok(Value::Dictionary(merged))
+        },
+        "update" => {
+            if args.len() != 2 {
+                return Err(RuntimeError {
+                    message: "update() takes exactly 2 arguments (dictionary, changes)".to_string(),
+                });
+            }
+            let mut dict = dictionary_arg(name, &args[0])?.clone();
+            match &args[1] {
+                Value::Dictionary(changes) => {
+                    for (key, value) in changes {
+                        dict.insert(key.clone(), value.clone());
+                    }
+                }
+                Value::Array(pairs) => {
+                    for pair in pairs {
+                        match pair {
+                            Value::Array(kv) | Value::Tuple(kv) if kv.len() == 2 => {
+                                let key = crate::interpreter::dictionary_key(kv[0].clone())?;
+                                dict.insert(key, kv[1].clone());
+                            }
+                            other => return Err(RuntimeError {
+                                message: format!("update() expects (key, value) pairs, got {}", other),
+                            }),
+                        }
+                    }
+                }
+                other => return Err(RuntimeError {
+                    message: format!("update() requires a dictionary or an array of (key, value) pairs, got {}", other.type_name()),
+                }),
+            }
+            Ok(Value::Dictionary(dict))
+        },
+        
         // Collection functions
         "collection" => {
             // Creates a new empty collection or from array/string arguments
-            let mut set = std::collections::HashSet::new();
+            let mut set = indexmap::IndexSet::new();
             for arg in args {
                 match arg {
-                    Value::Array(arr) => {
+                    Value::Array(arr) | Value::UniqueArray(arr) => {
                         for item in arr {
-                            set.insert(item.to_string());
+                            set.insert(collection_item(item)?);
                         }
                     }
                     _ => {
-                        set.insert(arg.to_string());
+                        set.insert(collection_item(arg)?);
                     }
                 }
             }
@@ -2121,7 +2366,7 @@ match name {
             match &args[0] {
                 Value::Collection(set) => {
                     let mut new_set = set.clone();
-                    new_set.insert(args[1].to_string());
+                    new_set.insert(collection_item(args[1].clone())?);
                     Ok(Value::Collection(new_set))
                 }
                 _ => Err(RuntimeError {
@@ -2138,7 +2383,7 @@ match name {
             match &args[0] {
                 Value::Collection(set) => {
                     let mut new_set = set.clone();
-                    new_set.remove(&args[1].to_string());
+                    new_set.shift_remove(&args[1]);
                     Ok(Value::Collection(new_set))
                 }
                 _ => Err(RuntimeError {
@@ -2154,7 +2399,7 @@ match name {
             }
             match &args[0] {
                 Value::Collection(set) => {
-                    Ok(Value::Bool(set.contains(&args[1].to_string())))
+                    Ok(Value::Bool(set.contains(&args[1])))
                 }
                 _ => Err(RuntimeError {
                     message: "has() requires a collection as first argument".to_string(),
@@ -2169,7 +2414,7 @@ match name {
             }
             match (&args[0], &args[1]) {
                 (Value::Collection(set1), Value::Collection(set2)) => {
-                    let union_set: std::collections::HashSet<String> = set1.union(set2).cloned().collect();
+                    let union_set: indexmap::IndexSet<Value> = set1.union(set2).cloned().collect();
                     Ok(Value::Collection(union_set))
                 }
                 _ => Err(RuntimeError {
@@ -2185,7 +2430,7 @@ match name {
             }
             match (&args[0], &args[1]) {
                 (Value::Collection(set1), Value::Collection(set2)) => {
-                    let intersection_set: std::collections::HashSet<String> = set1.intersection(set2).cloned().collect();
+                    let intersection_set: indexmap::IndexSet<Value> = set1.intersection(set2).cloned().collect();
                     Ok(Value::Collection(intersection_set))
                 }
                 _ => Err(RuntimeError {
@@ -2201,7 +2446,7 @@ match name {
             }
             match (&args[0], &args[1]) {
                 (Value::Collection(set1), Value::Collection(set2)) => {
-                    let difference_set: std::collections::HashSet<String> = set1.difference(set2).cloned().collect();
+                    let difference_set: indexmap::IndexSet<Value> = set1.difference(set2).cloned().collect();
                     Ok(Value::Collection(difference_set))
                 }
                 _ => Err(RuntimeError {
@@ -2271,7 +2516,7 @@ match name {
             }
             match &args[0] {
                 Value::Collection(_) => {
-                    Ok(Value::Collection(std::collections::HashSet::new()))
+                    Ok(Value::Collection(indexmap::IndexSet::new()))
                 }
                 _ => Err(RuntimeError {
                     message: "clear_collection() requires a collection".to_string(),
@@ -2291,10 +2536,9 @@ match name {
                             message: "to_array() delimiter parameter only works with strings".to_string(),
                         });
                     }
-                    let mut items: Vec<String> = set.iter().cloned().collect();
-                    items.sort(); // Sort for consistent output
-                    let values: Vec<Value> = items.into_iter().map(Value::String).collect();
-                    Ok(Value::Array(values))
+                    let mut items: Vec<Value> = set.iter().cloned().collect();
+                    items.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal)); // Sort for consistent output
+                    Ok(Value::Array(items))
                 }
                 Value::UniqueArray(uarr) => {
                     // Convert unique array to regular array
@@ -2305,6 +2549,7 @@ match name {
                     }
                     Ok(Value::Array(uarr.clone()))
                 }
+                Value::Range(range) if args.len() == 1 => Ok(Value::Array(range.to_vec())),
                 Value::String(s) => {
                     if args.len() == 2 {
                         // to_array(string, delimiter) - split by delimiter
@@ -2414,7 +2659,7 @@ match name {
 }
 
 // Helper function to convert serde_json::Value to Ject Value
-fn json_to_ject_value(json_value: serde_json::Value) -> Value {
+pub(crate) fn json_to_ject_value(json_value: serde_json::Value) -> Value {
     match json_value {
         serde_json::Value::Null => Value::Nil,
         serde_json::Value::Bool(b) => Value::Bool(b),
@@ -2433,12 +2678,78 @@ fn json_to_ject_value(json_value: serde_json::Value) -> Value {
             Value::Array(ject_array)
         }
         serde_json::Value::Object(obj) => {
-            // Convert JSON object to Ject array of [key, value] pairs
-            let pairs: Vec<Value> = obj.into_iter()
-                .map(|(k, v)| Value::Array(vec![Value::String(k), json_to_ject_value(v)]))
-                .collect();
-            Value::Array(pairs)
+            // Keys keep the order they appear in the document
+            Value::Dictionary(obj.into_iter().map(|(k, v)| (Value::String(k), json_to_ject_value(v))).collect())
+        }
+    }
+}
+
+/// The dictionary a dictionary function was given, or an error naming the function
+fn dictionary_arg<'a>(name: &str, value: &'a Value) -> Result<&'a indexmap::IndexMap<Value, Value>, RuntimeError> {
+    match value {
+        Value::Dictionary(dict) => Ok(dict),
+        other => Err(RuntimeError {
+            message: format!("{}() requires a dictionary, got {}", name, other.type_name()),
+        }),
+    }
+}
+
+/// Dictionary functions that call back into Ject (map_values, filter_keys)
+pub fn call_dictionary_function(name: &str, args: Vec<Value>, call: &mut Callback) -> Result<Value, RuntimeError> {
+    if args.len() != 2 {
+        return Err(RuntimeError {
+            message: format!("{}() takes exactly 2 arguments (dictionary, function)", name),
+        });
+    }
+    let dict = dictionary_arg(name, &args[0])?;
+    let mut result = indexmap::IndexMap::new();
+    match (name, &args[1]) {
+        ("map_values", func) => {
+            for (key, value) in dict {
+                result.insert(key.clone(), call(func, vec![value.clone()])?);
+            }
+        }
+        ("filter_keys", Value::Array(wanted)) => {
+            for (key, value) in dict {
+                if wanted.contains(key) {
+                    result.insert(key.clone(), value.clone());
+                }
+            }
         }
+        ("filter_keys", func) => {
+            for (key, value) in dict {
+                if call(func, vec![key.clone()])?.is_truthy() {
+                    result.insert(key.clone(), value.clone());
+                }
+            }
+        }
+        _ => return Err(RuntimeError {
+            message: format!("Unknown dictionary function: {}", name),
+        }),
+    }
+    Ok(Value::Dictionary(result))
+}
+
+fn collection_item(value: Value) -> Result<Value, RuntimeError> {
+    crate::interpreter::hashable(value, "Collection items")
+}
+
+/// Map a round_decimal() mode name onto a rust_decimal rounding strategy
+fn rounding_strategy(mode: &str) -> Result<RoundingStrategy, RuntimeError> {
+    match mode {
+        "half_even" => Ok(RoundingStrategy::MidpointNearestEven),
+        "half_up" => Ok(RoundingStrategy::MidpointAwayFromZero),
+        "half_down" => Ok(RoundingStrategy::MidpointTowardZero),
+        "up" => Ok(RoundingStrategy::AwayFromZero),
+        "down" => Ok(RoundingStrategy::ToZero),
+        "ceiling" => Ok(RoundingStrategy::ToPositiveInfinity),
+        "floor" => Ok(RoundingStrategy::ToNegativeInfinity),
+        _ => Err(RuntimeError {
+            message: format!(
+                "Unknown rounding mode '{}' (expected half_even, half_up, half_down, up, down, ceiling or floor)",
+                mode
+            ),
+        }),
     }
 }
 
@@ -2475,8 +2786,16 @@ fn ject_value_to_json(ject_value: &Value) -> Result<serde_json::Value, RuntimeEr
                 })
             }
         }
+        Value::Decimal(d) => {
+            // Emit the exact digits (serde_json is built with arbitrary_precision)
+            d.to_string().parse::<serde_json::Number>()
+                .map(serde_json::Value::Number)
+                .map_err(|_| RuntimeError {
+                    message: format!("Invalid decimal value for JSON conversion: {}", d),
+                })
+        }
         Value::String(s) => Ok(serde_json::Value::String(s.clone())),
-        Value::Array(arr) => {
+        Value::Array(arr) | Value::Tuple(arr) => {
             let json_array: Result<Vec<serde_json::Value>, RuntimeError> =
                 arr.iter().map(ject_value_to_json).collect();
             match json_array {
@@ -2484,6 +2803,12 @@ fn ject_value_to_json(ject_value: &Value) -> Result<serde_json::Value, RuntimeEr
                 Err(e) => Err(e),
             }
         }
+        Value::Range(range) => {
+            // Serialized as the array it stands for
+            let json_array: Result<Vec<serde_json::Value>, RuntimeError> =
+                range.iter().map(|v| ject_value_to_json(&v)).collect();
+            Ok(serde_json::Value::Array(json_array?))
+        }
         Value::UniqueArray(arr) => {
             // UniqueArray converts to regular JSON array
             let json_array: Result<Vec<serde_json::Value>, RuntimeError> =
@@ -2496,22 +2821,37 @@ fn ject_value_to_json(ject_value: &Value) -> Result<serde_json::Value, RuntimeEr
         Value::Dictionary(dict) => {
             let mut json_obj = serde_json::Map::new();
             for (key, value) in dict {
-                let json_value = ject_value_to_json(value)?;
-                json_obj.insert(key.clone(), json_value);
+                // JSON object keys are strings; other keys would not survive a round trip
+                let key = match key {
+                    Value::String(key) => key.clone(),
+                    other => return Err(RuntimeError {
+                        message: format!(
+                            "Cannot convert dictionary key {} ({}) to JSON: object keys must be strings",
+                            other, other.type_name()
+                        ),
+                    }),
+                };
+                json_obj.insert(key, ject_value_to_json(value)?);
             }
             Ok(serde_json::Value::Object(json_obj))
         }
         Value::Collection(set) => {
-            let json_array: Vec<serde_json::Value> = set.iter()
-                .map(|s| serde_json::Value::String(s.clone()))
+            let json_array: Result<Vec<serde_json::Value>, RuntimeError> = set.iter()
+                .map(ject_value_to_json)
                 .collect();
-            Ok(serde_json::Value::Array(json_array))
+            Ok(serde_json::Value::Array(json_array?))
         }
         Value::Function { .. } | Value::ModuleFunction { .. } | Value::Lambda { .. } | Value::BuiltinFunction(_) | Value::ModuleObject(_) => {
             Err(RuntimeError {
                 message: "Cannot convert function to JSON".to_string(),
             })
         }
+        Value::CsvReader(_) => {
+            Err(RuntimeError {
+                message: "Cannot convert csv reader to JSON (collect its rows first)".to_string(),
+            })
+        }
+        Value::DataFrame(frame) => ject_value_to_json(&frame.to_rows()),
         Value::Error(msg) => {
             let mut json_obj = serde_json::Map::new();
             json_obj.insert("_type".to_string(), serde_json::Value::String("error".to_string()));
@@ -0,0 +1,37 @@
+#[cfg(test)]
+mod tests {
+    use crate::{Cli, Command};
+    use clap::Parser;
+
+    fn parse(args: &[&str]) -> Cli {
+        Cli::try_parse_from(std::iter::once("ject").chain(args.iter().copied()))
+            .unwrap_or_else(|e| panic!("{:?}: {}", args, e))
+    }
+
+    #[test]
+    fn test_global_options_before_a_command() {
+        let cli = parse(&["--no-color", "check", "x.ject"]);
+        assert!(cli.no_color);
+        assert!(matches!(cli.command, Some(Command::Check { files, .. }) if files == ["x.ject"]));
+
+        let cli = parse(&["--module-path", ".", "run", "a.ject"]);
+        assert_eq!(cli.module_paths, [std::path::PathBuf::from(".")]);
+        assert!(matches!(cli.command, Some(Command::Run { file: Some(file), .. }) if file == "a.ject"));
+
+        let cli = parse(&["--deny", "warnings", "fmt", "--check", "a.ject"]);
+        assert_eq!(cli.deny, ["warnings"]);
+        assert!(matches!(cli.command, Some(Command::Fmt { check: true, .. })));
+    }
+
+    #[test]
+    fn test_script_with_arguments() {
+        let cli = parse(&["--no-color", "a.ject", "x", "--flag"]);
+        assert!(cli.no_color);
+        assert!(matches!(cli.command, Some(Command::Script(argv)) if argv == ["a.ject", "x", "--flag"]));
+
+        let cli = parse(&["-"]);
+        assert!(matches!(cli.command, Some(Command::Script(argv)) if argv == ["-"]));
+
+        assert!(parse(&[]).command.is_none());
+    }
+}
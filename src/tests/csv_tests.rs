@@ -0,0 +1,172 @@
+#[cfg(test)]
+mod tests {
+    use crate::lexer::Lexer;
+    use crate::parser::Parser;
+    use crate::interpreter::Interpreter;
+
+    fn run(input: &str) -> Result<(), String> {
+        let mut lexer = Lexer::new(input);
+        let located_tokens = lexer.tokenize_with_positions();
+        let tokens: Vec<_> = located_tokens.into_iter().map(|lt| lt.token).collect();
+        let mut parser = Parser::new_simple(tokens);
+        let statements = parser.parse().map_err(|e| e.message)?;
+
+        let mut interpreter = Interpreter::new();
+        interpreter.interpret(&statements).map_err(|e| e.message)?;
+
+        Ok(())
+    }
+
+    fn temp_path(name: &str) -> String {
+        std::env::temp_dir()
+            .join(format!("ject_csv_{}_{}", std::process::id(), name))
+            .to_string_lossy()
+            .into_owned()
+    }
+
+    // ========== Parsing Tests ==========
+
+    #[test]
+    fn test_parse_header_rows_with_types() {
+        let result = run(r#"
+import "csv" as csv
+let rows = csv.parse("name,age,score,active\nAda,36,9.5,true\nBob,,7,false\n")
+assert(len(rows) == 2, "header row is not data")
+assert(rows[0]["name"] == "Ada", "string field")
+assert(rows[0]["age"] == 36 and type_of(rows[0]["age"]) == "int", "int inferred")
+assert(rows[0]["score"] == 9.5, "float inferred")
+assert(rows[0]["active"] == true, "bool inferred")
+assert(rows[1]["age"] == nil, "empty field is nil")
+"#);
+        assert!(result.is_ok(), "{:?}", result);
+    }
+
+    #[test]
+    fn test_leading_zeros_stay_strings() {
+        let result = run(r#"
+import "csv" as csv
+let rows = csv.parse("zip,id,zero,fraction,signed\n02134,007,0,0.25,-00123\n")
+assert(rows[0]["zip"] == "02134", "zip code keeps its leading zero")
+assert(rows[0]["id"] == "007", "ID keeps its leading zeros")
+assert(rows[0]["zero"] == 0, "a lone zero is still an int")
+assert(rows[0]["fraction"] == 0.25, "0.x is still a float")
+assert(rows[0]["signed"] == "-00123", "sign before leading zeros")
+"#);
+        assert!(result.is_ok(), "{:?}", result);
+    }
+
+    #[test]
+    fn test_parse_quoted_fields() {
+        let result = run(r#"
+import "csv" as csv
+let rows = csv.parse("\"Smith, J\",\"said \"\"hi\"\"\",\"line\nbreak\",\"42\"\r\nx,y,z,w", {"header": false})
+assert(rows[0][0] == "Smith, J", "quoted delimiter")
+assert(rows[0][1] == "said \"hi\"", "doubled quotes")
+assert(rows[0][2] == "line\nbreak", "quoted newline")
+assert(rows[0][3] == "42", "quoted fields stay strings")
+assert(rows[1] == ["x", "y", "z", "w"], "CRLF record separator")
+"#);
+        assert!(result.is_ok(), "{:?}", result);
+    }
+
+    #[test]
+    fn test_parse_options() {
+        let result = run(r#"
+import "csv" as csv
+let rows = csv.parse("a;'b;c'\n1;2", {"delimiter": ";", "quote": "'", "infer_types": false})
+assert(rows[0]["b;c"] == "2", "custom delimiter and quote")
+"#);
+        assert!(result.is_ok(), "{:?}", result);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let unterminated = run(r#"
+import "csv" as csv
+let rows = csv.parse("a,b\n\"open,1\n")
+"#);
+        assert!(unterminated.unwrap_err().contains("unterminated quoted field"));
+
+        let ragged = run(r#"
+import "csv" as csv
+let rows = csv.parse("a,b\n1,2,3\n")
+"#);
+        assert!(ragged.unwrap_err().contains("row 1 has 3 fields but the header has 2"));
+    }
+
+    // ========== Writing Tests ==========
+
+    #[test]
+    fn test_stringify_round_trip() {
+        let result = run(r#"
+import "csv" as csv
+let text = csv.stringify([["id", "note"], [1, "a, b"], [2, "say \"x\""]])
+assert(text == "id,note\n1,\"a, b\"\n2,\"say \"\"x\"\"\"\n", "quotes only where needed")
+let rows = csv.parse(text)
+assert(rows[1]["note"] == "say \"x\"", "round trip")
+let dicts = csv.stringify([{"b": 2, "a": 1}], {"columns": ["b", "a"]})
+assert(dicts == "b,a\n2,1\n", "dictionary rows use the columns option")
+"#);
+        assert!(result.is_ok(), "{:?}", result);
+    }
+
+    // ========== File Tests ==========
+
+    #[test]
+    fn test_stream_rows_from_file() {
+        let path = temp_path("stream.csv");
+        std::fs::write(&path, "city,pop\nOslo,700\n\n\"Rio\nde Janeiro\",6700\nLima,9700\n").unwrap();
+        let result = run(&format!(r#"
+import "csv" as csv
+let total = 0
+let names = []
+for row in csv.rows("{path}")
+    total = total + row["pop"]
+    names = push(names, row["city"])
+    if row["city"] == "Lima" then
+        break
+    end
+end
+assert(total == 17100, "summed while streaming")
+assert(names[1] == "Rio\nde Janeiro", "quoted newline spans lines")
+assert(len(csv.read("{path}")) == 3, "read loads every row")
+"#));
+        std::fs::remove_file(&path).ok();
+        assert!(result.is_ok(), "{:?}", result);
+    }
+
+    #[test]
+    fn test_stream_matches_read() {
+        // A stray quote inside an unquoted field, CRLF endings, a quoted field with
+        // a doubled quote spanning lines, a blank line and no final newline
+        let path = temp_path("stream_matches.csv");
+        std::fs::write(&path, "size,item\r\n5\",tv\r\n\"2\"\"\r\nwide\",radio\r\n\r\n7,\"lamp\"").unwrap();
+        let result = run(&format!(r#"
+import "csv" as csv
+let streamed = []
+for row in csv.rows("{path}")
+    streamed = push(streamed, row)
+end
+let loaded = csv.read("{path}")
+assert(len(loaded) == 3, "three records")
+assert(streamed == loaded, "rows streams what read loads")
+assert(loaded[0]["size"] == "5\"", "a quote inside an unquoted field is literal")
+assert(loaded[1]["size"] == "2\"\r\nwide", "quoted field spans lines")
+"#));
+        std::fs::remove_file(&path).ok();
+        assert!(result.is_ok(), "{:?}", result);
+    }
+
+    #[test]
+    fn test_write_file() {
+        let path = temp_path("write.csv");
+        let result = run(&format!(r#"
+import "csv" as csv
+csv.write("{path}", [[1, 2], [3, 4]], {{"columns": ["x", "y"], "delimiter": "\t"}})
+"#));
+        let contents = std::fs::read_to_string(&path).unwrap_or_default();
+        std::fs::remove_file(&path).ok();
+        assert!(result.is_ok(), "{:?}", result);
+        assert_eq!(contents, "x\ty\n1\t2\n3\t4\n");
+    }
+}
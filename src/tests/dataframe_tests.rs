@@ -0,0 +1,175 @@
+#[cfg(test)]
+mod tests {
+    use crate::lexer::Lexer;
+    use crate::parser::Parser;
+    use crate::interpreter::Interpreter;
+    use crate::value::Value;
+
+    fn run(input: &str) -> Result<(), String> {
+        let mut lexer = Lexer::new(input);
+        let located_tokens = lexer.tokenize_with_positions();
+        let tokens: Vec<_> = located_tokens.into_iter().map(|lt| lt.token).collect();
+        let mut parser = Parser::new_simple(tokens);
+        let statements = parser.parse().map_err(|e| e.message)?;
+
+        let mut interpreter = Interpreter::new();
+        interpreter.interpret(&statements).map_err(|e| e.message)?;
+
+        Ok(())
+    }
+
+    const SALES: &str = r#"
+import "dataframe" as df
+let sales = df.from_csv("region,rep,amount\nnorth,ann,100\nsouth,bob,250\nnorth,cid,50\neast,dee,\nsouth,eve,75\n")
+"#;
+
+    // ========== Construction Tests ==========
+
+    #[test]
+    fn test_from_csv_infers_column_types() {
+        let result = run(&format!("{}{}", SALES, r#"
+assert(df.shape(sales) == [5, 3], "rows x columns")
+assert(len(sales) == 5, "len counts rows")
+assert(df.columns(sales) == ["region", "rep", "amount"], "header order is kept")
+let types = df.dtypes(sales)
+assert(types["region"] == "string" and types["amount"] == "int", "typed columns")
+assert(df.column(sales, "amount")[3] == nil, "empty field is missing")
+"#));
+        assert!(result.is_ok(), "{:?}", result);
+    }
+
+    #[test]
+    fn test_from_rows_and_json() {
+        let result = run(r#"
+import "dataframe" as df
+let people = df.from_rows([{"name": "Ada", "age": 36}, {"name": "Bob", "score": 7.5}])
+assert(df.columns(people) == ["name", "age", "score"], "union of keys in insertion order")
+assert(df.column(people, "age") == [36, nil], "missing keys are nil")
+let mixed = df.from_rows([[1, "x"], [2.5, "y"]], ["n", "s"])
+assert(df.dtypes(mixed)["n"] == "float", "ints and floats promote to float")
+assert(df.column(mixed, "n")[0] == 1.0, "ints converted")
+let from_json = df.from_json("[{\"id\": 1, \"ok\": true}, {\"id\": 2, \"ok\": false}]")
+assert(df.dtypes(from_json)["ok"] == "boolean", "json rows")
+let columns = df.from_json("{\"id\": [1, 2, 3]}")
+assert(len(columns) == 3, "json columns")
+let rows = df.rows(from_json)
+assert(rows[1]["id"] == 2, "rows() round trip")
+"#);
+        assert!(result.is_ok(), "{:?}", result);
+    }
+
+    #[test]
+    fn test_construction_errors() {
+        let ragged = run(r#"
+import "dataframe" as df
+let frame = df.from_columns({"a": [1, 2], "b": [1]})
+"#);
+        assert!(ragged.unwrap_err().contains("column 'b' has 1 values but the first column has 2"));
+
+        let missing = run(&format!("{}{}", SALES, r#"
+let frame = df.select(sales, ["region", "total"])
+"#));
+        assert!(missing.unwrap_err().contains("Column 'total' not found"));
+    }
+
+    // ========== Transformation Tests ==========
+
+    #[test]
+    fn test_select_filter_and_sort() {
+        let result = run(&format!("{}{}", SALES, r#"
+let south = df.filter(sales, lambda(row) -> row["region"] == "south")
+assert(df.column(south, "rep") == ["bob", "eve"], "filter keeps order")
+let names = df.select(south, "rep")
+assert(df.shape(names) == [2, 1], "select one column")
+let ranked = df.sort_by(sales, "amount", true)
+assert(df.column(ranked, "rep") == ["bob", "ann", "eve", "cid", "dee"], "descending with nil last")
+let by_region = df.sort_by(sales, ["region", "amount"])
+assert(df.column(by_region, "rep") == ["dee", "cid", "ann", "eve", "bob"], "multi-column sort")
+let by_length = df.sort_by(sales, lambda(row) -> len(row["rep"] + row["region"]))
+assert(df.column(by_length, "rep")[0] == "dee", "sort by a computed key")
+"#));
+        assert!(result.is_ok(), "{:?}", result);
+    }
+
+    #[test]
+    fn test_group_by_aggregations() {
+        let result = run(&format!("{}{}", SALES, r#"
+let totals = df.group_by(sales, "region", {"amount": ["sum", "mean", "count"], "rep": "count"})
+assert(df.columns(totals) == ["region", "amount_sum", "amount_mean", "amount_count", "rep"], "aggregation columns")
+assert(df.column(totals, "region") == ["north", "south", "east"], "groups in order of first appearance")
+assert(df.column(totals, "amount_sum") == [150, 325, 0], "int sums stay ints")
+assert(df.column(totals, "amount_mean")[0] == 75.0, "mean")
+assert(df.column(totals, "amount_mean")[2] == nil, "mean of nothing is nil")
+assert(df.column(totals, "rep") == [2, 2, 1], "count")
+"#));
+        assert!(result.is_ok(), "{:?}", result);
+
+        let bad = run(&format!("{}{}", SALES, r#"
+let frame = df.group_by(sales, "region", {"rep": "sum"})
+"#));
+        assert!(bad.unwrap_err().contains("cannot sum column 'rep' containing string"));
+    }
+
+    #[test]
+    fn test_group_by_and_join_compare_keys_by_value() {
+        // Both keys display as ["a", "b", "c"], but they are different values
+        let result = run(r#"
+import "dataframe" as df
+let frame = df.from_rows([{"x": "a\", \"b", "y": "c", "n": 1}, {"x": "a", "y": "b\", \"c", "n": 2}])
+assert(len(df.group_by(frame, ["x", "y"], {"n": "sum"})) == 2, "one group per distinct key")
+let other = df.from_rows([{"x": "a", "y": "b\", \"c", "m": 3}])
+let joined = df.join(frame, other, ["x", "y"])
+assert(df.column(joined, "n") == [2], "only the equal key matches")
+"#);
+        assert!(result.is_ok(), "{:?}", result);
+    }
+
+    #[test]
+    fn test_group_by_int_sum_overflow() {
+        let err = run(r#"
+import "dataframe" as df
+let frame = df.from_rows([{"k": 1, "n": 9223372036854775807}, {"k": 1, "n": 1}])
+df.group_by(frame, "k", {"n": "sum"})
+"#).unwrap_err();
+        assert!(err.contains("integer overflow in the sum of column 'n'"), "{}", err);
+    }
+
+    #[test]
+    fn test_join_on_keys() {
+        let result = run(&format!("{}{}", SALES, r#"
+let managers = df.from_rows([{"region": "north", "rep": "max"}, {"region": "south", "rep": "liz"}])
+let inner = df.join(sales, managers, "region")
+assert(len(inner) == 4, "unmatched rows dropped")
+assert(df.columns(inner) == ["region", "rep", "amount", "rep_right"], "clashing names get a suffix")
+assert(df.column(inner, "rep_right")[1] == "liz", "matched on region")
+let left = df.join(sales, managers, ["region"], "left")
+assert(len(left) == 5, "left join keeps every left row")
+assert(df.column(left, "rep_right")[3] == nil, "unmatched right values are nil")
+"#));
+        assert!(result.is_ok(), "{:?}", result);
+    }
+
+    #[test]
+    fn test_iterate_and_display() {
+        let result = run(&format!("{}{}", SALES, r#"
+let total = 0
+for row in sales
+    if row["amount"] != nil then
+        total = total + row["amount"]
+    end
+end
+assert(total == 475, "for loop yields row dictionaries")
+"#));
+        assert!(result.is_ok(), "{:?}", result);
+
+        let frame = crate::dataframe::call_dataframe_function(
+            "df_from_csv",
+            vec![Value::String("city,pop\nOslo,700\nLima,9700\n".to_string())],
+            &mut |_, _| Ok(Value::Nil),
+        ).unwrap();
+        assert_eq!(
+            frame.to_string(),
+            "| city   | pop  |\n| string | int  |\n|--------|------|\n| Oslo   |  700 |\n| Lima   | 9700 |\n[2 rows x 2 columns]"
+        );
+    }
+}
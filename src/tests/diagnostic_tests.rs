@@ -0,0 +1,100 @@
+#[cfg(test)]
+mod tests {
+    use crate::codes::{lookup, CODES};
+    use crate::diagnostic::{json_report, sarif_report, Diagnostic, DiagnosticLevel, Span};
+
+    fn undeclared() -> Diagnostic {
+        Diagnostic::error("use of undeclared variable `nme`\n  help: did you mean `name`?".to_string())
+            .with_code("E0001".to_string())
+            .with_location(2, 7)
+            .with_source_line("print nme + 1".to_string())
+            .with_filename("main.ject".to_string())
+    }
+
+    fn unused() -> Diagnostic {
+        Diagnostic::warning("unused variable `x`".to_string())
+            .with_code("W0001".to_string())
+            .with_filename("util.ject".to_string())
+    }
+
+    // ========== Span Tests ==========
+
+    #[test]
+    fn test_span_covers_word_under_caret() {
+        assert_eq!(undeclared().span(), Some(Span { line: 2, column: 7, end_column: 10 }));
+        assert_eq!(unused().span(), None);
+
+        // A caret on punctuation still spans one column
+        let diagnostic = Diagnostic::error("x".to_string()).with_location(1, 3).with_source_line("a + b".to_string());
+        assert_eq!(diagnostic.span(), Some(Span { line: 1, column: 3, end_column: 4 }));
+    }
+
+    #[test]
+    fn test_help_split_from_message() {
+        let (message, help) = undeclared().message_and_help();
+        assert_eq!(message, "use of undeclared variable `nme`");
+        assert_eq!(help.as_deref(), Some("did you mean `name`?"));
+
+        let listed = Diagnostic::error("use of undeclared variable `y`\n  help: did you mean one of these?\n    - `x`\n    - `z`".to_string());
+        assert_eq!(listed.message_and_help().1.as_deref(), Some("did you mean one of these? `x`, `z`"));
+    }
+
+    // ========== Code Registry Tests ==========
+
+    #[test]
+    fn test_code_registry() {
+        let mut seen = std::collections::HashSet::new();
+        for info in CODES {
+            assert!(seen.insert(info.code), "{} is registered twice", info.code);
+            let expected = if info.code.starts_with('E') { DiagnosticLevel::Error } else { DiagnosticLevel::Warning };
+            assert_eq!(info.level, expected, "{}", info.code);
+            assert!(!info.title.is_empty() && !info.explanation.is_empty(), "{}", info.code);
+        }
+        assert_eq!(lookup("w0003").map(|info| info.title), Some("function redefined"));
+        assert!(lookup("E9999").is_none());
+    }
+
+    // ========== Report Tests ==========
+
+    #[test]
+    fn test_json_report() {
+        let report = json_report(&[undeclared(), unused()], 2);
+        let first = &report["diagnostics"][0];
+        assert_eq!(first["level"], "error");
+        assert_eq!(first["code"], "E0001");
+        assert_eq!(first["file"], "main.ject");
+        assert_eq!(first["message"], "use of undeclared variable `nme`");
+        assert_eq!(first["help"], "did you mean `name`?");
+        assert_eq!(first["span"]["line"], 2);
+        assert_eq!(first["span"]["column"], 7);
+        assert_eq!(first["span"]["end_column"], 10);
+        assert!(report["diagnostics"][1]["span"].is_null());
+        assert_eq!(report["summary"], serde_json::json!({"files": 2, "errors": 1, "warnings": 1}));
+    }
+
+    #[test]
+    fn test_sarif_report() {
+        let report = sarif_report(&[undeclared(), unused()]);
+        assert_eq!(report["version"], "2.1.0");
+        let run = &report["runs"][0];
+        assert_eq!(run["tool"]["driver"]["name"], "ject");
+        assert_eq!(run["tool"]["driver"]["rules"], serde_json::json!([
+            {"id": "E0001", "shortDescription": {"text": "use of undeclared variable"}},
+            {"id": "W0001", "shortDescription": {"text": "unused variable"}},
+        ]));
+
+        let result = &run["results"][0];
+        assert_eq!(result["ruleId"], "E0001");
+        assert_eq!(result["level"], "error");
+        assert_eq!(result["message"]["text"], "use of undeclared variable `nme`");
+        assert_eq!(result["properties"]["help"], "did you mean `name`?");
+        let location = &result["locations"][0]["physicalLocation"];
+        assert_eq!(location["artifactLocation"]["uri"], "main.ject");
+        assert_eq!(location["region"], serde_json::json!({"startLine": 2, "startColumn": 7, "endColumn": 10}));
+
+        // No position: the file alone
+        let location = &run["results"][1]["locations"][0]["physicalLocation"];
+        assert_eq!(location["artifactLocation"]["uri"], "util.ject");
+        assert!(location["region"].is_null());
+    }
+}
@@ -0,0 +1,143 @@
+#[cfg(test)]
+mod tests {
+    use crate::formatter::{format_source, FormatError};
+
+    fn fmt(source: &str) -> String {
+        let formatted = format_source(source).expect("source should format");
+        assert_eq!(format_source(&formatted).unwrap(), formatted, "formatting should be idempotent");
+        formatted
+    }
+
+    // ========== Indentation Tests ==========
+
+    #[test]
+    fn test_indents_blocks() {
+        let source = "fn sign(n)\nif n > 0 then\nreturn 1\nelseif n < 0 then\n  return -1\nelse\n        return 0\nend\nend\n";
+        assert_eq!(fmt(source), "\
+fn sign(n)
+    if n > 0 then
+        return 1
+    elseif n < 0 then
+        return -1
+    else
+        return 0
+    end
+end
+");
+    }
+
+    #[test]
+    fn test_indents_loops_match_and_try() {
+        let source = "for x in xs do\nwhile x > 0 do\nx -= 1\nend\nend\nmatch n\n1 -> \"one\"\n_ -> \"many\"\nend\ntry\nrisky()\ncatch e\nprint e\nend\n";
+        assert_eq!(fmt(source), "\
+for x in xs do
+    while x > 0 do
+        x -= 1
+    end
+end
+match n
+    1 -> \"one\"
+    _ -> \"many\"
+end
+try
+    risky()
+catch e
+    print e
+end
+");
+    }
+
+    #[test]
+    fn test_indents_braces_spanning_lines() {
+        assert_eq!(fmt("struct Point {\nx,\n   y\n}\n"), "struct Point {\n    x,\n    y\n}\n");
+        let source = "let f = lambda(x) -> {\nlet y = x * 2\n        return y\n}\nprint f(2)\n";
+        assert_eq!(fmt(source), "\
+let f = lambda(x) -> {
+    let y = x * 2
+    return y
+}
+print f(2)
+");
+        assert_eq!(fmt("let d = {\n\"a\": 1,\n  ...rest,\n\"b\": 2\n}\n"), "let d = {\n    \"a\": 1,\n    ...rest,\n    \"b\": 2\n}\n");
+        assert_eq!(fmt("let d = {\"a\": 1,\n\"b\": 2}\n"), "let d = {\"a\": 1,\n    \"b\": 2}\n");
+    }
+
+    #[test]
+    fn test_comprehension_if_is_not_a_block() {
+        assert_eq!(fmt("let evens = [ x for x in xs if x % 2 == 0 ]\nprint evens\n"),
+            "let evens = [x for x in xs if x % 2 == 0]\nprint evens\n");
+    }
+
+    #[test]
+    fn test_lambdas_do_not_open_blocks() {
+        assert_eq!(fmt("let double = lambda(x)->x*2\nprint double(2)\n"),
+            "let double = lambda(x) -> x * 2\nprint double(2)\n");
+    }
+
+    // ========== Spacing Tests ==========
+
+    #[test]
+    fn test_normalizes_spacing() {
+        assert_eq!(fmt("let   total=a+b*  2\n"), "let total = a + b * 2\n");
+        assert_eq!(fmt("print f( 1 ,2 )[ 0 ]\n"), "print f(1, 2)[0]\n");
+        assert_eq!(fmt("let p = new Point { x : 1, y: 2 }\n"), "let p = new Point {x: 1, y: 2}\n");
+        assert_eq!(fmt("print -x, !done, a - b\n"), "print -x, !done, a - b\n");
+    }
+
+    #[test]
+    fn test_slices_and_steps_stay_tight() {
+        assert_eq!(fmt("print a[1 : 3]\n"), "print a[1:3]\n");
+        assert_eq!(fmt("for i in 1..10:2 do\nprint i\nend\n"), "for i in 1..10:2 do\n    print i\nend\n");
+    }
+
+    // ========== Comment and Blank Line Tests ==========
+
+    #[test]
+    fn test_keeps_comments() {
+        let source = "# Header\nlet x = 1 # trailing\n#* block\n   comment *#\nif x then\n# inside\nprint x\nend\n";
+        assert_eq!(fmt(source), "\
+# Header
+let x = 1  # trailing
+#* block
+   comment *#
+if x then
+    # inside
+    print x
+end
+");
+    }
+
+    #[test]
+    fn test_block_comment_lines_move_with_the_first() {
+        let source = "if x then\n#* one\n   two\n *#\nend\n        #* three\n           four *#\n";
+        assert_eq!(fmt(source), "\
+if x then
+    #* one
+       two
+     *#
+end
+#* three
+   four *#
+");
+    }
+
+    #[test]
+    fn test_collapses_blank_lines() {
+        assert_eq!(fmt("\n\nlet a = 1\n\n\n\nlet b = 2   \n\n"), "let a = 1\n\nlet b = 2\n");
+    }
+
+    // ========== Error Tests ==========
+
+    #[test]
+    fn test_parse_errors_are_not_formatted() {
+        assert!(matches!(format_source("let = 5\n"), Err(FormatError::Parse(_))));
+    }
+
+    #[test]
+    fn test_formats_bundled_modules_idempotently() {
+        for name in ["array", "datetime", "io", "json", "math", "string", "system"] {
+            let source = std::fs::read_to_string(format!("stdlib/{}.ject", name)).unwrap();
+            fmt(&source);
+        }
+    }
+}
@@ -523,6 +523,58 @@ print fn_ref(2, 3)
         // assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_variadic_functions_and_spread_calls() {
+        let result = run(r#"
+fn log(level, ...msgs)
+    let line = level + ":"
+    for msg in msgs
+        line = line + " " + msg
+    end
+    return line
+end
+assert(log("info") == "info:", "rest parameter may be empty")
+assert(log("warn", "disk", "full") == "warn: disk full", "extra arguments are collected")
+let words = ["a", "b"]
+assert(log("debug", ...words, "c") == "debug: a b c", "spread into positional arguments")
+fn connect(host, port=80, tls=false)
+    return host + ":" + to_string(port) + ":" + to_string(tls)
+end
+let opts = {"port": 443, "tls": true}
+assert(connect("example.com", **opts) == "example.com:443:true", "dictionary spread into keywords")
+assert(connect(...["localhost", 8080]) == "localhost:8080:false", "spread fills defaults last")
+assert(max(...[3, 9, 1]) == 9, "spread into builtins")
+let add = lambda(a, b) -> a + b
+assert(add(...(2, 3)) == 5, "tuples spread too")
+"#);
+        assert!(result.is_ok(), "{:?}", result);
+
+        let too_many = run("fn f(a)\n    return a\nend\nf(...[1, 2])");
+        assert!(too_many.unwrap_err().contains("Too many positional arguments"));
+
+        let twice = run("fn f(a)\n    return a\nend\nf(1, **{\"a\": 2})");
+        assert!(twice.unwrap_err().contains("Argument 'a' specified multiple times"));
+    }
+
+    #[test]
+    fn test_array_and_dictionary_spread() {
+        let result = run(r#"
+let a = [1, 2]
+let b = [3]
+assert([...a, ...b, 4] == [1, 2, 3, 4], "array spread")
+assert([0, ...(5, 6)] == [0, 5, 6], "tuple spread into array")
+let defaults = {"host": "localhost", "port": 80}
+let overrides = {"port": 8080}
+let config = {...defaults, ...overrides, "debug": true}
+assert(config == {"host": "localhost", "port": 8080, "debug": true}, "later entries win")
+assert(keys(config) == ["host", "port", "debug"], "spread keeps key order")
+"#);
+        assert!(result.is_ok(), "{:?}", result);
+
+        let bad = run("let x = [...5]");
+        assert!(bad.unwrap_err().contains("Cannot spread int"));
+    }
+
     // ========== Lambda Tests ==========
 
     #[test]
@@ -807,6 +859,51 @@ print person["age"]
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_dictionary_insertion_order() {
+        let result = run(r#"
+let d = {zebra: 1, apple: 2, mango: 3}
+d["banana"] = 4
+d["apple"] = 20
+assert(to_string(d) == "{\"zebra\": 1, \"apple\": 20, \"mango\": 3, \"banana\": 4}", "display follows insertion order")
+let keys = []
+for k in d
+    keys = push(keys, k)
+end
+assert(keys == ["zebra", "apple", "mango", "banana"], "iteration follows insertion order")
+"#);
+        assert!(result.is_ok(), "{:?}", result);
+    }
+
+    #[test]
+    fn test_dictionary_non_string_keys() {
+        let result = run(r#"
+let counts = {}
+for id in [3, 1, 3, 2, 3]
+    if id in counts then
+        counts[id] = counts[id] + 1
+    else
+        counts[id] = 1
+    end
+end
+assert(counts[3] == 3 and counts[1] == 1, "int keys")
+assert(to_string(counts) == "{3: 3, 1: 1, 2: 1}", "display shows key values")
+let mixed = {true: "yes", 1: "one", "1": "string one"}
+assert(mixed[true] == "yes" and mixed[1] == "one" and mixed["1"] == "string one", "keys of different types are distinct")
+let grid = {[[0, 1]]: "computed"}
+grid[[2, 3]] = "assigned"
+assert(grid[[0, 1]] == "computed" and grid[[2, 3]] == "assigned", "array keys compare by value")
+struct Point { x, y }
+let labels = {}
+labels[new Point { x: 1, y: 2 }] = "p"
+assert(labels[new Point { y: 2, x: 1 }] == "p", "struct keys compare by fields")
+"#);
+        assert!(result.is_ok(), "{:?}", result);
+
+        let unhashable = run("let d = {}\nd[1.5] = 1");
+        assert!(unhashable.unwrap_err().contains("Dictionary keys must be hashable"));
+    }
+
     #[test]
     fn test_dictionary_missing_key() {
         let result = run(r#"
@@ -817,6 +914,202 @@ print person["age"]
         assert!(result.is_ok());
     }
 
+    // ========== Tuple and Destructuring Tests ==========
+
+    #[test]
+    fn test_tuple_return_and_let_destructuring() {
+        let result = run(r#"
+fn min_max(xs)
+    let sorted = sort(xs)
+    return sorted[0], sorted[-1]
+end
+let (lo, hi) = min_max([3, 9, 1])
+assert(lo == 1 and hi == 9, "return a, b gives a tuple")
+let pair = min_max([5])
+assert(type_of(pair) == "tuple" and pair[0] == 5 and len(pair) == 2, "tuples index like arrays")
+assert(to_string((1, "a")) == "(1, \"a\")" and to_string((1,)) == "(1,)", "tuple display")
+let (q, r) = divmod(-7, 2)
+assert(q == -4 and r == 1, "floored divmod")
+let [head, ..tail] = [1, 2, 3]
+assert(head == 1 and tail == [2, 3], "array rest pattern")
+let [a, [b, c]] = [1, (2, 3)]
+assert(a + b + c == 6, "nested patterns")
+let {name, age: years} = {"name": "Ada", "age": 36}
+assert(name == "Ada" and years == 36, "dictionary pattern with rename")
+struct Point { x, y }
+let {x, y} = new Point { x: 4, y: 5 }
+assert(x * y == 20, "struct fields destructure like keys")
+let seen = {(0, 1): "tuple key"}
+assert(seen[(0, 1)] == "tuple key", "tuples are hashable")
+"#);
+        assert!(result.is_ok(), "{:?}", result);
+    }
+
+    #[test]
+    fn test_for_destructuring() {
+        let result = run(r#"
+let totals = {"a": 1, "b": 2}
+let text = ""
+for (k, v) in items(totals)
+    text = text + k + to_string(v)
+end
+assert(text == "a1b2", "for over items() pairs")
+let sum = 0
+for [x, ..rest] in [[1, 2, 3], [10]]
+    sum = sum + x + len(rest)
+end
+assert(sum == 13, "array pattern in for")
+"#);
+        assert!(result.is_ok(), "{:?}", result);
+    }
+
+    #[test]
+    fn test_destructuring_errors() {
+        let short = run("let (a, b, c) = (1, 2)");
+        assert!(short.unwrap_err().contains("Cannot destructure 2 values into 3 names in (a, b, c)"));
+
+        let missing = run("let {name, email} = {\"name\": \"Ada\"}");
+        assert!(missing.unwrap_err().contains("Key 'email' not found in dictionary"));
+
+        let wrong_type = run("let [a, ..b] = 5");
+        assert!(wrong_type.unwrap_err().contains("Cannot destructure int into [a, ..b]"));
+    }
+
+    // ========== Constant Tests ==========
+
+    #[test]
+    fn test_constants() {
+        let result = run(r#"
+const LIMIT = 3
+assert(LIMIT == 3, "constants read like variables")
+fn shadow()
+    let LIMIT = 5
+    return LIMIT
+end
+assert(shadow() == 5, "an inner scope may shadow a constant")
+let len = 2
+assert(len == 2, "CorLib names can be shadowed with let")
+"#);
+        assert!(result.is_ok(), "{:?}", result);
+    }
+
+    #[test]
+    fn test_constants_in_loop_bodies() {
+        let result = run(r#"
+let n = 0
+let total = 0
+while n < 3
+    const SQUARE = n * n
+    total = total + SQUARE
+    n = n + 1
+end
+for i in [1, 2]
+    const DOUBLE = i * 2
+    total = total + DOUBLE
+end
+assert(total == 11, "each iteration declares its own constant")
+"#);
+        assert!(result.is_ok(), "{:?}", result);
+    }
+
+    #[test]
+    fn test_constant_errors() {
+        let reassign = run("const LIMIT = 3\nLIMIT = 4");
+        assert!(reassign.unwrap_err().contains("Cannot assign to constant 'LIMIT'"));
+
+        let mutate = run("const XS = [1, 2]\nXS[0] = 5");
+        assert!(mutate.unwrap_err().contains("Cannot modify constant 'XS'"));
+
+        let increment = run("const N = 1\nN++");
+        assert!(increment.unwrap_err().contains("Cannot assign to constant 'N'"));
+
+        let redeclare = run("const N = 1\nlet N = 2");
+        assert!(redeclare.unwrap_err().contains("Cannot redeclare constant 'N'"));
+
+        let builtin = run("PI = 3");
+        assert!(builtin.unwrap_err().contains("Cannot assign to constant 'PI'"));
+
+        let import = run("import \"math\" as m\nm = 1");
+        assert!(import.unwrap_err().contains("Cannot assign to constant 'm'"));
+    }
+
+    // ========== Lazy Range Tests ==========
+
+    #[test]
+    fn test_range_forms() {
+        let result = run(r#"
+assert(collect(0..5) == [0, 1, 2, 3, 4], "exclusive")
+assert(collect(1..=5) == [1, 2, 3, 4, 5], "inclusive")
+assert(collect(0..10 by 3) == [0, 3, 6, 9], "step")
+assert(collect(10..0 by -3) == [10, 7, 4, 1], "negative step")
+assert(collect(0..1 by 0.25) == [0.0, 0.25, 0.5, 0.75], "float step")
+assert(len(0..1 by 0.1) == 10, "float step stops before the end")
+assert(collect(5..1) == [], "empty when the step points away from the end")
+assert(type_of(0..3) == "range", "ranges are their own type")
+assert(range(5) == 0..5, "range() builds the same range")
+"#);
+        assert!(result.is_ok(), "{:?}", result);
+    }
+
+    #[test]
+    fn test_range_is_lazy() {
+        // A trillion elements would never fit in an array
+        let result = run(r#"
+let big = 0..1000000000000
+assert(len(big) == 1000000000000, "len is computed")
+assert(big[-1] == 999999999999, "negative index")
+assert(999999999998 in big, "membership is arithmetic")
+assert((5 in 0..10 by 2) == false, "off-step value")
+assert(0.5 in 0..2 by 0.25, "float membership")
+assert(big[10..13] == [10, 11, 12], "slicing a range stays lazy")
+let seen = 0
+for i in big
+    if i == 3 then
+        break
+    end
+    seen = seen + 1
+end
+assert(seen == 3, "for stops early without building the range")
+"#);
+        assert!(result.is_ok(), "{:?}", result);
+    }
+
+    #[test]
+    fn test_range_indexing_and_collecting() {
+        let result = run(r#"
+let arr = [10, 20, 30, 40, 50]
+assert(arr[1..=3] == [20, 30, 40], "inclusive slice")
+assert(arr[0..5 by 2] == [10, 30, 50], "stepped slice")
+assert(arr[-2..=-1] == [40, 50], "negative bounds")
+assert("hello"[1..=3] == "ell", "string slice")
+assert([...1..4, 9] == [1, 2, 3, 9], "spread collects")
+assert([x * x for x in 1..=3] == [1, 4, 9], "comprehension")
+assert(sum(1..=100) == 5050, "array builtins see the elements")
+assert(map(0..3, lambda(x) -> x + 1) == [1, 2, 3], "map")
+"#);
+        assert!(result.is_ok(), "{:?}", result);
+
+        let zero = run("let r = 0..10 by 0");
+        assert!(zero.unwrap_err().contains("Range step cannot be zero"));
+    }
+
+    #[test]
+    fn test_range_overflow_is_an_error() {
+        let huge = run("let n = len(-9223372036854775807..9223372036854775807)");
+        assert!(huge.unwrap_err().contains("has too many elements to count"));
+
+        let result = run(r#"
+let r = -9223372036854775807..9223372036854775807 by 2305843009213693952
+assert(len(r) == 8, "len of a wide stepped range")
+assert(r[1:2] == [-6917529027641081855], "a one-element slice")
+assert(r[0:4:2] == [-9223372036854775807, -4611686018427387903], "slice steps that still fit")
+"#);
+        assert!(result.is_ok(), "{:?}", result);
+
+        let step = run("let r = -9223372036854775807..9223372036854775807 by 2305843009213693952\nlet s = r[0:8:4]");
+        assert!(step.unwrap_err().contains("gives a step too large for an integer"));
+    }
+
     // ========== Struct Tests ==========
 
     #[test]
@@ -911,6 +1204,155 @@ print sqrt(16)
         assert!(result.is_ok() || result.is_err());
     }
 
+    /// Write module files into a fresh temp directory, returning its path
+    fn module_dir(name: &str, files: &[(&str, &str)]) -> String {
+        let dir = std::env::temp_dir().join(format!("ject_modules_{}_{}", std::process::id(), name));
+        std::fs::create_dir_all(&dir).unwrap();
+        for (file, source) in files {
+            std::fs::write(dir.join(file), source.replace("$DIR", &dir.to_string_lossy())).unwrap();
+        }
+        dir.to_string_lossy().into_owned()
+    }
+
+    #[test]
+    fn test_module_evaluated_once() {
+        // Every importer sees the same random draw, so the module ran once
+        let dir = module_dir("once", &[
+            ("token.ject", "export value = random()\n"),
+            ("user.ject", "import {value} from \"$DIR/token\"\nexport seen = value\n"),
+        ]);
+        let result = run(&format!(r#"
+import "{dir}/token" as first
+import {{value}} from "{dir}/token"
+import {{seen}} from "{dir}/user"
+assert(first.value == value, "alias and selective import share one evaluation")
+assert(seen == value, "a nested import shares it too")
+"#));
+        assert!(result.is_ok(), "{:?}", result);
+    }
+
+    #[test]
+    fn test_import_cycle_reports_chain() {
+        let dir = module_dir("cycle", &[
+            ("a.ject", "import \"$DIR/b\"\nexport x = 1\n"),
+            ("b.ject", "import \"$DIR/c\"\nexport y = 2\n"),
+            ("c.ject", "import \"$DIR/a\"\nexport z = 3\n"),
+        ]);
+        let err = run(&format!("import \"{}/a\"", dir)).unwrap_err();
+        assert!(err.contains("Import cycle:"), "{}", err);
+        assert!(err.contains("a.ject -> ") && err.contains("b.ject -> ") && err.contains("c.ject -> ") && err.ends_with("a.ject"), "{}", err);
+    }
+
+    /// Run a script with a configured interpreter instead of the default one
+    fn run_with(mut interpreter: Interpreter, input: &str) -> Result<(), String> {
+        let tokens = Lexer::new(input).tokenize_with_positions().into_iter().map(|lt| lt.token).collect();
+        let statements = Parser::new_simple(tokens).parse().map_err(|e| e.message)?;
+        interpreter.interpret(&statements).map(|_| ()).map_err(|e| e.message)
+    }
+
+    #[test]
+    fn test_relative_imports_follow_importing_file() {
+        // ./ and ../ are relative to the file doing the import, not the working directory
+        let dir = module_dir("relative", &[
+            ("shared.ject", "export base = 40\n"),
+            ("helper.ject", "import {base} from \"./shared\"\nexport answer = base + 2\n"),
+        ]);
+        std::fs::create_dir_all(format!("{}/app", dir)).unwrap();
+        let script = std::path::Path::new(&dir).join("app/main.ject");
+        let interpreter = Interpreter::new().with_script_path(&script);
+        let result = run_with(interpreter, r#"
+import {answer} from "../helper"
+assert(answer == 42, "helper found its sibling through ./")
+"#);
+        assert!(result.is_ok(), "{:?}", result);
+    }
+
+    #[test]
+    fn test_module_search_paths() {
+        let dir = module_dir("search", &[("greeting.ject", "export hello = \"hi\"\n")]);
+        let resolver = crate::resolver::ModuleResolver::new(vec![dir.into()]);
+        let result = run_with(Interpreter::new().with_resolver(resolver), r#"
+import {hello} from "greeting"
+assert(hello == "hi", "bare module names are looked up in the search paths")
+"#);
+        assert!(result.is_ok(), "{:?}", result);
+
+        let err = run(r#"import "no_such_module_anywhere""#).unwrap_err();
+        assert!(err.contains("Module 'no_such_module_anywhere' not found. Searched:"), "{}", err);
+    }
+
+    #[test]
+    fn test_reexports_and_namespaces() {
+        let dir = module_dir("reexport", &[
+            ("impl.ject", "let scale = 2\nexport fn area(r)\n    return r * r * scale\nend\nexport UNIT = 1\n"),
+            ("shapes.ject", "export {area} from \"./impl\"\nexport * from \"./impl\"\nexport SIDES = 0\n"),
+        ]);
+        // A nested module path is imported as a namespace named after its last part
+        let root = std::path::Path::new(&dir).parent().unwrap().to_path_buf();
+        let name = std::path::Path::new(&dir).file_name().unwrap().to_string_lossy().into_owned();
+        let resolver = crate::resolver::ModuleResolver::new(vec![root]);
+        let result = run_with(Interpreter::new().with_resolver(resolver), &format!(r#"
+import "{name}/shapes"
+assert(shapes.area(3) == 18, "re-exported function keeps its module's private state")
+assert(shapes.UNIT == 1 and shapes.SIDES == 0, "export * and own exports combine")
+"#));
+        assert!(result.is_ok(), "{:?}", result);
+    }
+
+    #[test]
+    fn test_private_names_cannot_be_imported() {
+        let dir = module_dir("private", &[
+            ("impl.ject", "let scale = 2\nfn helper()\n    return 1\nend\nexport UNIT = 1\n"),
+            ("shapes.ject", "export {scale} from \"./impl\"\n"),
+        ]);
+        let err = run(&format!("import {{helper}} from \"{}/impl\"", dir)).unwrap_err();
+        assert!(err.contains("'helper' is private to module"), "{}", err);
+        let err = run(&format!("import {{shapes}} from \"{}/shapes\"", dir)).unwrap_err();
+        assert!(err.contains("'scale' is private to module './impl'"), "{}", err);
+        let err = run(&format!("import {{missing}} from \"{}/impl\"", dir)).unwrap_err();
+        assert!(err.contains("does not export 'missing'"), "{}", err);
+    }
+
+    #[test]
+    fn test_bundled_stdlib_modules() {
+        use crate::resolver::{ModuleResolver, ModuleSource};
+
+        // The Ject-source stdlib is served from the binary, not from disk
+        let source = ModuleResolver::default().resolve("math", None).unwrap();
+        assert!(matches!(source, ModuleSource::Bundled { name: "math", .. }), "{:?}", source);
+        let result = run(r#"
+import {PHI} from "math"
+import "string" as s
+assert(PHI > 1.6 and PHI < 1.7, "bundled math exports PHI")
+assert(s.capitalize("ject") == "Ject", "bundled string module works")
+"#);
+        assert!(result.is_ok(), "{:?}", result);
+
+        // A module on the search path deliberately replaces the bundled one
+        let dir = module_dir("override", &[("math.ject", "export PHI = 2\n")]);
+        let resolver = ModuleResolver::new(vec![dir.into()]);
+        let result = run_with(Interpreter::new().with_resolver(resolver), r#"
+import {PHI} from "math"
+assert(PHI == 2, "local math.ject wins over the bundled one")
+"#);
+        assert!(result.is_ok(), "{:?}", result);
+    }
+
+    // ========== Script Argument Tests ==========
+
+    #[test]
+    fn test_script_args() {
+        let interpreter = Interpreter::new().with_args(vec!["data.csv".to_string(), "--verbose".to_string()]);
+        let result = run_with(interpreter, r#"
+assert(args() == ["data.csv", "--verbose"], "args() returns the command-line arguments")
+"#);
+        assert!(result.is_ok(), "{:?}", result);
+
+        let result = run(r#"assert(len(args()) == 0, "no arguments by default")"#);
+        assert!(result.is_ok(), "{:?}", result);
+        assert!(run("args(1)").unwrap_err().contains("args() takes no arguments"));
+    }
+
     // ========== Error Handling Tests ==========
 
     #[test]
@@ -981,6 +1423,47 @@ end
         assert!(result.is_ok());
     }
 
+    // ========== Decimal Tests ==========
+
+    #[test]
+    fn test_decimal_arithmetic_is_exact() {
+        let result = run(r#"
+assert(0.1d + 0.2d == 0.3d, "decimal addition should be exact")
+assert(12.50d * 3 == 37.5d, "decimal times int")
+assert(10d - 0.01d == 9.99d, "decimal subtraction")
+assert(1d / 4 == 0.25d, "decimal division")
+assert(7.5d % 2 == 1.5d, "decimal modulo")
+assert(-1.25d < 0, "negation")
+assert(type_of(1.5d) == "decimal", "type name")
+"#);
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_decimal_compares_with_int_and_float() {
+        let result = run(r#"
+assert(2.00d == 2, "decimal equals int")
+assert(1.5d > 1, "decimal greater than int")
+assert(1.5d == 1.5, "decimal equals float")
+assert(0.25d < 0.3, "decimal less than float")
+assert(3 >= 2.99d, "int compared with decimal")
+"#);
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_decimal_float_arithmetic_error() {
+        let result = run("let x = 1.5d + 0.5");
+        assert!(result.is_err());
+        assert!(result.unwrap_err().contains("Cannot mix decimal and float"));
+    }
+
+    #[test]
+    fn test_decimal_division_by_zero() {
+        let result = run("let x = 1.5d / 0");
+        assert!(result.is_err());
+    }
+
     // ========== Edge Cases and Bug Tests ==========
 
     #[test]
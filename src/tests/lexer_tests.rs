@@ -32,6 +32,23 @@ mod tests {
         assert_eq!(lexer.next_token().token, Token::Float(123.456));
     }
 
+    #[test]
+    fn test_decimal_literals() {
+        let mut lexer = Lexer::new("12.50d");
+        assert_eq!(lexer.next_token().token, Token::Decimal("12.50".parse().unwrap()));
+
+        let mut lexer = Lexer::new("7d");
+        assert_eq!(lexer.next_token().token, Token::Decimal(7.into()));
+
+        // A `d` that starts an identifier is not a suffix
+        let mut lexer = Lexer::new("3 do");
+        assert_eq!(lexer.next_token().token, Token::Integer(3));
+
+        // Too many digits for a decimal is an error, not zero
+        let mut lexer = Lexer::new("99999999999999999999999999999999.5d");
+        assert!(matches!(lexer.next_token().token, Token::Invalid(message) if message.contains("out of range")));
+    }
+
     #[test]
     fn test_string_literals() {
         let mut lexer = Lexer::new("\"hello\"");
@@ -263,6 +280,9 @@ mod tests {
         let mut lexer = Lexer::new("..");
         assert_eq!(lexer.next_token().token, Token::DotDot);
 
+        let mut lexer = Lexer::new("..=");
+        assert_eq!(lexer.next_token().token, Token::DotDotEq);
+
         let mut lexer = Lexer::new(":");
         assert_eq!(lexer.next_token().token, Token::Colon);
 
@@ -3,6 +3,7 @@ mod tests {
     use crate::lexer::Lexer;
     use crate::parser::Parser;
     use crate::linter::Linter;
+    use crate::diagnostic::DiagnosticLevel;
 
     fn lint(input: &str) -> (Vec<String>, Vec<String>) {
         let mut lexer = Lexer::new(input);
@@ -168,6 +169,21 @@ end
         assert!(!errors.iter().any(|e| e.contains("undefined")));
     }
 
+    #[test]
+    fn test_destructuring_declares_every_name() {
+        let (errors, warnings) = lint(r#"
+let (q, r) = divmod(7, 2)
+let [head, ..tail] = [1, 2, 3]
+for (k, v) in items({"a": 1})
+    print k, v
+end
+print q, r, head
+"#);
+        assert!(errors.is_empty(), "{:?}", errors);
+        assert!(warnings.iter().any(|w| w.contains("unused variable `tail`")));
+        assert!(!warnings.iter().any(|w| w.contains("`q`") || w.contains("`k`")));
+    }
+
     #[test]
     fn test_nested_scope_access() {
         let (errors, _) = lint(r#"
@@ -198,6 +214,39 @@ x = 42
         assert!(!errors.iter().any(|e| e.contains("undeclared")));
     }
 
+    #[test]
+    fn test_constant_cannot_be_reassigned() {
+        let (errors, _) = lint("const LIMIT = 10\nLIMIT = 20");
+        assert!(errors.iter().any(|e| e.contains("cannot assign to constant `LIMIT`")));
+
+        let (errors, _) = lint("const XS = [1, 2]\nXS[0] = 5\nXS++");
+        assert!(errors.iter().any(|e| e.contains("cannot modify constant `XS`")));
+        assert!(errors.iter().any(|e| e.contains("cannot assign to constant `XS`")));
+
+        let (errors, _) = lint("const LIMIT = 10\nlet LIMIT = 20");
+        assert!(errors.iter().any(|e| e.contains("cannot redeclare constant `LIMIT`")));
+
+        let (errors, _) = lint("PI = 3");
+        assert!(errors.iter().any(|e| e.contains("cannot assign to constant `PI`")));
+    }
+
+    #[test]
+    fn test_private_import() {
+        let dir = std::env::temp_dir().join(format!("ject_lint_private_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("shapes.ject"), "let scale = 2\nexport fn area(r)\n    return r * r * scale\nend\n").unwrap();
+
+        let source = "import {area, scale} from \"./shapes\"\nprint area(scale)";
+        let tokens = Lexer::new(source).tokenize_with_positions().into_iter().map(|lt| lt.token).collect();
+        let statements = Parser::new_simple(tokens).parse().unwrap();
+        let mut linter = Linter::new().with_file(&dir.join("main.ject"));
+        let (diagnostics, has_errors) = linter.lint(&statements);
+        assert!(has_errors);
+        let messages: Vec<&str> = diagnostics.iter().map(|d| d.message.as_str()).collect();
+        assert!(messages.iter().any(|m| m.contains("`scale` is private to module `./shapes`")), "{:?}", messages);
+        assert!(!messages.iter().any(|m| m.contains("`area`")), "{:?}", messages);
+    }
+
     // ========== Return Statement Tests ==========
 
     #[test]
@@ -359,6 +408,34 @@ greet()
         assert!(!errors.iter().any(|e| e.contains("undefined")));
     }
 
+    #[test]
+    fn test_variadic_and_spread_arity() {
+        let (errors, _) = lint(r#"
+fn log(level, ...msgs)
+    print level, msgs
+end
+fn pair(a, b)
+    return a + b
+end
+log("info", "a", "b", "c")
+log("info")
+let args = [1, 2]
+pair(...args)
+pair(**{"a": 1, "b": 2})
+"#);
+        assert!(errors.is_empty(), "{:?}", errors);
+
+        let (errors, _) = lint(r#"
+fn log(level, ...msgs)
+    print level, msgs
+end
+log()
+log("x", msgs=1)
+"#);
+        assert!(errors.iter().any(|e| e.contains("missing required argument `level`")));
+        assert!(errors.iter().any(|e| e.contains("unknown parameter `msgs`")));
+    }
+
     #[test]
     fn test_match_expression() {
         let (errors, _) = lint(r#"
@@ -486,4 +563,88 @@ calculate_sums()
         // Should not have errors because x was defined earlier
         assert!(!has_errors);
     }
+
+    // ========== Diagnostic Code Tests ==========
+
+    /// Lint with real token positions and source, as `ject check` does
+    fn lint_file(source: &str, linter: Linter) -> Vec<crate::diagnostic::Diagnostic> {
+        let tokens: Vec<_> = Lexer::new(source).tokenize_with_positions().into_iter()
+            .map(|lt| (lt.token, lt.position))
+            .collect();
+        let statements = Parser::new(tokens.clone()).parse().unwrap();
+        let mut linter = linter.with_tokens_and_source(tokens, source.to_string());
+        linter.lint(&statements).0
+    }
+
+    fn codes(diagnostics: &[crate::diagnostic::Diagnostic]) -> Vec<&str> {
+        diagnostics.iter().filter_map(|d| d.code.as_deref()).collect()
+    }
+
+    #[test]
+    fn test_each_check_has_its_own_code() {
+        let cases = [
+            ("print nope", "E0001"),
+            ("const A = 1\nlet A = 2\nprint A", "E0009"),
+            ("const A = 1\nA = 2", "E0010"),
+            ("count = 1", "E0011"),
+            ("return 1", "E0013"),
+            ("let p = new Point {x: 1}\nprint p", "E0015"),
+            ("fn f(a)\n    return a\nend\nprint f(1, 2)", "E0016"),
+            ("fn f(a)\n    return a\nend\nprint f(1, a=2)", "E0017"),
+            ("fn f(a)\n    return a\nend\nprint f(b=2)", "E0018"),
+            ("fn f(a, b)\n    return a\nend\nprint f(1)", "E0019"),
+            ("let x = 1", "W0001"),
+            ("let x = 1\nlet x = 2\nprint x", "W0002"),
+            ("fn f()\nend\nfn f()\nend", "W0003"),
+        ];
+        for (source, code) in cases {
+            let diagnostics = lint_file(source, Linter::new());
+            assert!(codes(&diagnostics).contains(&code), "{:?}: {:?}", source, codes(&diagnostics));
+            assert!(crate::codes::lookup(code).is_some(), "{} is not registered", code);
+        }
+    }
+
+    #[test]
+    fn test_redefinition_reported_where_it_happens() {
+        let diagnostics = lint_file("fn f()\nend\n\nfn f()\nend", Linter::new());
+        let redefined = diagnostics.iter().find(|d| d.code.as_deref() == Some("W0003")).unwrap();
+        assert_eq!(redefined.line, Some(4));
+    }
+
+    #[test]
+    fn test_allow_comments() {
+        // Trailing comment: that line; comment on its own: the next line
+        let source = "fn f()\nend\nfn f()  # ject: allow(W0003)\nend\n# ject: allow(W0001)\nlet unused = 1\nlet other = 2";
+        let diagnostics = lint_file(source, Linter::new());
+        let messages: Vec<&str> = diagnostics.iter().map(|d| d.message.as_str()).collect();
+        assert_eq!(messages, vec!["unused variable `other`"]);
+
+        // Whole file
+        let diagnostics = lint_file("# ject: allow-file(w0001)\nlet a = 1\nlet b = 2", Linter::new());
+        assert!(diagnostics.is_empty(), "{:?}", codes(&diagnostics));
+
+        // Errors cannot be allowed
+        let diagnostics = lint_file("print nope  # ject: allow(E0001)", Linter::new());
+        assert_eq!(codes(&diagnostics), vec!["E0001"]);
+
+        // Only comments count, not the same text inside a string
+        let diagnostics = lint_file("let s = \"# ject: allow(W0001)\"\nlet t = \"# ject: allow-file(W0001)\"", Linter::new());
+        assert_eq!(codes(&diagnostics), vec!["W0001", "W0001"]);
+    }
+
+    #[test]
+    fn test_denied_warnings_are_errors() {
+        let source = "let x = 1\nfn f()\nend\nfn f()\nend";
+        let denied = lint_file(source, Linter::new().with_denied(&["W0003".to_string()]));
+        let levels: Vec<(&str, &DiagnosticLevel)> = denied.iter().map(|d| (d.code.as_deref().unwrap(), &d.level)).collect();
+        assert!(levels.contains(&("W0003", &DiagnosticLevel::Error)), "{:?}", levels);
+        assert!(levels.contains(&("W0001", &DiagnosticLevel::Warning)), "{:?}", levels);
+
+        let all = lint_file(source, Linter::new().with_denied(&["warnings".to_string()]));
+        assert!(all.iter().all(|d| d.level == DiagnosticLevel::Error));
+
+        // An allowed warning stays silent even when denied
+        let allowed = lint_file("let x = 1  # ject: allow(W0001)", Linter::new().with_denied(&["warnings".to_string()]));
+        assert!(allowed.is_empty());
+    }
 }
@@ -0,0 +1,257 @@
+#[cfg(test)]
+mod tests {
+    use crate::analysis::{Analysis, SymbolKind};
+    use crate::lsp::{path_to_uri, read_message, uri_to_path, write_message, Server};
+    use crate::resolver::ModuleResolver;
+    use serde_json::{json, Value};
+    use std::path::PathBuf;
+
+    const MAIN: &str = "\
+import {area} from \"./shapes\"
+import \"./shapes\" as s
+struct Point { x, y }
+fn greet(name, greeting = \"Hello\")
+    let message = \"$greeting, ${name}!\"
+    return message
+end
+let total = area(2, 3) + s.area(1)
+print greet(\"Ada\"), len([total])
+let p = new Point {x: 1, y: 2}
+print [v * 2 for v in [1, 2] if v > 1], p
+";
+
+    const SHAPES: &str = "\
+export fn area(w, h = 1)
+    return w * h
+end
+export UNIT = 1
+";
+
+    /// A fresh temp directory holding main.ject and shapes.ject
+    fn temp_project(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("ject_lsp_{}_{}", std::process::id(), name));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("main.ject"), MAIN).unwrap();
+        std::fs::write(dir.join("shapes.ject"), SHAPES).unwrap();
+        dir
+    }
+
+    /// A server with main.ject open, and its URI
+    fn open(name: &str) -> (Server, String, PathBuf) {
+        let dir = temp_project(name);
+        let uri = path_to_uri(&dir.join("main.ject"));
+        let mut server = Server::new(ModuleResolver::default(), Vec::new());
+        server.handle(&json!({ "jsonrpc": "2.0", "id": 1, "method": "initialize", "params": {} }));
+        open_document(&mut server, &uri, MAIN);
+        (server, uri, dir)
+    }
+
+    fn open_document(server: &mut Server, uri: &str, text: &str) -> Vec<Value> {
+        server.handle(&json!({
+            "jsonrpc": "2.0",
+            "method": "textDocument/didOpen",
+            "params": { "textDocument": { "uri": uri, "languageId": "ject", "version": 1, "text": text } },
+        }))
+    }
+
+    fn request(server: &mut Server, method: &str, uri: &str, line: u64, character: u64, extra: Value) -> Value {
+        let mut params = json!({ "textDocument": { "uri": uri }, "position": { "line": line, "character": character } });
+        if let (Some(params), Some(extra)) = (params.as_object_mut(), extra.as_object()) {
+            params.extend(extra.clone());
+        }
+        let replies = server.handle(&json!({ "jsonrpc": "2.0", "id": 2, "method": method, "params": params }));
+        assert_eq!(replies.len(), 1);
+        replies[0].clone()
+    }
+
+    fn range(line: u64, start: u64, end: u64) -> Value {
+        json!({ "start": { "line": line, "character": start }, "end": { "line": line, "character": end } })
+    }
+
+    // ========== Analysis Tests ==========
+
+    #[test]
+    fn test_names_resolve_to_innermost_declaration() {
+        let source = "let x = 1\nfn f(x)\n    return x\nend\nprint x\n";
+        let analysis = Analysis::new(source);
+        let uses: Vec<_> = analysis.occurrences.iter().filter(|o| o.name == "x").collect();
+        let kinds: Vec<_> = uses.iter().map(|o| analysis.symbols[o.symbol.unwrap()].kind).collect();
+        assert_eq!(kinds, [SymbolKind::Variable, SymbolKind::Parameter, SymbolKind::Parameter, SymbolKind::Variable]);
+    }
+
+    #[test]
+    fn test_let_reads_the_outer_name() {
+        let analysis = Analysis::new("let n = 1\nfn f()\n    let n = n + 1\n    return n\nend\n");
+        let symbols: Vec<_> = analysis.occurrences.iter().filter(|o| o.name == "n").map(|o| o.symbol).collect();
+        assert_eq!(symbols, [Some(0), Some(2), Some(0), Some(2)]);
+    }
+
+    #[test]
+    fn test_functions_resolve_before_their_definition() {
+        let analysis = Analysis::new("fn main()\n    helper()\nend\nfn helper()\nend\n");
+        let call = analysis.occurrences.iter().find(|o| o.name == "helper" && o.position.line == 2).unwrap();
+        assert_eq!(analysis.symbols[call.symbol.unwrap()].kind, SymbolKind::Function);
+    }
+
+    #[test]
+    fn test_labels_and_members_are_not_variables() {
+        let analysis = Analysis::new("let x = 1\nlet d = {x: x}\nprint d.x, sep: \", \"\nprint f(x=2)\n");
+        let uses: Vec<_> = analysis.occurrences.iter().filter(|o| o.name == "x").collect();
+        // The declaration, the value in the dictionary and the member, but not the key or keyword
+        assert_eq!(uses.len(), 3);
+        assert_eq!(uses[2].member_of.as_deref(), Some("d"));
+        assert!(uses[2].symbol.is_none());
+        assert!(!analysis.occurrences.iter().any(|o| o.name == "sep"));
+    }
+
+    #[test]
+    fn test_visible_names_follow_scopes() {
+        let source = "let a = 1\nfn f(b)\n    let c = 2\n    \nend\n";
+        let analysis = Analysis::new(source);
+        let inside = source.find("    \n").unwrap() + 2;
+        let mut names: Vec<_> = analysis.visible_at(inside).iter().map(|s| s.name.clone()).collect();
+        names.sort();
+        assert_eq!(names, ["a", "b", "c", "f"]);
+        let mut outside: Vec<_> = analysis.visible_at(source.len()).iter().map(|s| s.name.clone()).collect();
+        outside.sort();
+        assert_eq!(outside, ["a", "f"]);
+    }
+
+    // ========== Protocol Tests ==========
+
+    #[test]
+    fn test_message_framing() {
+        let mut buffer = Vec::new();
+        write_message(&mut buffer, &json!({ "jsonrpc": "2.0", "id": 1, "result": "é" })).unwrap();
+        assert!(buffer.starts_with(b"Content-Length: 38\r\n\r\n"));
+        let mut input = &buffer[..];
+        assert_eq!(read_message(&mut input).unwrap().unwrap()["result"], "é");
+        assert!(read_message(&mut input).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_lifecycle() {
+        let mut server = Server::new(ModuleResolver::default(), Vec::new());
+        let reply = &server.handle(&json!({ "jsonrpc": "2.0", "id": 1, "method": "initialize", "params": {} }))[0];
+        assert_eq!(reply["result"]["capabilities"]["renameProvider"], true);
+        let unknown = &server.handle(&json!({ "jsonrpc": "2.0", "id": 2, "method": "workspace/symbol", "params": {} }))[0];
+        assert_eq!(unknown["error"]["code"], -32601);
+
+        let input = b"Content-Length: 44\r\n\r\n{\"jsonrpc\":\"2.0\",\"id\":3,\"method\":\"shutdown\"}Content-Length: 33\r\n\r\n{\"jsonrpc\":\"2.0\",\"method\":\"exit\"}";
+        let mut output = Vec::new();
+        assert!(server.run(&mut &input[..], &mut output).unwrap());
+        assert!(String::from_utf8(output).unwrap().contains("\"id\":3"));
+    }
+
+    #[test]
+    fn test_uri_paths() {
+        assert_eq!(uri_to_path("file:///tmp/my%20code/a.ject"), Some(PathBuf::from("/tmp/my code/a.ject")));
+        assert_eq!(uri_to_path("untitled:Untitled-1"), None);
+    }
+
+    // ========== Feature Tests ==========
+
+    #[test]
+    fn test_publishes_diagnostics_on_change() {
+        let (mut server, uri, _) = open("diagnostics");
+        let replies = server.handle(&json!({
+            "jsonrpc": "2.0",
+            "method": "textDocument/didChange",
+            "params": { "textDocument": { "uri": uri, "version": 2 }, "contentChanges": [{ "text": "print missing\n" }] },
+        }));
+        assert_eq!(replies[0]["method"], "textDocument/publishDiagnostics");
+        let diagnostic = &replies[0]["params"]["diagnostics"][0];
+        assert_eq!(diagnostic["code"], "E0001");
+        assert_eq!(diagnostic["severity"], 1);
+        assert_eq!(diagnostic["range"], range(0, 6, 13));
+    }
+
+    #[test]
+    fn test_definition() {
+        let (mut server, uri, dir) = open("definition");
+        let shapes = path_to_uri(&dir.join("shapes.ject"));
+
+        // A local function, a struct, an imported function and a module member
+        let greet = request(&mut server, "textDocument/definition", &uri, 8, 7, json!({}));
+        assert_eq!(greet["result"], json!({ "uri": uri, "range": range(3, 3, 8) }));
+        let point = request(&mut server, "textDocument/definition", &uri, 9, 14, json!({}));
+        assert_eq!(point["result"]["range"], range(2, 7, 12));
+        let area = request(&mut server, "textDocument/definition", &uri, 7, 14, json!({}));
+        assert_eq!(area["result"], json!({ "uri": shapes, "range": range(0, 10, 14) }));
+        let member = request(&mut server, "textDocument/definition", &uri, 7, 29, json!({}));
+        assert_eq!(member["result"]["uri"], shapes);
+
+        // The module path itself opens the module
+        let module = request(&mut server, "textDocument/definition", &uri, 0, 22, json!({}));
+        assert_eq!(module["result"], json!({ "uri": shapes, "range": range(0, 0, 0) }));
+    }
+
+    #[test]
+    fn test_hover() {
+        let (mut server, uri, _) = open("hover");
+        let greet = request(&mut server, "textDocument/hover", &uri, 8, 7, json!({}));
+        assert_eq!(greet["result"]["contents"]["value"], "```ject\nfn greet(name, greeting=\"Hello\")\n```");
+        let area = request(&mut server, "textDocument/hover", &uri, 7, 14, json!({}));
+        assert_eq!(area["result"]["contents"]["value"], "```ject\nfn area(w, h=1)\n```\n\nfrom \"./shapes\"");
+        let len = request(&mut server, "textDocument/hover", &uri, 8, 22, json!({}));
+        assert!(len["result"]["contents"]["value"].as_str().unwrap().starts_with("```ject\nlen(collection)\n```\n\nThe number"));
+        let nothing = request(&mut server, "textDocument/hover", &uri, 2, 0, json!({}));
+        assert_eq!(nothing["result"], Value::Null);
+    }
+
+    #[test]
+    fn test_completion() {
+        let (mut server, uri, _) = open("completion");
+        let labels = |reply: &Value| -> Vec<String> {
+            reply["result"].as_array().unwrap().iter().map(|item| item["label"].as_str().unwrap().to_string()).collect()
+        };
+
+        // Inside greet: its locals, file names, imports and builtins
+        let inside = labels(&request(&mut server, "textDocument/completion", &uri, 5, 4, json!({})));
+        for name in ["message", "name", "greeting", "greet", "Point", "area", "s", "len", "PI"] {
+            assert!(inside.contains(&name.to_string()), "missing {}", name);
+        }
+        let outside = labels(&request(&mut server, "textDocument/completion", &uri, 10, 0, json!({})));
+        assert!(!outside.contains(&"message".to_string()));
+
+        // After `s.`: the module's exports
+        let members = labels(&request(&mut server, "textDocument/completion", &uri, 7, 27, json!({})));
+        assert_eq!(members, ["area", "UNIT"]);
+    }
+
+    #[test]
+    fn test_document_symbols() {
+        let (mut server, uri, _) = open("symbols");
+        let reply = server.handle(&json!({
+            "jsonrpc": "2.0", "id": 2, "method": "textDocument/documentSymbol", "params": { "textDocument": { "uri": uri } },
+        }));
+        let symbols = reply[0]["result"].as_array().unwrap();
+        let names: Vec<_> = symbols.iter().map(|s| s["name"].as_str().unwrap()).collect();
+        assert_eq!(names, ["s", "Point", "greet", "total", "p"]);
+        let greet = &symbols[2];
+        assert_eq!(greet["kind"], 12);
+        assert_eq!(greet["range"], json!({ "start": { "line": 3, "character": 0 }, "end": { "line": 6, "character": 3 } }));
+        assert_eq!(greet["children"][0]["name"], "message");
+    }
+
+    #[test]
+    fn test_rename() {
+        let (mut server, uri, _) = open("rename");
+        let reply = request(&mut server, "textDocument/rename", &uri, 3, 10, json!({ "newName": "who" }));
+        let edits = reply["result"]["changes"][&uri].as_array().unwrap();
+        // The parameter and its use inside the interpolated string
+        let ranges: Vec<_> = edits.iter().map(|edit| edit["range"].clone()).collect();
+        assert_eq!(ranges, [range(3, 9, 13), range(4, 32, 36)]);
+
+        let comprehension = request(&mut server, "textDocument/rename", &uri, 10, 7, json!({ "newName": "w" }));
+        assert_eq!(comprehension["result"]["changes"][&uri].as_array().unwrap().len(), 3);
+
+        let keyword = request(&mut server, "textDocument/rename", &uri, 3, 10, json!({ "newName": "end" }));
+        assert_eq!(keyword["error"]["code"], -32602);
+        let builtin = request(&mut server, "textDocument/rename", &uri, 8, 22, json!({ "newName": "size" }));
+        assert_eq!(builtin["error"]["message"], "`len` is a builtin");
+        let imported = request(&mut server, "textDocument/rename", &uri, 7, 14, json!({ "newName": "size" }));
+        assert!(imported["error"]["message"].as_str().unwrap().contains("rename it there"));
+    }
+}
@@ -12,3 +12,24 @@ mod stdlib_tests;
 
 #[cfg(test)]
 mod linter_tests;
+
+#[cfg(test)]
+mod numpy_tests;
+
+#[cfg(test)]
+mod csv_tests;
+
+#[cfg(test)]
+mod dataframe_tests;
+
+#[cfg(test)]
+mod package_tests;
+
+#[cfg(test)]
+mod diagnostic_tests;
+
+#[cfg(test)]
+mod formatter_tests;
+
+#[cfg(test)]
+mod lsp_tests;
@@ -0,0 +1,510 @@
+#[cfg(test)]
+mod tests {
+    use crate::lexer::Lexer;
+    use crate::parser::Parser;
+    use crate::interpreter::Interpreter;
+
+    fn run(input: &str) -> Result<(), String> {
+        let mut lexer = Lexer::new(input);
+        let located_tokens = lexer.tokenize_with_positions();
+        let tokens: Vec<_> = located_tokens.into_iter().map(|lt| lt.token).collect();
+        let mut parser = Parser::new_simple(tokens);
+        let statements = parser.parse().map_err(|e| e.message)?;
+
+        let mut interpreter = Interpreter::new();
+        interpreter.interpret(&statements).map_err(|e| e.message)?;
+
+        Ok(())
+    }
+
+    // ========== Operator Tests ==========
+
+    #[test]
+    fn test_scalar_arithmetic() {
+        let result = run(r#"
+import "numpy" as np
+let a = np.array([1, 2, 3])
+assert(np.all(a + 1 == np.array([2, 3, 4])), "a + 1")
+assert(np.all(2 * a == np.array([2, 4, 6])), "2 * a")
+assert(np.all(10 - a == np.array([9, 8, 7])), "scalar on the left keeps operand order")
+assert(np.all(a / 2 == np.array([0.5, 1, 1.5])), "a / 2")
+assert(np.all(-a == np.array([-1, -2, -3])), "unary minus")
+"#);
+        assert!(result.is_ok(), "{:?}", result);
+    }
+
+    #[test]
+    fn test_integer_modulo_edge_cases() {
+        let result = run(r#"
+import "numpy" as np
+let x = np.permutation(1) - 9223372036854775807 - 1
+assert(np.all(x % -1 == 0), "i64 min % -1 is 0")
+assert(np.all(x % 7 == 6), "floor modulo of i64 min")
+let big = np.permutation(1) + 9223372036854775806
+assert(np.all(big % (big + 1) == 9223372036854775806), "remainder near i64 max doesn't overflow")
+let a = np.permutation(1) + 7
+assert(np.all(a % -3 == -2), "result takes the sign of the divisor")
+assert(np.all((a - 14) % 3 == 2), "negative dividend, positive divisor")
+assert(np.all((a - 14) % -3 == -1), "both negative")
+assert(np.dtype(a % 3) == "int64", "stays int64")
+"#);
+        assert!(result.is_ok(), "{:?}", result);
+    }
+
+    #[test]
+    fn test_broadcasting() {
+        let result = run(r#"
+import "numpy" as np
+let m = np.reshape(np.array([0, 1, 2, 3, 4, 5]), [2, 3])
+let row = np.array([10, 20, 30])
+let col = np.reshape(np.array([100, 200]), [2, 1])
+assert(np.all(m + row == np.reshape(np.array([10, 21, 32, 13, 24, 35]), [2, 3])), "row broadcast")
+assert(np.all(m + col == np.reshape(np.array([100, 101, 102, 203, 204, 205]), [2, 3])), "column broadcast")
+assert(np.shape(row + col) == [2, 3], "both operands stretch")
+"#);
+        assert!(result.is_ok(), "{:?}", result);
+    }
+
+    #[test]
+    fn test_broadcasting_shape_mismatch() {
+        let result = run(r#"
+import "numpy" as np
+let x = np.reshape(np.array([0, 1, 2, 3, 4, 5]), [2, 3]) + np.array([1, 2])
+"#);
+        assert!(result.unwrap_err().contains("could not be broadcast"));
+    }
+
+    #[test]
+    fn test_comparisons_produce_masks() {
+        let result = run(r#"
+import "numpy" as np
+let a = np.array([0.2, 0.7, 0.9])
+let mask = a > 0.5
+assert(np.dtype(mask) == "bool", "comparison gives a boolean array")
+assert(np.sum(mask) == 2, "two elements above 0.5")
+"#);
+        assert!(result.is_ok(), "{:?}", result);
+    }
+
+    // ========== Indexing Tests ==========
+
+    #[test]
+    fn test_multi_dimensional_indexing() {
+        let result = run(r#"
+import "numpy" as np
+let m = np.reshape(np.array([0, 1, 2, 3, 4, 5]), [2, 3])
+assert(m[1, 2] == 5, "scalar element")
+assert(np.all(m[1, :] == np.array([3, 4, 5])), "row")
+assert(np.all(m[:, 0] == np.array([0, 3])), "column")
+assert(np.all(m[-1, ::-1] == np.array([5, 4, 3])), "negative index and reversed slice")
+assert(np.all(m[0] == np.array([0, 1, 2])), "missing axes are taken whole")
+assert(np.shape(m[0:1]) == [1, 3], "slice keeps the axis")
+"#);
+        assert!(result.is_ok(), "{:?}", result);
+    }
+
+    #[test]
+    fn test_index_out_of_bounds() {
+        let result = run(r#"
+import "numpy" as np
+let m = np.reshape(np.array([0, 1, 2, 3, 4, 5]), [2, 3])
+let x = m[0, 3]
+"#);
+        assert!(result.unwrap_err().contains("out of bounds"));
+    }
+
+    #[test]
+    fn test_boolean_mask_indexing() {
+        let result = run(r#"
+import "numpy" as np
+let a = np.array([-2, 3, -1, 4])
+assert(np.all(a[a > 0] == np.array([3, 4])), "mask selects positive values")
+assert(np.all(a[[0, 3]] == np.array([-2, 4])), "integer list selects rows")
+"#);
+        assert!(result.is_ok(), "{:?}", result);
+    }
+
+    #[test]
+    fn test_slice_and_mask_assignment() {
+        let result = run(r#"
+import "numpy" as np
+let m = np.reshape(np.array([0, 1, 2, 3, 4, 5]), [2, 3])
+m[m > 3] = 0
+assert(np.all(m == np.reshape(np.array([0, 1, 2, 3, 0, 0]), [2, 3])), "mask assignment")
+m[0, :] = np.array([7, 8, 9])
+assert(np.all(m[0] == np.array([7, 8, 9])), "row assignment")
+m[:, 1] = -1
+assert(np.all(m[:, 1] == np.array([-1, -1])), "scalar broadcast into a column")
+m[1, 0:2] += 10
+assert(np.all(m[1] == np.array([13, 9, 0])), "compound slice assignment")
+"#);
+        assert!(result.is_ok(), "{:?}", result);
+    }
+
+    // ========== Random Tests ==========
+
+    #[test]
+    fn test_seed_is_reproducible() {
+        let result = run(r#"
+import "numpy" as np
+np.seed(42)
+let a = np.randn([3, 2])
+let c = np.choice([10, 20, 30], 5)
+np.seed(42)
+let b = np.randn([3, 2])
+let d = np.choice([10, 20, 30], 5)
+assert(np.all(a == b), "same seed gives the same normals")
+assert(np.all(c == d), "same seed gives the same choices")
+assert(np.shape(a) == [3, 2], "randn takes a shape")
+"#);
+        assert!(result.is_ok(), "{:?}", result);
+    }
+
+    #[test]
+    fn test_distributions_with_shapes() {
+        let result = run(r#"
+import "numpy" as np
+np.seed(7)
+let u = np.uniform(5, 6, [4, 4])
+assert(np.min(u) >= 5 and np.max(u) < 6, "uniform stays in [low, high)")
+assert(np.shape(np.normal(0, 1, 10)) == [10], "normal with an int size")
+assert(type_of(np.normal()) == "float", "no size gives a scalar")
+let b = np.binomial(10, 0.5, [100])
+assert(np.min(b) >= 0 and np.max(b) <= 10, "binomial bounded by n")
+assert(type_of(np.poisson(3)) == "int", "poisson draws are ints")
+"#);
+        assert!(result.is_ok(), "{:?}", result);
+    }
+
+    #[test]
+    fn test_shuffle_and_permutation() {
+        let result = run(r#"
+import "numpy" as np
+np.seed(1)
+let p = np.permutation(6)
+assert(np.all(np.sort(p) == np.arange(0, 6)), "permutation of n covers 0..n")
+let s = np.shuffle([1, 2, 3, 4])
+assert(len(s) == 4 and sum(s) == 10, "shuffle keeps the elements")
+let picked = np.choice(5, 5, false)
+assert(np.all(np.sort(picked) == np.arange(0, 5)), "choice without replacement has no repeats")
+"#);
+        assert!(result.is_ok(), "{:?}", result);
+    }
+
+    // ========== Reduction Tests ==========
+
+    #[test]
+    fn test_axis_reductions() {
+        let result = run(r#"
+import "numpy" as np
+let m = np.reshape(np.arange(0, 6), [2, 3])
+assert(np.all(np.sum(m, axis=0) == np.array([3, 5, 7])), "sum down columns")
+assert(np.all(np.sum(m, axis=-1) == np.array([3, 12])), "negative axis counts from the end")
+assert(np.shape(np.mean(m, axis=1, keepdims=true)) == [2, 1], "keepdims keeps a length-1 axis")
+assert(np.all(np.max(m, 0) == np.array([3, 4, 5])), "axis as a positional argument")
+assert(np.all(np.argmax(m, axis=1) == np.array([2, 2])), "argmax per row")
+assert(np.sum(m) == 15, "no axis reduces everything")
+"#);
+        assert!(result.is_ok(), "{:?}", result);
+    }
+
+    #[test]
+    fn test_reductions_keep_integer_dtype() {
+        let result = run(r#"
+import "numpy" as np
+let ints = np.permutation(5)
+assert(type_of(np.sum(ints)) == "int", "sum of ints is an int")
+assert(type_of(np.max(ints)) == "int", "max of ints is an int")
+assert(np.dtype(np.cumsum(ints)) == "int64", "cumsum keeps int64")
+assert(type_of(np.mean(ints)) == "float", "mean is always a float")
+"#);
+        assert!(result.is_ok(), "{:?}", result);
+    }
+
+    #[test]
+    fn test_sort_along_axis() {
+        let result = run(r#"
+import "numpy" as np
+let m = np.reshape(np.array([3, 1, 2, 0, 5, 4]), [2, 3])
+assert(np.all(np.sort(m) == np.reshape(np.array([1, 2, 3, 0, 4, 5]), [2, 3])), "rows sorted by default")
+assert(np.all(np.sort(m, axis=0) == np.reshape(np.array([0, 1, 2, 3, 5, 4]), [2, 3])), "columns sorted")
+assert(np.shape(np.sort(m, axis=nil)) == [6], "nil axis flattens")
+assert(np.all(np.argsort(m, axis=1)[0] == np.array([1, 2, 0])), "argsort per row")
+"#);
+        assert!(result.is_ok(), "{:?}", result);
+    }
+
+    #[test]
+    fn test_statistics_and_nan_functions() {
+        let result = run(r#"
+import "numpy" as np
+let a = np.array([1, 3, 2, 4])
+assert(np.median(a) == 2.5, "median of an even count")
+assert(np.percentile(a, 25) == 1.75, "percentile interpolates linearly")
+assert(np.all(np.percentile(a, [0, 100]) == np.array([1, 4])), "array of q values")
+assert(np.all(np.cumprod(a) == np.array([1, 3, 6, 24])), "cumprod")
+let b = np.array([1, np.nan, 3])
+assert(np.nansum(b) == 4, "nansum skips nan")
+assert(np.nanmean(b) == 2, "nanmean skips nan")
+"#);
+        assert!(result.is_ok(), "{:?}", result);
+    }
+
+    #[test]
+    fn test_reduction_argument_errors() {
+        let m = r#"
+import "numpy" as np
+let m = np.zeros([2, 2])
+"#;
+        let out_of_bounds = run(&format!("{}let s = np.sum(m, axis=2)", m));
+        assert!(out_of_bounds.unwrap_err().contains("out of bounds"));
+        let unknown = run(&format!("{}let s = np.sum(m, axes=0)", m));
+        assert!(unknown.unwrap_err().contains("unexpected keyword argument 'axes'"));
+    }
+
+    // ========== Linear Algebra Tests ==========
+
+    #[test]
+    fn test_linalg_inv_det_solve() {
+        let result = run(r#"
+import "numpy" as np
+let a = [[4, 7], [2, 6]]
+assert(abs(np.linalg.det(a) - 10) < 1e-9, "det of a 2x2")
+let identity = np.matmul(np.reshape(np.array([4, 7, 2, 6]), [2, 2]), np.linalg.inv(a))
+assert(np.max(np.abs(identity - np.eye(2))) < 1e-9, "a @ inv(a) is the identity")
+let x = np.linalg.solve(a, [1, 2])
+assert(np.max(np.abs(x - np.array([-0.8, 0.6]))) < 1e-9, "solve a vector system")
+"#);
+        assert!(result.is_ok(), "{:?}", result);
+    }
+
+    #[test]
+    fn test_linalg_lstsq_fits_a_line() {
+        let result = run(r#"
+import "numpy" as np
+# y = 2x + 1 sampled at x = 0..3, design matrix [x, 1]
+let design = [[0, 1], [1, 1], [2, 1], [3, 1]]
+let coef = np.linalg.lstsq(design, [1, 3, 5, 7])
+assert(np.max(np.abs(coef - np.array([2, 1]))) < 1e-9, "recovers slope and intercept")
+assert(abs(np.linalg.norm([3, 4]) - 5) < 1e-12, "vector 2-norm")
+assert(np.linalg.norm([[1, -2], [3, 4]], 1) == 6, "matrix 1-norm is the max column sum")
+"#);
+        assert!(result.is_ok(), "{:?}", result);
+    }
+
+    #[test]
+    fn test_linalg_decompositions() {
+        let result = run(r#"
+import "numpy" as np
+let a = np.reshape(np.array([4, 2, 0, 2, 5, 3, 0, 3, 6]), [3, 3])
+
+let qr = np.linalg.qr(a)
+assert(np.max(np.abs(np.matmul(qr[0], qr[1]) - a)) < 1e-9, "q @ r == a")
+
+let l = np.linalg.cholesky(a)
+assert(np.max(np.abs(np.matmul(l, np.transpose(l)) - a)) < 1e-9, "l @ l.T == a")
+
+let eig = np.linalg.eigh(a)
+let w = eig[0]
+let v = eig[1]
+assert(w[0] <= w[1] and w[1] <= w[2], "eigenvalues ascending")
+assert(np.max(np.abs(np.matmul(a, v) - v * w)) < 1e-9, "a v = v diag(w)")
+
+let svd = np.linalg.svd(np.reshape(np.array([1, 2, 3, 4, 5, 6]), [2, 3]))
+let rebuilt = np.matmul(svd[0] * svd[1], svd[2])
+assert(np.max(np.abs(rebuilt - np.reshape(np.array([1, 2, 3, 4, 5, 6]), [2, 3]))) < 1e-9, "u s vt == a")
+"#);
+        assert!(result.is_ok(), "{:?}", result);
+    }
+
+    #[test]
+    fn test_linalg_errors() {
+        let singular = run(r#"
+import "numpy" as np
+let x = np.linalg.inv([[1, 2], [2, 4]])
+"#);
+        assert!(singular.unwrap_err().contains("singular"));
+        let not_pd = run(r#"
+import "numpy" as np
+let l = np.linalg.cholesky([[1, 2], [2, 1]])
+"#);
+        assert!(not_pd.unwrap_err().contains("not positive definite"));
+    }
+
+    #[test]
+    fn test_linalg_singularity_is_relative_to_scale() {
+        let result = run(r#"
+import "numpy" as np
+let tiny = [[1e-13, 0], [0, 1e-13]]
+assert(abs(np.linalg.inv(tiny)[0, 0] - 1e13) < 1, "a small but well-conditioned matrix inverts")
+assert(np.linalg.det(tiny) > 0, "and has a nonzero determinant")
+assert(np.linalg.det([[1, 2], [2, 4]]) == 0, "det is 0 exactly when inv reports singular")
+assert(np.linalg.det([[1e-30, 2e-30], [2e-30, 4e-30]]) == 0, "at any scale")
+"#);
+        assert!(result.is_ok(), "{:?}", result);
+        let scaled = run(r#"
+import "numpy" as np
+let x = np.linalg.solve([[1e20, 2e20], [2e20, 4e20]], [1, 2])
+"#);
+        assert!(scaled.unwrap_err().contains("solve() matrix is singular"));
+    }
+
+    #[test]
+    fn test_linalg_eigh_reads_the_lower_triangle() {
+        let result = run(r#"
+import "numpy" as np
+# The upper triangle is ignored, so this is [[2, 1], [1, 2]] with eigenvalues 1 and 3
+let eig = np.linalg.eigh([[2, 100], [1, 2]])
+assert(np.max(np.abs(eig[0] - np.array([1, 3]))) < 1e-9, "eigenvalues of the symmetric lower part")
+let sym = np.linalg.eigh([[2, 1], [1, 2]])
+assert(np.max(np.abs(eig[1] - sym[1])) < 1e-9, "same eigenvectors as the symmetric matrix")
+"#);
+        assert!(result.is_ok(), "{:?}", result);
+    }
+
+    // ========== File I/O Tests ==========
+
+    fn temp_path(name: &str) -> String {
+        std::env::temp_dir()
+            .join(format!("ject_numpy_{}_{}", std::process::id(), name))
+            .to_string_lossy()
+            .into_owned()
+    }
+
+    #[test]
+    fn test_loadtxt_options() {
+        let path = temp_path("load.csv");
+        std::fs::write(&path, "x,y,z\n1, 2, 3\n# skipped comment\n4, 5, 6\n").unwrap();
+        let result = run(&format!(r#"
+import "numpy" as np
+let m = np.loadtxt("{}", delimiter=",", skiprows=1, dtype="int")
+assert(np.shape(m) == [2, 3], "two rows of three columns")
+assert(np.dtype(m) == "int64", "dtype option")
+assert(np.all(m[1] == np.array([4, 5, 6])), "second row")
+"#, path));
+        std::fs::remove_file(&path).ok();
+        assert!(result.is_ok(), "{:?}", result);
+    }
+
+    #[test]
+    fn test_savetxt_header_and_fmt() {
+        let path = temp_path("save.txt");
+        let result = run(&format!(r#"
+import "numpy" as np
+np.savetxt("{}", np.reshape(np.array([1, 2.5, 3, 4]), [2, 2]), delimiter=",", header="a,b", fmt="%.2f")
+"#, path));
+        let contents = std::fs::read_to_string(&path).unwrap_or_default();
+        std::fs::remove_file(&path).ok();
+        assert!(result.is_ok(), "{:?}", result);
+        assert_eq!(contents, "# a,b\n1.00,2.50\n3.00,4.00\n");
+    }
+
+    #[test]
+    fn test_savetxt_integer_fmt_truncates() {
+        let path = temp_path("save_int.txt");
+        let result = run(&format!(r#"
+import "numpy" as np
+np.savetxt("{}", np.array([2.7, -2.7, 0.5]), fmt="%d")
+"#, path));
+        let contents = std::fs::read_to_string(&path).unwrap_or_default();
+        std::fs::remove_file(&path).ok();
+        assert!(result.is_ok(), "{:?}", result);
+        assert_eq!(contents, "2\n-2\n0\n");
+    }
+
+    #[test]
+    fn test_npy_and_npz_round_trip() {
+        let npy = temp_path("arr");
+        let npz = temp_path("bundle.npz");
+        let result = run(&format!(r#"
+import "numpy" as np
+let m = np.reshape(np.arange(0, 6), [2, 3])
+np.save("{npy}", m)
+let back = np.load("{npy}.npy")
+assert(np.shape(back) == [2, 3] and np.all(back == m), "npy round trip keeps shape and values")
+np.savez("{npz}", {{"weights": m, "ids": np.permutation(4)}})
+let bundle = np.load("{npz}")
+assert(np.shape(bundle["weights"]) == [2, 3], "npz entries by name")
+assert(np.dtype(bundle["ids"]) == "int64", "npz keeps dtypes")
+"#));
+        std::fs::remove_file(format!("{}.npy", npy)).ok();
+        std::fs::remove_file(&npz).ok();
+        assert!(result.is_ok(), "{:?}", result);
+    }
+
+    /// A version 1.0 .npy file with the header padded the way NumPy pads it
+    fn npy_bytes(header: &str, data: &[u8]) -> Vec<u8> {
+        let mut header = header.to_string();
+        while !(10 + header.len() + 1).is_multiple_of(64) {
+            header.push(' ');
+        }
+        header.push('\n');
+        let mut bytes = b"\x93NUMPY\x01\x00".to_vec();
+        bytes.extend_from_slice(&(header.len() as u16).to_le_bytes());
+        bytes.extend_from_slice(header.as_bytes());
+        bytes.extend_from_slice(data);
+        bytes
+    }
+
+    #[test]
+    fn test_load_numpy_written_npy() {
+        // As written by NumPy for np.arange(6, dtype="<i4").reshape(3, 2, order="F")
+        let path = temp_path("fortran.npy");
+        let data: Vec<u8> = (0..6i32).flat_map(|x| x.to_le_bytes()).collect();
+        std::fs::write(&path, npy_bytes("{'descr': '<i4', 'fortran_order': True, 'shape': (3, 2), }", &data)).unwrap();
+
+        let result = run(&format!(r#"
+import "numpy" as np
+let m = np.load("{}")
+assert(np.shape(m) == [3, 2], "shape from the header")
+assert(np.all(m[:, 0] == np.array([0, 1, 2])), "fortran order fills columns first")
+"#, path));
+        std::fs::remove_file(&path).ok();
+        assert!(result.is_ok(), "{:?}", result);
+    }
+
+    #[test]
+    fn test_load_corrupt_npy() {
+        let path = temp_path("corrupt.npy");
+        let header = |descr: &str, shape: &str| format!("{{'descr': '{}', 'fortran_order': False, 'shape': {}, }}", descr, shape);
+        let cases: Vec<(Vec<u8>, &str)> = vec![
+            (b"\x93NUMPY\x01".to_vec(), "missing header"),
+            (b"\x93NUMPY\x01\x00\xff\x00{'descr'".to_vec(), "truncated header"),
+            (npy_bytes(&header("", "(2,)"), &[0; 16]), "unsupported dtype ''"),
+            (npy_bytes(&header("<", "(2,)"), &[0; 16]), "unsupported dtype '<'"),
+            (npy_bytes(&header("<f0", "(2,)"), &[0; 16]), "unsupported dtype '<f0'"),
+            (npy_bytes(&header("<f16", "(1,)"), &[0; 16]), "unsupported dtype '<f16'"),
+            (npy_bytes(&header("é8", "(2,)"), &[0; 16]), "unsupported dtype 'é8'"),
+            (npy_bytes(&header("<f8", "(3,)"), &[0; 16]), "truncated data"),
+            (npy_bytes(&header("<f8", "(4294967296, 4294967296)"), &[0; 16]), "shape too large"),
+            (npy_bytes(&header("<f8", "(2, x)"), &[0; 16]), "bad shape"),
+        ];
+        for (bytes, expected) in cases {
+            std::fs::write(&path, &bytes).unwrap();
+            let result = run(&format!("import \"numpy\" as np\nnp.load(\"{}\")\n", path));
+            let err = result.expect_err(expected);
+            assert!(err.contains("is not a valid .npy file") && err.contains(expected), "{}: {}", expected, err);
+        }
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn test_load_uint64_beyond_int64() {
+        let path = temp_path("uint64.npy");
+        let header = "{'descr': '<u8', 'fortran_order': False, 'shape': (2,), }";
+        let load = format!("import \"numpy\" as np\nassert(np.load(\"{}\")[1] == 9223372036854775807)\n", path);
+
+        let data: Vec<u8> = [1u64, i64::MAX as u64].iter().flat_map(|x| x.to_le_bytes()).collect();
+        std::fs::write(&path, npy_bytes(header, &data)).unwrap();
+        let fits = run(&load);
+
+        let data: Vec<u8> = [1u64, u64::MAX].iter().flat_map(|x| x.to_le_bytes()).collect();
+        std::fs::write(&path, npy_bytes(header, &data)).unwrap();
+        let too_big = run(&load);
+        std::fs::remove_file(&path).ok();
+
+        assert!(fits.is_ok(), "{:?}", fits);
+        let err = too_big.expect_err("u64::MAX does not fit in int64");
+        assert!(err.contains("18446744073709551615") && err.contains("does not fit in int64"), "{}", err);
+    }
+}
@@ -0,0 +1,175 @@
+#[cfg(test)]
+mod tests {
+    use crate::package::{create_package, parse_lock, render_lock, Manifest, Package, LOCK_FILE, MANIFEST_FILE};
+    use std::path::{Path, PathBuf};
+
+    /// A fresh temp directory for one test
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("ject_package_{}_{}", std::process::id(), name));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    // ========== Manifest Tests ==========
+
+    #[test]
+    fn test_parse_manifest() {
+        let manifest = Manifest::parse(r#"
+[package]
+name = "app"
+version = "1.2.0"
+
+[dependencies]
+utils = { path = "../utils" }
+colors = { path = "vendor/colors" }
+
+[lints]
+deny = ["W0003", "W0002"]
+"#).unwrap();
+        assert_eq!(manifest.name, "app");
+        assert_eq!(manifest.version, "1.2.0");
+        assert_eq!(manifest.entry, "src/main.ject");
+        let deps: Vec<(&str, &Path)> = manifest.dependencies.iter().map(|d| (d.name.as_str(), d.path.as_path())).collect();
+        assert_eq!(deps, vec![("utils", Path::new("../utils")), ("colors", Path::new("vendor/colors"))]);
+        assert_eq!(manifest.deny, vec!["W0003", "W0002"]);
+
+        // Writing it back out and reading it again gives the same manifest
+        assert_eq!(Manifest::parse(&manifest.to_toml()).unwrap(), manifest);
+    }
+
+    #[test]
+    fn test_invalid_manifests() {
+        let err = Manifest::parse("[dependencies]\n").unwrap_err();
+        assert!(err.contains("missing a [package] section"), "{}", err);
+
+        let err = Manifest::parse("[package]\nname = \"my app\"\n").unwrap_err();
+        assert!(err.contains("Invalid package name 'my app'"), "{}", err);
+
+        let err = Manifest::parse("[package]\nname = \"app\"\n[dependencies]\nutils = \"1.0\"\n").unwrap_err();
+        assert!(err.contains("utils = { path = \"...\" }"), "{}", err);
+
+        let err = Manifest::parse("[package]\nname = \"app\"\n[lints]\ndeny = \"W0001\"\n").unwrap_err();
+        assert!(err.contains("lints.deny in ject.toml must be an array of strings"), "{}", err);
+    }
+
+    #[test]
+    fn test_discover_only_skips_a_missing_manifest() {
+        let dir = temp_dir("discover");
+        assert!(Package::discover(&dir).unwrap().is_none());
+        assert!(Package::find(&dir).unwrap_err().contains("Could not find ject.toml"));
+
+        std::fs::write(dir.join(MANIFEST_FILE), "[package]\nname = \"app\"\n[lints]\ndeny = \"W0001\"\n").unwrap();
+        let err = Package::discover(&dir).unwrap_err();
+        assert!(err.contains("lints.deny in ject.toml must be an array of strings"), "{}", err);
+
+        std::fs::write(dir.join(MANIFEST_FILE), "[package]\nname = \"app\"\n[lints]\ndeny = [\"W0001\"]\n").unwrap();
+        let package = Package::discover(&dir).unwrap().expect("a package");
+        assert_eq!(package.manifest.deny, ["W0001"]);
+    }
+
+    // ========== Resolution Tests ==========
+
+    #[test]
+    fn test_resolve_transitive_dependencies() {
+        let dir = temp_dir("resolve");
+        let mut app = create_package(&dir.join("app"), "app").unwrap();
+        let mut utils = create_package(&dir.join("utils"), "utils").unwrap();
+        std::fs::create_dir_all(dir.join("vendor/colors")).unwrap();
+
+        // utils vendors colors; app only depends on utils
+        utils.add_path_dependency(&dir.join("vendor/colors")).unwrap();
+        let added = app.add_path_dependency(&dir.join("utils")).unwrap();
+        assert_eq!(added.path, Path::new("../utils"));
+
+        let app = Package::find(&dir.join("app/src")).unwrap();
+        let locked = app.lock().unwrap();
+        let lock = std::fs::read_to_string(app.root.join(LOCK_FILE)).unwrap();
+        assert_eq!(lock, render_lock(&locked));
+        assert!(lock.contains("name = \"utils\"\nversion = \"0.1.0\"\npath = \"../utils\""), "{}", lock);
+        assert!(lock.contains("name = \"colors\"\npath = \"../vendor/colors\""), "{}", lock);
+
+        let search_paths = app.search_paths(&locked);
+        assert_eq!(search_paths, vec![app.root.join("../utils"), app.root.join("../vendor/colors")]);
+    }
+
+    #[test]
+    fn test_add_keeps_manifest_formatting() {
+        let dir = temp_dir("add");
+        std::fs::create_dir_all(dir.join("vendor/colors")).unwrap();
+        std::fs::write(dir.join(MANIFEST_FILE), r#"# my comment
+[package]
+name = "app"
+license = "MIT"
+
+[tool.editor]
+tabs = false
+"#).unwrap();
+        Package::find(&dir).unwrap().add_path_dependency(&dir.join("vendor/colors")).unwrap();
+
+        let manifest = std::fs::read_to_string(dir.join(MANIFEST_FILE)).unwrap();
+        assert!(manifest.starts_with("# my comment\n[package]\nname = \"app\"\nlicense = \"MIT\"\n"), "{}", manifest);
+        assert!(manifest.contains("[tool.editor]\ntabs = false\n"), "{}", manifest);
+        assert!(manifest.contains("[dependencies]\ncolors = { path = \"vendor/colors\" }\n"), "{}", manifest);
+    }
+
+    #[test]
+    fn test_lockfile_is_reused_until_stale() {
+        let dir = temp_dir("relock");
+        let mut app = create_package(&dir.join("app"), "app").unwrap();
+        create_package(&dir.join("utils"), "utils").unwrap();
+        std::fs::create_dir_all(dir.join("colors")).unwrap();
+        app.add_path_dependency(&dir.join("utils")).unwrap();
+        let lock_path = app.root.join(LOCK_FILE);
+        let locked = app.lock().unwrap();
+        assert_eq!(parse_lock(&std::fs::read_to_string(&lock_path).unwrap()).unwrap(), locked);
+
+        // A current lockfile is read rather than re-resolved
+        let edited = std::fs::read_to_string(&lock_path).unwrap().replace("# Generated", "# Still generated");
+        std::fs::write(&lock_path, &edited).unwrap();
+        assert_eq!(app.lock().unwrap(), locked);
+        assert_eq!(std::fs::read_to_string(&lock_path).unwrap(), edited);
+
+        // A dependency's new dependency makes it stale
+        std::fs::write(dir.join("utils").join(MANIFEST_FILE), "[package]\nname = \"utils\"\n[dependencies]\ncolors = { path = \"../colors\" }\n").unwrap();
+        let relocked = app.lock().unwrap();
+        let names: Vec<&str> = relocked.iter().map(|package| package.name.as_str()).collect();
+        assert_eq!(names, vec!["utils", "colors"]);
+        assert_eq!(std::fs::read_to_string(&lock_path).unwrap(), render_lock(&relocked));
+
+        // So does a dependency's version changing
+        std::fs::write(dir.join("utils").join(MANIFEST_FILE), "[package]\nname = \"utils\"\nversion = \"0.2.0\"\n").unwrap();
+        let relocked = app.lock().unwrap();
+        assert_eq!(relocked.len(), 1);
+        assert_eq!(relocked[0].version.as_deref(), Some("0.2.0"));
+    }
+
+    #[test]
+    fn test_resolve_errors() {
+        let dir = temp_dir("errors");
+        std::fs::create_dir_all(dir.join("one/shared")).unwrap();
+        std::fs::create_dir_all(dir.join("two/shared")).unwrap();
+        std::fs::write(dir.join("ject.toml"), r#"
+[package]
+name = "app"
+
+[dependencies]
+missing = { path = "nowhere" }
+"#).unwrap();
+        let err = Package::find(&dir).unwrap().resolve().unwrap_err();
+        assert!(err.contains("Dependency 'missing' not found at nowhere"), "{}", err);
+
+        // Two different directories cannot both provide "shared"
+        std::fs::write(dir.join("one/ject.toml"), "[package]\nname = \"one\"\n[dependencies]\nshared = { path = \"shared\" }\n").unwrap();
+        std::fs::write(dir.join("ject.toml"), r#"
+[package]
+name = "app"
+
+[dependencies]
+one = { path = "one" }
+shared = { path = "two/shared" }
+"#).unwrap();
+        let err = Package::find(&dir).unwrap().resolve().unwrap_err();
+        assert!(err.contains("Dependency 'shared' is required from both"), "{}", err);
+    }
+}
@@ -2,7 +2,7 @@
 mod tests {
     use crate::lexer::Lexer;
     use crate::parser::Parser;
-    use crate::ast::{Expr, Stmt, BinaryOp, UnaryOp, Pattern};
+    use crate::ast::{Argument, Binding, DictEntry, Expr, Stmt, BinaryOp, UnaryOp, Pattern, IndexComponent, AssignTarget};
 
     fn parse(input: &str) -> Result<Vec<Stmt>, String> {
         let mut lexer = Lexer::new(input);
@@ -18,8 +18,8 @@ mod tests {
     fn test_let_statement() {
         let stmts = parse("let x = 42").unwrap();
         assert_eq!(stmts.len(), 1);
-        if let Stmt::Let { name, value } = &stmts[0] {
-            assert_eq!(name, "x");
+        if let Stmt::Let { binding, value } = &stmts[0] {
+            assert_eq!(binding, &Binding::Name("x".to_string()));
             if let Expr::Integer(n) = value {
                 assert_eq!(n, &42);
             } else {
@@ -30,12 +30,54 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_const_statement() {
+        let stmts = parse("const MAX = 10").unwrap();
+        assert!(matches!(&stmts[0], Stmt::Const { name, value: Expr::Integer(10) } if name == "MAX"));
+        assert!(parse("const (a, b) = (1, 2)").is_err());
+    }
+
+    #[test]
+    fn test_export_from() {
+        let stmts = parse("export {a, b} from \"./sub\"\nexport * from \"./other\"").unwrap();
+        assert!(matches!(&stmts[0], Stmt::ExportFrom { module_path, items: Some(items) }
+            if module_path == "./sub" && items == &["a", "b"]));
+        assert!(matches!(&stmts[1], Stmt::ExportFrom { module_path, items: None } if module_path == "./other"));
+        assert_eq!(stmts[0].to_string(), "export {a, b} from \"./sub\"");
+        assert!(parse("export {a} \"./sub\"").is_err());
+    }
+
+    #[test]
+    fn test_parse_error_codes() {
+        let code = |input: &str| {
+            let tokens = Lexer::new(input).tokenize_with_positions().into_iter().map(|lt| lt.token).collect();
+            Parser::new_simple(tokens).parse().unwrap_err().code
+        };
+        assert_eq!(code("let = 5"), "E0002");
+        assert_eq!(code("if true then\n    print 1\n"), "E0003");
+        assert_eq!(code("f()[0] = 1"), "E0004");
+        assert_eq!(code("print a[]"), "E0005");
+        assert_eq!(code("let [..rest, last] = a"), "E0006");
+        assert_eq!(code("fn f(...parts, level)\nend"), "E0007");
+        assert_eq!(code("import math"), "E0008");
+    }
+
+    #[test]
+    fn test_out_of_range_decimal_literal() {
+        let err = parse("let x = 99999999999999999999999999999999.5d").unwrap_err();
+        assert_eq!(err, "Decimal literal 99999999999999999999999999999999.5d is out of range");
+        let err = parse("match x
+    99999999999999999999999999999999.5d => 1
+end").unwrap_err();
+        assert!(err.contains("out of range"), "{}", err);
+    }
+
     #[test]
     fn test_let_statement_with_string() {
         let stmts = parse("let name = \"Alice\"").unwrap();
         assert_eq!(stmts.len(), 1);
-        if let Stmt::Let { name, value } = &stmts[0] {
-            assert_eq!(name, "name");
+        if let Stmt::Let { binding, value } = &stmts[0] {
+            assert_eq!(binding, &Binding::Name("name".to_string()));
             if let Expr::String(s) = value {
                 assert_eq!(s, "Alice");
             } else {
@@ -121,8 +163,8 @@ mod tests {
     fn test_print_statement() {
         let stmts = parse("print \"Hello\"").unwrap();
         assert_eq!(stmts.len(), 1);
-        if let Stmt::Print(expr) = &stmts[0] {
-            if let Expr::String(s) = expr {
+        if let Stmt::Print { values, .. } = &stmts[0] {
+            if let Expr::String(s) = &values[0] {
                 assert_eq!(s, "Hello");
             } else {
                 panic!("Expected String");
@@ -389,7 +431,7 @@ mod tests {
         if let Stmt::If { condition, then_branch, elseif_branches, else_branch } = &stmts[0] {
             assert!(matches!(condition, Expr::Binary { operator: BinaryOp::Greater, .. }));
             assert_eq!(then_branch.len(), 1);
-            assert!(matches!(then_branch[0], Stmt::Print(_)));
+            assert!(matches!(then_branch[0], Stmt::Print { .. }));
             assert!(elseif_branches.is_empty());
             assert!(else_branch.is_none());
         } else {
@@ -406,7 +448,7 @@ mod tests {
             assert!(else_branch.is_some());
             let else_body = else_branch.as_ref().unwrap();
             assert_eq!(else_body.len(), 1);
-            assert!(matches!(else_body[0], Stmt::Print(_)));
+            assert!(matches!(else_body[0], Stmt::Print { .. }));
         } else {
             panic!("Expected If statement");
         }
@@ -441,11 +483,11 @@ mod tests {
     fn test_for_statement() {
         let stmts = parse("for i in 1..10 do\n    print i\nend").unwrap();
         assert_eq!(stmts.len(), 1);
-        if let Stmt::For { var, iterable, body } = &stmts[0] {
-            assert_eq!(var, "i");
+        if let Stmt::For { binding, iterable, body } = &stmts[0] {
+            assert_eq!(binding, &Binding::Name("i".to_string()));
             assert!(matches!(iterable, Expr::Range { .. }));
             assert_eq!(body.len(), 1);
-            assert!(matches!(body[0], Stmt::Print(_)));
+            assert!(matches!(body[0], Stmt::Print { .. }));
         } else {
             panic!("Expected For statement");
         }
@@ -455,8 +497,8 @@ mod tests {
     fn test_for_in_array() {
         let stmts = parse("for item in [1, 2, 3] do\n    print item\nend").unwrap();
         assert_eq!(stmts.len(), 1);
-        if let Stmt::For { var, iterable, body } = &stmts[0] {
-            assert_eq!(var, "item");
+        if let Stmt::For { binding, iterable, body } = &stmts[0] {
+            assert_eq!(binding, &Binding::Name("item".to_string()));
             assert!(matches!(iterable, Expr::Array(_)));
             assert_eq!(body.len(), 1);
         } else {
@@ -464,6 +506,37 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_destructuring_bindings() {
+        let stmts = parse("let (q, r) = divmod(7, 2)\nlet [head, ..tail] = xs\nlet {name, age: years} = person\nfor (k, v) in items(d) do\n    print k\nend").unwrap();
+        let names = |b: &[&str]| b.iter().map(|n| Binding::Name(n.to_string())).collect::<Vec<_>>();
+        assert!(matches!(&stmts[0], Stmt::Let { binding: Binding::Tuple(items), .. } if *items == names(&["q", "r"])));
+        assert!(matches!(&stmts[1], Stmt::Let { binding: Binding::Array { items, rest: Some(rest) }, .. }
+            if *items == names(&["head"]) && rest == "tail"));
+        if let Stmt::Let { binding, .. } = &stmts[2] {
+            assert_eq!(binding.names(), vec!["name", "years"]);
+            assert_eq!(binding.to_string(), "{name, age: years}");
+        } else {
+            panic!("Expected Let statement");
+        }
+        assert!(matches!(&stmts[3], Stmt::For { binding: Binding::Tuple(_), .. }));
+
+        assert!(parse("let [..rest, last] = xs").unwrap_err().contains("must be the last item"));
+    }
+
+    #[test]
+    fn test_tuple_expressions() {
+        let stmts = parse("let t = (1, \"a\")\nlet one = (1,)\nlet grouped = (1)\nfn f()\n    return 1, 2\nend").unwrap();
+        assert!(matches!(&stmts[0], Stmt::Let { value: Expr::Tuple(items), .. } if items.len() == 2));
+        assert!(matches!(&stmts[1], Stmt::Let { value: Expr::Tuple(items), .. } if items.len() == 1));
+        assert!(matches!(&stmts[2], Stmt::Let { value: Expr::Integer(1), .. }));
+        if let Stmt::Function { body, .. } = &stmts[3] {
+            assert!(matches!(&body[0], Stmt::Return(Some(Expr::Tuple(items))) if items.len() == 2));
+        } else {
+            panic!("Expected Function statement");
+        }
+    }
+
     #[test]
     fn test_return_statement() {
         let stmts = parse("return 42").unwrap();
@@ -578,7 +651,7 @@ mod tests {
             assert_eq!(params.len(), 1);
             if let crate::ast::LambdaBody::Block(stmts) = body {
                 assert_eq!(stmts.len(), 1);
-                assert!(matches!(stmts[0], Stmt::Print(_)));
+                assert!(matches!(stmts[0], Stmt::Print { .. }));
             } else {
                 panic!("Expected Lambda block body");
             }
@@ -654,6 +727,33 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_multi_dimensional_index() {
+        let stmts = parse("m[1, :]").unwrap();
+        if let Stmt::Expression(Expr::MultiIndex { indices, .. }) = &stmts[0] {
+            assert_eq!(indices.len(), 2);
+            assert!(matches!(indices[0], IndexComponent::Index(Expr::Integer(1))));
+            assert!(matches!(indices[1], IndexComponent::Slice { from: None, to: None, step: None }));
+        } else {
+            panic!("Expected MultiIndex expression");
+        }
+
+        // A comma inside a nested call does not make a multi-index
+        let stmts = parse("a[f(1, 2)]").unwrap();
+        assert!(matches!(stmts[0], Stmt::Expression(Expr::Index { .. })));
+    }
+
+    #[test]
+    fn test_slice_assignment() {
+        let stmts = parse("m[0:2, ::-1] = 0").unwrap();
+        if let Stmt::Assign { target: AssignTarget::Subscript { object, indices }, .. } = &stmts[0] {
+            assert_eq!(object, "m");
+            assert_eq!(indices.len(), 2);
+        } else {
+            panic!("Expected subscript assignment");
+        }
+    }
+
     // ========== Dictionary Tests ==========
 
     #[test]
@@ -662,8 +762,20 @@ mod tests {
         assert_eq!(stmts.len(), 1);
         if let Stmt::Expression(Expr::Dictionary(pairs)) = &stmts[0] {
             assert_eq!(pairs.len(), 2);
-            assert_eq!(pairs[0].0, "name");
-            assert_eq!(pairs[1].0, "age");
+            assert!(matches!(&pairs[0], DictEntry::Pair(Expr::String(key), _) if key == "name"));
+            assert!(matches!(&pairs[1], DictEntry::Pair(Expr::String(key), _) if key == "age"));
+        } else {
+            panic!("Expected Dictionary expression");
+        }
+    }
+
+    #[test]
+    fn test_dictionary_literal_spanning_lines() {
+        let stmts = parse("let d = {\n    name: \"Alice\",\n    ...defaults\n    ,\n    age: 30\n}\nprint d").unwrap();
+        assert_eq!(stmts.len(), 2);
+        if let Stmt::Let { value: Expr::Dictionary(pairs), .. } = &stmts[0] {
+            assert_eq!(pairs.len(), 3);
+            assert!(matches!(&pairs[1], DictEntry::Spread(_)));
         } else {
             panic!("Expected Dictionary expression");
         }
@@ -680,13 +792,30 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_spread_and_rest_syntax() {
+        let stmts = parse("fn log(level, ...msgs)\n    print level\nend\nlog(...args, **opts)\n[...a, 1]\n{...defaults, debug: true}").unwrap();
+        if let Stmt::Function { params, .. } = &stmts[0] {
+            assert!(!params[0].variadic && params[1].variadic);
+            assert_eq!(params[1].to_string(), "...msgs");
+        } else {
+            panic!("Expected Function statement");
+        }
+        assert!(matches!(&stmts[1], Stmt::Expression(Expr::Call { args, .. })
+            if matches!(args[..], [Argument::Spread(_), Argument::KeywordSpread(_)])));
+        assert!(matches!(&stmts[2], Stmt::Expression(Expr::Array(items)) if matches!(items[0], Expr::Spread(_))));
+        assert!(matches!(&stmts[3], Stmt::Expression(Expr::Dictionary(entries)) if matches!(entries[0], DictEntry::Spread(_))));
+
+        assert!(parse("fn f(...rest, last)\nend").unwrap_err().contains("must be the last parameter"));
+    }
+
     // ========== Range Tests ==========
 
     #[test]
     fn test_range_expression() {
         let stmts = parse("1..10").unwrap();
         assert_eq!(stmts.len(), 1);
-        if let Stmt::Expression(Expr::Range { start, end, step }) = &stmts[0] {
+        if let Stmt::Expression(Expr::Range { start, end, step, .. }) = &stmts[0] {
             if let Expr::Integer(n) = &**start {
                 assert_eq!(n, &1);
             }
@@ -717,7 +846,7 @@ mod tests {
     fn test_reverse_range() {
         let stmts = parse("10..0:-1").unwrap();
         assert_eq!(stmts.len(), 1);
-        if let Stmt::Expression(Expr::Range { start, end, step }) = &stmts[0] {
+        if let Stmt::Expression(Expr::Range { start, end, step, .. }) = &stmts[0] {
             if let Expr::Integer(n) = &**start {
                 assert_eq!(n, &10);
             }
@@ -732,6 +861,23 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_inclusive_range_with_by_step() {
+        let stmts = parse("0..=10 by 2").unwrap();
+        if let Stmt::Expression(Expr::Range { step, inclusive, .. }) = &stmts[0] {
+            assert!(inclusive);
+            assert!(matches!(step.as_deref(), Some(Expr::Integer(2))));
+        } else {
+            panic!("Expected Range expression");
+        }
+
+        // Ranges bind tighter than `in` and `==`
+        let stmts = parse("x in 0..10").unwrap();
+        assert!(matches!(&stmts[0], Stmt::Expression(Expr::Binary { operator: BinaryOp::In, right, .. }) if matches!(**right, Expr::Range { .. })));
+        let stmts = parse("0..3 == y").unwrap();
+        assert!(matches!(&stmts[0], Stmt::Expression(Expr::Binary { operator: BinaryOp::Equal, left, .. }) if matches!(**left, Expr::Range { .. })));
+    }
+
     // ========== Struct Tests ==========
 
     #[test]
@@ -535,6 +535,138 @@ assert(to_bool(1) == true, "1 should be true")
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_decimal_constructor() {
+        let result = run(r#"
+assert(decimal("19.99") == 19.99d, "should parse string exactly")
+assert(decimal(0.1) == 0.1d, "should use the shortest float text")
+assert(decimal(5) == 5d, "should convert int")
+assert(to_float(2.5d) == 2.5, "should convert decimal to float")
+assert(to_int(2.99d) == 2, "should truncate decimal to int")
+assert(to_string(12.50d) == "12.50", "should keep the scale")
+"#);
+        assert!(result.is_ok());
+        assert!(run("decimal(\"abc\")").is_err());
+    }
+
+    #[test]
+    fn test_round_decimal_modes() {
+        let result = run(r#"
+assert(round_decimal(2.665d, 2) == 2.66d, "default is half_even")
+assert(round_decimal(2.665d, 2, "half_up") == 2.67d, "half_up")
+assert(round_decimal(2.665d, 2, "half_down") == 2.66d, "half_down")
+assert(round_decimal(2.661d, 2, "up") == 2.67d, "up")
+assert(round_decimal(2.669d, 2, "down") == 2.66d, "down")
+assert(round_decimal(-2.661d, 2, "ceiling") == -2.66d, "ceiling")
+assert(round_decimal(-2.661d, 2, "floor") == -2.67d, "floor")
+assert(round(2.5d) == 3, "round() on a decimal returns an int")
+"#);
+        assert!(result.is_ok());
+        assert!(run("round_decimal(1.5d, 0, \"sideways\")").is_err());
+    }
+
+    #[test]
+    fn test_decimal_sum_and_to_json() {
+        use crate::value::Value;
+        let result = run(r#"
+assert(sum([1.10d, 2.20d, 3]) == 6.3d, "sum of decimals is exact")
+assert(max(1.5d, 2, 0.5) == 2, "max across numeric types")
+"#);
+        assert!(result.is_ok());
+
+        let big: rust_decimal::Decimal = "123456789012345678.123456789".parse().unwrap();
+        let json = crate::stdlib::call_builtin_function(
+            "to_json",
+            vec![Value::Array(vec![Value::Decimal("1.10".parse().unwrap()), Value::Decimal(big)])],
+        ).unwrap();
+        assert_eq!(json, Value::String("[1.10,123456789012345678.123456789]".to_string()));
+    }
+
+    #[test]
+    fn test_json_round_trip_keeps_key_order() {
+        use crate::value::Value;
+        let text = r#"{"zeta":1,"alpha":{"y":true,"x":null},"mid":[{"b":2,"a":1}]}"#;
+        let parsed = crate::stdlib::call_builtin_function("parse_json", vec![Value::String(text.to_string())]).unwrap();
+        match &parsed {
+            Value::Dictionary(dict) => assert_eq!(dict.keys().filter_map(Value::as_str).collect::<Vec<_>>(), ["zeta", "alpha", "mid"]),
+            other => panic!("objects parse to dictionaries, got {:?}", other),
+        }
+        let json = crate::stdlib::call_builtin_function("to_json", vec![parsed]).unwrap();
+        assert_eq!(json, Value::String(text.to_string()));
+
+        let int_keys = Value::Dictionary([(Value::Integer(7), Value::Bool(true))].into_iter().collect());
+        let err = crate::stdlib::call_builtin_function("to_json", vec![int_keys]).unwrap_err();
+        assert!(err.message.contains("dictionary key 7 (int) to JSON: object keys must be strings"), "{}", err.message);
+    }
+
+    // ========== Dictionary Functions Tests ==========
+
+    #[test]
+    fn test_dictionary_accessors() {
+        let result = run(r#"
+let config = {"host": "localhost", "port": 8080, 7: "seven"}
+assert(keys(config) == ["host", "port", 7], "keys in insertion order")
+assert(values(config) == ["localhost", 8080, "seven"], "values in insertion order")
+assert(items(config)[1] == ("port", 8080), "items are (key, value) tuples")
+assert(get(config, "port") == 8080, "get existing key")
+assert(get(config, "user") == nil, "missing key defaults to nil")
+assert(get(config, "user", "admin") == "admin", "explicit default")
+assert(len(config) == 3 and contains(config, 7), "len and contains")
+let smaller = remove(config, "port")
+assert(keys(smaller) == ["host", 7] and len(config) == 3, "remove returns a new dictionary")
+"#);
+        assert!(result.is_ok(), "{:?}", result);
+    }
+
+    #[test]
+    fn test_merge_and_update() {
+        let result = run(r#"
+let defaults = {"host": "localhost", "port": 80, "debug": false}
+let user = {"port": 8080, "debug": true}
+let merged = merge(defaults, user, {"name": "api"})
+assert(merged == {"host": "localhost", "port": 8080, "debug": true, "name": "api"}, "later dictionaries win")
+assert(keys(merged) == ["host", "port", "debug", "name"], "keys keep their first position")
+let updated = update(defaults, [["port", 443], ["tls", true]])
+assert(updated["port"] == 443 and updated["tls"] == true, "update from pairs")
+assert(update(defaults, user)["debug"] == true, "update from a dictionary")
+assert(defaults["port"] == 80, "inputs are unchanged")
+"#);
+        assert!(result.is_ok(), "{:?}", result);
+
+        let bad = run("let d = merge({}, [1, 2])");
+        assert!(bad.unwrap_err().contains("merge() requires a dictionary, got array"));
+    }
+
+    #[test]
+    fn test_map_values_and_filter_keys() {
+        let result = run(r#"
+let prices = {"apple": 1.5, "pear": 2.0, "plum": 3.0}
+let doubled = map_values(prices, lambda(p) -> p * 2)
+assert(doubled["plum"] == 6.0 and keys(doubled) == keys(prices), "map_values keeps keys")
+let p_only = filter_keys(prices, lambda(k) -> char_at(k, 0) == "p")
+assert(keys(p_only) == ["pear", "plum"], "filter_keys with a predicate")
+let picked = filter_keys(prices, ["plum", "apple", "kiwi"])
+assert(keys(picked) == ["apple", "plum"], "filter_keys with a key list keeps dictionary order")
+"#);
+        assert!(result.is_ok(), "{:?}", result);
+    }
+
+    #[test]
+    fn test_collection_holds_any_hashable_value() {
+        let result = run(r#"
+import "collections"
+let ids = collection([3, 1, 3, "1", [0, 1]])
+assert(size(ids) == 4, "duplicates removed, 1 and \"1\" distinct")
+assert(has(ids, 3) and has(ids, [0, 1]) and 2 in ids == false, "lookup by value")
+assert(to_array(collection([3, 1, 2])) == [1, 2, 3], "to_array sorts")
+assert(has(add_to(ids, 2), 2), "add_to")
+"#);
+        assert!(result.is_ok(), "{:?}", result);
+
+        let unhashable = run("import \"collections\"\nlet c = collection([1.5])");
+        assert!(unhashable.unwrap_err().contains("Collection items must be hashable"));
+    }
+
     // ========== Base Conversion Tests ==========
 
     #[test]
@@ -1,5 +1,6 @@
 use std::collections::{HashMap, HashSet};
 use std::fmt;
+use std::hash::{Hash, Hasher};
 use crate::ast::{Stmt, Parameter};
 use crate::numpy::NdArray;
 
@@ -7,13 +8,16 @@ use crate::numpy::NdArray;
 pub enum Value {
     Integer(i64),
     Float(f64),
+    Decimal(rust_decimal::Decimal),  // Exact fixed-point number (e.g. money)
     String(String),
     Bool(bool),
     Nil,
     Array(Vec<Value>),
     UniqueArray(Vec<Value>),  // Unique array (set-like)
-    Dictionary(std::collections::HashMap<String, Value>),
-    Collection(std::collections::HashSet<String>),
+    Tuple(Vec<Value>),  // Fixed group of values: (q, r), `return a, b`
+    Range(crate::range::Range),  // Lazy arithmetic sequence: 0..10, 1..=5, 0..1 by 0.25
+    Dictionary(indexmap::IndexMap<Value, Value>),  // Hashable keys, kept in insertion order
+    Collection(indexmap::IndexSet<Value>),  // Set of hashable values
     Function {
         params: Vec<Parameter>,
         body: Vec<Stmt>,
@@ -39,6 +43,8 @@ pub enum Value {
         fields: Vec<String>,
     },
     NdArray(NdArray),
+    CsvReader(crate::csv::CsvReader),  // Lazy row stream from csv.rows()
+    DataFrame(crate::dataframe::DataFrame),
     Error(String),
 }
 
@@ -47,6 +53,7 @@ impl fmt::Display for Value {
         match self {
             Value::Integer(n) => write!(f, "{}", n),
             Value::Float(n) => write!(f, "{}", n),
+            Value::Decimal(d) => write!(f, "{}", d),
             Value::String(s) => write!(f, "\"{}\"", s),
             Value::Bool(b) => write!(f, "{}", b),
             Value::Nil => write!(f, "nil"),
@@ -66,18 +73,29 @@ impl fmt::Display for Value {
                 }
                 write!(f, "|}}")
             }
+            Value::Tuple(elements) => {
+                write!(f, "(")?;
+                for (i, elem) in elements.iter().enumerate() {
+                    if i > 0 { write!(f, ", ")?; }
+                    write!(f, "{}", elem)?;
+                }
+                // A one-element tuple keeps its comma so it doesn't read as a grouping
+                if elements.len() == 1 { write!(f, ",")?; }
+                write!(f, ")")
+            }
+            Value::Range(range) => write!(f, "{}", range),
             Value::Dictionary(map) => {
                 write!(f, "{{")?;
                 for (i, (key, value)) in map.iter().enumerate() {
                     if i > 0 { write!(f, ", ")?; }
-                    write!(f, "\"{}\": {}", key, value)?;
+                    write!(f, "{}: {}", key, value)?;
                 }
                 write!(f, "}}")
             }
             Value::Collection(set) => {
                 write!(f, "collection{{")?;
                 let mut items: Vec<_> = set.iter().collect();
-                items.sort(); // Sort for consistent display
+                items.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal)); // Sort for consistent display
                 for (i, item) in items.iter().enumerate() {
                     if i > 0 { write!(f, ", ")?; }
                     write!(f, "{}", item)?;
@@ -157,11 +175,61 @@ impl fmt::Display for Value {
                 }
                 write!(f, ")")
             }
+            Value::CsvReader(reader) => write!(f, "<csv rows: {}>", reader.path()),
+            Value::DataFrame(frame) => write!(f, "{}", frame),
             Value::Error(msg) => write!(f, "error: {}", msg),
         }
     }
 }
 
+// Dictionary keys are limited to hashable values (see `is_hashable`), for which the
+// derived equality is a true equivalence
+impl Eq for Value {}
+
+impl Hash for Value {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        match self {
+            // Hashed exactly like `str`, so string lookups can borrow a `&str`
+            Value::String(s) => s.hash(state),
+            Value::Integer(n) => {
+                std::mem::discriminant(self).hash(state);
+                n.hash(state);
+            }
+            Value::Bool(b) => {
+                std::mem::discriminant(self).hash(state);
+                b.hash(state);
+            }
+            Value::Decimal(d) => {
+                std::mem::discriminant(self).hash(state);
+                d.hash(state);
+            }
+            Value::Array(items) | Value::Tuple(items) => {
+                std::mem::discriminant(self).hash(state);
+                items.hash(state);
+            }
+            Value::StructInstance { struct_name, fields } => {
+                // Field order is not part of a struct's identity
+                std::mem::discriminant(self).hash(state);
+                struct_name.hash(state);
+                let mut names: Vec<&String> = fields.keys().collect();
+                names.sort();
+                for name in names {
+                    name.hash(state);
+                    fields[name].hash(state);
+                }
+            }
+            // Not hashable; equal values still hash alike
+            _ => std::mem::discriminant(self).hash(state),
+        }
+    }
+}
+
+impl indexmap::Equivalent<Value> for str {
+    fn equivalent(&self, key: &Value) -> bool {
+        matches!(key, Value::String(s) if s == self)
+    }
+}
+
 impl PartialOrd for Value {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
         use std::cmp::Ordering;
@@ -171,6 +239,11 @@ impl PartialOrd for Value {
             (Value::Float(a), Value::Float(b)) => a.partial_cmp(b),
             (Value::Integer(a), Value::Float(b)) => (*a as f64).partial_cmp(b),
             (Value::Float(a), Value::Integer(b)) => a.partial_cmp(&(*b as f64)),
+            (Value::Decimal(a), Value::Decimal(b)) => a.partial_cmp(b),
+            (Value::Decimal(a), Value::Integer(b)) => a.partial_cmp(&rust_decimal::Decimal::from(*b)),
+            (Value::Integer(a), Value::Decimal(b)) => rust_decimal::Decimal::from(*a).partial_cmp(b),
+            (Value::Decimal(a), Value::Float(b)) => decimal_to_f64(a).partial_cmp(b),
+            (Value::Float(a), Value::Decimal(b)) => a.partial_cmp(&decimal_to_f64(b)),
             
             // Strings can be compared
             (Value::String(a), Value::String(b)) => a.partial_cmp(b),
@@ -180,6 +253,7 @@ impl PartialOrd for Value {
             
             // Arrays can be compared lexicographically
             (Value::Array(a), Value::Array(b)) => a.partial_cmp(b),
+            (Value::Tuple(a), Value::Tuple(b)) => a.partial_cmp(b),
             
             // For different types, use a consistent ordering
             (a, b) => {
@@ -202,6 +276,11 @@ impl PartialOrd for Value {
                     Value::StructDefinition { .. } => 15,
                     Value::Error(_) => 16,
                     Value::NdArray(_) => 17,
+                    Value::Decimal(_) => 18,
+                    Value::CsvReader(_) => 19,
+                    Value::DataFrame(_) => 20,
+                    Value::Tuple(_) => 21,
+                    Value::Range(_) => 22,
                 };
                 type_order(a).partial_cmp(&type_order(b))
             }
@@ -209,6 +288,12 @@ impl PartialOrd for Value {
     }
 }
 
+/// Nearest float to a decimal, used when a decimal meets a float in a comparison
+pub fn decimal_to_f64(d: &rust_decimal::Decimal) -> f64 {
+    use rust_decimal::prelude::ToPrimitive;
+    d.to_f64().unwrap_or(f64::NAN)
+}
+
 impl Value {
     pub fn is_truthy(&self) -> bool {
         match self {
@@ -216,23 +301,48 @@ impl Value {
             Value::Nil => false,
             Value::Integer(0) => false,
             Value::Float(f) => *f != 0.0,
+            Value::Decimal(d) => !d.is_zero(),
             Value::String(s) => !s.is_empty(),
             Value::Array(arr) => !arr.is_empty(),
+            Value::Tuple(items) => !items.is_empty(),
+            Value::Range(range) => !range.is_empty(),
             Value::Dictionary(dict) => !dict.is_empty(),
             Value::Collection(set) => !set.is_empty(),
             _ => true,
         }
     }
     
+    /// The text of a string value
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    /// Whether the value can be a dictionary key or collection item: ints, bools,
+    /// strings, decimals, and arrays, tuples or struct instances made only of those
+    pub fn is_hashable(&self) -> bool {
+        match self {
+            Value::Integer(_) | Value::Bool(_) | Value::String(_) | Value::Decimal(_) => true,
+            Value::Array(items) | Value::Tuple(items) => items.iter().all(Value::is_hashable),
+            Value::StructInstance { fields, .. } => fields.values().all(Value::is_hashable),
+            _ => false,
+        }
+    }
+
     pub fn type_name(&self) -> &'static str {
         match self {
             Value::Integer(_) => "int",
             Value::Float(_) => "float",
+            Value::Decimal(_) => "decimal",
             Value::String(_) => "string",
             Value::Bool(_) => "boolean",
             Value::Nil => "nil",
             Value::Array(_) => "array",
             Value::UniqueArray(_) => "unique_array",
+            Value::Tuple(_) => "tuple",
+            Value::Range(_) => "range",
             Value::Dictionary(_) => "dictionary",
             Value::Collection(_) => "collection",
             Value::Function { .. } => "function",
@@ -243,6 +353,8 @@ impl Value {
             Value::StructInstance { .. } => "struct",
             Value::StructDefinition { .. } => "struct_definition",
             Value::NdArray(_) => "ndarray",
+            Value::CsvReader(_) => "csv_reader",
+            Value::DataFrame(_) => "dataframe",
             Value::Error(_) => "error",
         }
     }
@@ -260,12 +372,14 @@ impl Value {
 #[derive(Debug, Clone, PartialEq)]
 pub struct Environment {
     scopes: Vec<HashMap<String, Value>>,
+    constants: Vec<HashSet<String>>,  // Names in the matching scope that can't be reassigned
 }
 
 impl Environment {
     pub fn new() -> Self {
         Environment {
             scopes: vec![HashMap::new()],
+            constants: vec![HashSet::new()],
         }
     }
     
@@ -275,6 +389,14 @@ impl Environment {
         }
     }
     
+    /// Define a read-only binding (`const`, CorLib, imports)
+    pub fn define_constant(&mut self, name: String, value: Value) {
+        if let Some(constants) = self.constants.last_mut() {
+            constants.insert(name.clone());
+        }
+        self.define(name, value);
+    }
+    
     pub fn get(&self, name: &str) -> Option<Value> {
         for scope in self.scopes.iter().rev() {
             if let Some(value) = scope.get(name) {
@@ -294,13 +416,35 @@ impl Environment {
         false
     }
     
+    /// Whether the binding `name` currently resolves to is read-only
+    pub fn is_constant(&self, name: &str) -> bool {
+        for (scope, constants) in self.scopes.iter().zip(&self.constants).rev() {
+            if scope.contains_key(name) {
+                return constants.contains(name);
+            }
+        }
+        false
+    }
+    
+    /// Whether the innermost scope already holds a constant called `name`
+    pub fn is_constant_in_scope(&self, name: &str) -> bool {
+        self.constants.last().is_some_and(|constants| constants.contains(name))
+    }
+    
+    /// Names defined in the innermost scope
+    pub fn local_names(&self) -> impl Iterator<Item = &String> {
+        self.scopes.last().into_iter().flat_map(|scope| scope.keys())
+    }
+
     pub fn push_scope(&mut self) {
         self.scopes.push(HashMap::new());
+        self.constants.push(HashSet::new());
     }
     
     pub fn pop_scope(&mut self) {
         if self.scopes.len() > 1 {
             self.scopes.pop();
+            self.constants.pop();
         }
     }
 }